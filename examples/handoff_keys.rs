@@ -0,0 +1,33 @@
+//! Generate the key pair that signs ZKPass handoff tokens.
+//!
+//! ```bash
+//! cargo run --example handoff_keys
+//! ```
+//!
+//! The handoff service (`zkpass/handoff-server.js`) signs with the private JWK from
+//! `HANDOFF_SIGNING_JWK`, and the UI is built with the public key in
+//! `WEB5CLAIMS_HANDOFF_PUBLIC_KEY`. Never put the private key in a `VITE_*` variable: Vite
+//! ships those in the public bundle.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use ed25519_dalek::SigningKey;
+use rand::{rngs::OsRng, RngCore};
+
+fn main() {
+    let mut secret = [0u8; 32];
+    OsRng.fill_bytes(&mut secret);
+    let signing_key = SigningKey::from_bytes(&secret);
+    let x = URL_SAFE_NO_PAD.encode(signing_key.verifying_key().as_bytes());
+    let jwk = serde_json::json!({
+        "kty": "OKP",
+        "crv": "Ed25519",
+        "d": URL_SAFE_NO_PAD.encode(signing_key.to_bytes()),
+        "x": x,
+    });
+
+    println!("# handoff service environment (server only)");
+    println!("HANDOFF_SIGNING_JWK='{}'", jwk);
+    println!();
+    println!("# web5claims-ui build environment");
+    println!("WEB5CLAIMS_HANDOFF_PUBLIC_KEY={}", x);
+}
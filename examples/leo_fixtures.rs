@@ -0,0 +1,106 @@
+//! Regenerate `web5claimsleo/tests/test_web5claimsleo.leo` from the Rust reference logic.
+//!
+//! ```bash
+//! cargo run --example leo_fixtures > web5claimsleo/tests/test_web5claimsleo.leo
+//! ```
+
+use chrono::{TimeZone, Utc};
+use konnektoren_core::certificates::CertificateData;
+use web5claims::aleo::fixtures::{
+    FixtureError, LeoCertificateInputs, LeoFixture, LeoFixtureGenerator, LeoTestSuite,
+    DEFAULT_RECIPIENT,
+};
+use web5claims::{CefrLevel, CertificateIssuer, ClaimType, ProofOptions, ProofRequest};
+
+fn main() -> Result<(), FixtureError> {
+    let certificate = CertificateData::new(
+        "German_B2_Complete".to_string(),
+        7,
+        7,
+        "Test Student".to_string(),
+        Utc.timestamp_opt(1703664000, 0).unwrap(),
+    );
+
+    let generator = LeoFixtureGenerator::new(
+        CertificateIssuer::new(
+            "leo_fixture_issuer".to_string(),
+            "Leo Fixture Issuer".to_string(),
+        )
+        .accept_unsigned_certificates(),
+    );
+
+    let request = |claim_type: ClaimType| ProofRequest {
+        certificate: certificate.clone(),
+        claim_type,
+        target_platform: "aleo".to_string(),
+        options: ProofOptions::default(),
+    };
+
+    let german_b1 = ClaimType::LanguageProficiency {
+        language: "German".to_string(),
+        min_level: CefrLevel::B1,
+    };
+    let performance_90 = ClaimType::PerformanceThreshold { min_percentage: 90 };
+
+    let mut suite = LeoTestSuite::new();
+    suite
+        .add(generator.issue_fixture("issue_certificate", &certificate)?)
+        .add(generator.proof_fixture("prove_language_proficiency", &request(german_b1.clone()))?)
+        .add(generator.proof_fixture(
+            "prove_language_proficiency_below_level",
+            &request(ClaimType::LanguageProficiency {
+                language: "German".to_string(),
+                min_level: CefrLevel::C1,
+            }),
+        )?)
+        .add(generator.proof_fixture(
+            "prove_performance_threshold",
+            &request(performance_90.clone()),
+        )?)
+        .add(generator.proof_fixture(
+            "prove_combined_requirements",
+            &request(ClaimType::Combined {
+                criteria: vec![german_b1, performance_90],
+            }),
+        )?)
+        .add(generator.completion_rate_fixture("prove_completion_rate", &certificate, 90)?)
+        .add(generator.proof_fixture(
+            "prove_certificate_recency",
+            &request(ClaimType::CompletionDate {
+                after_date: Utc.timestamp_opt(1700000000, 0).unwrap(),
+            }),
+        )?)
+        .add(generator.proof_fixture(
+            "wrong_language_should_fail",
+            &request(ClaimType::LanguageProficiency {
+                language: "Spanish".to_string(),
+                min_level: CefrLevel::A1,
+            }),
+        )?);
+
+    let valid_inputs = LeoCertificateInputs::from_certificate(&certificate, DEFAULT_RECIPIENT)?;
+
+    let mut invalid_level = valid_inputs.clone();
+    invalid_level.level = 7;
+    let mut invalid_score = valid_inputs.clone();
+    invalid_score.score = 101;
+    let mut invalid_challenges = valid_inputs;
+    invalid_challenges.challenges_solved = 8;
+
+    suite
+        .add(LeoFixture::from_inputs(
+            "invalid_level_should_fail",
+            invalid_level,
+        ))
+        .add(LeoFixture::from_inputs(
+            "invalid_score_should_fail",
+            invalid_score,
+        ))
+        .add(LeoFixture::from_inputs(
+            "invalid_challenges_should_fail",
+            invalid_challenges,
+        ));
+
+    print!("{}", suite.render());
+    Ok(())
+}
@@ -0,0 +1,859 @@
+use super::program::{cefr_level_to_u8, encode_language_to_field};
+use crate::issuer::{CertificateIssuer, IssuerError, ProofRequest};
+use crate::zk_proof::{CefrLevel, ClaimType};
+use konnektoren_core::certificates::CertificateData;
+use std::fmt::Write;
+use thiserror::Error;
+
+/// Leo program the fixtures are generated for
+pub const LEO_PROGRAM_ID: &str = "web5claimsleo.aleo";
+
+/// Leo test program wrapping the generated test functions
+pub const LEO_TEST_PROGRAM_ID: &str = "test_web5claimsleo.aleo";
+
+/// Placeholder recipient address used by the Leo tests
+pub const DEFAULT_RECIPIENT: &str =
+    "aleo1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq3ljyzc";
+
+/// Seconds between issuance and proof generation in generated fixtures
+pub const VERIFICATION_DELAY_SECS: u32 = 100;
+
+#[derive(Error, Debug)]
+pub enum FixtureError {
+    #[error("Invalid Aleo address: {0}")]
+    InvalidAddress(String),
+    #[error("Value out of range for Leo input {field}: {value}")]
+    OutOfRange { field: String, value: String },
+    #[error("Claim type is not supported by the Leo program: {0}")]
+    UnsupportedClaim(String),
+    #[error("Reference evaluation failed: {0}")]
+    Issuer(#[from] IssuerError),
+}
+
+/// Inputs of the `issue_certificate` transition
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeoCertificateInputs {
+    /// Recipient address owning the certificate record
+    pub recipient: String,
+    /// Language as a field literal (e.g. "1field")
+    pub language: String,
+    /// CEFR level (1=A1 ... 6=C2)
+    pub level: u8,
+    /// Performance percentage
+    pub score: u8,
+    /// Total number of challenges
+    pub challenges_total: u16,
+    /// Number of solved challenges
+    pub challenges_solved: u16,
+    /// Issuance timestamp
+    pub issued_at: u32,
+}
+
+/// A single public input of a proof transition
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeoInput {
+    /// Parameter name in the transition signature
+    pub name: String,
+    /// Leo type of the parameter
+    pub ty: String,
+    /// Leo literal passed for the parameter
+    pub value: String,
+}
+
+/// A proof transition call together with its expected public output
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeoProofCall {
+    /// Transition name in the Leo program
+    pub transition: String,
+    /// Struct returned by the transition
+    pub output_type: String,
+    /// Public inputs following the certificate record
+    pub inputs: Vec<LeoInput>,
+    /// Expected output fields, computed by the Rust reference logic
+    pub expected: Vec<(String, String)>,
+}
+
+/// A generated Leo test case
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeoFixture {
+    /// Test name (without the `test_` prefix)
+    pub name: String,
+    /// Inputs used to issue the certificate record
+    pub certificate: LeoCertificateInputs,
+    /// Optional proof transition run on the issued record
+    pub proof: Option<LeoProofCall>,
+    /// Whether the Leo program is expected to reject the inputs
+    pub should_fail: bool,
+}
+
+/// Generates Leo fixtures from certificates and proof requests
+#[derive(Debug, Clone)]
+pub struct LeoFixtureGenerator {
+    /// Issuer providing the reference claim evaluation
+    pub issuer: CertificateIssuer,
+    /// Recipient address used for issued records
+    pub recipient: String,
+}
+
+/// A collection of fixtures rendered as one Leo test program
+#[derive(Debug, Clone, Default)]
+pub struct LeoTestSuite {
+    /// Fixtures in rendering order
+    pub fixtures: Vec<LeoFixture>,
+}
+
+impl LeoCertificateInputs {
+    /// Build transition inputs from a certificate
+    ///
+    /// The certificate date is used as `issued_at` so generated fixtures are reproducible.
+    pub fn from_certificate(
+        certificate: &CertificateData,
+        recipient: &str,
+    ) -> Result<Self, FixtureError> {
+        validate_address(recipient)?;
+
+        let language = certificate
+            .game_path_name
+            .split('_')
+            .next()
+            .unwrap_or("unknown");
+        let level = CefrLevel::from_course_name(&certificate.game_path_name)
+            .map(|level| cefr_level_to_u8(&level))
+            .ok_or_else(|| IssuerError::InvalidCefrLevel(certificate.game_path_name.clone()))?;
+
+        Ok(Self {
+            recipient: recipient.to_string(),
+            language: encode_language_to_field(language),
+            level,
+            score: certificate.performance_percentage,
+            challenges_total: u16::try_from(certificate.total_challenges).map_err(|_| {
+                FixtureError::OutOfRange {
+                    field: "challenges_total".to_string(),
+                    value: certificate.total_challenges.to_string(),
+                }
+            })?,
+            challenges_solved: u16::try_from(certificate.solved_challenges).map_err(|_| {
+                FixtureError::OutOfRange {
+                    field: "challenges_solved".to_string(),
+                    value: certificate.solved_challenges.to_string(),
+                }
+            })?,
+            issued_at: u32::try_from(certificate.date.timestamp()).map_err(|_| {
+                FixtureError::OutOfRange {
+                    field: "issued_at".to_string(),
+                    value: certificate.date.to_rfc3339(),
+                }
+            })?,
+        })
+    }
+
+    /// Whether the inputs pass the assertions of `issue_certificate`
+    pub fn is_valid(&self) -> bool {
+        (1..=6).contains(&self.level)
+            && self.score <= 100
+            && self.challenges_solved <= self.challenges_total
+    }
+
+    /// Leo literals in `issue_certificate` parameter order
+    pub fn to_args(&self) -> Vec<String> {
+        vec![
+            self.recipient.clone(),
+            self.language.clone(),
+            format!("{}u8", self.level),
+            format!("{}u8", self.score),
+            format!("{}u16", self.challenges_total),
+            format!("{}u16", self.challenges_solved),
+            format!("{}u32", self.issued_at),
+        ]
+    }
+}
+
+impl LeoInput {
+    fn new(name: &str, ty: &str, value: String) -> Self {
+        Self {
+            name: name.to_string(),
+            ty: ty.to_string(),
+            value,
+        }
+    }
+}
+
+impl LeoFixture {
+    /// Create a fixture that only issues a certificate from raw inputs
+    ///
+    /// Inputs that violate the transition's assertions produce a `@should_fail` test.
+    pub fn from_inputs(name: &str, certificate: LeoCertificateInputs) -> Self {
+        let should_fail = !certificate.is_valid();
+        Self {
+            name: name.to_string(),
+            certificate,
+            proof: None,
+            should_fail,
+        }
+    }
+
+    /// `leo run` arguments issuing the certificate, led by the transition name
+    pub fn issue_run_args(&self) -> Vec<String> {
+        let mut args = vec!["issue_certificate".to_string()];
+        args.extend(self.certificate.to_args());
+        args
+    }
+
+    /// `leo run` arguments for the proof transition, led by the transition name
+    pub fn proof_run_args(&self, certificate_record: &str) -> Option<Vec<String>> {
+        self.proof.as_ref().map(|proof| {
+            let mut args = vec![proof.transition.clone(), certificate_record.to_string()];
+            args.extend(proof.inputs.iter().map(|input| input.value.clone()));
+            args
+        })
+    }
+
+    /// Render the fixture as a Leo `@test` function
+    pub fn to_leo_test(&self) -> String {
+        let cert = &self.certificate;
+        let mut out = String::new();
+
+        let _ = writeln!(out, "    @test");
+        if self.should_fail {
+            let _ = writeln!(out, "    @should_fail");
+        }
+        let _ = writeln!(out, "    function test_{}() {{", self.name);
+        let _ = writeln!(out, "        let recipient: address = {};", cert.recipient);
+        let _ = writeln!(out, "        let language: field = {};", cert.language);
+        let _ = writeln!(out, "        let level: u8 = {}u8;", cert.level);
+        let _ = writeln!(out, "        let score: u8 = {}u8;", cert.score);
+        let _ = writeln!(
+            out,
+            "        let challenges_total: u16 = {}u16;",
+            cert.challenges_total
+        );
+        let _ = writeln!(
+            out,
+            "        let challenges_solved: u16 = {}u16;",
+            cert.challenges_solved
+        );
+        let _ = writeln!(out, "        let issued_at: u32 = {}u32;", cert.issued_at);
+        let _ = writeln!(out);
+        let _ = writeln!(
+            out,
+            "        let certificate: {0}/LanguageCertificate = {0}/issue_certificate(",
+            LEO_PROGRAM_ID
+        );
+        let _ = writeln!(out, "            recipient,");
+        let _ = writeln!(out, "            language,");
+        let _ = writeln!(out, "            level,");
+        let _ = writeln!(out, "            score,");
+        let _ = writeln!(out, "            challenges_total,");
+        let _ = writeln!(out, "            challenges_solved,");
+        let _ = writeln!(out, "            issued_at");
+        let _ = writeln!(out, "        );");
+
+        match &self.proof {
+            None if !self.should_fail => {
+                let _ = writeln!(out);
+                for field in ["owner", "language", "level", "score"] {
+                    let variable = if field == "owner" { "recipient" } else { field };
+                    let _ = writeln!(
+                        out,
+                        "        assert_eq(certificate.{}, {});",
+                        field, variable
+                    );
+                }
+            }
+            None => {}
+            Some(proof) => {
+                let _ = writeln!(out);
+                for input in &proof.inputs {
+                    let _ = writeln!(
+                        out,
+                        "        let {}: {} = {};",
+                        input.name, input.ty, input.value
+                    );
+                }
+                let _ = writeln!(out);
+                let _ = writeln!(
+                    out,
+                    "        let proof: {0}/{1} = {0}/{2}(",
+                    LEO_PROGRAM_ID, proof.output_type, proof.transition
+                );
+                let _ = writeln!(out, "            certificate,");
+                let names: Vec<String> = proof
+                    .inputs
+                    .iter()
+                    .map(|input| format!("            {}", input.name))
+                    .collect();
+                let _ = writeln!(out, "{}", names.join(",\n"));
+                let _ = writeln!(out, "        );");
+
+                if !self.should_fail && !proof.expected.is_empty() {
+                    let _ = writeln!(out);
+                    for (field, value) in &proof.expected {
+                        let _ = writeln!(out, "        assert_eq(proof.{}, {});", field, value);
+                    }
+                }
+            }
+        }
+
+        let _ = writeln!(out, "    }}");
+        out
+    }
+}
+
+impl LeoFixtureGenerator {
+    /// Create a generator using the default test recipient
+    pub fn new(issuer: CertificateIssuer) -> Self {
+        Self {
+            issuer,
+            recipient: DEFAULT_RECIPIENT.to_string(),
+        }
+    }
+
+    /// Use a different recipient address for issued records
+    pub fn with_recipient(mut self, recipient: &str) -> Result<Self, FixtureError> {
+        validate_address(recipient)?;
+        self.recipient = recipient.to_string();
+        Ok(self)
+    }
+
+    /// Fixture issuing a certificate and checking the resulting record
+    pub fn issue_fixture(
+        &self,
+        name: &str,
+        certificate: &CertificateData,
+    ) -> Result<LeoFixture, FixtureError> {
+        let inputs = LeoCertificateInputs::from_certificate(certificate, &self.recipient)?;
+        Ok(LeoFixture::from_inputs(name, inputs))
+    }
+
+    /// Fixture issuing the request's certificate and proving its claim
+    ///
+    /// Expected outputs come from [`CertificateIssuer::evaluate_claim`]. A language
+    /// mismatch, which the Leo program rejects with `assert_eq`, yields a `@should_fail` test.
+    pub fn proof_fixture(
+        &self,
+        name: &str,
+        request: &ProofRequest,
+    ) -> Result<LeoFixture, FixtureError> {
+        let inputs = LeoCertificateInputs::from_certificate(&request.certificate, &self.recipient)?;
+        let current_time = inputs.issued_at.saturating_add(VERIFICATION_DELAY_SECS);
+
+        let (proof, language_mismatch) = self.proof_call(
+            &request.certificate,
+            &inputs,
+            &request.claim_type,
+            current_time,
+        )?;
+
+        Ok(LeoFixture {
+            name: name.to_string(),
+            should_fail: language_mismatch || !inputs.is_valid(),
+            certificate: inputs,
+            proof: Some(proof),
+        })
+    }
+
+    /// Fixture for `prove_completion_rate`, which has no matching `ClaimType`
+    pub fn completion_rate_fixture(
+        &self,
+        name: &str,
+        certificate: &CertificateData,
+        min_completion_percentage: u8,
+    ) -> Result<LeoFixture, FixtureError> {
+        let inputs = LeoCertificateInputs::from_certificate(certificate, &self.recipient)?;
+        let current_time = inputs.issued_at.saturating_add(VERIFICATION_DELAY_SECS);
+
+        // Same u16 arithmetic as the Leo transition, which aborts on overflow, division by
+        // zero and a rate that does not fit its `u8` cast
+        let completion_percentage = inputs
+            .challenges_solved
+            .checked_mul(100)
+            .and_then(|scaled| scaled.checked_div(inputs.challenges_total))
+            .and_then(|rate| u8::try_from(rate).ok());
+        let meets_threshold =
+            completion_percentage.is_some_and(|percentage| percentage >= min_completion_percentage);
+
+        let proof = LeoProofCall {
+            transition: "prove_completion_rate".to_string(),
+            output_type: "PerformanceProof".to_string(),
+            inputs: vec![
+                LeoInput::new("language_required", "field", inputs.language.clone()),
+                LeoInput::new(
+                    "min_completion",
+                    "u8",
+                    format!("{}u8", min_completion_percentage),
+                ),
+                LeoInput::new("current_time", "u32", format!("{}u32", current_time)),
+            ],
+            expected: vec![
+                ("meets_threshold".to_string(), meets_threshold.to_string()),
+                ("language".to_string(), "language_required".to_string()),
+            ],
+        };
+
+        Ok(LeoFixture {
+            name: name.to_string(),
+            should_fail: !inputs.is_valid() || completion_percentage.is_none(),
+            certificate: inputs,
+            proof: Some(proof),
+        })
+    }
+
+    /// Map a claim to its Leo transition and compute the expected outputs
+    ///
+    /// Returns the call and whether the certificate language differs from the requested one.
+    fn proof_call(
+        &self,
+        certificate: &CertificateData,
+        inputs: &LeoCertificateInputs,
+        claim_type: &ClaimType,
+        current_time: u32,
+    ) -> Result<(LeoProofCall, bool), FixtureError> {
+        let time_input = LeoInput::new("current_time", "u32", format!("{}u32", current_time));
+
+        match claim_type {
+            ClaimType::LanguageProficiency {
+                language,
+                min_level,
+            } => {
+                let evaluation = self.evaluate(certificate, claim_type)?;
+                Ok((
+                    LeoProofCall {
+                        transition: "prove_language_proficiency".to_string(),
+                        output_type: "LanguageProficiencyProof".to_string(),
+                        inputs: vec![
+                            LeoInput::new(
+                                "language_required",
+                                "field",
+                                encode_language_to_field(language),
+                            ),
+                            LeoInput::new(
+                                "min_level",
+                                "u8",
+                                format!("{}u8", cefr_level_to_u8(min_level)),
+                            ),
+                            time_input,
+                        ],
+                        expected: evaluation
+                            .map(|meets| {
+                                vec![
+                                    ("meets_requirement".to_string(), meets.to_string()),
+                                    ("language".to_string(), "language_required".to_string()),
+                                    ("min_level_required".to_string(), "min_level".to_string()),
+                                ]
+                            })
+                            .unwrap_or_default(),
+                    },
+                    evaluation.is_none(),
+                ))
+            }
+            ClaimType::PerformanceThreshold { min_percentage } => {
+                let meets = self.issuer.evaluate_claim(certificate, claim_type)?;
+                Ok((
+                    LeoProofCall {
+                        transition: "prove_performance_threshold".to_string(),
+                        output_type: "PerformanceProof".to_string(),
+                        inputs: vec![
+                            LeoInput::new("language_required", "field", inputs.language.clone()),
+                            LeoInput::new("min_score", "u8", format!("{}u8", min_percentage)),
+                            time_input,
+                        ],
+                        expected: vec![
+                            ("meets_threshold".to_string(), meets.to_string()),
+                            ("language".to_string(), "language_required".to_string()),
+                            ("min_score_required".to_string(), "min_score".to_string()),
+                        ],
+                    },
+                    false,
+                ))
+            }
+            ClaimType::CompletionDate { after_date } => {
+                let meets = self.issuer.evaluate_claim(certificate, claim_type)?;
+                let min_issued_time = u32::try_from(after_date.timestamp()).map_err(|_| {
+                    FixtureError::OutOfRange {
+                        field: "min_issued_time".to_string(),
+                        value: after_date.to_rfc3339(),
+                    }
+                })?;
+                Ok((
+                    LeoProofCall {
+                        transition: "prove_certificate_recency".to_string(),
+                        output_type: "LanguageProficiencyProof".to_string(),
+                        inputs: vec![
+                            LeoInput::new("language_required", "field", inputs.language.clone()),
+                            LeoInput::new(
+                                "min_issued_time",
+                                "u32",
+                                format!("{}u32", min_issued_time),
+                            ),
+                            time_input,
+                        ],
+                        expected: vec![
+                            ("meets_requirement".to_string(), meets.to_string()),
+                            ("language".to_string(), "language_required".to_string()),
+                        ],
+                    },
+                    false,
+                ))
+            }
+            ClaimType::IdentityBound { .. }
+            | ClaimType::NationalityNotIn { .. }
+            | ClaimType::DocumentValid { .. } => Err(FixtureError::UnsupportedClaim(
+                "identity claims are verified off-chain".to_string(),
+            )),
+            ClaimType::Combined { criteria } => {
+                let language_claim = criteria
+                    .iter()
+                    .find(|criterion| matches!(criterion, ClaimType::LanguageProficiency { .. }));
+                let min_percentage = criteria.iter().find_map(|criterion| match criterion {
+                    ClaimType::PerformanceThreshold { min_percentage } => Some(*min_percentage),
+                    _ => None,
+                });
+
+                let (
+                    Some(
+                        language_claim @ ClaimType::LanguageProficiency {
+                            language,
+                            min_level,
+                        },
+                    ),
+                    Some(min_percentage),
+                ) = (language_claim, min_percentage)
+                else {
+                    return Err(FixtureError::UnsupportedClaim(
+                        "combined claims need a language and a performance criterion".to_string(),
+                    ));
+                };
+                if criteria.len() != 2 {
+                    return Err(FixtureError::UnsupportedClaim(
+                        "combined claims need a language and a performance criterion".to_string(),
+                    ));
+                }
+
+                let meets_language = self.evaluate(certificate, language_claim)?;
+                let meets_performance = self.issuer.evaluate_claim(
+                    certificate,
+                    &ClaimType::PerformanceThreshold { min_percentage },
+                )?;
+
+                Ok((
+                    LeoProofCall {
+                        transition: "prove_combined_requirements".to_string(),
+                        output_type: "CombinedProof".to_string(),
+                        inputs: vec![
+                            LeoInput::new(
+                                "language_required",
+                                "field",
+                                encode_language_to_field(language),
+                            ),
+                            LeoInput::new(
+                                "min_level",
+                                "u8",
+                                format!("{}u8", cefr_level_to_u8(min_level)),
+                            ),
+                            LeoInput::new("min_score", "u8", format!("{}u8", min_percentage)),
+                            time_input,
+                        ],
+                        expected: meets_language
+                            .map(|meets| {
+                                vec![
+                                    ("meets_language_requirement".to_string(), meets.to_string()),
+                                    (
+                                        "meets_performance_requirement".to_string(),
+                                        meets_performance.to_string(),
+                                    ),
+                                    ("language".to_string(), "language_required".to_string()),
+                                ]
+                            })
+                            .unwrap_or_default(),
+                    },
+                    meets_language.is_none(),
+                ))
+            }
+        }
+    }
+
+    /// Evaluate a language claim, mapping a language mismatch to `None`
+    fn evaluate(
+        &self,
+        certificate: &CertificateData,
+        claim_type: &ClaimType,
+    ) -> Result<Option<bool>, FixtureError> {
+        match self.issuer.evaluate_claim(certificate, claim_type) {
+            Ok(meets) => Ok(Some(meets)),
+            Err(IssuerError::InvalidCertificate(_)) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+impl LeoTestSuite {
+    /// Create an empty test suite
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a fixture to the suite
+    pub fn add(&mut self, fixture: LeoFixture) -> &mut Self {
+        self.fixtures.push(fixture);
+        self
+    }
+
+    /// Render the suite as a complete Leo test program
+    pub fn render(&self) -> String {
+        let tests: Vec<String> = self.fixtures.iter().map(LeoFixture::to_leo_test).collect();
+
+        format!(
+            "// Generated by `just leo-fixtures` from the Rust reference logic. Do not edit by hand.\n\
+             import {};\n\nprogram {} {{\n\n{}}}\n",
+            LEO_PROGRAM_ID,
+            LEO_TEST_PROGRAM_ID,
+            tests.join("\n")
+        )
+    }
+}
+
+/// Check that a string looks like an Aleo address
+fn validate_address(address: &str) -> Result<(), FixtureError> {
+    let valid = address.len() == 63
+        && address.starts_with("aleo1")
+        && address[5..]
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit());
+
+    if valid {
+        Ok(())
+    } else {
+        Err(FixtureError::InvalidAddress(address.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issuer::ProofOptions;
+    use chrono::{TimeZone, Utc};
+
+    fn create_test_certificate() -> CertificateData {
+        CertificateData::new(
+            "German_B2_Complete".to_string(),
+            50,
+            47,
+            "Test Student".to_string(),
+            Utc.timestamp_opt(1703664000, 0).unwrap(),
+        )
+    }
+
+    fn create_test_generator() -> LeoFixtureGenerator {
+        LeoFixtureGenerator::new(
+            CertificateIssuer::new(
+                "leo_fixture_issuer".to_string(),
+                "Leo Fixture Issuer".to_string(),
+            )
+            .accept_unsigned_certificates(),
+        )
+    }
+
+    fn create_request(claim_type: ClaimType) -> ProofRequest {
+        ProofRequest {
+            certificate: create_test_certificate(),
+            claim_type,
+            target_platform: "aleo".to_string(),
+            options: ProofOptions::default(),
+        }
+    }
+
+    #[test]
+    fn test_certificate_inputs() {
+        let inputs =
+            LeoCertificateInputs::from_certificate(&create_test_certificate(), DEFAULT_RECIPIENT)
+                .unwrap();
+
+        assert!(inputs.is_valid());
+        assert_eq!(
+            inputs.to_args(),
+            vec![
+                DEFAULT_RECIPIENT.to_string(),
+                "1field".to_string(),
+                "4u8".to_string(),
+                "94u8".to_string(),
+                "50u16".to_string(),
+                "47u16".to_string(),
+                "1703664000u32".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_recipient() {
+        let result = create_test_generator().with_recipient("not_an_address");
+        assert!(matches!(result, Err(FixtureError::InvalidAddress(_))));
+    }
+
+    #[test]
+    fn test_language_fixture_matches_reference() {
+        let generator = create_test_generator();
+
+        let passing = generator
+            .proof_fixture(
+                "b1",
+                &create_request(ClaimType::LanguageProficiency {
+                    language: "German".to_string(),
+                    min_level: CefrLevel::B1,
+                }),
+            )
+            .unwrap();
+        let failing = generator
+            .proof_fixture(
+                "c1",
+                &create_request(ClaimType::LanguageProficiency {
+                    language: "German".to_string(),
+                    min_level: CefrLevel::C1,
+                }),
+            )
+            .unwrap();
+
+        let passing_proof = passing.proof.unwrap();
+        assert_eq!(passing_proof.transition, "prove_language_proficiency");
+        assert!(passing_proof
+            .expected
+            .contains(&("meets_requirement".to_string(), "true".to_string())));
+        assert!(failing
+            .proof
+            .unwrap()
+            .expected
+            .contains(&("meets_requirement".to_string(), "false".to_string())));
+    }
+
+    #[test]
+    fn test_language_mismatch_should_fail() {
+        let fixture = create_test_generator()
+            .proof_fixture(
+                "spanish",
+                &create_request(ClaimType::LanguageProficiency {
+                    language: "Spanish".to_string(),
+                    min_level: CefrLevel::A1,
+                }),
+            )
+            .unwrap();
+
+        assert!(fixture.should_fail);
+        assert!(fixture.to_leo_test().contains("@should_fail"));
+    }
+
+    #[test]
+    fn test_completion_rate_fixture() {
+        let generator = create_test_generator();
+        let fixture = generator
+            .completion_rate_fixture("rate", &create_test_certificate(), 90)
+            .unwrap();
+        assert!(!fixture.should_fail);
+        assert!(fixture
+            .proof
+            .unwrap()
+            .expected
+            .contains(&("meets_threshold".to_string(), "true".to_string())));
+
+        // 700 * 100 overflows the u16 the Leo transition computes in
+        let large = CertificateData::new(
+            "German_B2_Complete".to_string(),
+            700,
+            700,
+            "Test Student".to_string(),
+            Utc.timestamp_opt(1703664000, 0).unwrap(),
+        );
+        let fixture = generator
+            .completion_rate_fixture("overflow", &large, 90)
+            .unwrap();
+        assert!(fixture.should_fail);
+    }
+
+    #[test]
+    fn test_combined_fixture() {
+        let fixture = create_test_generator()
+            .proof_fixture(
+                "combined",
+                &create_request(ClaimType::Combined {
+                    criteria: vec![
+                        ClaimType::PerformanceThreshold { min_percentage: 95 },
+                        ClaimType::LanguageProficiency {
+                            language: "German".to_string(),
+                            min_level: CefrLevel::B1,
+                        },
+                    ],
+                }),
+            )
+            .unwrap();
+
+        let proof = fixture.proof.unwrap();
+        assert_eq!(proof.transition, "prove_combined_requirements");
+        assert!(proof
+            .expected
+            .contains(&("meets_language_requirement".to_string(), "true".to_string())));
+        assert!(proof.expected.contains(&(
+            "meets_performance_requirement".to_string(),
+            "false".to_string()
+        )));
+    }
+
+    #[test]
+    fn test_unsupported_combined_claim() {
+        let result = create_test_generator().proof_fixture(
+            "unsupported",
+            &create_request(ClaimType::Combined {
+                criteria: vec![ClaimType::PerformanceThreshold { min_percentage: 90 }],
+            }),
+        );
+
+        assert!(matches!(result, Err(FixtureError::UnsupportedClaim(_))));
+    }
+
+    #[test]
+    fn test_run_args() {
+        let fixture = create_test_generator()
+            .proof_fixture(
+                "performance",
+                &create_request(ClaimType::PerformanceThreshold { min_percentage: 90 }),
+            )
+            .unwrap();
+
+        assert_eq!(fixture.issue_run_args()[0], "issue_certificate");
+        assert_eq!(
+            fixture.proof_run_args("{record}").unwrap(),
+            vec![
+                "prove_performance_threshold".to_string(),
+                "{record}".to_string(),
+                "1field".to_string(),
+                "90u8".to_string(),
+                "1703664100u32".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_suite() {
+        let generator = create_test_generator();
+        let mut invalid =
+            LeoCertificateInputs::from_certificate(&create_test_certificate(), DEFAULT_RECIPIENT)
+                .unwrap();
+        invalid.level = 7;
+
+        let mut suite = LeoTestSuite::new();
+        suite
+            .add(
+                generator
+                    .issue_fixture("issue_certificate", &create_test_certificate())
+                    .unwrap(),
+            )
+            .add(LeoFixture::from_inputs(
+                "invalid_level_should_fail",
+                invalid,
+            ));
+
+        let rendered = suite.render();
+        assert!(rendered.contains("import web5claimsleo.aleo;"));
+        assert!(rendered.contains("program test_web5claimsleo.aleo {"));
+        assert!(rendered.contains("function test_issue_certificate()"));
+        assert!(rendered.contains("let level: u8 = 7u8;"));
+        assert_eq!(rendered.matches("@should_fail").count(), 1);
+    }
+}
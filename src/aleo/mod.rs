@@ -0,0 +1,4 @@
+pub mod fixtures;
+pub mod program;
+
+pub use fixtures::{FixtureError, LeoCertificateInputs, LeoFixture, LeoProofCall, LeoTestSuite};
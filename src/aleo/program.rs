@@ -17,16 +17,17 @@ pub fn encode_language_to_field(language: &str) -> String {
         "japanese" => "10field",
         _ => "0field", // Unknown language
     }
+    .to_string()
 }
 
-pub fn cefr_level_to_u8(level: &web5claims::CefrLevel) -> u8 {
+pub fn cefr_level_to_u8(level: &crate::zk_proof::CefrLevel) -> u8 {
     match level {
-        web5claims::CefrLevel::A1 => 1,
-        web5claims::CefrLevel::A2 => 2,
-        web5claims::CefrLevel::B1 => 3,
-        web5claims::CefrLevel::B2 => 4,
-        web5claims::CefrLevel::C1 => 5,
-        web5claims::CefrLevel::C2 => 6,
+        crate::zk_proof::CefrLevel::A1 => 1,
+        crate::zk_proof::CefrLevel::A2 => 2,
+        crate::zk_proof::CefrLevel::B1 => 3,
+        crate::zk_proof::CefrLevel::B2 => 4,
+        crate::zk_proof::CefrLevel::C1 => 5,
+        crate::zk_proof::CefrLevel::C2 => 6,
     }
 }
 
@@ -42,7 +43,7 @@ pub fn certificate_to_leo_inputs(
     recipient_address: &str,
 ) -> Vec<String> {
     let language = cert.game_path_name.split('_').next().unwrap_or("unknown");
-    let level = web5claims::CefrLevel::from_course_name(&cert.game_path_name)
+    let level = crate::zk_proof::CefrLevel::from_course_name(&cert.game_path_name)
         .map(|l| cefr_level_to_u8(&l))
         .unwrap_or(1);
 
@@ -58,7 +59,10 @@ pub fn certificate_to_leo_inputs(
 }
 
 // Generate inputs for language proficiency proof
-pub fn language_proof_inputs(language: &str, min_level: &web5claims::CefrLevel) -> Vec<String> {
+pub fn language_proof_inputs(
+    language: &str,
+    min_level: &crate::zk_proof::CefrLevel,
+) -> Vec<String> {
     vec![
         encode_language_to_field(language),           // language_required
         format!("{}u8", cefr_level_to_u8(min_level)), // min_level
@@ -78,7 +82,7 @@ pub fn performance_proof_inputs(language: &str, min_score: u8) -> Vec<String> {
 // Generate inputs for combined proof
 pub fn combined_proof_inputs(
     language: &str,
-    min_level: &web5claims::CefrLevel,
+    min_level: &crate::zk_proof::CefrLevel,
     min_score: u8,
 ) -> Vec<String> {
     vec![
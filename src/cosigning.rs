@@ -0,0 +1,397 @@
+//! k-of-n co-signing of proofs.
+//!
+//! Accredited certificates need sign-off from several parties, for example the platform and a
+//! human examiner. Each [`Cosigner`] adds an Ed25519 signature over the claim to the
+//! `issuer_cosignatures` metadata property, and a [`CosigningPolicy`] accepts the claim once
+//! at least `threshold` of its listed signers have signed.
+//!
+//! This is a plain multi-signature: every co-signature is a separate Ed25519 signature that
+//! is verified and counted on its own. Signatures are not aggregated and there is no FROST
+//! threshold key, so a claim grows with each co-signer and verifiers see who signed.
+
+use crate::zk_proof::{ZkProofClaim, COSIGNATURES_PROPERTY};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum CosigningError {
+    #[error("Invalid co-signing policy: {0}")]
+    InvalidPolicy(String),
+    #[error("Malformed co-signatures: {0}")]
+    MalformedCosignatures(String),
+    #[error("Signer quorum not met: {valid} of {required} required co-signatures")]
+    QuorumNotMet { required: usize, valid: usize },
+}
+
+/// Signature of one co-signer over a claim
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Cosignature {
+    /// Identifier of the co-signer
+    pub signer_id: String,
+    /// Hex-encoded Ed25519 signature over the claim
+    pub signature: String,
+}
+
+/// Party co-signing claims with its own key
+#[derive(Debug, Clone)]
+pub struct Cosigner {
+    /// Identifier of the co-signer
+    pub signer_id: String,
+    signing_key: SigningKey,
+}
+
+/// Co-signer accepted by a policy
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PolicySigner {
+    /// Identifier of the co-signer
+    pub signer_id: String,
+    /// Hex-encoded public key
+    pub public_key: String,
+}
+
+/// Quorum of co-signers a claim needs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CosigningPolicy {
+    /// Number of valid co-signatures required
+    pub threshold: usize,
+    /// Accepted co-signers
+    pub signers: Vec<PolicySigner>,
+}
+
+impl Cosigner {
+    /// Create a co-signer
+    pub fn new(signer_id: String, signing_key: SigningKey) -> Self {
+        Self {
+            signer_id,
+            signing_key,
+        }
+    }
+
+    /// Public key of the co-signer
+    pub fn public_key(&self) -> VerifyingKey {
+        self.signing_key.verifying_key()
+    }
+
+    /// Add the co-signature to a claim, replacing an earlier one by the same signer
+    pub fn cosign(&self, proof: &mut ZkProofClaim) -> Result<(), CosigningError> {
+        let signature = self.signing_key.sign(&proof.issuer_signing_bytes());
+        let mut cosignatures = cosignatures(proof)?;
+        cosignatures.retain(|cosignature| cosignature.signer_id != self.signer_id);
+        cosignatures.push(Cosignature {
+            signer_id: self.signer_id.clone(),
+            signature: hex::encode(signature.to_bytes()),
+        });
+
+        let json = serde_json::to_string(&cosignatures)
+            .map_err(|e| CosigningError::MalformedCosignatures(e.to_string()))?;
+        proof
+            .metadata
+            .properties
+            .insert(COSIGNATURES_PROPERTY.to_string(), json);
+        Ok(())
+    }
+}
+
+impl CosigningPolicy {
+    /// Create a policy requiring `threshold` co-signatures
+    pub fn new(threshold: usize) -> Self {
+        Self {
+            threshold,
+            signers: Vec::new(),
+        }
+    }
+
+    /// Accept a co-signer
+    pub fn with_signer(mut self, signer_id: String, public_key: &VerifyingKey) -> Self {
+        self.signers.push(PolicySigner {
+            signer_id,
+            public_key: hex::encode(public_key.as_bytes()),
+        });
+        self
+    }
+
+    /// Check that the threshold is reachable and signer ids and keys are unique
+    ///
+    /// A key listed under two ids would let one party sign for both, so it only counts once.
+    pub fn validate(&self) -> Result<(), CosigningError> {
+        if self.threshold == 0 || self.threshold > self.signers.len() {
+            return Err(CosigningError::InvalidPolicy(format!(
+                "threshold {} with {} signers",
+                self.threshold,
+                self.signers.len()
+            )));
+        }
+
+        let mut signer_ids = HashSet::new();
+        let mut public_keys = HashSet::new();
+        for signer in &self.signers {
+            if !signer_ids.insert(&signer.signer_id) {
+                return Err(CosigningError::InvalidPolicy(format!(
+                    "duplicate signer {}",
+                    signer.signer_id
+                )));
+            }
+            let public_key = decode_public_key(&signer.public_key).ok_or_else(|| {
+                CosigningError::InvalidPolicy(format!(
+                    "invalid public key of signer {}",
+                    signer.signer_id
+                ))
+            })?;
+            if !public_keys.insert(public_key.to_bytes()) {
+                return Err(CosigningError::InvalidPolicy(format!(
+                    "duplicate public key of signer {}",
+                    signer.signer_id
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Verify the co-signatures of a claim against the quorum
+    ///
+    /// Returns the ids of the signers with a valid co-signature, in policy order. Signatures by
+    /// unknown signers and invalid signatures do not count towards the quorum.
+    pub fn verify(&self, proof: &ZkProofClaim) -> Result<Vec<String>, CosigningError> {
+        self.validate()?;
+
+        let cosignatures = cosignatures(proof)?;
+        let message = proof.issuer_signing_bytes();
+        let signers: Vec<String> = self
+            .signers
+            .iter()
+            .filter(|signer| {
+                cosignatures
+                    .iter()
+                    .filter(|cosignature| cosignature.signer_id == signer.signer_id)
+                    .any(|cosignature| verify_cosignature(signer, cosignature, &message))
+            })
+            .map(|signer| signer.signer_id.clone())
+            .collect();
+
+        if signers.len() < self.threshold {
+            return Err(CosigningError::QuorumNotMet {
+                required: self.threshold,
+                valid: signers.len(),
+            });
+        }
+
+        Ok(signers)
+    }
+}
+
+/// Co-signatures attached to a claim
+pub fn cosignatures(proof: &ZkProofClaim) -> Result<Vec<Cosignature>, CosigningError> {
+    match proof.metadata.properties.get(COSIGNATURES_PROPERTY) {
+        Some(json) => serde_json::from_str(json)
+            .map_err(|e| CosigningError::MalformedCosignatures(e.to_string())),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn decode_public_key(public_key: &str) -> Option<VerifyingKey> {
+    hex::decode(public_key)
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+}
+
+fn verify_cosignature(signer: &PolicySigner, cosignature: &Cosignature, message: &[u8]) -> bool {
+    let public_key = decode_public_key(&signer.public_key);
+    let signature = hex::decode(&cosignature.signature)
+        .ok()
+        .and_then(|bytes| <[u8; 64]>::try_from(bytes).ok())
+        .map(|bytes| Signature::from_bytes(&bytes));
+
+    match (public_key, signature) {
+        (Some(public_key), Some(signature)) => public_key.verify(message, &signature).is_ok(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issuer::{CertificateIssuer, ProofOptions, ProofRequest};
+    use crate::zk_proof::{CefrLevel, ClaimType};
+    use chrono::Utc;
+    use konnektoren_core::certificates::CertificateData;
+
+    fn create_test_proof() -> ZkProofClaim {
+        let issuer = CertificateIssuer::new("test_issuer".to_string(), "Test Issuer".to_string())
+            .accept_unsigned_certificates();
+        let request = ProofRequest {
+            certificate: CertificateData::new(
+                "German_B2_Complete".to_string(),
+                7,
+                7,
+                "Test Student".to_string(),
+                Utc::now(),
+            ),
+            claim_type: ClaimType::LanguageProficiency {
+                language: "German".to_string(),
+                min_level: CefrLevel::B1,
+            },
+            target_platform: "test".to_string(),
+            options: ProofOptions::default(),
+        };
+
+        issuer.generate_proof(request).unwrap()
+    }
+
+    fn create_test_cosigners() -> Vec<Cosigner> {
+        ["platform", "examiner", "school"]
+            .iter()
+            .enumerate()
+            .map(|(i, id)| {
+                Cosigner::new(id.to_string(), SigningKey::from_bytes(&[i as u8 + 1; 32]))
+            })
+            .collect()
+    }
+
+    fn create_test_policy(threshold: usize, cosigners: &[Cosigner]) -> CosigningPolicy {
+        cosigners
+            .iter()
+            .fold(CosigningPolicy::new(threshold), |policy, cosigner| {
+                policy.with_signer(cosigner.signer_id.clone(), &cosigner.public_key())
+            })
+    }
+
+    #[test]
+    fn test_quorum_met() {
+        let cosigners = create_test_cosigners();
+        let mut proof = create_test_proof();
+        cosigners[1].cosign(&mut proof).unwrap();
+        cosigners[0].cosign(&mut proof).unwrap();
+
+        let signers = create_test_policy(2, &cosigners).verify(&proof).unwrap();
+        assert_eq!(signers, vec!["platform", "examiner"]);
+        assert!(proof.verify_integrity());
+    }
+
+    #[test]
+    fn test_quorum_not_met() {
+        let cosigners = create_test_cosigners();
+        let mut proof = create_test_proof();
+        let policy = create_test_policy(2, &cosigners);
+
+        assert_eq!(
+            policy.verify(&proof),
+            Err(CosigningError::QuorumNotMet {
+                required: 2,
+                valid: 0
+            })
+        );
+
+        // Signing twice does not count twice
+        cosigners[0].cosign(&mut proof).unwrap();
+        cosigners[0].cosign(&mut proof).unwrap();
+        assert_eq!(cosignatures(&proof).unwrap().len(), 1);
+        assert_eq!(
+            policy.verify(&proof),
+            Err(CosigningError::QuorumNotMet {
+                required: 2,
+                valid: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_unknown_signer_ignored() {
+        let cosigners = create_test_cosigners();
+        let mut proof = create_test_proof();
+        cosigners[0].cosign(&mut proof).unwrap();
+        Cosigner::new("examiner".to_string(), SigningKey::from_bytes(&[42u8; 32]))
+            .cosign(&mut proof)
+            .unwrap();
+        Cosigner::new("outsider".to_string(), SigningKey::from_bytes(&[43u8; 32]))
+            .cosign(&mut proof)
+            .unwrap();
+
+        assert!(matches!(
+            create_test_policy(2, &cosigners).verify(&proof),
+            Err(CosigningError::QuorumNotMet { valid: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_tampered_claim_fails_quorum() {
+        let cosigners = create_test_cosigners();
+        let mut proof = create_test_proof();
+        for cosigner in &cosigners {
+            cosigner.cosign(&mut proof).unwrap();
+        }
+        proof.public_inputs.verification_result = false;
+
+        assert!(matches!(
+            create_test_policy(1, &cosigners).verify(&proof),
+            Err(CosigningError::QuorumNotMet { valid: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_policy() {
+        let cosigners = create_test_cosigners();
+        let proof = create_test_proof();
+
+        assert!(matches!(
+            create_test_policy(0, &cosigners).verify(&proof),
+            Err(CosigningError::InvalidPolicy(_))
+        ));
+        assert!(matches!(
+            create_test_policy(4, &cosigners).verify(&proof),
+            Err(CosigningError::InvalidPolicy(_))
+        ));
+        let duplicate = create_test_policy(1, &cosigners)
+            .with_signer("platform".to_string(), &cosigners[1].public_key());
+        assert_eq!(
+            duplicate.validate(),
+            Err(CosigningError::InvalidPolicy(
+                "duplicate signer platform".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_duplicate_public_key_rejected() {
+        let cosigners = create_test_cosigners();
+        let mut proof = create_test_proof();
+        cosigners[0].cosign(&mut proof).unwrap();
+        // The platform key listed a second time under the examiner's id
+        let sybil = Cosigner::new("examiner".to_string(), SigningKey::from_bytes(&[1u8; 32]));
+        sybil.cosign(&mut proof).unwrap();
+
+        let policy = CosigningPolicy::new(2)
+            .with_signer("platform".to_string(), &cosigners[0].public_key())
+            .with_signer("examiner".to_string(), &sybil.public_key());
+        assert_eq!(
+            policy.verify(&proof),
+            Err(CosigningError::InvalidPolicy(
+                "duplicate public key of signer examiner".to_string()
+            ))
+        );
+
+        let mut malformed = create_test_policy(1, &cosigners);
+        malformed.signers[2].public_key = "zz".to_string();
+        assert!(matches!(
+            malformed.validate(),
+            Err(CosigningError::InvalidPolicy(_))
+        ));
+    }
+
+    #[test]
+    fn test_malformed_cosignatures() {
+        let mut proof = create_test_proof();
+        proof
+            .metadata
+            .properties
+            .insert(COSIGNATURES_PROPERTY.to_string(), "not json".to_string());
+
+        assert!(matches!(
+            cosignatures(&proof),
+            Err(CosigningError::MalformedCosignatures(_))
+        ));
+        assert!(create_test_cosigners()[0].cosign(&mut proof).is_err());
+    }
+}
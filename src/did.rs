@@ -0,0 +1,866 @@
+//! Decentralized identifiers for issuers.
+//!
+//! Issuers are identified by `did:key` identifiers derived from their Ed25519 key, or by
+//! `did:web` identifiers whose DID document is published on the issuer's domain. A `did:web`
+//! document may also list ML-DSA-65 or hybrid Ed25519+ML-DSA-65 keys, as `JsonWebKey`
+//! verification methods with an `AKP` JWK. Documents are
+//! fetched through a [`DidResolver`]; [`FileDidResolver`] serves them from a local directory
+//! laid out like the web server would, for tests and offline verification.
+//!
+//! On the issuer side, [`DidWebPublisher`] generates the `did.json` of a `did:web` issuer and
+//! rotates its key. Retired keys stay in the document as assertion methods with the time they
+//! were retired, so proofs signed before a rotation keep verifying and later ones do not.
+
+use crate::issuer::CertificateIssuer;
+use crate::signature::{IssuerPublicKey, SignatureAlgorithm};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// JSON-LD context of DID documents
+pub const DID_CONTEXT: &str = "https://www.w3.org/ns/did/v1";
+/// JSON-LD context of `Multikey` verification methods
+pub const MULTIKEY_CONTEXT: &str = "https://w3id.org/security/multikey/v1";
+/// Verification method type of multibase encoded keys
+pub const MULTIKEY_TYPE: &str = "Multikey";
+/// Verification method type of JWK encoded keys
+pub const JSON_WEB_KEY_TYPE: &str = "JsonWebKey";
+/// JWK key type of ML-DSA and hybrid keys, whose `alg` names the signature algorithm
+const AKP_KEY_TYPE: &str = "AKP";
+/// Service type of credential status list endpoints
+pub const STATUS_LIST_SERVICE_TYPE: &str = "CredentialStatusList";
+/// Service type of proof request endpoints
+pub const PROOF_REQUEST_SERVICE_TYPE: &str = "ProofRequestEndpoint";
+/// Multicodec prefix of Ed25519 public keys
+const ED25519_MULTICODEC: [u8; 2] = [0xed, 0x01];
+
+#[derive(Error, Debug, PartialEq)]
+pub enum DidError {
+    #[error("Invalid DID: {0}")]
+    InvalidDid(String),
+    #[error("Unsupported DID method: {0}")]
+    UnsupportedMethod(String),
+    #[error("Invalid public key: {0}")]
+    InvalidKey(String),
+    #[error("DID not found: {0}")]
+    NotFound(String),
+    #[error("Invalid DID document: {0}")]
+    InvalidDocument(String),
+    #[error("Verification method {0} is not an assertion method of the DID")]
+    UnknownVerificationMethod(String),
+    #[error("Signing key is not the current key of the DID document")]
+    KeyMismatch,
+    #[error("Verification method {0} was retired at {1}")]
+    RetiredKey(String, DateTime<Utc>),
+    #[error("IO error: {0}")]
+    Io(String),
+}
+
+/// DID document
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DidDocument {
+    /// JSON-LD contexts
+    #[serde(rename = "@context", default)]
+    pub context: Vec<String>,
+    /// The DID the document describes
+    pub id: String,
+    /// Keys of the DID subject
+    #[serde(default)]
+    pub verification_method: Vec<VerificationMethod>,
+    /// Verification methods the subject authenticates with
+    #[serde(default)]
+    pub authentication: Vec<String>,
+    /// Verification methods the subject issues credentials with
+    #[serde(default)]
+    pub assertion_method: Vec<String>,
+    /// Endpoints of the DID subject
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub service: Vec<Service>,
+}
+
+/// Public key in a DID document
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationMethod {
+    /// Verification method identifier, `<did>#<fragment>`
+    pub id: String,
+    /// Verification method type, e.g. `Multikey` or `JsonWebKey2020`
+    #[serde(rename = "type")]
+    pub method_type: String,
+    /// DID controlling the key
+    pub controller: String,
+    /// Multibase encoded public key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key_multibase: Option<String>,
+    /// Public key as JWK
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key_jwk: Option<Value>,
+    /// When the key was retired; nothing it signed afterwards is accepted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoked: Option<DateTime<Utc>>,
+}
+
+/// Service endpoint in a DID document
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Service {
+    /// Service identifier, `<did>#<fragment>`
+    pub id: String,
+    /// Service type, e.g. [`STATUS_LIST_SERVICE_TYPE`]
+    #[serde(rename = "type")]
+    pub service_type: String,
+    /// URL of the service
+    pub service_endpoint: String,
+}
+
+/// Issuer side of a `did:web` identity: the current signing key, retired keys and services
+///
+/// The current key authenticates the issuer and signs new proofs. After [`rotate`](Self::rotate)
+/// the previous key is kept as a historical assertion method, marked with its retirement time.
+#[derive(Debug, Clone)]
+pub struct DidWebPublisher {
+    /// The `did:web` identifier
+    pub did: String,
+    /// Fragment of the current key's verification method
+    pub current_key_id: String,
+    /// Current signing key
+    signing_key: SigningKey,
+    /// Verification methods of retired keys, oldest first
+    pub historical_keys: Vec<VerificationMethod>,
+    /// Published service endpoints
+    pub services: Vec<Service>,
+}
+
+/// Resolves DIDs to their documents
+///
+/// Implementations fetching `did:web` documents over HTTPS plug in here; they should resolve
+/// `did:key` identifiers with [`resolve_did_key`].
+pub trait DidResolver: std::fmt::Debug + Send + Sync {
+    /// Resolve a DID to its document
+    fn resolve(&self, did: &str) -> Result<DidDocument, DidError>;
+}
+
+/// Resolver serving `did:web` documents from a directory
+///
+/// `did:web:example.com` resolves to `<root>/example.com/.well-known/did.json` and
+/// `did:web:example.com:issuers:1` to `<root>/example.com/issuers/1/did.json`. `did:key`
+/// identifiers are resolved without touching the file system.
+#[derive(Debug, Clone)]
+pub struct FileDidResolver {
+    /// Directory holding one subdirectory per domain
+    pub root: PathBuf,
+}
+
+/// Derive the `did:key` identifier of an Ed25519 key
+pub fn did_key(public_key: &VerifyingKey) -> String {
+    format!("did:key:{}", encode_multikey(public_key))
+}
+
+/// Resolve a `did:key` identifier to its document
+pub fn resolve_did_key(did: &str) -> Result<DidDocument, DidError> {
+    let multikey = did
+        .strip_prefix("did:key:")
+        .ok_or_else(|| DidError::InvalidDid(did.to_string()))?;
+    let public_key = decode_multikey(multikey)?;
+
+    Ok(DidDocument::for_key(
+        did.to_string(),
+        multikey.to_string(),
+        &public_key,
+    ))
+}
+
+/// URL of the DID document of a `did:web` identifier
+pub fn did_web_url(did: &str) -> Result<String, DidError> {
+    let segments = did_web_segments(did)?;
+    let (domain, path) = segments
+        .split_first()
+        .ok_or_else(|| DidError::InvalidDid(did.to_string()))?;
+
+    if path.is_empty() {
+        Ok(format!("https://{}/.well-known/did.json", domain))
+    } else {
+        Ok(format!("https://{}/{}/did.json", domain, path.join("/")))
+    }
+}
+
+impl DidDocument {
+    /// Document with a single Ed25519 key used for authentication and assertions
+    pub fn for_key(did: String, fragment: String, public_key: &VerifyingKey) -> Self {
+        let method_id = format!("{}#{}", did, fragment);
+
+        Self {
+            context: vec![DID_CONTEXT.to_string(), MULTIKEY_CONTEXT.to_string()],
+            verification_method: vec![VerificationMethod {
+                id: method_id.clone(),
+                method_type: MULTIKEY_TYPE.to_string(),
+                controller: did.clone(),
+                public_key_multibase: Some(encode_multikey(public_key)),
+                public_key_jwk: None,
+                revoked: None,
+            }],
+            authentication: vec![method_id.clone()],
+            assertion_method: vec![method_id],
+            service: vec![],
+            id: did,
+        }
+    }
+
+    /// Ed25519 key of an assertion method, by absolute (`<did>#key-1`) or relative (`#key-1`) id
+    pub fn assertion_key(&self, method_id: &str) -> Result<VerifyingKey, DidError> {
+        self.assertion_method(method_id)?.public_key()
+    }
+
+    /// Key of an assertion method for any signature algorithm, if it was not retired by `at`
+    pub fn assertion_key_at(
+        &self,
+        method_id: &str,
+        at: DateTime<Utc>,
+    ) -> Result<IssuerPublicKey, DidError> {
+        let method = self.assertion_method(method_id)?;
+        if let Some(revoked) = method.revoked.filter(|revoked| at > *revoked) {
+            return Err(DidError::RetiredKey(method.id.clone(), revoked));
+        }
+        method.issuer_public_key()
+    }
+
+    fn assertion_method(&self, method_id: &str) -> Result<&VerificationMethod, DidError> {
+        let absolute = |id: &str| {
+            if id.starts_with('#') {
+                format!("{}{}", self.id, id)
+            } else {
+                id.to_string()
+            }
+        };
+        let method_id = absolute(method_id);
+
+        if !self
+            .assertion_method
+            .iter()
+            .any(|id| absolute(id) == method_id)
+        {
+            return Err(DidError::UnknownVerificationMethod(method_id));
+        }
+
+        self.verification_method
+            .iter()
+            .find(|method| absolute(&method.id) == method_id)
+            .ok_or_else(|| DidError::UnknownVerificationMethod(method_id.clone()))
+    }
+}
+
+impl VerificationMethod {
+    /// Verification method of an issuer key; Ed25519 keys become `Multikey` methods, ML-DSA
+    /// and hybrid keys `JsonWebKey` methods
+    pub fn for_issuer_key(id: String, controller: String, public_key: &IssuerPublicKey) -> Self {
+        if let (SignatureAlgorithm::Ed25519, Some(key)) =
+            (public_key.algorithm(), public_key.ed25519())
+        {
+            return Self {
+                id,
+                method_type: MULTIKEY_TYPE.to_string(),
+                controller,
+                public_key_multibase: Some(encode_multikey(key)),
+                public_key_jwk: None,
+                revoked: None,
+            };
+        }
+
+        Self {
+            id,
+            method_type: JSON_WEB_KEY_TYPE.to_string(),
+            controller,
+            public_key_multibase: None,
+            public_key_jwk: Some(serde_json::json!({
+                "kty": AKP_KEY_TYPE,
+                "alg": public_key.algorithm().as_str(),
+                "pub": URL_SAFE_NO_PAD.encode(public_key.to_bytes()),
+            })),
+            revoked: None,
+        }
+    }
+
+    /// Public key of the method for any signature algorithm
+    pub fn issuer_public_key(&self) -> Result<IssuerPublicKey, DidError> {
+        let Some(jwk) = self
+            .public_key_jwk
+            .as_ref()
+            .filter(|jwk| jwk["kty"] == AKP_KEY_TYPE)
+        else {
+            return self.public_key().map(IssuerPublicKey::Ed25519);
+        };
+
+        let malformed = || DidError::InvalidKey(format!("{} has a malformed key", self.id));
+        let algorithm = jwk["alg"]
+            .as_str()
+            .ok_or_else(malformed)?
+            .parse::<SignatureAlgorithm>()
+            .map_err(|e| DidError::InvalidKey(e.to_string()))?;
+        let bytes = jwk["pub"]
+            .as_str()
+            .and_then(|key| URL_SAFE_NO_PAD.decode(key).ok())
+            .ok_or_else(malformed)?;
+        IssuerPublicKey::from_bytes(algorithm, &bytes)
+            .map_err(|e| DidError::InvalidKey(e.to_string()))
+    }
+
+    /// Ed25519 public key of the method
+    pub fn public_key(&self) -> Result<VerifyingKey, DidError> {
+        if let Some(multibase) = &self.public_key_multibase {
+            return decode_multikey(multibase);
+        }
+
+        let jwk = self
+            .public_key_jwk
+            .as_ref()
+            .ok_or_else(|| DidError::InvalidKey(format!("{} has no public key", self.id)))?;
+        if jwk["kty"] != "OKP" || jwk["crv"] != "Ed25519" {
+            return Err(DidError::InvalidKey(format!(
+                "{} is not an Ed25519 key",
+                self.id
+            )));
+        }
+
+        let bytes: [u8; 32] = jwk["x"]
+            .as_str()
+            .and_then(|x| URL_SAFE_NO_PAD.decode(x).ok())
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| DidError::InvalidKey(format!("{} has a malformed key", self.id)))?;
+        VerifyingKey::from_bytes(&bytes).map_err(|e| DidError::InvalidKey(e.to_string()))
+    }
+}
+
+impl DidWebPublisher {
+    /// Create the identity of a `did:web` issuer with its first key, `key-1`
+    pub fn new(did: String, signing_key: SigningKey) -> Result<Self, DidError> {
+        did_web_segments(&did)?;
+
+        Ok(Self {
+            did,
+            current_key_id: "key-1".to_string(),
+            signing_key,
+            historical_keys: vec![],
+            services: vec![],
+        })
+    }
+
+    /// Create the identity of a `did:web` issuer with a fresh key
+    pub fn generate(did: String) -> Result<Self, DidError> {
+        let mut secret = [0u8; 32];
+        OsRng.fill_bytes(&mut secret);
+        Self::new(did, SigningKey::from_bytes(&secret))
+    }
+
+    /// Restore the identity from its published document and current signing key
+    ///
+    /// The current key is the authentication method matching `signing_key`; other keys of the
+    /// document become historical keys.
+    pub fn from_document(
+        document: &DidDocument,
+        signing_key: SigningKey,
+    ) -> Result<Self, DidError> {
+        let public_key = signing_key.verifying_key();
+        let current = document
+            .verification_method
+            .iter()
+            .find(|method| {
+                document.authentication.contains(&method.id)
+                    && method.public_key().ok() == Some(public_key)
+            })
+            .ok_or(DidError::KeyMismatch)?;
+        let current_key_id = current
+            .id
+            .rsplit_once('#')
+            .map(|(_, fragment)| fragment.to_string())
+            .ok_or_else(|| DidError::InvalidDocument(format!("method id {}", current.id)))?;
+
+        let mut publisher = Self::new(document.id.clone(), signing_key)?;
+        publisher.current_key_id = current_key_id;
+        publisher.historical_keys = document
+            .verification_method
+            .iter()
+            .filter(|method| method.id != current.id)
+            .cloned()
+            .collect();
+        publisher.services = document.service.clone();
+        Ok(publisher)
+    }
+
+    /// Publish a service endpoint, replacing a service with the same fragment
+    pub fn with_service(mut self, fragment: &str, service_type: &str, endpoint: String) -> Self {
+        let id = format!("{}#{}", self.did, fragment);
+        self.services.retain(|service| service.id != id);
+        self.services.push(Service {
+            id,
+            service_type: service_type.to_string(),
+            service_endpoint: endpoint,
+        });
+        self
+    }
+
+    /// Publish the credential status list URL
+    pub fn with_status_list(self, endpoint: String) -> Self {
+        self.with_service("status-list", STATUS_LIST_SERVICE_TYPE, endpoint)
+    }
+
+    /// Publish the endpoint verifiers send proof requests to
+    pub fn with_proof_request_endpoint(self, endpoint: String) -> Self {
+        self.with_service("proof-request", PROOF_REQUEST_SERVICE_TYPE, endpoint)
+    }
+
+    /// Current signing key
+    pub fn signing_key(&self) -> &SigningKey {
+        &self.signing_key
+    }
+
+    /// Verification method id of the current key
+    pub fn verification_method(&self) -> String {
+        format!("{}#{}", self.did, self.current_key_id)
+    }
+
+    /// Replace the signing key, keeping the previous one as a historical key retired as of now
+    ///
+    /// Returns the fragment of the new key's verification method.
+    pub fn rotate(&mut self, signing_key: SigningKey) -> String {
+        let mut retired = self.current_method();
+        retired.revoked = Some(Utc::now());
+        let next = (1..)
+            .map(|n| format!("key-{}", n))
+            .find(|key_id| {
+                let id = format!("{}#{}", self.did, key_id);
+                id != retired.id && self.historical_keys.iter().all(|method| method.id != id)
+            })
+            .expect("key ids are unbounded");
+
+        self.historical_keys.push(retired);
+        self.signing_key = signing_key;
+        self.current_key_id = next.clone();
+        next
+    }
+
+    /// DID document to publish as `did.json`
+    pub fn document(&self) -> DidDocument {
+        let current = self.current_method();
+        let mut assertion_method = vec![current.id.clone()];
+        assertion_method.extend(self.historical_keys.iter().map(|method| method.id.clone()));
+
+        let mut verification_method = vec![current.clone()];
+        verification_method.extend(self.historical_keys.iter().cloned());
+
+        DidDocument {
+            context: vec![DID_CONTEXT.to_string(), MULTIKEY_CONTEXT.to_string()],
+            id: self.did.clone(),
+            verification_method,
+            authentication: vec![current.id],
+            assertion_method,
+            service: self.services.clone(),
+        }
+    }
+
+    /// Write the DID document to `path`, creating missing directories
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), DidError> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| DidError::Io(e.to_string()))?;
+        }
+
+        let json = serde_json::to_string_pretty(&self.document())
+            .map_err(|e| DidError::InvalidDocument(e.to_string()))?;
+        std::fs::write(path, json).map_err(|e| DidError::Io(e.to_string()))
+    }
+
+    /// Issuer signing proofs with the current key
+    pub fn issuer(&self, issuer_name: String) -> CertificateIssuer {
+        CertificateIssuer::new(self.did.clone(), issuer_name).with_did_web(
+            self.did.clone(),
+            &self.current_key_id,
+            self.signing_key.clone(),
+        )
+    }
+
+    fn current_method(&self) -> VerificationMethod {
+        VerificationMethod {
+            id: self.verification_method(),
+            method_type: MULTIKEY_TYPE.to_string(),
+            controller: self.did.clone(),
+            public_key_multibase: Some(encode_multikey(&self.signing_key.verifying_key())),
+            public_key_jwk: None,
+            revoked: None,
+        }
+    }
+}
+
+impl FileDidResolver {
+    /// Create a resolver serving documents from `root`
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// File the document of a `did:web` identifier is read from
+    pub fn document_path(&self, did: &str) -> Result<PathBuf, DidError> {
+        let segments = did_web_segments(did)?;
+        let mut path = self.root.join(&segments[0]);
+
+        if segments.len() == 1 {
+            path.push(".well-known");
+        } else {
+            path.extend(&segments[1..]);
+        }
+        path.push("did.json");
+        Ok(path)
+    }
+}
+
+impl DidResolver for FileDidResolver {
+    fn resolve(&self, did: &str) -> Result<DidDocument, DidError> {
+        match did_method(did)? {
+            "key" => resolve_did_key(did),
+            "web" => {
+                let path = self.document_path(did)?;
+                let json = std::fs::read_to_string(&path)
+                    .map_err(|_| DidError::NotFound(did.to_string()))?;
+                let document: DidDocument = serde_json::from_str(&json)
+                    .map_err(|e| DidError::InvalidDocument(e.to_string()))?;
+
+                if document.id != did {
+                    return Err(DidError::InvalidDocument(format!(
+                        "document describes {}",
+                        document.id
+                    )));
+                }
+                Ok(document)
+            }
+            other => Err(DidError::UnsupportedMethod(other.to_string())),
+        }
+    }
+}
+
+/// Method of a DID, `key` for `did:key:...`
+fn did_method(did: &str) -> Result<&str, DidError> {
+    match did.split(':').collect::<Vec<_>>().as_slice() {
+        ["did", method, rest @ ..] if !method.is_empty() && !rest.is_empty() => Ok(method),
+        _ => Err(DidError::InvalidDid(did.to_string())),
+    }
+}
+
+/// Domain and path segments of a `did:web` identifier, percent-decoded
+fn did_web_segments(did: &str) -> Result<Vec<String>, DidError> {
+    let invalid = || DidError::InvalidDid(did.to_string());
+    let identifier = did.strip_prefix("did:web:").ok_or_else(invalid)?;
+
+    identifier
+        .split(':')
+        .map(|segment| {
+            // The only percent-encoding did:web defines is the port separator
+            let segment = segment.replace("%3A", ":").replace("%3a", ":");
+            if segment.is_empty() || segment == "." || segment == ".." || segment.contains('/') {
+                return Err(invalid());
+            }
+            Ok(segment)
+        })
+        .collect()
+}
+
+fn encode_multikey(public_key: &VerifyingKey) -> String {
+    let mut bytes = ED25519_MULTICODEC.to_vec();
+    bytes.extend_from_slice(public_key.as_bytes());
+    format!("z{}", bs58::encode(bytes).into_string())
+}
+
+fn decode_multikey(multikey: &str) -> Result<VerifyingKey, DidError> {
+    let invalid = || DidError::InvalidKey(multikey.to_string());
+    let bytes = multikey
+        .strip_prefix('z')
+        .and_then(|encoded| bs58::decode(encoded).into_vec().ok())
+        .ok_or_else(invalid)?;
+    let key: [u8; 32] = bytes
+        .strip_prefix(&ED25519_MULTICODEC)
+        .and_then(|key| key.try_into().ok())
+        .ok_or_else(invalid)?;
+
+    VerifyingKey::from_bytes(&key).map_err(|e| DidError::InvalidKey(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::SigningKey;
+    use tempfile::TempDir;
+
+    fn create_test_key() -> VerifyingKey {
+        SigningKey::from_bytes(&[7u8; 32]).verifying_key()
+    }
+
+    /// Resolver over a temporary directory, removed when the guard is dropped
+    fn create_test_resolver() -> (TempDir, FileDidResolver) {
+        let dir = TempDir::new().unwrap();
+        let resolver = FileDidResolver::new(dir.path());
+        (dir, resolver)
+    }
+
+    fn publish(resolver: &FileDidResolver, did: &str, document: &DidDocument) {
+        let path = resolver.document_path(did).unwrap();
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, serde_json::to_string_pretty(document).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_did_key_round_trip() {
+        let key = create_test_key();
+        let did = did_key(&key);
+        assert!(did.starts_with("did:key:z6Mk"));
+
+        let document = resolve_did_key(&did).unwrap();
+        assert_eq!(document.id, did);
+        assert_eq!(
+            document
+                .assertion_key(&document.assertion_method[0])
+                .unwrap(),
+            key
+        );
+    }
+
+    #[test]
+    fn test_invalid_did_key_rejected() {
+        assert!(matches!(
+            resolve_did_key("did:key:z6MkInvalid"),
+            Err(DidError::InvalidKey(_))
+        ));
+        assert!(matches!(
+            resolve_did_key("did:web:example.com"),
+            Err(DidError::InvalidDid(_))
+        ));
+    }
+
+    #[test]
+    fn test_did_web_url() {
+        assert_eq!(
+            did_web_url("did:web:web5.claims").unwrap(),
+            "https://web5.claims/.well-known/did.json"
+        );
+        assert_eq!(
+            did_web_url("did:web:localhost%3A8080:issuers:konnektoren").unwrap(),
+            "https://localhost:8080/issuers/konnektoren/did.json"
+        );
+        assert!(did_web_url("did:web:example.com:..").is_err());
+    }
+
+    #[test]
+    fn test_file_resolver_resolves_did_web() {
+        let (_dir, resolver) = create_test_resolver();
+        let did = "did:web:web5.claims:issuers:konnektoren".to_string();
+        let document = DidDocument::for_key(did.clone(), "key-1".to_string(), &create_test_key());
+        publish(&resolver, &did, &document);
+
+        assert!(resolver
+            .document_path(&did)
+            .unwrap()
+            .ends_with("web5.claims/issuers/konnektoren/did.json"));
+        assert_eq!(resolver.resolve(&did).unwrap(), document);
+        assert_eq!(
+            resolver
+                .resolve(&did)
+                .unwrap()
+                .assertion_key("#key-1")
+                .unwrap(),
+            create_test_key()
+        );
+    }
+
+    #[test]
+    fn test_file_resolver_errors() {
+        let (_dir, resolver) = create_test_resolver();
+        assert_eq!(
+            resolver.resolve("did:web:unknown.example"),
+            Err(DidError::NotFound("did:web:unknown.example".to_string()))
+        );
+        assert!(matches!(
+            resolver.resolve("did:example:123"),
+            Err(DidError::UnsupportedMethod(_))
+        ));
+        assert!(resolver.resolve(&did_key(&create_test_key())).is_ok());
+
+        let document = DidDocument::for_key(
+            "did:web:other.example".to_string(),
+            "key-1".to_string(),
+            &create_test_key(),
+        );
+        publish(&resolver, "did:web:web5.claims", &document);
+        assert!(matches!(
+            resolver.resolve("did:web:web5.claims"),
+            Err(DidError::InvalidDocument(_))
+        ));
+    }
+
+    #[test]
+    fn test_assertion_key_requires_assertion_method() {
+        let mut document = DidDocument::for_key(
+            "did:web:web5.claims".to_string(),
+            "key-1".to_string(),
+            &create_test_key(),
+        );
+        document.assertion_method.clear();
+
+        assert!(matches!(
+            document.assertion_key("did:web:web5.claims#key-1"),
+            Err(DidError::UnknownVerificationMethod(_))
+        ));
+    }
+
+    #[test]
+    fn test_jwk_verification_method() {
+        let key = create_test_key();
+        let method = VerificationMethod {
+            id: "did:web:web5.claims#jwk".to_string(),
+            method_type: "JsonWebKey2020".to_string(),
+            controller: "did:web:web5.claims".to_string(),
+            public_key_multibase: None,
+            public_key_jwk: Some(serde_json::json!({
+                "kty": "OKP",
+                "crv": "Ed25519",
+                "x": URL_SAFE_NO_PAD.encode(key.as_bytes()),
+            })),
+            revoked: None,
+        };
+
+        assert_eq!(method.public_key().unwrap(), key);
+    }
+
+    fn create_test_publisher() -> DidWebPublisher {
+        DidWebPublisher::new(
+            "did:web:web5.claims".to_string(),
+            SigningKey::from_bytes(&[7u8; 32]),
+        )
+        .unwrap()
+        .with_status_list("https://web5.claims/status/1".to_string())
+        .with_proof_request_endpoint("https://web5.claims/proof-requests".to_string())
+    }
+
+    #[test]
+    fn test_publisher_document() {
+        let document = create_test_publisher().document();
+
+        assert_eq!(document.id, "did:web:web5.claims");
+        assert_eq!(document.authentication, vec!["did:web:web5.claims#key-1"]);
+        assert_eq!(document.assertion_key("#key-1").unwrap(), create_test_key());
+        assert_eq!(document.service.len(), 2);
+        assert_eq!(document.service[0].service_type, STATUS_LIST_SERVICE_TYPE);
+
+        let json = serde_json::to_value(&document).unwrap();
+        assert_eq!(json["@context"][0], DID_CONTEXT);
+        assert_eq!(
+            json["service"][1]["serviceEndpoint"],
+            "https://web5.claims/proof-requests"
+        );
+        assert!(
+            DidWebPublisher::new("did:key:z6Mk".to_string(), SigningKey::from_bytes(&[1; 32]))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_rotation_keeps_historical_keys() {
+        let mut publisher = create_test_publisher();
+        let new_key = SigningKey::from_bytes(&[8u8; 32]);
+        assert_eq!(publisher.rotate(new_key.clone()), "key-2");
+
+        let document = publisher.document();
+        assert_eq!(document.authentication, vec!["did:web:web5.claims#key-2"]);
+        assert_eq!(
+            document.assertion_key("#key-2").unwrap(),
+            new_key.verifying_key()
+        );
+        assert_eq!(document.assertion_key("#key-1").unwrap(), create_test_key());
+        assert_eq!(document.service.len(), 2);
+
+        let retired_at = document.verification_method[1].revoked.unwrap();
+        assert!(document.verification_method[0].revoked.is_none());
+        assert!(document.assertion_key_at("#key-1", retired_at).is_ok());
+        assert_eq!(
+            document.assertion_key_at("#key-1", retired_at + chrono::Duration::seconds(1)),
+            Err(DidError::RetiredKey(
+                "did:web:web5.claims#key-1".to_string(),
+                retired_at
+            ))
+        );
+        assert!(document
+            .assertion_key_at("#key-2", retired_at + chrono::Duration::days(1))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_publisher_restored_from_document() {
+        let mut publisher = create_test_publisher();
+        let new_key = SigningKey::from_bytes(&[8u8; 32]);
+        publisher.rotate(new_key.clone());
+        let document = publisher.document();
+
+        let restored = DidWebPublisher::from_document(&document, new_key).unwrap();
+        assert_eq!(restored.current_key_id, "key-2");
+        assert_eq!(restored.document(), document);
+
+        assert!(matches!(
+            DidWebPublisher::from_document(&document, SigningKey::from_bytes(&[7u8; 32])),
+            Err(DidError::KeyMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_old_proofs_verify_after_rotation() {
+        use crate::issuer::{ProofOptions, ProofRequest};
+        use crate::verifier::ZkProofVerifier;
+        use crate::zk_proof::ClaimType;
+        use konnektoren_core::certificates::CertificateData;
+
+        let mut publisher = create_test_publisher();
+        let (_dir, resolver) = create_test_resolver();
+        let path = resolver.document_path(&publisher.did).unwrap();
+        let request = ProofRequest {
+            certificate: CertificateData::new(
+                "German_B2_Complete".to_string(),
+                7,
+                7,
+                "Test Student".to_string(),
+                chrono::Utc::now(),
+            ),
+            claim_type: ClaimType::PerformanceThreshold { min_percentage: 80 },
+            target_platform: "test".to_string(),
+            options: ProofOptions::default(),
+        };
+
+        let old_issuer = publisher
+            .issuer("Web5 Claims".to_string())
+            .accept_unsigned_certificates();
+        let old_proof = old_issuer.generate_proof(request.clone()).unwrap();
+        publisher.rotate(SigningKey::from_bytes(&[8u8; 32]));
+        let new_proof = publisher
+            .issuer("Web5 Claims".to_string())
+            .accept_unsigned_certificates()
+            .generate_proof(request.clone())
+            .unwrap();
+        let late_proof = old_issuer.generate_proof(request).unwrap();
+        publisher.save(&path).unwrap();
+
+        let mut verifier =
+            ZkProofVerifier::new("test_verifier".to_string()).with_did_resolver(resolver);
+        verifier.add_trusted_issuer(publisher.did.clone());
+        for proof in [old_proof, new_proof] {
+            let result = verifier.verify_proof(&proof).unwrap();
+            assert_eq!(result.details.metadata["issuer_did"], publisher.did);
+        }
+
+        // The retired key no longer signs proofs
+        assert!(matches!(
+            verifier.verify_proof(&late_proof),
+            Err(crate::verifier::VerifierError::IssuerAuthenticationFailed(
+                _
+            ))
+        ));
+    }
+}
@@ -0,0 +1,750 @@
+//! Ethereum Attestation Service (EAS) export for verified proof claims.
+//!
+//! A [`ZkProofClaim`] checked by a [`ZkProofVerifier`] is mapped to the Web5 Claims EAS schema, ABI-encoded as
+//! attestation data and signed by the issuer as an EIP-712 off-chain attestation. The
+//! [`EasVerifier`] checks such attestations and turns them back into a
+//! [`VerificationResult`].
+
+use crate::verifier::{VerificationDetails, VerificationResult, VerifierError, ZkProofVerifier};
+use crate::zk_proof::{ClaimType, ProofMode, ZkProofClaim};
+use alloy_primitives::{keccak256, Address, Bytes, PrimitiveSignature, B256};
+use alloy_sol_types::{eip712_domain, sol, Eip712Domain, SolStruct, SolValue};
+use k256::ecdsa::SigningKey;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+/// EAS schema for Web5 Claims attestations
+pub const EAS_SCHEMA: &str =
+    "string language,string minLevel,string claimType,bytes32 certificateCommitment,string issuer";
+
+/// Off-chain attestation version (EIP-712 type with salt)
+pub const OFFCHAIN_ATTESTATION_VERSION: u16 = 2;
+
+/// Platform name reported for verified attestations
+pub const EAS_PLATFORM: &str = "eas";
+
+sol! {
+    /// EIP-712 message of an EAS off-chain attestation
+    struct Attest {
+        uint16 version;
+        bytes32 schema;
+        address recipient;
+        uint64 time;
+        uint64 expirationTime;
+        bool revocable;
+        bytes32 refUID;
+        bytes data;
+        bytes32 salt;
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum EasError {
+    #[error("Claim has not been verified")]
+    ClaimNotVerified,
+    #[error("Only claims with a verified real proof are attested")]
+    SimulatedProof,
+    #[error("Invalid certificate commitment: {0}")]
+    InvalidCommitment(String),
+    #[error("Attestation data decoding failed: {0}")]
+    DecodingFailed(String),
+    #[error("Unsupported attestation version: {0}")]
+    UnsupportedVersion(u16),
+    #[error("Attestation domain does not match verifier domain")]
+    DomainMismatch,
+    #[error("Attestation uses unknown schema: {0}")]
+    SchemaMismatch(B256),
+    #[error("Attestation UID does not match its contents")]
+    UidMismatch,
+    #[error("Invalid attestation signature: {0}")]
+    InvalidSignature(String),
+    #[error("Attester is not trusted: {0}")]
+    UntrustedAttester(Address),
+    #[error("Attestation expired at {0}")]
+    Expired(u64),
+    #[error("Claim verification failed: {0}")]
+    Verifier(#[from] VerifierError),
+}
+
+/// EAS deployment the attestations are bound to (the EIP-712 domain)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EasConfig {
+    /// Chain id of the EAS deployment
+    pub chain_id: u64,
+    /// Address of the EAS contract
+    pub eas_contract: Address,
+    /// EAS contract version used in the EIP-712 domain
+    pub contract_version: String,
+}
+
+/// Decoded Web5 Claims attestation data
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EasAttestationData {
+    /// Language of the claim (empty if the claim does not cover a language)
+    pub language: String,
+    /// Minimum CEFR level (empty if the claim does not cover a level)
+    pub min_level: String,
+    /// Claim type name
+    pub claim_type: String,
+    /// Certificate hash committed to by the proof
+    pub certificate_commitment: B256,
+    /// Issuer of the attestation
+    pub issuer: String,
+}
+
+/// ECDSA signature of an off-chain attestation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EasSignature {
+    pub v: u8,
+    pub r: B256,
+    pub s: B256,
+}
+
+/// EIP-712 signed off-chain attestation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OffchainAttestation {
+    /// Deployment the attestation is bound to
+    pub domain: EasConfig,
+    /// Off-chain attestation UID
+    pub uid: B256,
+    pub version: u16,
+    /// Schema UID
+    pub schema: B256,
+    pub recipient: Address,
+    /// Attestation time (unix seconds)
+    pub time: u64,
+    /// Expiration time (unix seconds, 0 for none)
+    pub expiration_time: u64,
+    pub revocable: bool,
+    pub ref_uid: B256,
+    /// ABI-encoded [`EasAttestationData`]
+    pub data: Bytes,
+    pub salt: B256,
+    pub signature: EasSignature,
+    /// Address of the signing issuer
+    pub signer: Address,
+}
+
+/// Issuer side: signs off-chain attestations for verified claims
+#[derive(Debug, Clone)]
+pub struct EasAttester {
+    /// Issuer identifier written into the attestation
+    pub issuer_id: String,
+    /// EAS deployment to attest for
+    pub config: EasConfig,
+    /// Validity period in seconds (None for attestations without expiration)
+    pub validity_secs: Option<u64>,
+    signing_key: SigningKey,
+}
+
+/// Verifier side: checks off-chain attestations from trusted issuers
+#[derive(Debug, Clone)]
+pub struct EasVerifier {
+    /// EAS deployment attestations must be bound to
+    pub config: EasConfig,
+    /// Trusted attester addresses
+    pub trusted_attesters: HashSet<Address>,
+}
+
+impl EasConfig {
+    /// Create a config for an EAS deployment
+    pub fn new(chain_id: u64, eas_contract: Address) -> Self {
+        Self {
+            chain_id,
+            eas_contract,
+            contract_version: "1.2.0".to_string(),
+        }
+    }
+
+    /// EIP-712 domain of the deployment
+    pub fn eip712_domain(&self) -> Eip712Domain {
+        eip712_domain! {
+            name: "EAS Attestation",
+            version: self.contract_version.clone(),
+            chain_id: self.chain_id,
+            verifying_contract: self.eas_contract,
+        }
+    }
+}
+
+impl EasAttestationData {
+    /// Map a proof claim to the attestation schema
+    pub fn from_claim(claim: &ZkProofClaim, issuer: &str) -> Result<Self, EasError> {
+        let commitment = hex::decode(&claim.public_inputs.certificate_hash)
+            .ok()
+            .filter(|bytes| bytes.len() == 32)
+            .ok_or_else(|| {
+                EasError::InvalidCommitment(claim.public_inputs.certificate_hash.clone())
+            })?;
+
+        let (language, min_level) = language_requirement(&claim.claim_type).unwrap_or_default();
+
+        Ok(Self {
+            language,
+            min_level,
+            claim_type: claim_type_name(&claim.claim_type).to_string(),
+            certificate_commitment: B256::from_slice(&commitment),
+            issuer: issuer.to_string(),
+        })
+    }
+
+    /// ABI-encode the data as EAS expects (`abi.encode` of the schema fields)
+    pub fn abi_encode(&self) -> Vec<u8> {
+        (
+            self.language.clone(),
+            self.min_level.clone(),
+            self.claim_type.clone(),
+            self.certificate_commitment,
+            self.issuer.clone(),
+        )
+            .abi_encode_params()
+    }
+
+    /// Decode ABI-encoded attestation data
+    pub fn abi_decode(data: &[u8]) -> Result<Self, EasError> {
+        let (language, min_level, claim_type, certificate_commitment, issuer) =
+            <(String, String, String, B256, String)>::abi_decode_params(data, true)
+                .map_err(|e| EasError::DecodingFailed(e.to_string()))?;
+
+        Ok(Self {
+            language,
+            min_level,
+            claim_type,
+            certificate_commitment,
+            issuer,
+        })
+    }
+}
+
+impl OffchainAttestation {
+    /// EIP-712 message of the attestation
+    pub fn message(&self) -> Attest {
+        Attest {
+            version: self.version,
+            schema: self.schema,
+            recipient: self.recipient,
+            time: self.time,
+            expirationTime: self.expiration_time,
+            revocable: self.revocable,
+            refUID: self.ref_uid,
+            data: self.data.clone(),
+            salt: self.salt,
+        }
+    }
+
+    /// Compute the off-chain UID from the attestation contents
+    pub fn compute_uid(&self) -> B256 {
+        let mut packed = Vec::new();
+        packed.extend_from_slice(&self.version.to_be_bytes());
+        packed.extend_from_slice(self.schema.as_slice());
+        packed.extend_from_slice(self.recipient.as_slice());
+        packed.extend_from_slice(Address::ZERO.as_slice());
+        packed.extend_from_slice(&self.time.to_be_bytes());
+        packed.extend_from_slice(&self.expiration_time.to_be_bytes());
+        packed.push(self.revocable as u8);
+        packed.extend_from_slice(self.ref_uid.as_slice());
+        packed.extend_from_slice(&self.data);
+        packed.extend_from_slice(self.salt.as_slice());
+        packed.extend_from_slice(&0u32.to_be_bytes());
+        keccak256(packed)
+    }
+
+    /// Decode the attestation data
+    pub fn decode_data(&self) -> Result<EasAttestationData, EasError> {
+        EasAttestationData::abi_decode(&self.data)
+    }
+
+    /// Recover the address that signed the attestation
+    pub fn recover_signer(&self) -> Result<Address, EasError> {
+        let parity = match self.signature.v {
+            27 => false,
+            28 => true,
+            v => return Err(EasError::InvalidSignature(format!("invalid v: {}", v))),
+        };
+
+        let signature =
+            PrimitiveSignature::from_scalars_and_parity(self.signature.r, self.signature.s, parity);
+        let hash = self
+            .message()
+            .eip712_signing_hash(&self.domain.eip712_domain());
+
+        signature
+            .recover_address_from_prehash(&hash)
+            .map_err(|e| EasError::InvalidSignature(e.to_string()))
+    }
+}
+
+impl EasAttester {
+    /// Create an attester from an issuer signing key
+    pub fn new(issuer_id: String, signing_key: SigningKey, config: EasConfig) -> Self {
+        Self {
+            issuer_id,
+            config,
+            validity_secs: None,
+            signing_key,
+        }
+    }
+
+    /// Create an attester with a freshly generated key
+    pub fn random(issuer_id: String, config: EasConfig) -> Self {
+        Self::new(
+            issuer_id,
+            SigningKey::random(&mut rand::rngs::OsRng),
+            config,
+        )
+    }
+
+    /// Set the validity period of issued attestations
+    pub fn with_validity(mut self, validity_secs: u64) -> Self {
+        self.validity_secs = Some(validity_secs);
+        self
+    }
+
+    /// Ethereum address of the issuer key
+    pub fn address(&self) -> Address {
+        Address::from_private_key(&self.signing_key)
+    }
+
+    /// Verify a claim and create a signed off-chain attestation for it
+    ///
+    /// The schema does not record the proof mode, so only claims whose real proof the
+    /// verifier checked are attested.
+    pub fn attest(
+        &self,
+        claim: &ZkProofClaim,
+        verifier: &ZkProofVerifier,
+        recipient: Address,
+    ) -> Result<OffchainAttestation, EasError> {
+        let result = verifier.verify_proof(claim)?;
+        if !result.is_valid || !result.requirements_met || !claim.verify_integrity() {
+            return Err(EasError::ClaimNotVerified);
+        }
+        let verified_mode = result.details.metadata.get("proof_mode");
+        if claim.proof_data.mode != ProofMode::Real
+            || verified_mode != Some(&ProofMode::Real.to_string())
+        {
+            return Err(EasError::SimulatedProof);
+        }
+
+        let data = EasAttestationData::from_claim(claim, &self.issuer_id)?;
+        let time = chrono::Utc::now().timestamp() as u64;
+
+        let mut salt = B256::ZERO;
+        rand::thread_rng().fill_bytes(salt.as_mut_slice());
+
+        let mut attestation = OffchainAttestation {
+            domain: self.config.clone(),
+            uid: B256::ZERO,
+            version: OFFCHAIN_ATTESTATION_VERSION,
+            schema: schema_uid(),
+            recipient,
+            time,
+            expiration_time: self.validity_secs.map_or(0, |secs| time + secs),
+            revocable: true,
+            ref_uid: B256::ZERO,
+            data: data.abi_encode().into(),
+            salt,
+            signature: EasSignature {
+                v: 0,
+                r: B256::ZERO,
+                s: B256::ZERO,
+            },
+            signer: self.address(),
+        };
+        attestation.uid = attestation.compute_uid();
+        attestation.signature = self.sign(&attestation)?;
+
+        Ok(attestation)
+    }
+
+    /// Sign the EIP-712 hash of an attestation
+    fn sign(&self, attestation: &OffchainAttestation) -> Result<EasSignature, EasError> {
+        let hash = attestation
+            .message()
+            .eip712_signing_hash(&self.config.eip712_domain());
+        let (signature, recovery_id) = self
+            .signing_key
+            .sign_prehash_recoverable(hash.as_slice())
+            .map_err(|e| EasError::InvalidSignature(e.to_string()))?;
+        let signature =
+            PrimitiveSignature::from_signature_and_parity(signature, recovery_id.is_y_odd());
+
+        Ok(EasSignature {
+            v: 27 + signature.v() as u8,
+            r: B256::from(signature.r()),
+            s: B256::from(signature.s()),
+        })
+    }
+}
+
+impl EasVerifier {
+    /// Create a verifier for an EAS deployment
+    pub fn new(config: EasConfig) -> Self {
+        Self {
+            config,
+            trusted_attesters: HashSet::new(),
+        }
+    }
+
+    /// Trust an attester address
+    pub fn add_trusted_attester(&mut self, attester: Address) {
+        self.trusted_attesters.insert(attester);
+    }
+
+    /// Verify an off-chain attestation and map it back to a verification result
+    pub fn verify(
+        &self,
+        attestation: &OffchainAttestation,
+    ) -> Result<VerificationResult, EasError> {
+        if attestation.version != OFFCHAIN_ATTESTATION_VERSION {
+            return Err(EasError::UnsupportedVersion(attestation.version));
+        }
+
+        if attestation.domain != self.config {
+            return Err(EasError::DomainMismatch);
+        }
+
+        if attestation.schema != schema_uid() {
+            return Err(EasError::SchemaMismatch(attestation.schema));
+        }
+
+        if attestation.uid != attestation.compute_uid() {
+            return Err(EasError::UidMismatch);
+        }
+
+        let signer = attestation.recover_signer()?;
+        if signer != attestation.signer {
+            return Err(EasError::InvalidSignature(format!(
+                "signed by {}, claims {}",
+                signer, attestation.signer
+            )));
+        }
+
+        if !self.trusted_attesters.contains(&signer) {
+            return Err(EasError::UntrustedAttester(signer));
+        }
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        if attestation.expiration_time != 0 && attestation.expiration_time < now {
+            return Err(EasError::Expired(attestation.expiration_time));
+        }
+
+        let data = attestation.decode_data()?;
+
+        let mut verified_inputs = HashMap::new();
+        if !data.language.is_empty() {
+            verified_inputs.insert(
+                "language".to_string(),
+                serde_json::Value::String(data.language.clone()),
+            );
+        }
+        if !data.min_level.is_empty() {
+            verified_inputs.insert(
+                "min_level".to_string(),
+                serde_json::Value::String(data.min_level.clone()),
+            );
+        }
+        verified_inputs.insert(
+            "certificate_commitment".to_string(),
+            serde_json::Value::String(hex::encode(data.certificate_commitment)),
+        );
+        verified_inputs.insert(
+            "issuer".to_string(),
+            serde_json::Value::String(data.issuer.clone()),
+        );
+
+        let mut metadata = HashMap::new();
+        metadata.insert("attestation_uid".to_string(), attestation.uid.to_string());
+        metadata.insert("schema_uid".to_string(), attestation.schema.to_string());
+        metadata.insert("attester".to_string(), signer.to_string());
+        metadata.insert("recipient".to_string(), attestation.recipient.to_string());
+
+        let warnings = if attestation.revocable {
+            vec!["Attestation is revocable; check revocation status on-chain".to_string()]
+        } else {
+            vec![]
+        };
+
+        Ok(VerificationResult {
+            is_valid: true,
+            requirements_met: true,
+            details: VerificationDetails {
+                platform: EAS_PLATFORM.to_string(),
+                circuit_id: data.claim_type,
+                verified_at: chrono::Utc::now(),
+                verified_inputs,
+                metadata,
+                signers: Vec::new(),
+            },
+            warnings,
+            trust_score: None,
+        })
+    }
+}
+
+/// Schema UID of [`EAS_SCHEMA`] (no resolver, revocable)
+pub fn schema_uid() -> B256 {
+    let mut packed = EAS_SCHEMA.as_bytes().to_vec();
+    packed.extend_from_slice(Address::ZERO.as_slice());
+    packed.push(1);
+    keccak256(packed)
+}
+
+/// Schema name of a claim type
+fn claim_type_name(claim_type: &ClaimType) -> &'static str {
+    match claim_type {
+        ClaimType::LanguageProficiency { .. } => "language_proficiency",
+        ClaimType::PerformanceThreshold { .. } => "performance_threshold",
+        ClaimType::CompletionDate { .. } => "completion_date",
+        ClaimType::Combined { .. } => "combined",
+        ClaimType::IdentityBound { .. } => "identity_bound",
+        ClaimType::NationalityNotIn { .. } => "nationality_not_in",
+        ClaimType::DocumentValid { .. } => "document_valid",
+    }
+}
+
+/// Language and minimum level covered by a claim, including combined claims
+fn language_requirement(claim_type: &ClaimType) -> Option<(String, String)> {
+    match claim_type {
+        ClaimType::LanguageProficiency {
+            language,
+            min_level,
+        } => Some((language.clone(), min_level.to_string())),
+        ClaimType::Combined { criteria } => criteria.iter().find_map(language_requirement),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issuer::{CertificateIssuer, ProofOptions, ProofRequest};
+    use crate::testing::{create_real_proof_setup, generate_real_proof};
+    use crate::verifier::ZkProofVerifier;
+    use crate::zk_proof::CefrLevel;
+    use alloy_primitives::address;
+    use chrono::Utc;
+    use konnektoren_core::certificates::CertificateData;
+
+    const EAS_CONTRACT: Address = address!("C2679fBD37d54388Ce493F1DB75320D236e1815e");
+    const RECIPIENT: Address = address!("00000000000000000000000000000000000000bb");
+
+    fn create_test_config() -> EasConfig {
+        EasConfig::new(11155111, EAS_CONTRACT)
+    }
+
+    fn create_verified_claim() -> (ZkProofClaim, ZkProofVerifier) {
+        let (issuer, verifier) = create_real_proof_setup();
+        let claim = generate_real_proof(
+            &issuer,
+            ClaimType::LanguageProficiency {
+                language: "German".to_string(),
+                min_level: CefrLevel::B1,
+            },
+            "stylus",
+        );
+
+        (claim, verifier)
+    }
+
+    fn create_test_verifier(attester: &EasAttester) -> EasVerifier {
+        let mut verifier = EasVerifier::new(create_test_config());
+        verifier.add_trusted_attester(attester.address());
+        verifier
+    }
+
+    #[test]
+    fn test_schema_uid_is_stable() {
+        assert_eq!(schema_uid(), schema_uid());
+        assert_ne!(schema_uid(), B256::ZERO);
+    }
+
+    #[test]
+    fn test_attestation_data_abi_round_trip() {
+        let (claim, _) = create_verified_claim();
+        let data = EasAttestationData::from_claim(&claim, "test_issuer").unwrap();
+
+        assert_eq!(data.language, "German");
+        assert_eq!(data.min_level, "B1");
+        assert_eq!(data.claim_type, "language_proficiency");
+
+        let encoded = data.abi_encode();
+        // abi.encode of five params starts with the offset of the first string
+        assert_eq!(encoded[31], 0xa0);
+        assert_eq!(EasAttestationData::abi_decode(&encoded).unwrap(), data);
+    }
+
+    #[test]
+    fn test_attest_and_verify() {
+        let (claim, proof_verifier) = create_verified_claim();
+        let attester = EasAttester::random("test_issuer".to_string(), create_test_config());
+        let verifier = create_test_verifier(&attester);
+
+        let attestation = attester.attest(&claim, &proof_verifier, RECIPIENT).unwrap();
+        assert_eq!(attestation.signer, attester.address());
+        assert_eq!(attestation.recover_signer().unwrap(), attester.address());
+
+        let verified = verifier.verify(&attestation).unwrap();
+        assert!(verified.is_valid);
+        assert!(verified.requirements_met);
+        assert_eq!(verified.details.platform, EAS_PLATFORM);
+        assert_eq!(verified.details.circuit_id, "language_proficiency");
+        assert_eq!(
+            verified.details.verified_inputs["certificate_commitment"],
+            serde_json::Value::String(claim.public_inputs.certificate_hash.clone())
+        );
+        assert_eq!(
+            verified.details.metadata["attestation_uid"],
+            attestation.uid.to_string()
+        );
+    }
+
+    #[test]
+    fn test_unverified_claim_rejected() {
+        let (claim, proof_verifier) = create_verified_claim();
+        let attester = EasAttester::random("test_issuer".to_string(), create_test_config());
+
+        let mut tampered = claim.clone();
+        tampered.proof_data.proof_bytes.clear();
+        assert!(matches!(
+            attester.attest(&tampered, &proof_verifier, RECIPIENT),
+            Err(EasError::Verifier(VerifierError::IntegrityCheckFailed))
+        ));
+
+        let proof_verifier = proof_verifier.with_revocation_list([claim.proof_id.clone()]);
+        assert!(matches!(
+            attester.attest(&claim, &proof_verifier, RECIPIENT),
+            Err(EasError::Verifier(VerifierError::RevokedProof(_)))
+        ));
+    }
+
+    #[test]
+    fn test_simulated_claim_rejected() {
+        let issuer = CertificateIssuer::new(
+            "test_issuer".to_string(),
+            "Test Certificate Issuer".to_string(),
+        )
+        .accept_unsigned_certificates();
+        let claim = issuer
+            .generate_proof(ProofRequest {
+                certificate: CertificateData::new(
+                    "German_B2_Complete".to_string(),
+                    7,
+                    7,
+                    "Test Student".to_string(),
+                    Utc::now(),
+                ),
+                claim_type: ClaimType::LanguageProficiency {
+                    language: "German".to_string(),
+                    min_level: CefrLevel::B1,
+                },
+                target_platform: "stylus".to_string(),
+                options: ProofOptions::default(),
+            })
+            .unwrap();
+        let proof_verifier = ZkProofVerifier::new("test_verifier".to_string());
+        let result = proof_verifier.verify_proof(&claim).unwrap();
+        let attester = EasAttester::random("test_issuer".to_string(), create_test_config());
+
+        assert!(result.is_valid && result.requirements_met);
+        assert!(matches!(
+            attester.attest(&claim, &proof_verifier, RECIPIENT),
+            Err(EasError::SimulatedProof)
+        ));
+
+        // A real proof counts only once the verifier checked it with a proof backend
+        let (issuer, _) = create_real_proof_setup();
+        let claim = generate_real_proof(&issuer, claim.claim_type, "stylus");
+        assert!(matches!(
+            attester.attest(&claim, &proof_verifier, RECIPIENT),
+            Err(EasError::SimulatedProof)
+        ));
+    }
+
+    #[test]
+    fn test_tampered_attestation_rejected() {
+        let (claim, proof_verifier) = create_verified_claim();
+        let attester = EasAttester::random("test_issuer".to_string(), create_test_config());
+        let verifier = create_test_verifier(&attester);
+        let attestation = attester.attest(&claim, &proof_verifier, RECIPIENT).unwrap();
+
+        let mut tampered = attestation.clone();
+        let mut data = tampered.decode_data().unwrap();
+        data.min_level = "C2".to_string();
+        tampered.data = data.abi_encode().into();
+        assert!(matches!(
+            verifier.verify(&tampered),
+            Err(EasError::UidMismatch)
+        ));
+
+        // Recomputing the UID does not help without the issuer key
+        tampered.uid = tampered.compute_uid();
+        assert!(matches!(
+            verifier.verify(&tampered),
+            Err(EasError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn test_untrusted_attester_rejected() {
+        let (claim, proof_verifier) = create_verified_claim();
+        let attester = EasAttester::random("test_issuer".to_string(), create_test_config());
+        let verifier = EasVerifier::new(create_test_config());
+
+        let attestation = attester.attest(&claim, &proof_verifier, RECIPIENT).unwrap();
+        assert!(matches!(
+            verifier.verify(&attestation),
+            Err(EasError::UntrustedAttester(_))
+        ));
+    }
+
+    #[test]
+    fn test_domain_mismatch_rejected() {
+        let (claim, proof_verifier) = create_verified_claim();
+        let attester = EasAttester::random("test_issuer".to_string(), create_test_config());
+        let mut verifier = EasVerifier::new(EasConfig::new(1, EAS_CONTRACT));
+        verifier.add_trusted_attester(attester.address());
+
+        let attestation = attester.attest(&claim, &proof_verifier, RECIPIENT).unwrap();
+        assert!(matches!(
+            verifier.verify(&attestation),
+            Err(EasError::DomainMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_expired_attestation_rejected() {
+        let (claim, proof_verifier) = create_verified_claim();
+        let attester = EasAttester::random("test_issuer".to_string(), create_test_config())
+            .with_validity(3600);
+        let verifier = create_test_verifier(&attester);
+
+        let mut attestation = attester.attest(&claim, &proof_verifier, RECIPIENT).unwrap();
+        assert!(verifier.verify(&attestation).is_ok());
+
+        attestation.time -= 7200;
+        attestation.expiration_time -= 7200;
+        attestation.uid = attestation.compute_uid();
+        attestation.signature = attester.sign(&attestation).unwrap();
+        assert!(matches!(
+            verifier.verify(&attestation),
+            Err(EasError::Expired(_))
+        ));
+    }
+
+    #[test]
+    fn test_attestation_serialization() {
+        let (claim, proof_verifier) = create_verified_claim();
+        let attester = EasAttester::random("test_issuer".to_string(), create_test_config());
+        let verifier = create_test_verifier(&attester);
+
+        let attestation = attester.attest(&claim, &proof_verifier, RECIPIENT).unwrap();
+        let json = serde_json::to_string(&attestation).unwrap();
+        let parsed: OffchainAttestation = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed, attestation);
+        assert!(verifier.verify(&parsed).is_ok());
+    }
+}
@@ -0,0 +1,829 @@
+//! EVM export for proofs produced by a pairing-based (Groth16 over BN254) backend.
+//!
+//! Turns a [`ZkProofClaim`] into ABI-encoded calldata for `verifyProof` and generates a
+//! Solidity verifier contract for each circuit in the trusted circuit registry.
+
+use crate::verifier::{CircuitInfo, ZkProofVerifier};
+use crate::zk_proof::{ProofMode, ZkProofClaim};
+use alloy_primitives::U256;
+use alloy_sol_types::{sol, SolCall};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use thiserror::Error;
+
+/// Size of an uncompressed Groth16 BN254 proof in EVM encoding (8 words)
+pub const GROTH16_PROOF_LENGTH: usize = 256;
+
+/// Number of public signals passed to the on-chain verifier
+pub const PUBLIC_SIGNAL_COUNT: usize = 3;
+
+/// BN254 scalar field modulus
+pub const BN254_SCALAR_FIELD: U256 = U256::from_limbs([
+    0x43e1f593f0000001,
+    0x2833e84879b97091,
+    0xb85045b68181585d,
+    0x30644e72e131a029,
+]);
+
+sol! {
+    /// Interface implemented by the generated verifier contracts
+    interface IWeb5ClaimsVerifier {
+        function verifyProof(
+            uint256[2] calldata a,
+            uint256[2][2] calldata b,
+            uint256[2] calldata c,
+            uint256[3] calldata input
+        ) external view returns (bool);
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum EvmExportError {
+    #[error("Proof integrity check failed")]
+    IntegrityCheckFailed,
+    #[error("Simulated proofs cannot be verified on chain")]
+    SimulatedProof,
+    #[error("Proof is not a pairing-based proof: expected {expected} bytes, got {actual}")]
+    NotPairingProof { expected: usize, actual: usize },
+    #[error("Invalid public input: {0}")]
+    InvalidPublicInput(String),
+    #[error("Invalid verifying key for circuit {circuit_id}: {reason}")]
+    InvalidVerifyingKey { circuit_id: String, reason: String },
+    #[error("No verifying key for circuit: {0}")]
+    MissingVerifyingKey(String),
+    #[error("Calldata decoding failed: {0}")]
+    DecodingFailed(String),
+}
+
+/// A G1 point as (x, y)
+pub type G1Point = [U256; 2];
+
+/// A G2 point in EVM order: ([x_imag, x_real], [y_imag, y_real])
+pub type G2Point = [[U256; 2]; 2];
+
+/// Groth16 proof in the layout expected by EVM verifiers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Groth16Proof {
+    pub a: G1Point,
+    pub b: G2Point,
+    pub c: G1Point,
+}
+
+/// Groth16 verifying key of a circuit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Groth16VerifyingKey {
+    pub alpha: G1Point,
+    pub beta: G2Point,
+    pub gamma: G2Point,
+    pub delta: G2Point,
+    /// Input commitments, one more than the number of public signals
+    pub ic: Vec<G1Point>,
+}
+
+/// Contract-ready form of a proof claim
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvmCalldata {
+    /// Circuit the proof was generated for
+    pub circuit_id: String,
+    /// Decoded proof points
+    pub proof: Groth16Proof,
+    /// Public signals (certificate hash, verification result, requirements hash)
+    pub public_inputs: [U256; PUBLIC_SIGNAL_COUNT],
+    /// ABI-encoded `verifyProof` call
+    pub calldata: Vec<u8>,
+}
+
+/// Generated Solidity verifier for one circuit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolidityVerifier {
+    /// Circuit the contract verifies
+    pub circuit_id: String,
+    /// Name of the generated contract
+    pub contract_name: String,
+    /// Solidity source code
+    pub source: String,
+}
+
+impl Groth16Proof {
+    /// Decode a proof from its 256-byte EVM encoding
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EvmExportError> {
+        if bytes.len() != GROTH16_PROOF_LENGTH {
+            return Err(EvmExportError::NotPairingProof {
+                expected: GROTH16_PROOF_LENGTH,
+                actual: bytes.len(),
+            });
+        }
+
+        let word = |i: usize| U256::from_be_slice(&bytes[i * 32..(i + 1) * 32]);
+
+        Ok(Self {
+            a: [word(0), word(1)],
+            b: [[word(2), word(3)], [word(4), word(5)]],
+            c: [word(6), word(7)],
+        })
+    }
+
+    /// Encode the proof as 256 bytes (a, b, c as big-endian words)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let words = [
+            self.a[0],
+            self.a[1],
+            self.b[0][0],
+            self.b[0][1],
+            self.b[1][0],
+            self.b[1][1],
+            self.c[0],
+            self.c[1],
+        ];
+        words
+            .iter()
+            .flat_map(|word| word.to_be_bytes::<32>())
+            .collect()
+    }
+}
+
+impl EvmCalldata {
+    /// Export a proof claim as calldata for the circuit's verifier contract
+    pub fn from_claim(claim: &ZkProofClaim) -> Result<Self, EvmExportError> {
+        if !claim.verify_integrity() {
+            return Err(EvmExportError::IntegrityCheckFailed);
+        }
+        if claim.proof_data.mode != ProofMode::Real {
+            return Err(EvmExportError::SimulatedProof);
+        }
+
+        let proof = Groth16Proof::from_bytes(&claim.proof_data.proof_bytes)?;
+        let public_inputs = public_signals(claim)?;
+
+        let call = IWeb5ClaimsVerifier::verifyProofCall {
+            a: proof.a,
+            b: proof.b,
+            c: proof.c,
+            input: public_inputs,
+        };
+
+        Ok(Self {
+            circuit_id: claim.proof_data.circuit_id.clone(),
+            proof,
+            public_inputs,
+            calldata: call.abi_encode(),
+        })
+    }
+
+    /// Decode calldata produced by [`EvmCalldata::from_claim`]
+    pub fn decode(circuit_id: &str, calldata: &[u8]) -> Result<Self, EvmExportError> {
+        let call = IWeb5ClaimsVerifier::verifyProofCall::abi_decode(calldata, true)
+            .map_err(|e| EvmExportError::DecodingFailed(e.to_string()))?;
+
+        Ok(Self {
+            circuit_id: circuit_id.to_string(),
+            proof: Groth16Proof {
+                a: call.a,
+                b: call.b,
+                c: call.c,
+            },
+            public_inputs: call.input,
+            calldata: calldata.to_vec(),
+        })
+    }
+
+    /// Hex-encoded calldata with `0x` prefix
+    pub fn to_hex(&self) -> String {
+        format!("0x{}", hex::encode(&self.calldata))
+    }
+}
+
+/// Compute the public signals a pairing-based proof commits to
+///
+/// The signals are the certificate hash, the verification result and a hash of the
+/// requirements, with hashes reduced into the BN254 scalar field.
+pub fn public_signals(claim: &ZkProofClaim) -> Result<[U256; PUBLIC_SIGNAL_COUNT], EvmExportError> {
+    let certificate_hash = hex::decode(&claim.public_inputs.certificate_hash).map_err(|_| {
+        EvmExportError::InvalidPublicInput(format!(
+            "certificate hash is not hex: {}",
+            claim.public_inputs.certificate_hash
+        ))
+    })?;
+
+    // Sort requirements so the hash does not depend on map ordering
+    let requirements: BTreeMap<_, _> = claim.public_inputs.requirements.iter().collect();
+    let requirements_json = serde_json::to_string(&requirements)
+        .map_err(|e| EvmExportError::InvalidPublicInput(e.to_string()))?;
+    let requirements_hash = Sha256::digest(requirements_json.as_bytes());
+
+    Ok([
+        to_field(&certificate_hash),
+        U256::from(claim.public_inputs.verification_result as u8),
+        to_field(&requirements_hash),
+    ])
+}
+
+/// Generate a Solidity verifier contract for a circuit
+pub fn generate_solidity_verifier(
+    circuit: &CircuitInfo,
+    vk: &Groth16VerifyingKey,
+) -> Result<SolidityVerifier, EvmExportError> {
+    if vk.ic.len() != PUBLIC_SIGNAL_COUNT + 1 {
+        return Err(EvmExportError::InvalidVerifyingKey {
+            circuit_id: circuit.circuit_id.clone(),
+            reason: format!(
+                "expected {} input commitments, got {}",
+                PUBLIC_SIGNAL_COUNT + 1,
+                vk.ic.len()
+            ),
+        });
+    }
+
+    let vk_hash = hex::decode(&circuit.vk_hash)
+        .ok()
+        .filter(|bytes| bytes.len() == 32)
+        .ok_or_else(|| EvmExportError::InvalidVerifyingKey {
+            circuit_id: circuit.circuit_id.clone(),
+            reason: "registry vk hash is not a 32-byte hex string".to_string(),
+        })?;
+
+    let contract_name = contract_name(&circuit.circuit_id);
+
+    let mut constants = vec![
+        g1_constants("ALPHA", &vk.alpha),
+        g2_constants("BETA", &vk.beta),
+        g2_constants("GAMMA", &vk.gamma),
+        g2_constants("DELTA", &vk.delta),
+    ];
+    for (i, ic) in vk.ic.iter().enumerate() {
+        constants.push(g1_constants(&format!("IC{}", i), ic));
+    }
+
+    let accumulate: Vec<String> = (1..=PUBLIC_SIGNAL_COUNT)
+        .map(|i| {
+            format!(
+                "        vkX = _add(vkX, _mul([IC{i}_X, IC{i}_Y], input[{}]));",
+                i - 1
+            )
+        })
+        .collect();
+
+    let source = format!(
+        r#"// SPDX-License-Identifier: MIT
+// Generated by web5claims for circuit `{circuit_id}` (version {version}). Do not edit by hand.
+pragma solidity ^0.8.20;
+
+/// @title {contract_name}
+/// @notice {description}
+contract {contract_name} {{
+    /// Verification key hash of this circuit in the web5claims trusted circuit registry
+    bytes32 public constant VK_HASH = 0x{vk_hash};
+    string public constant CIRCUIT_ID = "{circuit_id}";
+
+    uint256 internal constant SNARK_SCALAR_FIELD = {scalar_field};
+    uint256 internal constant PRIME_Q = 21888242871839275222246405745257275088696311157297823662689037894645226208583;
+
+{constants}
+
+    /// Verify a Groth16 proof for the public signals (certificate hash, result, requirements hash)
+    function verifyProof(
+        uint256[2] calldata a,
+        uint256[2][2] calldata b,
+        uint256[2] calldata c,
+        uint256[{signal_count}] calldata input
+    ) external view returns (bool) {{
+        for (uint256 i = 0; i < input.length; i++) {{
+            if (input[i] >= SNARK_SCALAR_FIELD) {{
+                return false;
+            }}
+        }}
+
+        // vk_x = IC0 + sum(input[i] * IC[i + 1])
+        uint256[2] memory vkX = [IC0_X, IC0_Y];
+{accumulate}
+
+        // e(-A, B) * e(alpha, beta) * e(vk_x, gamma) * e(C, delta) == 1
+        uint256[24] memory pairingInput = [
+            a[0], (PRIME_Q - (a[1] % PRIME_Q)) % PRIME_Q, b[0][0], b[0][1], b[1][0], b[1][1],
+            ALPHA_X, ALPHA_Y, BETA_X1, BETA_X2, BETA_Y1, BETA_Y2,
+            vkX[0], vkX[1], GAMMA_X1, GAMMA_X2, GAMMA_Y1, GAMMA_Y2,
+            c[0], c[1], DELTA_X1, DELTA_X2, DELTA_Y1, DELTA_Y2
+        ];
+
+        uint256[1] memory result;
+        bool success;
+        assembly {{
+            success := staticcall(gas(), 0x08, pairingInput, 768, result, 0x20)
+        }}
+        return success && result[0] == 1;
+    }}
+
+    function _add(uint256[2] memory p1, uint256[2] memory p2) internal view returns (uint256[2] memory r) {{
+        uint256[4] memory input = [p1[0], p1[1], p2[0], p2[1]];
+        bool success;
+        assembly {{
+            success := staticcall(gas(), 0x06, input, 0x80, r, 0x40)
+        }}
+        require(success, "ecAdd failed");
+    }}
+
+    function _mul(uint256[2] memory p, uint256 s) internal view returns (uint256[2] memory r) {{
+        uint256[3] memory input = [p[0], p[1], s];
+        bool success;
+        assembly {{
+            success := staticcall(gas(), 0x07, input, 0x60, r, 0x40)
+        }}
+        require(success, "ecMul failed");
+    }}
+}}
+"#,
+        circuit_id = circuit.circuit_id,
+        version = circuit.version,
+        contract_name = contract_name,
+        description = circuit.description,
+        vk_hash = hex::encode(vk_hash),
+        scalar_field = BN254_SCALAR_FIELD,
+        constants = constants.join("\n\n"),
+        signal_count = PUBLIC_SIGNAL_COUNT,
+        accumulate = accumulate.join("\n"),
+    );
+
+    Ok(SolidityVerifier {
+        circuit_id: circuit.circuit_id.clone(),
+        contract_name,
+        source,
+    })
+}
+
+/// Generate Solidity verifiers for every circuit trusted by a verifier
+///
+/// Every trusted circuit needs a verifying key; contracts are returned sorted by circuit id.
+pub fn export_verifier_contracts(
+    verifier: &ZkProofVerifier,
+    verifying_keys: &HashMap<String, Groth16VerifyingKey>,
+) -> Result<Vec<SolidityVerifier>, EvmExportError> {
+    let mut circuit_ids = verifier.list_trusted_circuits();
+    circuit_ids.sort();
+
+    circuit_ids
+        .iter()
+        .filter_map(|circuit_id| verifier.get_circuit_info(circuit_id))
+        .map(|circuit| {
+            let vk = verifying_keys
+                .get(&circuit.circuit_id)
+                .ok_or_else(|| EvmExportError::MissingVerifyingKey(circuit.circuit_id.clone()))?;
+            generate_solidity_verifier(circuit, vk)
+        })
+        .collect()
+}
+
+/// Reduce a big-endian byte string into the BN254 scalar field
+fn to_field(bytes: &[u8]) -> U256 {
+    U256::from_be_slice(bytes).reduce_mod(BN254_SCALAR_FIELD)
+}
+
+/// Contract name for a circuit id, e.g. `language_proficiency_v1` -> `LanguageProficiencyV1Verifier`
+fn contract_name(circuit_id: &str) -> String {
+    let mut name: String = circuit_id
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect();
+
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, 'C');
+    }
+    name.push_str("Verifier");
+    name
+}
+
+fn g1_constants(name: &str, point: &G1Point) -> String {
+    format!(
+        "    uint256 internal constant {name}_X = {};\n    uint256 internal constant {name}_Y = {};",
+        point[0], point[1]
+    )
+}
+
+fn g2_constants(name: &str, point: &G2Point) -> String {
+    format!(
+        "    uint256 internal constant {name}_X1 = {};\n    uint256 internal constant {name}_X2 = {};\n    uint256 internal constant {name}_Y1 = {};\n    uint256 internal constant {name}_Y2 = {};",
+        point[0][0], point[0][1], point[1][0], point[1][1]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zk_proof::{CefrLevel, ClaimType, ProofData, ProofMetadata, PublicInputs};
+    use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G2Affine};
+    use ark_ff::{BigInteger, PrimeField};
+    use ark_groth16::Groth16;
+    use ark_relations::lc;
+    use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+    use ark_snark::SNARK;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use revm::db::{CacheDB, EmptyDB};
+    use revm::primitives::{address, Address, ExecutionResult, Output, TransactTo};
+    use revm::Evm;
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    /// Circuit binding the three public signals to a witness sum
+    struct SignalCircuit {
+        signals: [Fr; PUBLIC_SIGNAL_COUNT],
+    }
+
+    impl ConstraintSynthesizer<Fr> for SignalCircuit {
+        fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+            let inputs = self
+                .signals
+                .iter()
+                .map(|signal| cs.new_input_variable(|| Ok(*signal)))
+                .collect::<Result<Vec<_>, _>>()?;
+            let sum = cs.new_witness_variable(|| Ok(self.signals.iter().sum()))?;
+
+            cs.enforce_constraint(
+                lc!() + inputs[0] + inputs[1] + inputs[2],
+                lc!() + ark_relations::r1cs::Variable::One,
+                lc!() + sum,
+            )
+        }
+    }
+
+    fn fq(value: Fq) -> U256 {
+        U256::from_be_slice(&value.into_bigint().to_bytes_be())
+    }
+
+    fn fr(value: U256) -> Fr {
+        Fr::from_be_bytes_mod_order(&value.to_be_bytes::<32>())
+    }
+
+    fn g1(point: G1Affine) -> G1Point {
+        [fq(point.x), fq(point.y)]
+    }
+
+    fn g2(point: G2Affine) -> G2Point {
+        let coords = |value: Fq2| [fq(value.c1), fq(value.c0)];
+        [coords(point.x), coords(point.y)]
+    }
+
+    fn create_claim(proof_bytes: Vec<u8>) -> ZkProofClaim {
+        let mut requirements = HashMap::new();
+        requirements.insert(
+            "min_level".to_string(),
+            serde_json::Value::String("B2".to_string()),
+        );
+        requirements.insert(
+            "language".to_string(),
+            serde_json::Value::String("German".to_string()),
+        );
+
+        ZkProofClaim::new(
+            ClaimType::LanguageProficiency {
+                language: "German".to_string(),
+                min_level: CefrLevel::B2,
+            },
+            PublicInputs {
+                requirements,
+                verification_result: true,
+                certificate_hash: hex::encode(Sha256::digest(b"certificate")),
+            },
+            ProofData {
+                proof_bytes,
+                circuit_id: "language_proficiency_v1".to_string(),
+                vk_hash: crate::CertificateIssuer::compute_verification_key_hash(
+                    "language_proficiency_v1",
+                ),
+                mode: ProofMode::Real,
+            },
+            ProofMetadata {
+                version: "1.0.0".to_string(),
+                platform: "stylus".to_string(),
+                properties: HashMap::new(),
+            },
+        )
+    }
+
+    /// Produce a real Groth16 proof for the claim's public signals
+    fn prove(claim: &ZkProofClaim) -> (Groth16VerifyingKey, Vec<u8>) {
+        let signals = public_signals(claim).unwrap().map(fr);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let (pk, vk) =
+            Groth16::<Bn254>::circuit_specific_setup(SignalCircuit { signals }, &mut rng).unwrap();
+        let proof = Groth16::<Bn254>::prove(&pk, SignalCircuit { signals }, &mut rng).unwrap();
+
+        let verifying_key = Groth16VerifyingKey {
+            alpha: g1(vk.alpha_g1),
+            beta: g2(vk.beta_g2),
+            gamma: g2(vk.gamma_g2),
+            delta: g2(vk.delta_g2),
+            ic: vk.gamma_abc_g1.iter().copied().map(g1).collect(),
+        };
+        let proof = Groth16Proof {
+            a: g1(proof.a),
+            b: g2(proof.b),
+            c: g1(proof.c),
+        };
+
+        (verifying_key, proof.to_bytes())
+    }
+
+    /// Call a precompile on a local EVM
+    fn call_precompile(precompile: Address, input: Vec<u8>) -> Vec<u8> {
+        let mut evm = Evm::builder()
+            .with_db(CacheDB::new(EmptyDB::default()))
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TransactTo::Call(precompile);
+                tx.data = input.into();
+                tx.gas_limit = 1_000_000;
+            })
+            .build();
+
+        match evm.transact().unwrap().result {
+            ExecutionResult::Success {
+                output: Output::Call(bytes),
+                ..
+            } => bytes.to_vec(),
+            other => panic!("precompile call failed: {:?}", other),
+        }
+    }
+
+    /// Compile a generated verifier with solc (`$SOLC`, or `solc` on the PATH)
+    fn compile(contract: &SolidityVerifier) -> Vec<u8> {
+        let solc = std::env::var("SOLC").unwrap_or_else(|_| "solc".to_string());
+        let mut child = Command::new(&solc)
+            .args(["--bin", "--optimize", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap_or_else(|e| panic!("cannot run {}: {}", solc, e));
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(contract.source.as_bytes())
+            .unwrap();
+
+        let output = child.wait_with_output().unwrap();
+        assert!(
+            output.status.success(),
+            "solc failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let binary = stdout
+            .lines()
+            .skip_while(|line| line.trim() != "Binary:")
+            .nth(1)
+            .expect("solc printed no binary");
+        hex::decode(binary.trim()).unwrap()
+    }
+
+    /// Deploy a contract on a local EVM and call `verifyProof` on it
+    fn deploy_and_verify(bytecode: &[u8], calldata: &[u8]) -> bool {
+        let mut evm = Evm::builder()
+            .with_db(CacheDB::new(EmptyDB::default()))
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TransactTo::Create;
+                tx.data = bytecode.to_vec().into();
+                tx.gas_limit = 10_000_000;
+            })
+            .build();
+        let contract = match evm.transact_commit().unwrap() {
+            ExecutionResult::Success {
+                output: Output::Create(_, Some(contract)),
+                ..
+            } => contract,
+            other => panic!("deployment failed: {:?}", other),
+        };
+
+        let tx = evm.tx_mut();
+        tx.transact_to = TransactTo::Call(contract);
+        tx.data = calldata.to_vec().into();
+        match evm.transact().unwrap().result {
+            ExecutionResult::Success {
+                output: Output::Call(bytes),
+                ..
+            } => {
+                IWeb5ClaimsVerifier::verifyProofCall::abi_decode_returns(&bytes, true)
+                    .unwrap()
+                    ._0
+            }
+            other => panic!("verifyProof call failed: {:?}", other),
+        }
+    }
+
+    fn words(values: &[U256]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_be_bytes::<32>()).collect()
+    }
+
+    fn point(bytes: &[u8]) -> G1Point {
+        [
+            U256::from_be_slice(&bytes[0..32]),
+            U256::from_be_slice(&bytes[32..64]),
+        ]
+    }
+
+    /// Run the generated contract's verification steps against the EVM precompiles
+    fn verify_on_evm(vk: &Groth16VerifyingKey, calldata: &[u8]) -> bool {
+        let decoded = EvmCalldata::decode("language_proficiency_v1", calldata).unwrap();
+        let prime_q = U256::from_be_slice(&fq(-Fq::from(1u8)).to_be_bytes::<32>()) + U256::from(1);
+
+        let mut vk_x = vk.ic[0];
+        for (ic, input) in vk.ic[1..].iter().zip(decoded.public_inputs.iter()) {
+            let product = point(&call_precompile(
+                address!("0000000000000000000000000000000000000007"),
+                words(&[ic[0], ic[1], *input]),
+            ));
+            vk_x = point(&call_precompile(
+                address!("0000000000000000000000000000000000000006"),
+                words(&[vk_x[0], vk_x[1], product[0], product[1]]),
+            ));
+        }
+
+        let proof = decoded.proof;
+        let pairing_input = words(&[
+            proof.a[0],
+            (prime_q - proof.a[1] % prime_q) % prime_q,
+            proof.b[0][0],
+            proof.b[0][1],
+            proof.b[1][0],
+            proof.b[1][1],
+            vk.alpha[0],
+            vk.alpha[1],
+            vk.beta[0][0],
+            vk.beta[0][1],
+            vk.beta[1][0],
+            vk.beta[1][1],
+            vk_x[0],
+            vk_x[1],
+            vk.gamma[0][0],
+            vk.gamma[0][1],
+            vk.gamma[1][0],
+            vk.gamma[1][1],
+            proof.c[0],
+            proof.c[1],
+            vk.delta[0][0],
+            vk.delta[0][1],
+            vk.delta[1][0],
+            vk.delta[1][1],
+        ]);
+
+        let result = call_precompile(
+            address!("0000000000000000000000000000000000000008"),
+            pairing_input,
+        );
+        U256::from_be_slice(&result) == U256::from(1)
+    }
+
+    #[test]
+    fn test_simulated_proof_rejected() {
+        let mut claim = create_claim(vec![1u8; GROTH16_PROOF_LENGTH]);
+        claim.proof_data.mode = ProofMode::Simulated;
+
+        assert!(matches!(
+            EvmCalldata::from_claim(&claim),
+            Err(EvmExportError::SimulatedProof)
+        ));
+    }
+
+    #[test]
+    fn test_non_pairing_proof_rejected() {
+        let claim = create_claim(vec![1u8; 32]);
+
+        match EvmCalldata::from_claim(&claim) {
+            Err(EvmExportError::NotPairingProof { expected, actual }) => {
+                assert_eq!(expected, GROTH16_PROOF_LENGTH);
+                assert_eq!(actual, 32);
+            }
+            other => panic!("Expected NotPairingProof error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_calldata_round_trip() {
+        let claim = create_claim(vec![1u8; GROTH16_PROOF_LENGTH]);
+        let exported = EvmCalldata::from_claim(&claim).unwrap();
+
+        assert_eq!(
+            &exported.calldata[..4],
+            IWeb5ClaimsVerifier::verifyProofCall::SELECTOR.as_slice()
+        );
+        assert!(exported.to_hex().starts_with("0x"));
+
+        let decoded = EvmCalldata::decode(&exported.circuit_id, &exported.calldata).unwrap();
+        assert_eq!(decoded, exported);
+        assert_eq!(decoded.proof.to_bytes(), claim.proof_data.proof_bytes);
+    }
+
+    #[test]
+    fn test_public_signals_in_field() {
+        let claim = create_claim(vec![1u8; GROTH16_PROOF_LENGTH]);
+        let signals = public_signals(&claim).unwrap();
+
+        assert!(signals.iter().all(|signal| *signal < BN254_SCALAR_FIELD));
+        assert_eq!(signals[1], U256::from(1));
+    }
+
+    #[test]
+    fn test_solidity_verifier_per_trusted_circuit() {
+        let verifier = ZkProofVerifier::new("evm_verifier".to_string());
+        let claim = create_claim(vec![1u8; GROTH16_PROOF_LENGTH]);
+        let (vk, _) = prove(&claim);
+
+        let verifying_keys: HashMap<String, Groth16VerifyingKey> = verifier
+            .list_trusted_circuits()
+            .into_iter()
+            .map(|circuit_id| (circuit_id, vk.clone()))
+            .collect();
+
+        let contracts = export_verifier_contracts(&verifier, &verifying_keys).unwrap();
+        assert_eq!(contracts.len(), verifier.trusted_circuits.len());
+
+        let language = contracts
+            .iter()
+            .find(|contract| contract.circuit_id == "language_proficiency_v1")
+            .unwrap();
+        assert_eq!(language.contract_name, "LanguageProficiencyV1Verifier");
+        assert!(language
+            .source
+            .contains("contract LanguageProficiencyV1Verifier {"));
+        assert!(language.source.contains(&format!(
+            "bytes32 public constant VK_HASH = 0x{};",
+            verifier.trusted_circuits["language_proficiency_v1"].vk_hash
+        )));
+        assert!(language.source.contains("IC3_X"));
+    }
+
+    #[test]
+    fn test_missing_verifying_key() {
+        let verifier = ZkProofVerifier::new("evm_verifier".to_string());
+        let result = export_verifier_contracts(&verifier, &HashMap::new());
+        assert!(matches!(
+            result,
+            Err(EvmExportError::MissingVerifyingKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_groth16_proof_verifies_on_local_evm() {
+        let unproven = create_claim(vec![1u8; GROTH16_PROOF_LENGTH]);
+        let (vk, proof_bytes) = prove(&unproven);
+
+        let mut claim = unproven;
+        claim.proof_data.proof_bytes = proof_bytes;
+
+        let exported = EvmCalldata::from_claim(&claim).unwrap();
+        assert!(verify_on_evm(&vk, &exported.calldata));
+
+        // Tampering with a public signal must break the pairing check
+        let mut tampered = exported.clone();
+        tampered.public_inputs[1] = U256::ZERO;
+        let tampered_calldata = IWeb5ClaimsVerifier::verifyProofCall {
+            a: tampered.proof.a,
+            b: tampered.proof.b,
+            c: tampered.proof.c,
+            input: tampered.public_inputs,
+        }
+        .abi_encode();
+        assert!(!verify_on_evm(&vk, &tampered_calldata));
+    }
+
+    #[test]
+    #[ignore = "needs solc, run with `just test-solidity` (CI pins solc through svm)"]
+    fn test_solidity_verifier_runs_on_local_evm() {
+        let unproven = create_claim(vec![1u8; GROTH16_PROOF_LENGTH]);
+        let (vk, proof_bytes) = prove(&unproven);
+
+        let mut claim = unproven;
+        claim.proof_data.proof_bytes = proof_bytes;
+
+        let verifier = ZkProofVerifier::new("evm_verifier".to_string());
+        let circuit = verifier
+            .get_circuit_info("language_proficiency_v1")
+            .unwrap();
+        let bytecode = compile(&generate_solidity_verifier(circuit, &vk).unwrap());
+
+        let exported = EvmCalldata::from_claim(&claim).unwrap();
+        assert!(deploy_and_verify(&bytecode, &exported.calldata));
+
+        // Tampering with a public signal must fail the on-chain check
+        let tampered_calldata = IWeb5ClaimsVerifier::verifyProofCall {
+            a: exported.proof.a,
+            b: exported.proof.b,
+            c: exported.proof.c,
+            input: [
+                exported.public_inputs[0],
+                U256::ZERO,
+                exported.public_inputs[2],
+            ],
+        }
+        .abi_encode();
+        assert!(!deploy_and_verify(&bytecode, &tampered_calldata));
+    }
+}
@@ -0,0 +1,411 @@
+//! Certificates from konnektoren game sessions.
+//!
+//! A session is the konnektoren [`ChallengeHistory`] a player recorded on a game path. The
+//! [`GameCatalogue`] lists the game paths certificates are issued for and their challenges;
+//! results are only derived for sessions on a known game path whose challenges all belong
+//! to that path, so challenge counts can no longer be typed in by hand.
+//!
+//! [`ChallengeHistory`]: konnektoren_core::challenges::ChallengeHistory
+
+use crate::issuer::GameResult;
+use chrono::{DateTime, Utc};
+use konnektoren_core::certificates::CertificateData;
+use konnektoren_core::challenges::{Challenge, Performance};
+use konnektoren_core::game::GamePath;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::OnceLock;
+use thiserror::Error;
+
+/// Built-in catalogue shipped with the crate
+const BUILTIN_CATALOGUE: &str = include_str!("../game_paths.toml");
+
+/// Performance at which a challenge counts as solved, in percent
+pub const CHALLENGE_PASS_PERCENTAGE: u32 = 60;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum GameSessionError {
+    #[error("Failed to parse game catalogue: {0}")]
+    Parse(String),
+    #[error("Unknown game path: {0}")]
+    UnknownGamePath(String),
+    #[error("Challenge {challenge} is not part of game path {game_path}")]
+    UnknownChallenge {
+        game_path: String,
+        challenge: String,
+    },
+    #[error("Invalid result for challenge {0}")]
+    InvalidResult(String),
+    #[error("Session has no completed challenges")]
+    EmptySession,
+    #[error("Session has no player profile")]
+    MissingProfile,
+}
+
+/// Game path of the catalogue, identified like the konnektoren [`GamePath`]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CatalogueEntry {
+    /// Game path identifier, used as the certificate's `game_path_name`
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// Identifiers of the challenges on the path
+    pub challenges: Vec<String>,
+}
+
+/// Catalogue of konnektoren game paths
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GameCatalogue {
+    /// Known game paths
+    pub game_paths: Vec<CatalogueEntry>,
+}
+
+/// Finished challenge of a session history
+///
+/// Implemented for the konnektoren [`Challenge`] recorded in a `ChallengeHistory`.
+pub trait CompletedChallenge {
+    /// Identifier of the challenge configuration
+    fn challenge_id(&self) -> &str;
+    /// Share of the challenge answered correctly, in percent
+    fn performance_percentage(&self) -> u32;
+    /// When the challenge was started
+    fn started_at(&self) -> Option<DateTime<Utc>>;
+    /// When the challenge was finished (`None` while it is in progress)
+    fn completed_at(&self) -> Option<DateTime<Utc>>;
+}
+
+impl CompletedChallenge for Challenge {
+    fn challenge_id(&self) -> &str {
+        &self.challenge_config.id
+    }
+
+    fn performance_percentage(&self) -> u32 {
+        self.performance(&self.challenge_result)
+    }
+
+    fn started_at(&self) -> Option<DateTime<Utc>> {
+        self.start_time
+    }
+
+    fn completed_at(&self) -> Option<DateTime<Utc>> {
+        self.end_time
+    }
+}
+
+impl From<&GamePath> for CatalogueEntry {
+    fn from(game_path: &GamePath) -> Self {
+        Self {
+            id: game_path.id.clone(),
+            name: game_path.name.clone(),
+            challenges: game_path
+                .challenges
+                .iter()
+                .map(|config| config.id.clone())
+                .collect(),
+        }
+    }
+}
+
+impl GameCatalogue {
+    /// Catalogue of the konnektoren game paths the web app offers
+    pub fn builtin() -> Self {
+        static BUILTIN: OnceLock<GameCatalogue> = OnceLock::new();
+        BUILTIN
+            .get_or_init(|| {
+                Self::parse(BUILTIN_CATALOGUE).expect("built-in game catalogue is valid")
+            })
+            .clone()
+    }
+
+    /// Parse a TOML catalogue
+    pub fn parse(content: &str) -> Result<Self, GameSessionError> {
+        toml::from_str(content).map_err(|e| GameSessionError::Parse(e.to_string()))
+    }
+
+    /// Catalogue of the game paths of a konnektoren game
+    pub fn from_game_paths(game_paths: &[GamePath]) -> Self {
+        Self {
+            game_paths: game_paths.iter().map(CatalogueEntry::from).collect(),
+        }
+    }
+
+    /// Look up a game path
+    pub fn game_path(&self, id: &str) -> Option<&CatalogueEntry> {
+        self.game_paths.iter().find(|path| path.id == id)
+    }
+
+    /// Derive the game result of a session
+    ///
+    /// `history` holds the challenges of the konnektoren `ChallengeHistory`. The challenge
+    /// total comes from the catalogue, a challenge is solved once any attempt reaches
+    /// [`CHALLENGE_PASS_PERCENTAGE`], and the completion date is that of the last attempt.
+    pub fn game_result(
+        &self,
+        game_path_id: &str,
+        profile_name: &str,
+        history: &[impl CompletedChallenge],
+    ) -> Result<GameResult, GameSessionError> {
+        let game_path = self
+            .game_path(game_path_id)
+            .ok_or_else(|| GameSessionError::UnknownGamePath(game_path_id.to_string()))?;
+        if profile_name.trim().is_empty() {
+            return Err(GameSessionError::MissingProfile);
+        }
+
+        let mut solved = HashSet::new();
+        let mut last_completed = None;
+        for challenge in history {
+            let challenge_id = challenge.challenge_id();
+            if !game_path.challenges.iter().any(|id| id == challenge_id) {
+                return Err(GameSessionError::UnknownChallenge {
+                    game_path: game_path.id.clone(),
+                    challenge: challenge_id.to_string(),
+                });
+            }
+            let invalid = || GameSessionError::InvalidResult(challenge_id.to_string());
+            let completed_at = challenge.completed_at().ok_or_else(invalid)?;
+            if challenge.performance_percentage() > 100
+                || challenge
+                    .started_at()
+                    .is_some_and(|started_at| completed_at < started_at)
+            {
+                return Err(invalid());
+            }
+            if challenge.performance_percentage() >= CHALLENGE_PASS_PERCENTAGE {
+                solved.insert(challenge_id);
+            }
+            last_completed = last_completed.max(Some(completed_at));
+        }
+
+        Ok(GameResult {
+            game_path_name: game_path.id.clone(),
+            profile_name: profile_name.to_string(),
+            total_challenges: game_path.challenges.len(),
+            solved_challenges: solved.len(),
+            completed_at: last_completed.ok_or(GameSessionError::EmptySession)?,
+        })
+    }
+
+    /// Build an unsigned certificate from a session
+    ///
+    /// Use [`CertificateIssuer::mint_certificate`](crate::issuer::CertificateIssuer::mint_certificate)
+    /// with [`GameCatalogue::game_result`] for a certificate signed by the issuer.
+    pub fn certificate(
+        &self,
+        game_path_id: &str,
+        profile_name: &str,
+        history: &[impl CompletedChallenge],
+    ) -> Result<CertificateData, GameSessionError> {
+        let result = self.game_result(game_path_id, profile_name, history)?;
+        Ok(CertificateData::new(
+            result.game_path_name,
+            result.total_challenges,
+            result.solved_challenges,
+            result.profile_name,
+            result.completed_at,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issuer::CertificateIssuer;
+    use chrono::Duration;
+
+    /// Challenge of a session history as recorded by konnektoren
+    struct TestChallenge {
+        challenge_id: String,
+        performance: u32,
+        started_at: Option<DateTime<Utc>>,
+        completed_at: Option<DateTime<Utc>>,
+    }
+
+    impl CompletedChallenge for TestChallenge {
+        fn challenge_id(&self) -> &str {
+            &self.challenge_id
+        }
+
+        fn performance_percentage(&self) -> u32 {
+            self.performance
+        }
+
+        fn started_at(&self) -> Option<DateTime<Utc>> {
+            self.started_at
+        }
+
+        fn completed_at(&self) -> Option<DateTime<Utc>> {
+            self.completed_at
+        }
+    }
+
+    fn create_test_challenge(challenge_id: &str, performance: u32) -> TestChallenge {
+        let completed_at = Utc::now();
+        TestChallenge {
+            challenge_id: challenge_id.to_string(),
+            performance,
+            started_at: Some(completed_at - Duration::minutes(5)),
+            completed_at: Some(completed_at),
+        }
+    }
+
+    fn create_test_history() -> Vec<TestChallenge> {
+        vec![
+            create_test_challenge("adjectives-b2", 90),
+            create_test_challenge("konnektoren-b2", 40),
+            create_test_challenge("konnektoren-b2", 80),
+            create_test_challenge("passive-voice-b2", 60),
+            create_test_challenge("subjunctive-b2", 50),
+        ]
+    }
+
+    #[test]
+    fn test_builtin_catalogue() {
+        let catalogue = GameCatalogue::builtin();
+
+        assert!(catalogue.game_path("German_B2_Complete").is_some());
+        assert!(catalogue.game_path("Klingon_C2").is_none());
+        assert!(catalogue
+            .game_paths
+            .iter()
+            .all(|path| !path.challenges.is_empty()));
+    }
+
+    #[test]
+    fn test_builtin_catalogue_covers_web_app_paths() {
+        let catalogue = GameCatalogue::builtin();
+
+        for id in [
+            "German_A1_Basic",
+            "German_A2_Elementary",
+            "German_B1_Intermediate",
+            "German_B2_Complete",
+            "German_C1_Advanced",
+            "Spanish_A1_Basic",
+            "Spanish_A2_Elementary",
+            "Spanish_B2_Complete",
+            "French_A2_Elementary",
+            "French_C1_Advanced",
+            "Italian_B1_Intermediate",
+        ] {
+            assert!(catalogue.game_path(id).is_some(), "{} missing", id);
+        }
+    }
+
+    #[test]
+    fn test_certificate_from_session() {
+        let history = create_test_history();
+        let certificate = GameCatalogue::builtin()
+            .certificate("German_B2_Complete", "Test Student", &history)
+            .unwrap();
+
+        assert_eq!(certificate.game_path_name, "German_B2_Complete");
+        assert_eq!(certificate.total_challenges, 7);
+        // The retried konnektoren challenge counts once; the subjunctive attempt failed
+        assert_eq!(certificate.solved_challenges, 3);
+        assert_eq!(
+            Some(certificate.date),
+            history.iter().filter_map(|c| c.completed_at).max()
+        );
+    }
+
+    #[test]
+    fn test_fake_game_path_rejected() {
+        assert_eq!(
+            GameCatalogue::builtin().certificate(
+                "Klingon_C2",
+                "Test Student",
+                &create_test_history()
+            ),
+            Err(GameSessionError::UnknownGamePath("Klingon_C2".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_foreign_challenge_rejected() {
+        let mut history = create_test_history();
+        history.push(create_test_challenge("articles-a1", 100));
+
+        assert_eq!(
+            GameCatalogue::builtin().game_result("German_B2_Complete", "Test Student", &history),
+            Err(GameSessionError::UnknownChallenge {
+                game_path: "German_B2_Complete".to_string(),
+                challenge: "articles-a1".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_sessions_rejected() {
+        let catalogue = GameCatalogue::builtin();
+        let game_result = |history: &[TestChallenge]| {
+            catalogue.game_result("German_B2_Complete", "Test Student", history)
+        };
+
+        let mut inflated = create_test_history();
+        inflated[0].performance = 110;
+        assert_eq!(
+            game_result(&inflated),
+            Err(GameSessionError::InvalidResult("adjectives-b2".to_string()))
+        );
+
+        let mut backwards = create_test_history();
+        backwards[0].started_at = backwards[0].completed_at.map(|at| at + Duration::hours(1));
+        assert!(matches!(
+            game_result(&backwards),
+            Err(GameSessionError::InvalidResult(_))
+        ));
+
+        let mut unfinished = create_test_history();
+        unfinished[0].completed_at = None;
+        assert!(matches!(
+            game_result(&unfinished),
+            Err(GameSessionError::InvalidResult(_))
+        ));
+
+        assert_eq!(game_result(&[]), Err(GameSessionError::EmptySession));
+
+        assert_eq!(
+            catalogue.game_result("German_B2_Complete", " ", &create_test_history()),
+            Err(GameSessionError::MissingProfile)
+        );
+    }
+
+    #[test]
+    fn test_issuer_mints_session_certificate() {
+        let issuer = CertificateIssuer::new("test_issuer".to_string(), "Test Issuer".to_string())
+            .accept_unsigned_certificates()
+            .with_signing_key(
+                "key-1".to_string(),
+                ed25519_dalek::SigningKey::from_bytes(&[31u8; 32]),
+            )
+            .require_own_certificates()
+            .unwrap();
+        let result = GameCatalogue::builtin()
+            .game_result("German_B2_Complete", "Test Student", &create_test_history())
+            .unwrap();
+        let minted = issuer.mint_certificate(&result).unwrap();
+
+        assert!(issuer.verify_certificate_signature(&minted).is_ok());
+        assert_eq!(minted.certificate.solved_challenges, 3);
+    }
+
+    #[test]
+    fn test_custom_catalogue() {
+        let catalogue = GameCatalogue::parse(
+            r#"
+            [[game_paths]]
+            id = "German_A1_Basic"
+            name = "German A1"
+            challenges = ["articles-a1"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(catalogue.game_paths.len(), 1);
+        assert!(matches!(
+            GameCatalogue::parse("game_paths = 1"),
+            Err(GameSessionError::Parse(_))
+        ));
+    }
+}
@@ -0,0 +1,533 @@
+//! Signed handoff of ZKPassport results from the zkpass app to the issuer page.
+//!
+//! The issuer page creates a nonce and opens the zkpass app with it. Once the passport check
+//! is done, the zkpass app posts the ZKPassport proofs to the handoff service, which verifies
+//! them against a ZKPassport scope derived from the nonce and signs a short-lived EdDSA JWT
+//! holding the verified result, the nonce and an expiry. The zkpass app redirects back with it in the `handoff` query parameter. The
+//! issuer page shows the result as verified only after [`HandoffVerifier::verify`] checked
+//! the signature, the expiry and the pending nonce.
+//!
+//! Consuming the nonce only prevents replays where the holder cannot edit the stored nonces.
+//! A browser page keeping them in its own storage binds a token to the session that requested
+//! it, but its user can reset that storage and reuse the token until it expires.
+
+use crate::identity::{disclosed_values, verified_min_age};
+use crate::sd_jwt::{decode_jwt, encode_jwt, SdJwtError};
+use crate::verifier::VerificationMode;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Duration, Utc};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Query parameter carrying the handoff token back to the issuer page
+pub const HANDOFF_QUERY_PARAM: &str = "handoff";
+/// Query parameter carrying the handoff nonce to the zkpass app
+pub const HANDOFF_NONCE_QUERY_PARAM: &str = "handoff_nonce";
+/// JWT type of handoff tokens
+pub const HANDOFF_TYP: &str = "zkpass-handoff+jwt";
+
+/// Warning attached to handoffs whose ZKPassport proofs did not verify
+pub const UNVERIFIED_HANDOFF_WARNING: &str =
+    "ZKPassport reported the proofs as unverified; the result is only suitable for demos";
+
+/// Tolerated clock difference between the handoff service and the issuer page, in seconds
+const CLOCK_SKEW_SECONDS: i64 = 60;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum HandoffError {
+    #[error("Malformed handoff token: {0}")]
+    Malformed(String),
+    #[error("Handoff token is not signed by a trusted key")]
+    InvalidSignature,
+    #[error("Handoff token has expired")]
+    Expired,
+    #[error("Handoff token lifetime is not allowed: {0}s")]
+    InvalidLifetime(i64),
+    #[error("Handoff token does not answer a pending request")]
+    UnknownNonce,
+    #[error("Handoff token has already been used")]
+    Replayed,
+    #[error("ZKPassport result is not verified")]
+    NotVerified,
+}
+
+/// Result of a ZKPassport request as reported by the SDK
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ZkPassportResult {
+    /// Whether the SDK verified the proofs
+    pub verified: bool,
+    /// Identifier of the passport holder, unique within the request scope
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unique_identifier: Option<String>,
+    /// Query result in the SDK format, e.g. `{"age": {"gte": {"expected": 18, "result": true}}}`
+    pub query_result: serde_json::Value,
+}
+
+/// Claims of a handoff token
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HandoffClaims {
+    /// Issuer of the token, e.g. the handoff service
+    #[serde(rename = "iss")]
+    pub issuer: String,
+    /// Nonce created by the issuer page
+    pub nonce: String,
+    /// Issue time as a Unix timestamp
+    #[serde(rename = "iat")]
+    pub issued_at: i64,
+    /// Expiry as a Unix timestamp
+    #[serde(rename = "exp")]
+    pub expires_at: i64,
+    /// ZKPassport result
+    pub result: ZkPassportResult,
+}
+
+/// Handoff accepted by a [`HandoffVerifier`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifiedHandoff {
+    /// Claims of the token
+    pub claims: HandoffClaims,
+    /// Warnings about the result
+    pub warnings: Vec<String>,
+}
+
+/// Nonces of a handoff verifier
+///
+/// Serializable so a browser page can keep them in storage across the redirect to the
+/// zkpass app and back. Storage the user can write does not stop that user from replaying
+/// a token.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct HandoffNonces {
+    /// Creation time of nonces awaiting a token, by nonce
+    pending: HashMap<String, i64>,
+    /// Expiry of the tokens that consumed a nonce, by nonce
+    consumed: HashMap<String, i64>,
+}
+
+/// Signer of handoff tokens
+#[derive(Debug, Clone)]
+pub struct HandoffSigner {
+    issuer: String,
+    signing_key: SigningKey,
+    ttl: Duration,
+}
+
+/// Verifier of handoff tokens
+#[derive(Debug, Clone)]
+pub struct HandoffVerifier {
+    /// Keys trusted to sign handoff tokens
+    pub trusted_keys: Vec<VerifyingKey>,
+    /// Handling of results the SDK did not verify
+    pub mode: VerificationMode,
+    /// Longest accepted token lifetime
+    pub max_ttl: Duration,
+    /// How long a nonce waits for its token
+    pub request_ttl: Duration,
+}
+
+impl ZkPassportResult {
+    /// Minimum age the result proves
+    pub fn age_over(&self) -> Option<u8> {
+        verified_min_age(&self.query_result)
+    }
+
+    /// Disclosed first name
+    pub fn first_name(&self) -> Option<String> {
+        disclosed_values(&self.query_result).remove("firstname")
+    }
+
+    /// Human readable summary, e.g. `Age (18+) • Name (Jane)`
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(age) = self.age_over() {
+            parts.push(format!("Age ({}+)", age));
+        }
+        if let Some(name) = self.first_name() {
+            parts.push(format!("Name ({})", name));
+        }
+        parts.join(" • ")
+    }
+}
+
+impl HandoffClaims {
+    /// Create claims valid from now for a lifetime
+    pub fn new(issuer: String, nonce: String, result: ZkPassportResult, ttl: Duration) -> Self {
+        let now = Utc::now();
+        Self {
+            issuer,
+            nonce,
+            issued_at: now.timestamp(),
+            expires_at: (now + ttl).timestamp(),
+            result,
+        }
+    }
+
+    /// Issue time
+    pub fn issued_at(&self) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp(self.issued_at, 0)
+    }
+}
+
+impl HandoffNonces {
+    /// Whether a nonce awaits its token
+    pub fn is_pending(&self, nonce: &str) -> bool {
+        self.pending.contains_key(nonce)
+    }
+
+    /// Drop nonces that can no longer be used
+    fn prune(&mut self, request_ttl: Duration, now: i64) {
+        let oldest = now - request_ttl.num_seconds();
+        self.pending.retain(|_, created_at| *created_at >= oldest);
+        // Expired tokens are refused anyway, so their nonces need not be remembered
+        self.consumed.retain(|_, expires_at| *expires_at > now);
+    }
+}
+
+impl HandoffSigner {
+    /// Create a signer issuing five minute tokens
+    pub fn new(issuer: String, signing_key: SigningKey) -> Self {
+        Self {
+            issuer,
+            signing_key,
+            ttl: Duration::minutes(5),
+        }
+    }
+
+    /// Set the lifetime of issued tokens
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Public key to trust in a [`HandoffVerifier`]
+    pub fn public_key(&self) -> VerifyingKey {
+        self.signing_key.verifying_key()
+    }
+
+    /// Sign a ZKPassport result for a nonce
+    pub fn sign_result(&self, nonce: &str, result: ZkPassportResult) -> String {
+        self.sign(&HandoffClaims::new(
+            self.issuer.clone(),
+            nonce.to_string(),
+            result,
+            self.ttl,
+        ))
+    }
+
+    /// Sign handoff claims
+    pub fn sign(&self, claims: &HandoffClaims) -> String {
+        let payload = serde_json::to_value(claims).expect("handoff claims serialize");
+        encode_jwt(HANDOFF_TYP, &payload, &self.signing_key)
+    }
+}
+
+impl HandoffVerifier {
+    /// Create a verifier accepting tokens of up to five minutes
+    pub fn new() -> Self {
+        Self {
+            trusted_keys: Vec::new(),
+            mode: VerificationMode::default(),
+            max_ttl: Duration::minutes(5),
+            request_ttl: Duration::minutes(30),
+        }
+    }
+
+    /// Trust a key to sign handoff tokens
+    pub fn with_trusted_key(mut self, key: VerifyingKey) -> Self {
+        self.trusted_keys.push(key);
+        self
+    }
+
+    /// Set how results the SDK did not verify are handled
+    pub fn with_mode(mut self, mode: VerificationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Create a fresh nonce and remember it until its token arrives
+    pub fn create_nonce(&self, nonces: &mut HandoffNonces) -> String {
+        let now = Utc::now().timestamp();
+        nonces.prune(self.request_ttl, now);
+
+        let mut nonce = [0u8; 16];
+        OsRng.fill_bytes(&mut nonce);
+        let nonce = hex::encode(nonce);
+        nonces.pending.insert(nonce.clone(), now);
+        nonce
+    }
+
+    /// Verify a handoff token against the pending nonces
+    ///
+    /// The nonce is consumed only when the token is accepted, so a tampered token does not
+    /// burn it.
+    pub fn verify(
+        &self,
+        token: &str,
+        nonces: &mut HandoffNonces,
+    ) -> Result<VerifiedHandoff, HandoffError> {
+        let claims = self.decode(token)?;
+
+        let now = Utc::now().timestamp();
+        let lifetime = claims.expires_at - claims.issued_at;
+        if lifetime <= 0
+            || lifetime > self.max_ttl.num_seconds()
+            || claims.issued_at > now + CLOCK_SKEW_SECONDS
+        {
+            return Err(HandoffError::InvalidLifetime(lifetime));
+        }
+        if now >= claims.expires_at {
+            return Err(HandoffError::Expired);
+        }
+
+        nonces.prune(self.request_ttl, now);
+        if nonces.consumed.contains_key(&claims.nonce) {
+            return Err(HandoffError::Replayed);
+        }
+        if !nonces.is_pending(&claims.nonce) {
+            return Err(HandoffError::UnknownNonce);
+        }
+
+        let mut warnings = Vec::new();
+        if !claims.result.verified {
+            if self.mode == VerificationMode::Strict {
+                return Err(HandoffError::NotVerified);
+            }
+            warnings.push(UNVERIFIED_HANDOFF_WARNING.to_string());
+        }
+
+        nonces.pending.remove(&claims.nonce);
+        nonces
+            .consumed
+            .insert(claims.nonce.clone(), claims.expires_at);
+        Ok(VerifiedHandoff { claims, warnings })
+    }
+
+    /// Check the signature against the trusted keys and parse the claims
+    fn decode(&self, token: &str) -> Result<HandoffClaims, HandoffError> {
+        let mut payload = Err(HandoffError::InvalidSignature);
+        for key in &self.trusted_keys {
+            match decode_jwt(token, HANDOFF_TYP, key) {
+                Ok(value) => {
+                    payload = Ok(value);
+                    break;
+                }
+                Err(SdJwtError::InvalidSignature(_)) => {}
+                Err(e) => return Err(HandoffError::Malformed(e.to_string())),
+            }
+        }
+        serde_json::from_value(payload?).map_err(|e| HandoffError::Malformed(e.to_string()))
+    }
+}
+
+impl Default for HandoffVerifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse a base64url encoded Ed25519 public key, e.g. the `x` member of a JWK
+pub fn decode_public_key(encoded: &str) -> Result<VerifyingKey, HandoffError> {
+    let bytes: [u8; 32] = URL_SAFE_NO_PAD
+        .decode(encoded.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| HandoffError::Malformed("public key".to_string()))?;
+    VerifyingKey::from_bytes(&bytes).map_err(|e| HandoffError::Malformed(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_signer() -> HandoffSigner {
+        HandoffSigner::new(
+            "web5claims-zkpass".to_string(),
+            SigningKey::from_bytes(&[7u8; 32]),
+        )
+    }
+
+    fn create_test_verifier() -> HandoffVerifier {
+        HandoffVerifier::new().with_trusted_key(create_test_signer().public_key())
+    }
+
+    fn create_test_result(verified: bool) -> ZkPassportResult {
+        ZkPassportResult {
+            verified,
+            unique_identifier: Some("nullifier-1".to_string()),
+            query_result: serde_json::json!({
+                "age": {"gte": {"expected": 18, "result": true}},
+                "firstname": {"disclose": {"result": "Jane"}}
+            }),
+        }
+    }
+
+    #[test]
+    fn test_handoff_roundtrip() {
+        let verifier = create_test_verifier();
+        let mut nonces = HandoffNonces::default();
+        let nonce = verifier.create_nonce(&mut nonces);
+        assert!(nonces.is_pending(&nonce));
+
+        let token = create_test_signer().sign_result(&nonce, create_test_result(true));
+        let handoff = verifier.verify(&token, &mut nonces).unwrap();
+
+        assert!(handoff.warnings.is_empty());
+        assert_eq!(handoff.claims.nonce, nonce);
+        assert_eq!(handoff.claims.result.age_over(), Some(18));
+        assert_eq!(handoff.claims.result.first_name().as_deref(), Some("Jane"));
+        assert_eq!(handoff.claims.result.summary(), "Age (18+) • Name (Jane)");
+        assert!(!nonces.is_pending(&nonce));
+    }
+
+    #[test]
+    fn test_replayed_token_rejected() {
+        let verifier = create_test_verifier();
+        let mut nonces = HandoffNonces::default();
+        let nonce = verifier.create_nonce(&mut nonces);
+        let token = create_test_signer().sign_result(&nonce, create_test_result(true));
+
+        assert!(verifier.verify(&token, &mut nonces).is_ok());
+        assert_eq!(
+            verifier.verify(&token, &mut nonces),
+            Err(HandoffError::Replayed)
+        );
+
+        // Nonces survive the redirect through storage
+        let mut restored: HandoffNonces =
+            serde_json::from_str(&serde_json::to_string(&nonces).unwrap()).unwrap();
+        assert_eq!(
+            verifier.verify(&token, &mut restored),
+            Err(HandoffError::Replayed)
+        );
+    }
+
+    #[test]
+    fn test_unknown_nonce_rejected() {
+        let verifier = create_test_verifier();
+        let mut nonces = HandoffNonces::default();
+        verifier.create_nonce(&mut nonces);
+        let token = create_test_signer().sign_result("forged", create_test_result(true));
+
+        assert_eq!(
+            verifier.verify(&token, &mut nonces),
+            Err(HandoffError::UnknownNonce)
+        );
+    }
+
+    #[test]
+    fn test_tampered_token_rejected() {
+        let verifier = create_test_verifier();
+        let mut nonces = HandoffNonces::default();
+        let nonce = verifier.create_nonce(&mut nonces);
+        let token = create_test_signer().sign_result(&nonce, create_test_result(true));
+
+        let parts: Vec<&str> = token.split('.').collect();
+        let mut claims: HandoffClaims =
+            serde_json::from_slice(&URL_SAFE_NO_PAD.decode(parts[1]).unwrap()).unwrap();
+        claims.result.query_result["firstname"]["disclose"]["result"] = "Mallory".into();
+        let tampered = format!(
+            "{}.{}.{}",
+            parts[0],
+            URL_SAFE_NO_PAD.encode(serde_json::to_string(&claims).unwrap()),
+            parts[2]
+        );
+        assert_eq!(
+            verifier.verify(&tampered, &mut nonces),
+            Err(HandoffError::InvalidSignature)
+        );
+
+        let untrusted =
+            HandoffSigner::new("mallory".to_string(), SigningKey::from_bytes(&[9u8; 32]))
+                .sign_result(&nonce, create_test_result(true));
+        assert_eq!(
+            verifier.verify(&untrusted, &mut nonces),
+            Err(HandoffError::InvalidSignature)
+        );
+        assert!(matches!(
+            verifier.verify("not-a-token", &mut nonces),
+            Err(HandoffError::Malformed(_))
+        ));
+
+        // Rejected tokens leave the nonce usable
+        assert!(verifier.verify(&token, &mut nonces).is_ok());
+    }
+
+    #[test]
+    fn test_expired_and_long_lived_tokens_rejected() {
+        let verifier = create_test_verifier();
+        let signer = create_test_signer();
+        let mut nonces = HandoffNonces::default();
+        let nonce = verifier.create_nonce(&mut nonces);
+
+        let mut claims = HandoffClaims::new(
+            "web5claims-zkpass".to_string(),
+            nonce.clone(),
+            create_test_result(true),
+            Duration::minutes(5),
+        );
+        claims.issued_at -= 600;
+        claims.expires_at -= 600;
+        assert_eq!(
+            verifier.verify(&signer.sign(&claims), &mut nonces),
+            Err(HandoffError::Expired)
+        );
+
+        let long_lived = signer
+            .with_ttl(Duration::days(1))
+            .sign_result(&nonce, create_test_result(true));
+        assert_eq!(
+            verifier.verify(&long_lived, &mut nonces),
+            Err(HandoffError::InvalidLifetime(86400))
+        );
+        assert!(nonces.is_pending(&nonce));
+    }
+
+    #[test]
+    fn test_unverified_result_by_mode() {
+        let signer = create_test_signer();
+        let lenient = create_test_verifier();
+        let mut nonces = HandoffNonces::default();
+        let nonce = lenient.create_nonce(&mut nonces);
+        let token = signer.sign_result(&nonce, create_test_result(false));
+
+        let strict = create_test_verifier().with_mode(VerificationMode::Strict);
+        assert_eq!(
+            strict.verify(&token, &mut nonces),
+            Err(HandoffError::NotVerified)
+        );
+
+        let handoff = lenient.verify(&token, &mut nonces).unwrap();
+        assert_eq!(handoff.warnings, vec![UNVERIFIED_HANDOFF_WARNING]);
+    }
+
+    #[test]
+    fn test_stale_nonces_pruned() {
+        let verifier = create_test_verifier();
+        let mut nonces = HandoffNonces::default();
+        nonces
+            .pending
+            .insert("stale".to_string(), Utc::now().timestamp() - 3600);
+        nonces
+            .consumed
+            .insert("used".to_string(), Utc::now().timestamp() - 1);
+
+        verifier.create_nonce(&mut nonces);
+        assert!(!nonces.is_pending("stale"));
+        assert!(nonces.consumed.is_empty());
+        assert_eq!(nonces.pending.len(), 1);
+    }
+
+    #[test]
+    fn test_decode_public_key() {
+        let key = create_test_signer().public_key();
+        let encoded = URL_SAFE_NO_PAD.encode(key.as_bytes());
+        assert_eq!(decode_public_key(&encoded).unwrap(), key);
+        assert!(matches!(
+            decode_public_key("abc"),
+            Err(HandoffError::Malformed(_))
+        ));
+    }
+}
@@ -0,0 +1,630 @@
+//! Holder role: the learner who owns certificates and presents proofs about them.
+
+use crate::issuer::{
+    CertificateIssuer, IssuerError, MintedCertificate, ProofOptions, ProofRequest,
+};
+use crate::presentation::{Presentation, PresentationError};
+use crate::presentation_exchange::{
+    DefinitionEvaluation, PresentationDefinition, PresentationExchangeError,
+};
+use crate::sd_jwt::SdJwt;
+use crate::verifier::ZkProofVerifier;
+use crate::zk_proof::{ClaimType, ZkProofClaim};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use konnektoren_core::certificates::CertificateData;
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum HolderError {
+    #[error("Proof integrity check failed")]
+    IntegrityCheckFailed,
+    #[error("Unknown proof: {0}")]
+    UnknownProof(String),
+    #[error("No stored certificate satisfies the request")]
+    NoMatchingCertificate,
+    #[error("Issuer error: {0}")]
+    Issuer(#[from] IssuerError),
+    #[error("Presentation error: {0}")]
+    Presentation(#[from] PresentationError),
+    #[error("Serialization failed: {0}")]
+    Serialization(String),
+}
+
+/// Request from a verifier for a proof of a claim
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClaimRequest {
+    /// Claim the verifier wants proven
+    pub claim_type: ClaimType,
+    /// Platform the verifier checks proofs on
+    pub target_platform: String,
+}
+
+/// Holder wallet with a key pair, certificates and received proofs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Holder {
+    /// Holder identifier
+    pub holder_id: String,
+    /// Holder signing key
+    #[serde(with = "signing_key_hex")]
+    signing_key: SigningKey,
+    /// Certificates owned by the holder
+    certificates: Vec<CertificateData>,
+    /// Minted certificates, kept for their issuer signatures
+    #[serde(default)]
+    minted_certificates: Vec<MintedCertificate>,
+    /// Proofs received from issuers
+    proofs: Vec<ZkProofClaim>,
+    /// SD-JWT credentials in compact serialization
+    #[serde(default)]
+    sd_jwts: Vec<String>,
+}
+
+impl Holder {
+    /// Create a holder with a fresh key pair
+    pub fn new(holder_id: String) -> Self {
+        Self::from_signing_key(holder_id, generate_signing_key())
+    }
+
+    /// Create a holder from an existing signing key
+    pub fn from_signing_key(holder_id: String, signing_key: SigningKey) -> Self {
+        Self {
+            holder_id,
+            signing_key,
+            certificates: Vec::new(),
+            minted_certificates: Vec::new(),
+            proofs: Vec::new(),
+            sd_jwts: Vec::new(),
+        }
+    }
+
+    /// Create a holder with a fresh key pair and an id derived from its public key
+    pub fn generate() -> Self {
+        let signing_key = generate_signing_key();
+        let holder_id = format!(
+            "holder_{}",
+            &hex::encode(signing_key.verifying_key().as_bytes())[..16]
+        );
+        Self::from_signing_key(holder_id, signing_key)
+    }
+
+    /// Public key of the holder
+    pub fn public_key(&self) -> VerifyingKey {
+        self.signing_key.verifying_key()
+    }
+
+    /// Hex-encoded public key of the holder
+    pub fn public_key_hex(&self) -> String {
+        hex::encode(self.public_key().as_bytes())
+    }
+
+    /// Sign a message with the holder key
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        self.signing_key.sign(message)
+    }
+
+    /// Store a certificate
+    pub fn add_certificate(&mut self, certificate: CertificateData) {
+        if !self.certificates.contains(&certificate) {
+            self.certificates.push(certificate);
+        }
+    }
+
+    /// Store a minted certificate, whose signature is sent along with proof requests
+    pub fn add_minted_certificate(&mut self, minted: MintedCertificate) {
+        self.add_certificate(minted.certificate.clone());
+        if !self.minted_certificates.contains(&minted) {
+            self.minted_certificates.push(minted);
+        }
+    }
+
+    /// Certificates owned by the holder
+    pub fn certificates(&self) -> &[CertificateData] {
+        &self.certificates
+    }
+
+    /// Store a proof received from an issuer
+    pub fn store_proof(&mut self, proof: ZkProofClaim) -> Result<(), HolderError> {
+        if !proof.verify_integrity() {
+            return Err(HolderError::IntegrityCheckFailed);
+        }
+
+        self.proofs
+            .retain(|stored| stored.proof_id != proof.proof_id);
+        self.proofs.push(proof);
+        Ok(())
+    }
+
+    /// Store an SD-JWT credential issued to the holder
+    pub fn add_sd_jwt(&mut self, sd_jwt: &SdJwt) {
+        let serialized = sd_jwt.serialize();
+        if !self.sd_jwts.contains(&serialized) {
+            self.sd_jwts.push(serialized);
+        }
+    }
+
+    /// SD-JWT credentials held by the holder
+    pub fn sd_jwts(&self) -> Vec<SdJwt> {
+        self.sd_jwts
+            .iter()
+            .filter_map(|serialized| SdJwt::parse(serialized).ok())
+            .collect()
+    }
+
+    /// Proofs held by the holder
+    pub fn proofs(&self) -> &[ZkProofClaim] {
+        &self.proofs
+    }
+
+    /// Get a stored proof by id
+    pub fn proof(&self, proof_id: &str) -> Option<&ZkProofClaim> {
+        self.proofs.iter().find(|proof| proof.proof_id == proof_id)
+    }
+
+    /// Collect stored proofs for a presentation
+    pub fn select_proofs(&self, proof_ids: &[&str]) -> Result<Vec<ZkProofClaim>, HolderError> {
+        proof_ids
+            .iter()
+            .map(|proof_id| {
+                self.proof(proof_id)
+                    .cloned()
+                    .ok_or_else(|| HolderError::UnknownProof(proof_id.to_string()))
+            })
+            .collect()
+    }
+
+    /// Work out which stored certificates, and proofs `verifier` accepts, satisfy a
+    /// presentation definition
+    pub fn evaluate_definition(
+        &self,
+        definition: &PresentationDefinition,
+        verifier: &ZkProofVerifier,
+    ) -> Result<DefinitionEvaluation, PresentationExchangeError> {
+        definition.evaluate(&self.certificates, &self.proofs, verifier)
+    }
+
+    /// Sign a presentation with the holder key
+    pub fn sign_presentation(&self, presentation: Presentation) -> Presentation {
+        presentation.sign(&self.signing_key)
+    }
+
+    /// Present stored proofs to a verifier, bound to its audience and nonce
+    pub fn present(
+        &self,
+        proof_ids: &[&str],
+        audience: &str,
+        nonce: &str,
+    ) -> Result<Presentation, HolderError> {
+        if proof_ids.is_empty() {
+            return Err(PresentationError::Empty.into());
+        }
+
+        let presentation = Presentation::new(
+            self.holder_id.clone(),
+            &self.public_key(),
+            self.select_proofs(proof_ids)?,
+            audience.to_string(),
+            nonce.to_string(),
+        );
+        Ok(self.sign_presentation(presentation))
+    }
+
+    /// Disclose the named attributes of an SD-JWT credential, bound to a verifier's audience
+    /// and nonce
+    pub fn present_sd_jwt(
+        &self,
+        sd_jwt: &SdJwt,
+        disclose: &[&str],
+        audience: &str,
+        nonce: &str,
+    ) -> SdJwt {
+        sd_jwt
+            .disclose(disclose)
+            .bind(&self.signing_key, audience, nonce)
+    }
+
+    /// Answer a proof request
+    ///
+    /// Reuses a stored proof that meets the request, otherwise asks the issuer for a new
+    /// proof bound to the holder key from the first stored certificate that satisfies the
+    /// claim. When no certificate satisfies it, the last issuer error other than an unmet
+    /// claim is returned, so a misconfigured request is not reported as a missing
+    /// certificate.
+    pub fn answer_request(
+        &mut self,
+        issuer: &CertificateIssuer,
+        request: &ClaimRequest,
+    ) -> Result<ZkProofClaim, HolderError> {
+        let holder_key = self.public_key_hex();
+        let stored = self.proofs.iter().find(|proof| {
+            proof.holder_key() == Some(holder_key.as_str())
+                && proof.claim_type == request.claim_type
+                && proof.metadata.platform == request.target_platform
+                && proof.public_inputs.verification_result
+        });
+        if let Some(proof) = stored {
+            return Ok(proof.clone());
+        }
+
+        let mut found = None;
+        let mut last_error = None;
+        for certificate in &self.certificates {
+            let mut proof_request = self.proof_request(certificate, request);
+            proof_request.options.holder_key = Some(holder_key.clone());
+            match issuer.generate_proof(proof_request) {
+                Ok(proof) if proof.public_inputs.verification_result => {
+                    found = Some(proof);
+                    break;
+                }
+                Ok(_) => {}
+                Err(error) if is_unmet_claim(&error) => {}
+                Err(error) => last_error = Some(error),
+            }
+        }
+
+        let proof = match (found, last_error) {
+            (Some(proof), _) => proof,
+            (None, Some(error)) => return Err(error.into()),
+            (None, None) => return Err(HolderError::NoMatchingCertificate),
+        };
+        self.store_proof(proof.clone())?;
+        Ok(proof)
+    }
+
+    /// Proof request for a stored certificate, carrying its signature when it was minted
+    fn proof_request(&self, certificate: &CertificateData, request: &ClaimRequest) -> ProofRequest {
+        let minted = self
+            .minted_certificates
+            .iter()
+            .find(|minted| &minted.certificate == certificate);
+        match minted {
+            Some(minted) => {
+                minted.proof_request(request.claim_type.clone(), request.target_platform.clone())
+            }
+            None => ProofRequest {
+                certificate: certificate.clone(),
+                claim_type: request.claim_type.clone(),
+                target_platform: request.target_platform.clone(),
+                options: ProofOptions::default(),
+            },
+        }
+    }
+
+    /// Export the wallet as JSON, including the secret key
+    pub fn to_json(&self) -> Result<String, HolderError> {
+        serde_json::to_string(self).map_err(|e| HolderError::Serialization(e.to_string()))
+    }
+
+    /// Import a wallet exported with [`Holder::to_json`]
+    pub fn from_json(json: &str) -> Result<Self, HolderError> {
+        serde_json::from_str(json).map_err(|e| HolderError::Serialization(e.to_string()))
+    }
+}
+
+impl Default for Holder {
+    fn default() -> Self {
+        Self::generate()
+    }
+}
+
+/// Whether an issuer error only means the certificate does not satisfy the claim
+fn is_unmet_claim(error: &IssuerError) -> bool {
+    matches!(
+        error,
+        IssuerError::InsufficientPerformance { .. }
+            | IssuerError::InvalidCertificate(_)
+            | IssuerError::InvalidCefrLevel(_)
+    )
+}
+
+/// Generate a random Ed25519 signing key
+fn generate_signing_key() -> SigningKey {
+    let mut secret = [0u8; 32];
+    OsRng.fill_bytes(&mut secret);
+    SigningKey::from_bytes(&secret)
+}
+
+/// Serialize signing keys as hex-encoded secret key bytes
+pub(crate) mod signing_key_hex {
+    use ed25519_dalek::SigningKey;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(key: &SigningKey, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(key.to_bytes()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<SigningKey, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        let bytes: [u8; 32] = hex::decode(encoded)
+            .map_err(D::Error::custom)?
+            .try_into()
+            .map_err(|_| D::Error::custom("secret key must be 32 bytes"))?;
+        Ok(SigningKey::from_bytes(&bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issuer::GameResult;
+    use crate::zk_proof::CefrLevel;
+    use chrono::Utc;
+    use ed25519_dalek::Verifier;
+
+    fn create_test_certificate() -> CertificateData {
+        CertificateData::new(
+            "German_B2_Complete".to_string(),
+            7,
+            7,
+            "Test Student".to_string(),
+            Utc::now(),
+        )
+    }
+
+    fn create_test_issuer() -> CertificateIssuer {
+        CertificateIssuer::new("test_issuer".to_string(), "Test Issuer".to_string())
+            .accept_unsigned_certificates()
+    }
+
+    fn create_language_request(level: CefrLevel) -> ClaimRequest {
+        ClaimRequest {
+            claim_type: ClaimType::LanguageProficiency {
+                language: "German".to_string(),
+                min_level: level,
+            },
+            target_platform: "test".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_holder_keys() {
+        let holder = Holder::generate();
+        let other = Holder::generate();
+
+        assert_ne!(holder.public_key(), other.public_key());
+        assert!(holder.holder_id.starts_with("holder_"));
+        assert_eq!(holder.public_key_hex().len(), 64);
+    }
+
+    #[test]
+    fn test_sign() {
+        let holder = Holder::new("alice".to_string());
+        let signature = holder.sign(b"presentation");
+
+        assert!(holder
+            .public_key()
+            .verify(b"presentation", &signature)
+            .is_ok());
+        assert!(holder.public_key().verify(b"tampered", &signature).is_err());
+    }
+
+    #[test]
+    fn test_add_certificate_deduplicates() {
+        let mut holder = Holder::generate();
+        let certificate = create_test_certificate();
+
+        holder.add_certificate(certificate.clone());
+        holder.add_certificate(certificate);
+        assert_eq!(holder.certificates().len(), 1);
+    }
+
+    #[test]
+    fn test_store_proof_checks_integrity() {
+        let mut holder = Holder::generate();
+        holder.add_certificate(create_test_certificate());
+        let mut proof = holder
+            .answer_request(
+                &create_test_issuer(),
+                &create_language_request(CefrLevel::B1),
+            )
+            .unwrap();
+
+        proof.proof_id = "broken".to_string();
+        proof.proof_data.proof_bytes.clear();
+        assert!(matches!(
+            holder.store_proof(proof),
+            Err(HolderError::IntegrityCheckFailed)
+        ));
+        assert_eq!(holder.proofs().len(), 1);
+    }
+
+    #[test]
+    fn test_answer_request_generates_and_reuses_proof() {
+        let mut holder = Holder::generate();
+        holder.add_certificate(create_test_certificate());
+        let issuer = create_test_issuer();
+        let request = create_language_request(CefrLevel::B1);
+
+        let first = holder.answer_request(&issuer, &request).unwrap();
+        assert!(first.public_inputs.verification_result);
+        assert!(holder.proof(&first.proof_id).is_some());
+        assert_eq!(first.holder_key(), Some(holder.public_key_hex().as_str()));
+
+        let second = holder.answer_request(&issuer, &request).unwrap();
+        assert_eq!(second.proof_id, first.proof_id);
+        assert_eq!(holder.proofs().len(), 1);
+    }
+
+    #[test]
+    fn test_answer_request_without_matching_certificate() {
+        let mut holder = Holder::generate();
+        holder.add_certificate(create_test_certificate());
+
+        let result = holder.answer_request(
+            &create_test_issuer(),
+            &create_language_request(CefrLevel::C2),
+        );
+        assert!(matches!(result, Err(HolderError::NoMatchingCertificate)));
+        assert!(holder.proofs().is_empty());
+    }
+
+    #[test]
+    fn test_answer_request_surfaces_issuer_errors() {
+        let mut holder = Holder::generate();
+        holder.add_certificate(create_test_certificate());
+        let issuer = create_test_issuer();
+
+        let identity_request = ClaimRequest {
+            claim_type: ClaimType::DocumentValid {
+                document_type: None,
+            },
+            target_platform: "test".to_string(),
+        };
+        let result = holder.answer_request(&issuer, &identity_request);
+        assert!(matches!(
+            result,
+            Err(HolderError::Issuer(IssuerError::IdentityRequired))
+        ));
+
+        let mut holder = Holder::generate();
+        holder.add_certificate(CertificateData::new(
+            "Klingon_B2_Complete".to_string(),
+            50,
+            47,
+            "Test Student".to_string(),
+            Utc::now(),
+        ));
+        let result = holder.answer_request(&issuer, &create_language_request(CefrLevel::B1));
+        assert!(matches!(
+            result,
+            Err(HolderError::Issuer(IssuerError::UnknownGamePath(_)))
+        ));
+        assert!(holder.proofs().is_empty());
+    }
+
+    #[test]
+    fn test_answer_request_with_minted_certificate() {
+        let issuer = CertificateIssuer::new("test_issuer".to_string(), "Test Issuer".to_string())
+            .with_signing_key("key-1".to_string(), SigningKey::from_bytes(&[21u8; 32]))
+            .require_own_certificates()
+            .unwrap();
+        let minted = issuer
+            .mint_certificate(&GameResult {
+                game_path_name: "German_B2_Complete".to_string(),
+                profile_name: "Test Student".to_string(),
+                total_challenges: 7,
+                solved_challenges: 7,
+                completed_at: Utc::now(),
+            })
+            .unwrap();
+        let request = create_language_request(CefrLevel::B1);
+
+        let mut holder = Holder::generate();
+        holder.add_certificate(minted.certificate.clone());
+        assert!(matches!(
+            holder.answer_request(&issuer, &request),
+            Err(HolderError::Issuer(IssuerError::UntrustedCertificate))
+        ));
+
+        holder.add_minted_certificate(minted);
+        assert_eq!(holder.certificates().len(), 1);
+        let proof = holder.answer_request(&issuer, &request).unwrap();
+        assert_eq!(proof.holder_key(), Some(holder.public_key_hex().as_str()));
+    }
+
+    #[test]
+    fn test_evaluate_definition() {
+        let mut holder = Holder::generate();
+        holder.add_certificate(create_test_certificate());
+        let proof = holder
+            .answer_request(
+                &create_test_issuer(),
+                &create_language_request(CefrLevel::B2),
+            )
+            .unwrap();
+
+        let definition: PresentationDefinition = serde_json::from_value(serde_json::json!({
+            "id": "german_b1",
+            "input_descriptors": [{
+                "id": "german",
+                "constraints": {
+                    "fields": [
+                        { "path": ["$.language"], "filter": { "const": "German" } },
+                        { "path": ["$.level"], "filter": { "enum": ["B1", "B2", "C1", "C2"] } }
+                    ]
+                }
+            }]
+        }))
+        .unwrap();
+
+        let evaluation = holder
+            .evaluate_definition(
+                &definition,
+                &ZkProofVerifier::new("test_verifier".to_string()),
+            )
+            .unwrap();
+        assert!(evaluation.is_satisfied());
+        assert_eq!(evaluation.descriptors[0].candidates.len(), 2);
+        assert_eq!(
+            evaluation.submission().unwrap().descriptor_map[0].format,
+            crate::openid4vp::FORMAT_ZK_PROOF
+        );
+        assert_eq!(
+            evaluation.selection().unwrap(),
+            vec![&crate::presentation_exchange::CandidateCredential::Proof(
+                proof.proof_id
+            )]
+        );
+    }
+
+    #[test]
+    fn test_select_proofs() {
+        let mut holder = Holder::generate();
+        holder.add_certificate(create_test_certificate());
+        let proof = holder
+            .answer_request(
+                &create_test_issuer(),
+                &create_language_request(CefrLevel::B1),
+            )
+            .unwrap();
+
+        let selected = holder.select_proofs(&[proof.proof_id.as_str()]).unwrap();
+        assert_eq!(selected, vec![proof]);
+
+        assert!(matches!(
+            holder.select_proofs(&["missing"]),
+            Err(HolderError::UnknownProof(_))
+        ));
+    }
+
+    #[test]
+    fn test_present() {
+        let mut holder = Holder::generate();
+        holder.add_certificate(create_test_certificate());
+        let proof = holder
+            .answer_request(
+                &create_test_issuer(),
+                &create_language_request(CefrLevel::B1),
+            )
+            .unwrap();
+
+        let presentation = holder
+            .present(&[proof.proof_id.as_str()], "verifier.example", "nonce-1")
+            .unwrap();
+        assert_eq!(presentation.claims, vec![proof]);
+        assert_eq!(presentation.holder_id, holder.holder_id);
+        assert!(presentation.verify_signature().is_ok());
+
+        assert!(matches!(
+            holder.present(&[], "verifier.example", "nonce-1"),
+            Err(HolderError::Presentation(PresentationError::Empty))
+        ));
+    }
+
+    #[test]
+    fn test_wallet_round_trip() {
+        let mut holder = Holder::generate();
+        holder.add_certificate(create_test_certificate());
+        holder
+            .answer_request(
+                &create_test_issuer(),
+                &create_language_request(CefrLevel::B1),
+            )
+            .unwrap();
+
+        let restored = Holder::from_json(&holder.to_json().unwrap()).unwrap();
+        assert_eq!(restored, holder);
+        assert_eq!(restored.public_key(), holder.public_key());
+    }
+}
@@ -0,0 +1,886 @@
+//! ZKPassport identity proofs.
+//!
+//! The ZKPassport app answers an identity request with a bundle of zero-knowledge proofs and
+//! the query result they attest, e.g. "age >= 18" and a disclosed first name. An
+//! [`IdentityVerifier`] checks the bundle against trusted circuit verifying keys and the scope
+//! and nonce of a pending request, and yields a [`VerifiedIdentity`]. Issuers bind it into a
+//! claim with [`CertificateIssuer::generate_proof_with_identity`], so the claim carries
+//! identity attributes covered by the issuer signature.
+//!
+//! [`CertificateIssuer::generate_proof_with_identity`]: crate::issuer::CertificateIssuer::generate_proof_with_identity
+
+pub use crate::country::normalize_country;
+use crate::name_binding::NameMatch;
+use crate::verifier::VerificationMode;
+use crate::zk_proof::{ProofMode, ZkProofClaim};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Prefix of the metadata properties holding identity attributes
+pub const IDENTITY_PROPERTY_PREFIX: &str = "identity_";
+/// Metadata property marking a claim as identity verified
+pub const IDENTITY_VERIFIED_PROPERTY: &str = "identity_verified";
+/// Metadata property holding the verified minimum age
+pub const IDENTITY_AGE_OVER_PROPERTY: &str = "identity_age_over";
+/// Metadata property holding the scoped ZKPassport unique identifier
+pub const IDENTITY_NULLIFIER_PROPERTY: &str = "identity_nullifier";
+/// Metadata property holding the scope the identity was verified for
+pub const IDENTITY_SCOPE_PROPERTY: &str = "identity_scope";
+/// Metadata property holding when the identity was verified
+pub const IDENTITY_VERIFIED_AT_PROPERTY: &str = "identity_verified_at";
+/// Metadata property holding whether the passport proofs were verified cryptographically
+pub const IDENTITY_PROOF_MODE_PROPERTY: &str = "identity_proof_mode";
+
+/// Warning attached to identities whose passport proofs were not checked by a proof backend
+pub const UNVERIFIED_PASSPORT_PROOF_WARNING: &str =
+    "passport proofs were not verified by a proof backend; only their verifying keys and bindings were checked";
+
+#[derive(Error, Debug, PartialEq)]
+pub enum IdentityError {
+    #[error("Invalid identity bundle: {0}")]
+    InvalidBundle(String),
+    #[error("Bundle does not answer a pending request")]
+    UnknownNonce,
+    #[error("Identity request has expired")]
+    Expired,
+    #[error("Scope mismatch: expected {expected}, got {actual}")]
+    ScopeMismatch { expected: String, actual: String },
+    #[error("Untrusted passport circuit {name} with verifying key {vkey_hash}")]
+    UntrustedCircuit { name: String, vkey_hash: String },
+    #[error("Passport proof {0} was rejected")]
+    ProofRejected(String),
+    #[error("No passport proof backend configured in strict mode")]
+    NoProofBackend,
+    #[error("Age {0}+ was not verified")]
+    AgeNotVerified(u8),
+}
+
+/// One proof of a ZKPassport bundle
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PassportProof {
+    /// Circuit name
+    pub name: String,
+    /// Circuit version
+    pub version: String,
+    /// Hash of the circuit verifying key
+    pub vkey_hash: String,
+    /// Hex-encoded proof
+    pub proof: String,
+    /// Scope the proof commits to
+    pub scope: String,
+    /// Request nonce the proof commits to
+    pub nonce: String,
+}
+
+/// Proofs and query result returned by the ZKPassport SDK
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IdentityProofBundle {
+    /// Proofs attesting the query result
+    pub proofs: Vec<PassportProof>,
+    /// Query result in the SDK format, e.g. `{"age": {"gte": {"expected": 18, "result": true}}}`
+    pub query_result: serde_json::Value,
+    /// Identifier of the passport holder, unique within the scope
+    pub unique_identifier: String,
+    /// Scope of the request
+    pub scope: String,
+    /// Nonce of the request
+    pub nonce: String,
+}
+
+/// Public inputs every proof of a bundle must commit to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PassportPublicInputs {
+    /// Scope of the request
+    pub scope: String,
+    /// Nonce of the request
+    pub nonce: String,
+    /// Query result the proofs attest
+    pub query_result: serde_json::Value,
+    /// Identifier of the passport holder, unique within the scope
+    pub unique_identifier: String,
+}
+
+/// Identity request shown to the ZKPassport app
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IdentityRequest {
+    /// Scope of the requesting service
+    pub scope: String,
+    /// Fresh nonce the proofs must commit to
+    pub nonce: String,
+    /// Minimum age the holder must prove
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_age: Option<u8>,
+    /// When the request was created
+    pub created_at: DateTime<Utc>,
+}
+
+/// Identity attributes a claim carries
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IdentityAttributes {
+    /// Identifier of the passport holder, unique within the scope
+    pub unique_identifier: String,
+    /// Scope the identity was verified for
+    pub scope: String,
+    /// Verified minimum age
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub age_over: Option<u8>,
+    /// When the identity was verified
+    pub verified_at: DateTime<Utc>,
+    /// Whether the passport proofs were verified cryptographically
+    pub mode: ProofMode,
+}
+
+/// Identity established from a verified ZKPassport bundle
+///
+/// Not deserializable, so an identity cannot be supplied by a proof requester; it only
+/// comes out of [`IdentityVerifier::verify_bundle`].
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct VerifiedIdentity {
+    /// Attributes bound into issued claims
+    pub attributes: IdentityAttributes,
+    /// Disclosed first name, kept out of issued claims
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_name: Option<String>,
+    /// Nationalities the holder is proven not to have, without disclosing the nationality
+    pub excluded_nationalities: Vec<String>,
+    /// Date until which the identity document is proven valid
+    pub document_valid_until: Option<NaiveDate>,
+    /// Disclosed values by ZKPassport field name
+    pub disclosed: HashMap<String, String>,
+    /// Public inputs of the passport proofs, which passport name commitments derive from
+    #[serde(skip)]
+    pub passport_inputs: PassportPublicInputs,
+    /// Warnings about the verification
+    pub warnings: Vec<String>,
+}
+
+/// Cryptographic verifier of ZKPassport proofs
+pub trait PassportProofBackend: std::fmt::Debug + Send + Sync {
+    /// Check a proof against its verifying key and the public inputs of its bundle
+    ///
+    /// Only returns `true` when the proof verifies and its public inputs commit to the scope,
+    /// the nonce and the query result, and, for proofs outputting one, to the unique
+    /// identifier.
+    fn verify(
+        &self,
+        proof: &PassportProof,
+        public_inputs: &PassportPublicInputs,
+    ) -> Result<bool, String>;
+}
+
+/// Verifier of ZKPassport bundles
+#[derive(Debug, Clone)]
+pub struct IdentityVerifier {
+    /// Scope of the verifying service
+    pub scope: String,
+    /// Trusted verifying key hashes by circuit name
+    pub trusted_circuits: HashMap<String, Vec<String>>,
+    /// Handling of bundles without a proof backend
+    pub mode: VerificationMode,
+    /// How long a request stays valid
+    pub request_ttl: Duration,
+    backend: Option<Arc<dyn PassportProofBackend>>,
+    pending: HashMap<String, IdentityRequest>,
+}
+
+impl IdentityVerifier {
+    /// Create a verifier for a scope
+    pub fn new(scope: String) -> Self {
+        Self {
+            scope,
+            trusted_circuits: HashMap::new(),
+            mode: VerificationMode::default(),
+            request_ttl: Duration::minutes(10),
+            backend: None,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Trust a verifying key of a passport circuit
+    pub fn with_trusted_circuit(mut self, name: &str, vkey_hash: &str) -> Self {
+        self.trusted_circuits
+            .entry(name.to_string())
+            .or_default()
+            .push(vkey_hash.to_string());
+        self
+    }
+
+    /// Verify passport proofs cryptographically with a backend
+    pub fn with_backend(mut self, backend: impl PassportProofBackend + 'static) -> Self {
+        self.backend = Some(Arc::new(backend));
+        self
+    }
+
+    /// Set how bundles are handled without a proof backend
+    pub fn with_mode(mut self, mode: VerificationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Create a request with a fresh nonce and remember it until the bundle arrives
+    pub fn create_request(&mut self, min_age: Option<u8>) -> IdentityRequest {
+        let mut nonce = [0u8; 16];
+        OsRng.fill_bytes(&mut nonce);
+
+        let request = IdentityRequest {
+            scope: self.scope.clone(),
+            nonce: hex::encode(nonce),
+            min_age,
+            created_at: Utc::now(),
+        };
+        self.pending.insert(request.nonce.clone(), request.clone());
+        request
+    }
+
+    /// Verify a bundle against its pending request
+    ///
+    /// The request is consumed, so a bundle cannot be replayed. Without a proof backend the
+    /// query result and unique identifier are not bound to the proofs, so the identity is
+    /// marked simulated (or rejected in strict mode).
+    pub fn verify_bundle(
+        &mut self,
+        bundle: &IdentityProofBundle,
+    ) -> Result<VerifiedIdentity, IdentityError> {
+        let request = self
+            .pending
+            .remove(&bundle.nonce)
+            .ok_or(IdentityError::UnknownNonce)?;
+        if Utc::now() > request.created_at + self.request_ttl {
+            return Err(IdentityError::Expired);
+        }
+        self.check_scope(&bundle.scope)?;
+        if bundle.proofs.is_empty() || bundle.unique_identifier.is_empty() {
+            return Err(IdentityError::InvalidBundle(
+                "bundle has no proofs or unique identifier".to_string(),
+            ));
+        }
+
+        if self.backend.is_none() && self.mode == VerificationMode::Strict {
+            return Err(IdentityError::NoProofBackend);
+        }
+        let public_inputs = PassportPublicInputs {
+            scope: request.scope.clone(),
+            nonce: request.nonce.clone(),
+            query_result: bundle.query_result.clone(),
+            unique_identifier: bundle.unique_identifier.clone(),
+        };
+        for proof in &bundle.proofs {
+            self.check_proof(proof, &public_inputs)?;
+        }
+
+        let mut warnings = Vec::new();
+        let mode = match &self.backend {
+            Some(_) => ProofMode::Real,
+            None => {
+                warnings.push(UNVERIFIED_PASSPORT_PROOF_WARNING.to_string());
+                ProofMode::Simulated
+            }
+        };
+
+        let age_over = verified_min_age(&bundle.query_result);
+        if let Some(min_age) = request.min_age {
+            if age_over.is_none_or(|age| age < min_age) {
+                return Err(IdentityError::AgeNotVerified(min_age));
+            }
+        }
+        let disclosed = disclosed_values(&bundle.query_result);
+        let document_valid_until = disclosed
+            .get("expiry_date")
+            .and_then(|date| parse_date(date))
+            .or_else(|| proven_expiry_bound(&bundle.query_result));
+
+        Ok(VerifiedIdentity {
+            attributes: IdentityAttributes {
+                unique_identifier: bundle.unique_identifier.clone(),
+                scope: bundle.scope.clone(),
+                age_over,
+                verified_at: Utc::now(),
+                mode,
+            },
+            first_name: disclosed.get("firstname").cloned(),
+            excluded_nationalities: proven_nationality_exclusions(&bundle.query_result),
+            document_valid_until,
+            disclosed,
+            passport_inputs: public_inputs,
+            warnings,
+        })
+    }
+
+    fn check_scope(&self, scope: &str) -> Result<(), IdentityError> {
+        if scope != self.scope {
+            return Err(IdentityError::ScopeMismatch {
+                expected: self.scope.clone(),
+                actual: scope.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    fn check_proof(
+        &self,
+        proof: &PassportProof,
+        public_inputs: &PassportPublicInputs,
+    ) -> Result<(), IdentityError> {
+        let trusted = self
+            .trusted_circuits
+            .get(&proof.name)
+            .is_some_and(|hashes| hashes.contains(&proof.vkey_hash));
+        if !trusted {
+            return Err(IdentityError::UntrustedCircuit {
+                name: proof.name.clone(),
+                vkey_hash: proof.vkey_hash.clone(),
+            });
+        }
+
+        self.check_scope(&proof.scope)?;
+        if proof.nonce != public_inputs.nonce {
+            return Err(IdentityError::UnknownNonce);
+        }
+
+        if let Some(backend) = &self.backend {
+            match backend.verify(proof, public_inputs) {
+                Ok(true) => {}
+                Ok(false) => return Err(IdentityError::ProofRejected(proof.name.clone())),
+                Err(e) => {
+                    return Err(IdentityError::ProofRejected(format!(
+                        "{}: {}",
+                        proof.name, e
+                    )))
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl VerifiedIdentity {
+    /// Disclosed nationality as an ISO 3166-1 alpha-3 code
+    pub fn nationality(&self) -> Option<String> {
+        self.disclosed
+            .get("nationality")
+            .map(|nationality| normalize_country(nationality))
+    }
+
+    /// Disclosed type of the identity document, e.g. "passport"
+    pub fn document_type(&self) -> Option<&str> {
+        self.disclosed.get("document_type").map(String::as_str)
+    }
+
+    /// Whether the holder is known not to hold any of the nationalities
+    ///
+    /// `None` when neither the nationality is disclosed nor an exclusion of all of them is
+    /// proven.
+    pub fn nationality_not_in(&self, countries: &[String]) -> Option<bool> {
+        let countries: Vec<String> = countries.iter().map(|c| normalize_country(c)).collect();
+        if let Some(nationality) = self.nationality() {
+            return Some(!countries.contains(&nationality));
+        }
+
+        let excluded: Vec<String> = self
+            .excluded_nationalities
+            .iter()
+            .map(|c| normalize_country(c))
+            .collect();
+        countries
+            .iter()
+            .all(|country| excluded.contains(country))
+            .then_some(true)
+    }
+
+    /// Disclosed passport name the parts of `name_match` are compared against
+    pub fn name(&self, name_match: NameMatch) -> Option<String> {
+        match name_match {
+            NameMatch::FirstName => self.first_name.clone(),
+            NameMatch::FullName => self.disclosed.get("fullname").cloned().or_else(|| {
+                let first_name = self.first_name.as_ref()?;
+                let last_name = self.disclosed.get("lastname")?;
+                Some(format!("{} {}", first_name, last_name))
+            }),
+        }
+    }
+
+    /// Write the identity attributes into claim metadata properties
+    pub fn write_properties(&self, properties: &mut HashMap<String, String>) {
+        let attributes = &self.attributes;
+        properties.insert(IDENTITY_VERIFIED_PROPERTY.to_string(), "true".to_string());
+        properties.insert(
+            IDENTITY_NULLIFIER_PROPERTY.to_string(),
+            attributes.unique_identifier.clone(),
+        );
+        properties.insert(
+            IDENTITY_SCOPE_PROPERTY.to_string(),
+            attributes.scope.clone(),
+        );
+        properties.insert(
+            IDENTITY_VERIFIED_AT_PROPERTY.to_string(),
+            attributes.verified_at.to_rfc3339(),
+        );
+        properties.insert(
+            IDENTITY_PROOF_MODE_PROPERTY.to_string(),
+            attributes.mode.to_string(),
+        );
+        if let Some(age) = attributes.age_over {
+            properties.insert(IDENTITY_AGE_OVER_PROPERTY.to_string(), age.to_string());
+        }
+    }
+}
+
+impl IdentityAttributes {
+    /// Identity attributes a claim carries, if it is identity verified
+    pub fn from_claim(proof: &ZkProofClaim) -> Option<Self> {
+        let properties = &proof.metadata.properties;
+        if properties
+            .get(IDENTITY_VERIFIED_PROPERTY)
+            .map(String::as_str)
+            != Some("true")
+        {
+            return None;
+        }
+
+        Some(Self {
+            unique_identifier: properties.get(IDENTITY_NULLIFIER_PROPERTY)?.clone(),
+            scope: properties.get(IDENTITY_SCOPE_PROPERTY)?.clone(),
+            age_over: properties
+                .get(IDENTITY_AGE_OVER_PROPERTY)
+                .and_then(|age| age.parse().ok()),
+            verified_at: properties
+                .get(IDENTITY_VERIFIED_AT_PROPERTY)?
+                .parse()
+                .ok()?,
+            mode: match properties.get(IDENTITY_PROOF_MODE_PROPERTY)?.as_str() {
+                "real" => ProofMode::Real,
+                _ => ProofMode::Simulated,
+            },
+        })
+    }
+
+    /// Human readable summary, e.g. "identity verified (18+)"
+    pub fn summary(&self) -> String {
+        match self.age_over {
+            Some(age) => format!("identity verified ({}+)", age),
+            None => "identity verified".to_string(),
+        }
+    }
+}
+
+/// Minimum age the query result proves
+pub(crate) fn verified_min_age(query_result: &serde_json::Value) -> Option<u8> {
+    let gte = query_result.get("age")?.get("gte")?;
+    if gte.get("result")?.as_bool()? {
+        gte.get("expected")?.as_u64()?.try_into().ok()
+    } else {
+        None
+    }
+}
+
+/// Nationalities the query result proves the holder does not have
+fn proven_nationality_exclusions(query_result: &serde_json::Value) -> Vec<String> {
+    let out = query_result.get("nationality").and_then(|n| n.get("out"));
+    match out {
+        Some(out) if out.get("result").and_then(|r| r.as_bool()) == Some(true) => out
+            .get("expected")
+            .and_then(|expected| expected.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|country| country.as_str())
+            .map(normalize_country)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Date the query result proves the document expires on or after
+fn proven_expiry_bound(query_result: &serde_json::Value) -> Option<NaiveDate> {
+    let gte = query_result.get("expiry_date")?.get("gte")?;
+    if gte.get("result")?.as_bool()? {
+        parse_date(gte.get("expected")?.as_str()?)
+    } else {
+        None
+    }
+}
+
+/// Parse a date, ignoring a time part
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.get(..10)?, "%Y-%m-%d").ok()
+}
+
+/// Values the query result discloses, by ZKPassport field name
+pub(crate) fn disclosed_values(query_result: &serde_json::Value) -> HashMap<String, String> {
+    query_result
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(field, checks)| {
+            let value = checks.get("disclose")?.get("result")?;
+            let value = match value {
+                serde_json::Value::String(value) => value.clone(),
+                other => other.to_string(),
+            };
+            Some((field.clone(), value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::did::FileDidResolver;
+    use crate::issuer::{CertificateIssuer, ProofOptions, ProofRequest};
+    use crate::verifier::{ZkProofVerifier, UNAUTHENTICATED_IDENTITY_WARNING};
+    use crate::zk_proof::{CefrLevel, ClaimType};
+    use konnektoren_core::certificates::CertificateData;
+
+    const TEST_SCOPE: &str = "web5claims.identity";
+    const TEST_VKEY_HASH: &str = "0x1f0e";
+
+    /// Backend accepting proofs that hash the public inputs they commit to
+    #[derive(Debug)]
+    struct TestBackend;
+
+    impl TestBackend {
+        fn proof(public_inputs: &PassportPublicInputs) -> String {
+            use sha2::{Digest, Sha256};
+            hex::encode(Sha256::digest(serde_json::to_vec(public_inputs).unwrap()))
+        }
+    }
+
+    impl PassportProofBackend for TestBackend {
+        fn verify(
+            &self,
+            proof: &PassportProof,
+            public_inputs: &PassportPublicInputs,
+        ) -> Result<bool, String> {
+            Ok(proof.proof == Self::proof(public_inputs))
+        }
+    }
+
+    fn create_test_verifier() -> IdentityVerifier {
+        IdentityVerifier::new(TEST_SCOPE.to_string())
+            .with_trusted_circuit("outer_evm_count_4", TEST_VKEY_HASH)
+            .with_backend(TestBackend)
+    }
+
+    fn create_test_bundle(request: &IdentityRequest) -> IdentityProofBundle {
+        create_test_bundle_with(
+            request,
+            serde_json::json!({
+                "age": {"gte": {"expected": 18, "result": true}},
+                "firstname": {"disclose": {"result": "Jane"}},
+                "nationality": {"disclose": {"result": "FRA"}}
+            }),
+        )
+    }
+
+    fn create_test_bundle_with(
+        request: &IdentityRequest,
+        query_result: serde_json::Value,
+    ) -> IdentityProofBundle {
+        let public_inputs = PassportPublicInputs {
+            scope: request.scope.clone(),
+            nonce: request.nonce.clone(),
+            query_result: query_result.clone(),
+            unique_identifier: "nullifier-1".to_string(),
+        };
+        IdentityProofBundle {
+            proofs: vec![PassportProof {
+                name: "outer_evm_count_4".to_string(),
+                version: "0.5.0".to_string(),
+                vkey_hash: TEST_VKEY_HASH.to_string(),
+                proof: TestBackend::proof(&public_inputs),
+                scope: request.scope.clone(),
+                nonce: request.nonce.clone(),
+            }],
+            query_result,
+            unique_identifier: public_inputs.unique_identifier,
+            scope: request.scope.clone(),
+            nonce: request.nonce.clone(),
+        }
+    }
+
+    #[test]
+    fn test_verify_bundle() {
+        let mut verifier = create_test_verifier();
+        let request = verifier.create_request(Some(18));
+        let identity = verifier
+            .verify_bundle(&create_test_bundle(&request))
+            .unwrap();
+
+        assert_eq!(identity.attributes.age_over, Some(18));
+        assert_eq!(identity.attributes.mode, ProofMode::Real);
+        assert_eq!(identity.first_name.as_deref(), Some("Jane"));
+        assert_eq!(identity.disclosed["nationality"], "FRA");
+        assert!(identity.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_bundle_cannot_be_replayed() {
+        let mut verifier = create_test_verifier();
+        let request = verifier.create_request(None);
+        let bundle = create_test_bundle(&request);
+
+        assert!(verifier.verify_bundle(&bundle).is_ok());
+        assert_eq!(
+            verifier.verify_bundle(&bundle),
+            Err(IdentityError::UnknownNonce)
+        );
+    }
+
+    #[test]
+    fn test_scope_and_nonce_binding() {
+        let mut verifier = create_test_verifier();
+
+        let request = verifier.create_request(None);
+        let mut other_scope = create_test_bundle(&request);
+        other_scope.proofs[0].scope = "other.service".to_string();
+        assert!(matches!(
+            verifier.verify_bundle(&other_scope),
+            Err(IdentityError::ScopeMismatch { .. })
+        ));
+
+        let request = verifier.create_request(None);
+        let mut other_nonce = create_test_bundle(&request);
+        other_nonce.proofs[0].nonce = "stale".to_string();
+        assert_eq!(
+            verifier.verify_bundle(&other_nonce),
+            Err(IdentityError::UnknownNonce)
+        );
+    }
+
+    #[test]
+    fn test_untrusted_circuit_rejected() {
+        let mut verifier = create_test_verifier();
+        let request = verifier.create_request(None);
+        let mut bundle = create_test_bundle(&request);
+        bundle.proofs[0].vkey_hash = "0xbad".to_string();
+
+        assert!(matches!(
+            verifier.verify_bundle(&bundle),
+            Err(IdentityError::UntrustedCircuit { .. })
+        ));
+    }
+
+    #[test]
+    fn test_rejected_proof_and_age() {
+        let mut verifier = create_test_verifier();
+        let request = verifier.create_request(None);
+        let mut bundle = create_test_bundle(&request);
+        bundle.proofs[0].proof = "00ff".to_string();
+        assert!(matches!(
+            verifier.verify_bundle(&bundle),
+            Err(IdentityError::ProofRejected(_))
+        ));
+
+        let mut verifier = create_test_verifier();
+        let request = verifier.create_request(Some(21));
+        assert_eq!(
+            verifier.verify_bundle(&create_test_bundle(&request)),
+            Err(IdentityError::AgeNotVerified(21))
+        );
+    }
+
+    #[test]
+    fn test_public_inputs_bound_to_proofs() {
+        let mut verifier = create_test_verifier();
+
+        // A query result the proofs do not commit to is rejected
+        let request = verifier.create_request(Some(18));
+        let mut bundle = create_test_bundle(&request);
+        bundle.query_result["firstname"]["disclose"]["result"] = "Mallory".into();
+        assert!(matches!(
+            verifier.verify_bundle(&bundle),
+            Err(IdentityError::ProofRejected(_))
+        ));
+
+        // So is a unique identifier of another passport
+        let request = verifier.create_request(None);
+        let mut bundle = create_test_bundle(&request);
+        bundle.unique_identifier = "nullifier-2".to_string();
+        assert!(matches!(
+            verifier.verify_bundle(&bundle),
+            Err(IdentityError::ProofRejected(_))
+        ));
+    }
+
+    #[test]
+    fn test_mode_without_backend() {
+        let mut lenient = IdentityVerifier::new(TEST_SCOPE.to_string())
+            .with_trusted_circuit("outer_evm_count_4", TEST_VKEY_HASH);
+        let request = lenient.create_request(None);
+        let identity = lenient
+            .verify_bundle(&create_test_bundle(&request))
+            .unwrap();
+        assert_eq!(identity.attributes.mode, ProofMode::Simulated);
+        assert_eq!(identity.warnings, vec![UNVERIFIED_PASSPORT_PROOF_WARNING]);
+
+        let mut strict = lenient.with_mode(VerificationMode::Strict);
+        let request = strict.create_request(None);
+        assert_eq!(
+            strict.verify_bundle(&create_test_bundle(&request)),
+            Err(IdentityError::NoProofBackend)
+        );
+    }
+
+    #[test]
+    fn test_expired_request() {
+        let mut verifier = create_test_verifier();
+        verifier.request_ttl = Duration::zero();
+        let mut request = verifier.create_request(None);
+        request.created_at -= Duration::seconds(1);
+        verifier
+            .pending
+            .insert(request.nonce.clone(), request.clone());
+
+        assert_eq!(
+            verifier.verify_bundle(&create_test_bundle(&request)),
+            Err(IdentityError::Expired)
+        );
+    }
+
+    #[test]
+    fn test_nationality_and_document_attributes() {
+        let mut verifier = create_test_verifier();
+        let request = verifier.create_request(None);
+        let bundle = create_test_bundle_with(
+            &request,
+            serde_json::json!({
+                "nationality": {"out": {"expected": ["RUS", "D"], "result": true}},
+                "expiry_date": {"gte": {"expected": "2030-01-01T00:00:00.000Z", "result": true}}
+            }),
+        );
+        let identity = verifier.verify_bundle(&bundle).unwrap();
+
+        assert_eq!(identity.nationality(), None);
+        assert_eq!(identity.excluded_nationalities, vec!["RUS", "DEU"]);
+        assert_eq!(
+            identity.nationality_not_in(&["DEU".to_string()]),
+            Some(true)
+        );
+        assert_eq!(identity.nationality_not_in(&["FRA".to_string()]), None);
+        assert_eq!(
+            identity.document_valid_until,
+            NaiveDate::from_ymd_opt(2030, 1, 1)
+        );
+
+        let request = verifier.create_request(None);
+        let bundle = create_test_bundle_with(
+            &request,
+            serde_json::json!({
+                "nationality": {"disclose": {"result": "D"}},
+                "document_type": {"disclose": {"result": "passport"}},
+                "expiry_date": {"disclose": {"result": "2031-06-30"}}
+            }),
+        );
+        let identity = verifier.verify_bundle(&bundle).unwrap();
+
+        assert_eq!(identity.nationality().as_deref(), Some("DEU"));
+        assert_eq!(
+            identity.nationality_not_in(&["deu".to_string()]),
+            Some(false)
+        );
+        assert_eq!(identity.document_type(), Some("passport"));
+        assert_eq!(
+            identity.document_valid_until,
+            NaiveDate::from_ymd_opt(2031, 6, 30)
+        );
+
+        // Failed exclusion proofs prove nothing
+        assert!(proven_nationality_exclusions(&serde_json::json!({
+            "nationality": {"out": {"expected": ["RUS"], "result": false}}
+        }))
+        .is_empty());
+    }
+
+    fn create_test_request() -> ProofRequest {
+        ProofRequest {
+            certificate: CertificateData::new(
+                "German_B2_Complete".to_string(),
+                7,
+                7,
+                "Test Student".to_string(),
+                Utc::now(),
+            ),
+            claim_type: ClaimType::LanguageProficiency {
+                language: "German".to_string(),
+                min_level: CefrLevel::B1,
+            },
+            target_platform: "aleo".to_string(),
+            options: ProofOptions::default(),
+        }
+    }
+
+    #[test]
+    fn test_identity_bound_into_signed_proof() {
+        let mut identity_verifier = create_test_verifier();
+        let request = identity_verifier.create_request(Some(18));
+        let identity = identity_verifier
+            .verify_bundle(&create_test_bundle(&request))
+            .unwrap();
+
+        let issuer = CertificateIssuer::new("test_issuer".to_string(), "Test Issuer".to_string())
+            .accept_unsigned_certificates()
+            .with_did_key(ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]));
+        let mut proof = issuer
+            .generate_proof_with_identity(create_test_request(), &identity)
+            .unwrap();
+
+        let attributes = IdentityAttributes::from_claim(&proof).unwrap();
+        assert_eq!(attributes.unique_identifier, "nullifier-1");
+        assert_eq!(attributes.age_over, Some(18));
+        assert_eq!(attributes.summary(), "identity verified (18+)");
+        // The disclosed name stays out of the claim
+        assert!(!serde_json::to_string(&proof).unwrap().contains("Jane"));
+
+        let dids = tempfile::tempdir().unwrap();
+        let mut verifier = ZkProofVerifier::new("test_verifier".to_string())
+            .with_did_resolver(FileDidResolver::new(dids.path()));
+        verifier.add_trusted_issuer(issuer.issuer_id.clone());
+        let result = verifier.verify_proof(&proof).unwrap();
+        assert_eq!(result.details.metadata["identity_age_over"], "18");
+        assert!(!result
+            .warnings
+            .contains(&UNAUTHENTICATED_IDENTITY_WARNING.to_string()));
+
+        // Identity attributes are covered by the issuer signature
+        proof
+            .metadata
+            .properties
+            .insert(IDENTITY_AGE_OVER_PROPERTY.to_string(), "21".to_string());
+        assert!(verifier.verify_proof(&proof).is_err());
+    }
+
+    #[test]
+    fn test_custom_properties_cannot_forge_identity() {
+        let issuer = CertificateIssuer::new("test_issuer".to_string(), "Test Issuer".to_string())
+            .accept_unsigned_certificates();
+        let mut request = create_test_request();
+        request
+            .options
+            .custom_properties
+            .insert(IDENTITY_VERIFIED_PROPERTY.to_string(), "true".to_string());
+        let proof = issuer.generate_proof(request).unwrap();
+        assert!(IdentityAttributes::from_claim(&proof).is_none());
+
+        // Without issuer authentication, bound identities are flagged
+        let mut identity_verifier = create_test_verifier();
+        let request = identity_verifier.create_request(None);
+        let identity = identity_verifier
+            .verify_bundle(&create_test_bundle(&request))
+            .unwrap();
+        let proof = issuer
+            .generate_proof_with_identity(create_test_request(), &identity)
+            .unwrap();
+        let result = ZkProofVerifier::new("test_verifier".to_string())
+            .verify_proof(&proof)
+            .unwrap();
+        assert!(result
+            .warnings
+            .contains(&UNAUTHENTICATED_IDENTITY_WARNING.to_string()));
+    }
+}
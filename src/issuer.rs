@@ -1,9 +1,29 @@
-use crate::zk_proof::{CefrLevel, ClaimType, ProofData, ProofMetadata, PublicInputs, ZkProofClaim};
+use crate::country::is_country_code;
+use crate::did::did_key;
+use crate::game_session::GameCatalogue;
+use crate::identity::{VerifiedIdentity, IDENTITY_PROPERTY_PREFIX};
+use crate::name_binding::{
+    NameBinding, NameBindingError, NameCommitment, NameMatch, SignedNameCommitment,
+    NAME_BINDING_REQUIREMENT,
+};
+use crate::platform::{Platform, PlatformError};
+use crate::presentation::decode_holder_key;
+use crate::signature::{IssuerPublicKey, IssuerSigningKey, SignatureAlgorithm};
+use crate::status_list::{SignedStatusList, StatusList};
+use crate::vc::CredentialStatus;
+use crate::zk_proof::{
+    CefrLevel, ClaimType, ProofData, ProofMetadata, ProofMode, PublicInputs, ZkProofClaim,
+    CREDENTIAL_STATUS_PROPERTY, HOLDER_KEY_PROPERTY, ISSUER_KEY_ID_PROPERTY,
+    ISSUER_SIGNATURE_ALGORITHM_PROPERTY, ISSUER_VERIFICATION_METHOD_PROPERTY, SIMULATED_PROOF_TAG,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
 use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use konnektoren_core::certificates::CertificateData;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::sync::Arc;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -18,6 +38,28 @@ pub enum IssuerError {
     ProofGenerationFailed(String),
     #[error("Invalid claim type for certificate")]
     InvalidClaimType,
+    #[error("Unsupported platform: {0}")]
+    UnsupportedPlatform(String),
+    #[error(transparent)]
+    Platform(#[from] PlatformError),
+    #[error("Issuer has no Ed25519 key to sign certificates")]
+    MissingSigningKey,
+    #[error("Certificate is not signed by a trusted issuer key")]
+    UntrustedCertificate,
+    #[error("No trusted certificate signer is configured")]
+    MissingCertificateSigner,
+    #[error("Claim requires a verified identity")]
+    IdentityRequired,
+    #[error("Verified identity does not disclose or prove {0}")]
+    MissingIdentityAttribute(String),
+    #[error(transparent)]
+    NameBinding(#[from] NameBindingError),
+    #[error("Invalid holder key: {0}")]
+    InvalidHolderKey(String),
+    #[error("Not an ISO 3166-1 country code: {0}")]
+    InvalidCountryCode(String),
+    #[error("Game path is not in the catalogue: {0}")]
+    UnknownGamePath(String),
 }
 
 /// Certificate issuer that can generate ZK proofs from language learning certificates
@@ -28,7 +70,95 @@ pub struct CertificateIssuer {
     /// Issuer name
     pub issuer_name: String,
     /// Supported platforms for proof generation
-    pub supported_platforms: Vec<String>,
+    pub supported_platforms: Vec<Platform>,
+    /// Key signing issued proofs
+    signing_key: Option<IssuerKey>,
+    /// Keys certificates must be signed with
+    certificate_signers: Vec<VerifyingKey>,
+    /// Whether simulated proofs are generated from certificates without a trusted signer
+    accept_unsigned_certificates: bool,
+    /// Proving system producing real proofs (`None` generates simulated proofs)
+    prover: Option<Arc<dyn ProvingBackend>>,
+    /// Game paths certificates are accepted for
+    game_catalogue: GameCatalogue,
+}
+
+/// Proving system producing real proof bytes for a circuit
+pub trait ProvingBackend: std::fmt::Debug + Send + Sync {
+    /// Prove the public inputs with the proving key of the circuit
+    fn prove(&self, circuit_id: &str, public_inputs: &PublicInputs) -> Result<Vec<u8>, String>;
+}
+
+/// Issuer signing key with the identifiers proofs reference it by
+#[derive(Debug, Clone)]
+struct IssuerKey {
+    /// Key identifier, embedded in the metadata of signed proofs
+    key_id: String,
+    /// Signing key
+    signing_key: IssuerSigningKey,
+    /// DID verification method the key is published as
+    verification_method: Option<String>,
+}
+
+/// Recorded result of a konnektoren game path, the source of a minted certificate
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GameResult {
+    /// Name of the completed game path
+    pub game_path_name: String,
+    /// Profile of the player
+    pub profile_name: String,
+    /// Number of challenges on the game path
+    pub total_challenges: usize,
+    /// Number of solved challenges
+    pub solved_challenges: usize,
+    /// When the game path was completed
+    pub completed_at: DateTime<Utc>,
+}
+
+/// Certificate minted by an issuer, with the issuer signature kept beside it
+///
+/// `CertificateData::signature` holds the konnektoren-core signature checked by
+/// `CertificateData::verify`, so the minting signature travels separately.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MintedCertificate {
+    /// The minted certificate
+    pub certificate: CertificateData,
+    /// Hex-encoded Ed25519 signature of the minting issuer
+    pub signature: String,
+    /// Commitments to the profile name, signed by the minting issuer, which identity bound
+    /// claims prove the passport name against
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub name_commitments: Vec<SignedNameCommitment>,
+}
+
+impl MintedCertificate {
+    /// Request a proof from the certificate, carrying its signature
+    pub fn proof_request(&self, claim_type: ClaimType, target_platform: String) -> ProofRequest {
+        ProofRequest {
+            certificate: self.certificate.clone(),
+            claim_type,
+            target_platform,
+            options: ProofOptions {
+                certificate_signature: Some(self.signature.clone()),
+                name_commitments: self.name_commitments.clone(),
+                ..ProofOptions::default()
+            },
+        }
+    }
+
+    /// Encode as base64url JSON, e.g. to share the certificate by link
+    pub fn to_base64(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).expect("certificate serializes to JSON"))
+    }
+
+    /// Decode from base64url JSON
+    pub fn from_base64(encoded: &str) -> Result<Self, IssuerError> {
+        URL_SAFE_NO_PAD
+            .decode(encoded.trim())
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .ok_or_else(|| IssuerError::InvalidCertificate("not a minted certificate".to_string()))
+    }
 }
 
 /// Request for generating a ZK proof
@@ -53,6 +183,18 @@ pub struct ProofOptions {
     pub include_completion_date: bool,
     /// Custom properties to include
     pub custom_properties: HashMap<String, String>,
+    /// Hex-encoded Ed25519 key of the holder the proof is bound to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub holder_key: Option<String>,
+    /// Hex-encoded signature of a certificate signer, see [`MintedCertificate`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub certificate_signature: Option<String>,
+    /// Signed commitments to the certificate's profile name, see [`MintedCertificate`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub name_commitments: Vec<SignedNameCommitment>,
+    /// Entry of the proof in the issuer's status list, see [`CertificateIssuer::sign_status_list`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credential_status: Option<CredentialStatus>,
 }
 
 impl CertificateIssuer {
@@ -61,25 +203,251 @@ impl CertificateIssuer {
         Self {
             issuer_id,
             issuer_name,
-            supported_platforms: vec![
-                "aleo".to_string(),
-                "stylus".to_string(),
-                "test".to_string(),
-                "web5claims_local".to_string(),
-            ],
+            supported_platforms: Platform::ALL.to_vec(),
+            signing_key: None,
+            certificate_signers: Vec::new(),
+            accept_unsigned_certificates: false,
+            prover: None,
+            game_catalogue: GameCatalogue::builtin(),
+        }
+    }
+
+    /// Identify the issuer by the `did:key` of its signing key and sign issued proofs
+    pub fn with_did_key(mut self, signing_key: SigningKey) -> Self {
+        let did = did_key(&signing_key.verifying_key());
+        let fragment = did.trim_start_matches("did:key:").to_string();
+
+        self.signing_key = Some(IssuerKey {
+            verification_method: Some(format!("{}#{}", did, fragment)),
+            key_id: fragment,
+            signing_key: signing_key.into(),
+        });
+        self.issuer_id = did;
+        self
+    }
+
+    /// Identify the issuer by a `did:web` whose document lists the key as `<did>#<key_id>`
+    ///
+    /// Accepts an Ed25519 key or, with the `post-quantum` feature, an ML-DSA or hybrid key.
+    pub fn with_did_web(
+        mut self,
+        did: String,
+        key_id: &str,
+        signing_key: impl Into<IssuerSigningKey>,
+    ) -> Self {
+        self.signing_key = Some(IssuerKey {
+            key_id: key_id.to_string(),
+            signing_key: signing_key.into(),
+            verification_method: Some(format!("{}#{}", did, key_id)),
+        });
+        self.issuer_id = did;
+        self
+    }
+
+    /// Sign issued proofs with a key verifiers trust by its key id
+    ///
+    /// Accepts an Ed25519 key or, with the `post-quantum` feature, an ML-DSA or hybrid key.
+    pub fn with_signing_key(
+        mut self,
+        key_id: String,
+        signing_key: impl Into<IssuerSigningKey>,
+    ) -> Self {
+        self.signing_key = Some(IssuerKey {
+            key_id,
+            signing_key: signing_key.into(),
+            verification_method: None,
+        });
+        self
+    }
+
+    /// Only generate proofs from certificates signed with this key
+    pub fn with_certificate_signer(mut self, public_key: VerifyingKey) -> Self {
+        if !self.certificate_signers.contains(&public_key) {
+            self.certificate_signers.push(public_key);
+        }
+        self
+    }
+
+    /// Generate simulated proofs from certificates no trusted signer signed (demos and tests)
+    ///
+    /// Without it, an issuer without a certificate signer refuses every certificate.
+    pub fn accept_unsigned_certificates(mut self) -> Self {
+        self.accept_unsigned_certificates = true;
+        self
+    }
+
+    /// Generate real proofs with a proving system
+    ///
+    /// Real proofs need a signing key as well: verifiers only trust the proof mode of
+    /// signed proofs. They are only generated from certificates of a configured
+    /// certificate signer.
+    pub fn with_prover(mut self, prover: impl ProvingBackend + 'static) -> Self {
+        self.prover = Some(Arc::new(prover));
+        self
+    }
+
+    /// Accept certificates of the game paths in this catalogue instead of the built-in one
+    pub fn with_game_catalogue(mut self, game_catalogue: GameCatalogue) -> Self {
+        self.game_catalogue = game_catalogue;
+        self
+    }
+
+    /// Only generate proofs from certificates this issuer minted itself
+    pub fn require_own_certificates(self) -> Result<Self, IssuerError> {
+        let public_key = self.public_key().ok_or(IssuerError::MissingSigningKey)?;
+        Ok(self.with_certificate_signer(public_key))
+    }
+
+    /// Mint a certificate from a recorded game result, signed with the issuer's Ed25519 key
+    pub fn mint_certificate(&self, result: &GameResult) -> Result<MintedCertificate, IssuerError> {
+        if result.solved_challenges > result.total_challenges {
+            return Err(IssuerError::InvalidCertificate(format!(
+                "{} solved of {} challenges",
+                result.solved_challenges, result.total_challenges
+            )));
         }
+        let signing_key = self
+            .signing_key
+            .as_ref()
+            .and_then(|key| key.signing_key.ed25519())
+            .ok_or(IssuerError::MissingSigningKey)?;
+
+        let certificate = CertificateData::new(
+            result.game_path_name.clone(),
+            result.total_challenges,
+            result.solved_challenges,
+            result.profile_name.clone(),
+            result.completed_at,
+        );
+        self.validate_certificate_fields(&certificate)?;
+
+        let signature = signing_key.sign(&certificate_signing_bytes(&certificate));
+        let certificate_hash = self.get_certificate_hash(&certificate);
+        let name_commitments = [NameMatch::FirstName, NameMatch::FullName]
+            .into_iter()
+            .filter_map(|name_match| {
+                NameCommitment::commit(&certificate.profile_name, name_match).ok()
+            })
+            .map(|commitment| {
+                SignedNameCommitment::sign(commitment, &certificate_hash, signing_key)
+            })
+            .collect();
+        Ok(MintedCertificate {
+            certificate,
+            signature: hex::encode(signature.to_bytes()),
+            name_commitments,
+        })
+    }
+
+    /// Check that a certificate is signed with a trusted certificate signer key
+    pub fn verify_certificate_signature(
+        &self,
+        minted: &MintedCertificate,
+    ) -> Result<(), IssuerError> {
+        self.check_certificate_signature(&minted.certificate, &minted.signature)
+    }
+
+    fn check_certificate_signature(
+        &self,
+        certificate: &CertificateData,
+        signature: &str,
+    ) -> Result<(), IssuerError> {
+        let signature: [u8; 64] = hex::decode(signature)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or(IssuerError::UntrustedCertificate)?;
+        let signature = Signature::from_bytes(&signature);
+        let message = certificate_signing_bytes(certificate);
+
+        self.certificate_signers
+            .iter()
+            .any(|public_key| public_key.verify(&message, &signature).is_ok())
+            .then_some(())
+            .ok_or(IssuerError::UntrustedCertificate)
+    }
+
+    /// DID of the issuer, if it has one
+    pub fn did(&self) -> Option<&str> {
+        Some(self.issuer_id.as_str()).filter(|id| id.starts_with("did:"))
+    }
+
+    /// Ed25519 public key issued proofs are signed with
+    pub fn public_key(&self) -> Option<VerifyingKey> {
+        self.signing_key
+            .as_ref()
+            .and_then(|key| key.signing_key.ed25519())
+            .map(|signing_key| signing_key.verifying_key())
+    }
+
+    /// Public key issued proofs are signed with, for any signature algorithm
+    pub fn issuer_public_key(&self) -> Option<IssuerPublicKey> {
+        self.signing_key
+            .as_ref()
+            .map(|key| key.signing_key.public_key())
+    }
+
+    /// Sign a credential status list as this issuer
+    pub fn sign_status_list(&self, mut list: StatusList) -> Result<SignedStatusList, IssuerError> {
+        let key = self
+            .signing_key
+            .as_ref()
+            .ok_or(IssuerError::MissingSigningKey)?;
+        list.issuer = self.issuer_id.clone();
+        Ok(list.sign(key.key_id.clone(), &key.signing_key))
+    }
+
+    /// Algorithm issued proofs are signed with
+    pub fn signature_algorithm(&self) -> Option<SignatureAlgorithm> {
+        self.signing_key
+            .as_ref()
+            .map(|key| key.signing_key.algorithm())
+    }
+
+    /// Id of the key issued proofs are signed with
+    pub fn key_id(&self) -> Option<&str> {
+        self.signing_key.as_ref().map(|key| key.key_id.as_str())
+    }
+
+    /// Verification method issued proofs reference
+    pub fn verification_method(&self) -> Option<&str> {
+        self.signing_key
+            .as_ref()
+            .and_then(|key| key.verification_method.as_deref())
     }
 
     /// Generate a ZK proof from a certificate
     pub fn generate_proof(&self, request: ProofRequest) -> Result<ZkProofClaim, IssuerError> {
+        self.generate_proof_for(request, None)
+    }
+
+    /// Generate a ZK proof bound to a verified ZKPassport identity
+    ///
+    /// The identity attributes are written into the metadata before the proof is signed, so
+    /// they are covered by the issuer signature.
+    pub fn generate_proof_with_identity(
+        &self,
+        request: ProofRequest,
+        identity: &VerifiedIdentity,
+    ) -> Result<ZkProofClaim, IssuerError> {
+        self.generate_proof_for(request, Some(identity))
+    }
+
+    fn generate_proof_for(
+        &self,
+        request: ProofRequest,
+        identity: Option<&VerifiedIdentity>,
+    ) -> Result<ZkProofClaim, IssuerError> {
         // Validate the certificate
-        self.validate_certificate(&request.certificate)?;
+        self.validate_certificate(
+            &request.certificate,
+            request.options.certificate_signature.as_deref(),
+        )?;
 
         // Validate the request
         self.validate_request(&request)?;
 
         // Generate the proof based on claim type
-        match &request.claim_type {
+        let mut proof = match &request.claim_type {
             ClaimType::LanguageProficiency {
                 language,
                 min_level,
@@ -96,13 +464,167 @@ impl CertificateIssuer {
                 self.generate_completion_date_proof(&request.certificate, after_date, &request)
             }
             ClaimType::Combined { criteria } => {
-                self.generate_combined_proof(&request.certificate, criteria, &request)
+                self.generate_combined_proof(&request.certificate, criteria, &request, identity)
+            }
+            ClaimType::IdentityBound { name_match } => self.generate_identity_bound_proof(
+                &request.certificate,
+                *name_match,
+                &request,
+                identity,
+            ),
+            ClaimType::NationalityNotIn { .. } | ClaimType::DocumentValid { .. } => {
+                self.generate_identity_attribute_proof(&request.certificate, &request, identity)
+            }
+        }?;
+
+        if let Some(identity) = identity {
+            identity.write_properties(&mut proof.metadata.properties);
+        }
+
+        Ok(self.sign_proof(proof))
+    }
+
+    /// Sign a proof with the issuer key, so verifiers can authenticate the issuer via its DID
+    /// or a trusted key set
+    fn sign_proof(&self, mut proof: ZkProofClaim) -> ZkProofClaim {
+        let Some(key) = &self.signing_key else {
+            return proof;
+        };
+
+        let properties = &mut proof.metadata.properties;
+        properties.insert(ISSUER_KEY_ID_PROPERTY.to_string(), key.key_id.clone());
+        if let Some(verification_method) = &key.verification_method {
+            properties.insert(
+                ISSUER_VERIFICATION_METHOD_PROPERTY.to_string(),
+                verification_method.clone(),
+            );
+        }
+        properties.insert(
+            ISSUER_SIGNATURE_ALGORITHM_PROPERTY.to_string(),
+            key.signing_key.algorithm().to_string(),
+        );
+        let signature = key.signing_key.sign(&proof.issuer_signing_bytes());
+        signature.write_properties(&mut proof.metadata.properties);
+        proof
+    }
+
+    /// Evaluate a claim against a certificate without generating a proof
+    ///
+    /// This is the reference logic that the Leo circuits mirror.
+    pub fn evaluate_claim(
+        &self,
+        certificate: &CertificateData,
+        claim_type: &ClaimType,
+    ) -> Result<bool, IssuerError> {
+        match claim_type {
+            ClaimType::LanguageProficiency {
+                language,
+                min_level,
+            } => {
+                let cert_language = self.extract_language_from_certificate(certificate)?;
+                if !cert_language.eq_ignore_ascii_case(language) {
+                    return Err(IssuerError::InvalidCertificate(format!(
+                        "Certificate language {} does not match requested language {}",
+                        cert_language, language
+                    )));
+                }
+
+                let cert_level = CefrLevel::from_course_name(&certificate.game_path_name)
+                    .ok_or_else(|| {
+                        IssuerError::InvalidCefrLevel(certificate.game_path_name.clone())
+                    })?;
+
+                Ok(cert_level >= *min_level)
+            }
+            ClaimType::PerformanceThreshold { min_percentage } => {
+                Ok(certificate.performance_percentage >= *min_percentage)
+            }
+            ClaimType::CompletionDate { after_date } => Ok(certificate.date >= *after_date),
+            ClaimType::Combined { criteria } => {
+                let mut all_pass = true;
+                for criterion in criteria {
+                    all_pass &= self.evaluate_claim(certificate, criterion)?;
+                }
+                Ok(all_pass)
+            }
+            // Identity claims need the passport data of a verified identity
+            ClaimType::IdentityBound { .. }
+            | ClaimType::NationalityNotIn { .. }
+            | ClaimType::DocumentValid { .. } => Err(IssuerError::IdentityRequired),
+        }
+    }
+
+    /// Evaluate an identity attribute claim against a certificate and a verified identity
+    ///
+    /// A nationality claim holds when the disclosed nationality is not excluded, or the
+    /// passport proved it is none of the excluded countries. A document claim holds when the
+    /// document was valid on the certificate date.
+    pub fn evaluate_identity_claim(
+        &self,
+        certificate: &CertificateData,
+        claim_type: &ClaimType,
+        identity: &VerifiedIdentity,
+    ) -> Result<bool, IssuerError> {
+        match claim_type {
+            ClaimType::NationalityNotIn { countries } => {
+                if let Some(code) = countries.iter().find(|code| !is_country_code(code)) {
+                    return Err(IssuerError::InvalidCountryCode(code.clone()));
+                }
+                identity
+                    .nationality_not_in(countries)
+                    .ok_or_else(|| IssuerError::MissingIdentityAttribute("nationality".to_string()))
             }
+            ClaimType::DocumentValid { document_type } => {
+                let valid_until = identity.document_valid_until.ok_or_else(|| {
+                    IssuerError::MissingIdentityAttribute("expiry_date".to_string())
+                })?;
+                let type_matches = match document_type {
+                    Some(document_type) => identity
+                        .document_type()
+                        .ok_or_else(|| {
+                            IssuerError::MissingIdentityAttribute("document_type".to_string())
+                        })?
+                        .eq_ignore_ascii_case(document_type),
+                    None => true,
+                };
+                Ok(type_matches && valid_until >= certificate.date.date_naive())
+            }
+            _ => Err(IssuerError::InvalidClaimType),
         }
     }
 
     /// Validate a certificate for proof generation
-    fn validate_certificate(&self, certificate: &CertificateData) -> Result<(), IssuerError> {
+    fn validate_certificate(
+        &self,
+        certificate: &CertificateData,
+        signature: Option<&str>,
+    ) -> Result<(), IssuerError> {
+        self.validate_certificate_fields(certificate)?;
+
+        // Verify certificate signature if present
+        if let Err(_) = certificate.verify() {
+            return Err(IssuerError::InvalidCertificate(
+                "Certificate signature verification failed".to_string(),
+            ));
+        }
+
+        if !self.certificate_signers.is_empty() {
+            let signature = signature.ok_or(IssuerError::UntrustedCertificate)?;
+            return self.check_certificate_signature(certificate, signature);
+        }
+        // Real proofs always need a signer, simulated ones only when not opted out
+        if self.prover.is_some() || !self.accept_unsigned_certificates {
+            return Err(IssuerError::MissingCertificateSigner);
+        }
+
+        Ok(())
+    }
+
+    /// Validate the recorded fields of a certificate
+    fn validate_certificate_fields(
+        &self,
+        certificate: &CertificateData,
+    ) -> Result<(), IssuerError> {
         if certificate.profile_name.is_empty() {
             return Err(IssuerError::InvalidCertificate(
                 "Profile name cannot be empty".to_string(),
@@ -115,17 +637,24 @@ impl CertificateIssuer {
             ));
         }
 
+        let game_path = self
+            .game_catalogue
+            .game_path(&certificate.game_path_name)
+            .ok_or_else(|| IssuerError::UnknownGamePath(certificate.game_path_name.clone()))?;
+
         if certificate.total_challenges == 0 {
             return Err(IssuerError::InvalidCertificate(
                 "Total challenges must be greater than 0".to_string(),
             ));
         }
 
-        // Verify certificate signature if present
-        if let Err(_) = certificate.verify() {
-            return Err(IssuerError::InvalidCertificate(
-                "Certificate signature verification failed".to_string(),
-            ));
+        if certificate.total_challenges != game_path.challenges.len() {
+            return Err(IssuerError::InvalidCertificate(format!(
+                "Total challenges {} do not match the {} challenges of game path {}",
+                certificate.total_challenges,
+                game_path.challenges.len(),
+                game_path.id
+            )));
         }
 
         Ok(())
@@ -133,8 +662,17 @@ impl CertificateIssuer {
 
     /// Validate a proof request
     fn validate_request(&self, request: &ProofRequest) -> Result<(), IssuerError> {
-        if !self.supported_platforms.contains(&request.target_platform) {
-            return Err(IssuerError::InvalidClaimType);
+        let platform: Platform = request.target_platform.parse()?;
+        if !self.supported_platforms.contains(&platform) {
+            return Err(IssuerError::UnsupportedPlatform(
+                request.target_platform.clone(),
+            ));
+        }
+
+        platform.check_claim(&request.claim_type)?;
+        if let Some(holder_key) = &request.options.holder_key {
+            decode_holder_key(holder_key)
+                .map_err(|_| IssuerError::InvalidHolderKey(holder_key.clone()))?;
         }
         Ok(())
     }
@@ -147,21 +685,8 @@ impl CertificateIssuer {
         min_level: &CefrLevel,
         request: &ProofRequest,
     ) -> Result<ZkProofClaim, IssuerError> {
-        // Extract language from certificate
-        let cert_language = self.extract_language_from_certificate(certificate)?;
-        if !cert_language.eq_ignore_ascii_case(language) {
-            return Err(IssuerError::InvalidCertificate(format!(
-                "Certificate language {} does not match requested language {}",
-                cert_language, language
-            )));
-        }
-
-        // Extract CEFR level from certificate
-        let cert_level = CefrLevel::from_course_name(&certificate.game_path_name)
-            .ok_or_else(|| IssuerError::InvalidCefrLevel(certificate.game_path_name.clone()))?;
-
-        // Check if certificate meets minimum level requirement
-        let meets_requirement = cert_level >= *min_level;
+        // Check language and minimum level requirement
+        let meets_requirement = self.evaluate_claim(certificate, &request.claim_type)?;
 
         // Create public inputs
         let mut requirements = HashMap::new();
@@ -181,7 +706,8 @@ impl CertificateIssuer {
         };
 
         // Generate simulated proof data
-        let proof_data = self.generate_proof_data("language_proficiency_v1")?;
+        let proof_data =
+            self.generate_proof_data(request.claim_type.circuit_id(), &public_inputs)?;
 
         // Create metadata
         let metadata = self.create_metadata(&request.target_platform, &request.options);
@@ -222,7 +748,8 @@ impl CertificateIssuer {
             certificate_hash: self.get_certificate_hash(certificate),
         };
 
-        let proof_data = self.generate_proof_data("performance_threshold_v1")?;
+        let proof_data =
+            self.generate_proof_data(request.claim_type.circuit_id(), &public_inputs)?;
         let metadata = self.create_metadata(&request.target_platform, &request.options);
 
         Ok(ZkProofClaim::new(
@@ -240,7 +767,7 @@ impl CertificateIssuer {
         after_date: &DateTime<Utc>,
         request: &ProofRequest,
     ) -> Result<ZkProofClaim, IssuerError> {
-        let meets_requirement = certificate.date >= *after_date;
+        let meets_requirement = self.evaluate_claim(certificate, &request.claim_type)?;
 
         let mut requirements = HashMap::new();
         requirements.insert(
@@ -254,7 +781,8 @@ impl CertificateIssuer {
             certificate_hash: self.get_certificate_hash(certificate),
         };
 
-        let proof_data = self.generate_proof_data("completion_date_v1")?;
+        let proof_data =
+            self.generate_proof_data(request.claim_type.circuit_id(), &public_inputs)?;
         let metadata = self.create_metadata(&request.target_platform, &request.options);
 
         Ok(ZkProofClaim::new(
@@ -271,6 +799,7 @@ impl CertificateIssuer {
         certificate: &CertificateData,
         criteria: &[ClaimType],
         request: &ProofRequest,
+        identity: Option<&VerifiedIdentity>,
     ) -> Result<ZkProofClaim, IssuerError> {
         // Validate each criterion
         let mut all_requirements = HashMap::new();
@@ -284,7 +813,7 @@ impl CertificateIssuer {
                 options: request.options.clone(),
             };
 
-            match self.generate_proof(individual_request) {
+            match self.generate_proof_for(individual_request, identity) {
                 Ok(proof) => {
                     all_pass &= proof.public_inputs.verification_result;
                     // Merge requirements with prefix
@@ -304,7 +833,99 @@ impl CertificateIssuer {
             certificate_hash: self.get_certificate_hash(certificate),
         };
 
-        let proof_data = self.generate_proof_data("combined_criteria_v1")?;
+        let proof_data =
+            self.generate_proof_data(request.claim_type.circuit_id(), &public_inputs)?;
+        let metadata = self.create_metadata(&request.target_platform, &request.options);
+
+        Ok(ZkProofClaim::new(
+            request.claim_type.clone(),
+            public_inputs,
+            proof_data,
+            metadata,
+        ))
+    }
+
+    /// Generate proof that the certificate name matches the passport name
+    ///
+    /// The public inputs carry commitments to both names and a proof of their equality,
+    /// bound to the certificate hash. The certificate commitment is the one signed when the
+    /// certificate was minted, the passport commitment is derived from the passport proofs.
+    fn generate_identity_bound_proof(
+        &self,
+        certificate: &CertificateData,
+        name_match: NameMatch,
+        request: &ProofRequest,
+        identity: Option<&VerifiedIdentity>,
+    ) -> Result<ZkProofClaim, IssuerError> {
+        let passport_name = identity
+            .and_then(|identity| identity.name(name_match))
+            .ok_or(IssuerError::IdentityRequired)?;
+        let passport_inputs = &identity
+            .ok_or(IssuerError::IdentityRequired)?
+            .passport_inputs;
+        let certificate_hash = self.get_certificate_hash(certificate);
+        let certificate_commitment = request
+            .options
+            .name_commitments
+            .iter()
+            .find(|signed| signed.commitment.name_match == name_match)
+            .ok_or(NameBindingError::UntrustedCommitment)?;
+        let signer = certificate_commitment.verify(&certificate_hash)?;
+        if !self.certificate_signers.is_empty() && !self.certificate_signers.contains(&signer) {
+            return Err(NameBindingError::UntrustedCommitment.into());
+        }
+        let binding = NameBinding::prove(
+            &certificate.profile_name,
+            certificate_commitment,
+            &passport_name,
+            passport_inputs,
+            certificate_hash.as_bytes(),
+        )?;
+
+        let mut requirements = request.claim_type.requirements();
+        requirements.insert(
+            NAME_BINDING_REQUIREMENT.to_string(),
+            serde_json::to_value(&binding)
+                .map_err(|e| IssuerError::ProofGenerationFailed(e.to_string()))?,
+        );
+
+        let public_inputs = PublicInputs {
+            requirements,
+            verification_result: true,
+            certificate_hash,
+        };
+
+        let proof_data =
+            self.generate_proof_data(request.claim_type.circuit_id(), &public_inputs)?;
+        let metadata = self.create_metadata(&request.target_platform, &request.options);
+
+        Ok(ZkProofClaim::new(
+            request.claim_type.clone(),
+            public_inputs,
+            proof_data,
+            metadata,
+        ))
+    }
+
+    /// Generate proof for a nationality or document claim
+    fn generate_identity_attribute_proof(
+        &self,
+        certificate: &CertificateData,
+        request: &ProofRequest,
+        identity: Option<&VerifiedIdentity>,
+    ) -> Result<ZkProofClaim, IssuerError> {
+        let identity = identity.ok_or(IssuerError::IdentityRequired)?;
+        let meets_requirement =
+            self.evaluate_identity_claim(certificate, &request.claim_type, identity)?;
+
+        let public_inputs = PublicInputs {
+            requirements: request.claim_type.requirements(),
+            verification_result: meets_requirement,
+            certificate_hash: self.get_certificate_hash(certificate),
+        };
+
+        let proof_data =
+            self.generate_proof_data(request.claim_type.circuit_id(), &public_inputs)?;
         let metadata = self.create_metadata(&request.target_platform, &request.options);
 
         Ok(ZkProofClaim::new(
@@ -330,15 +951,35 @@ impl CertificateIssuer {
         }
     }
 
-    /// Generate simulated proof data
-    fn generate_proof_data(&self, circuit_id: &str) -> Result<ProofData, IssuerError> {
-        // In a real implementation, this would generate actual cryptographic proofs
-        let proof_bytes = self.simulate_proof_generation(circuit_id);
+    /// Generate proof data with the configured prover, or simulated proof data without one
+    fn generate_proof_data(
+        &self,
+        circuit_id: &str,
+        public_inputs: &PublicInputs,
+    ) -> Result<ProofData, IssuerError> {
+        let (proof_bytes, mode) = match &self.prover {
+            Some(prover) => {
+                if self.signing_key.is_none() {
+                    return Err(IssuerError::ProofGenerationFailed(
+                        "real proofs must be signed by the issuer".to_string(),
+                    ));
+                }
+                let proof_bytes = prover
+                    .prove(circuit_id, public_inputs)
+                    .map_err(IssuerError::ProofGenerationFailed)?;
+                (proof_bytes, ProofMode::Real)
+            }
+            None => (
+                self.simulate_proof_generation(circuit_id),
+                ProofMode::Simulated,
+            ),
+        };
 
         Ok(ProofData {
             proof_bytes,
             circuit_id: circuit_id.to_string(),
             vk_hash: Self::compute_verification_key_hash(circuit_id),
+            mode,
         })
     }
 
@@ -348,15 +989,14 @@ impl CertificateIssuer {
         hasher.update(circuit_id.as_bytes());
         hasher.update(self.issuer_id.as_bytes());
         hasher.update(Utc::now().timestamp().to_string().as_bytes());
-        hasher.finalize().to_vec()
+        [SIMULATED_PROOF_TAG, hasher.finalize().as_slice()].concat()
     }
 
-    /// Compute verification key hash for a circuit (must match verifier's computation)
+    /// Compute verification key hash for a simulated circuit (listed in `circuits.toml`)
     pub fn compute_verification_key_hash(circuit_id: &str) -> String {
         let mut hasher = Sha256::new();
         hasher.update(b"vk_");
         hasher.update(circuit_id.as_bytes());
-        hasher.update(b"test"); // Use consistent platform for VK hash
         hex::encode(hasher.finalize())
     }
 
@@ -366,10 +1006,22 @@ impl CertificateIssuer {
         properties.insert("issuer_id".to_string(), self.issuer_id.clone());
         properties.insert("issuer_name".to_string(), self.issuer_name.clone());
 
-        // Add custom properties from options
+        // Add custom properties from options; identity attributes only come from a verified identity
         for (key, value) in &options.custom_properties {
+            if key.starts_with(IDENTITY_PROPERTY_PREFIX) {
+                continue;
+            }
             properties.insert(key.clone(), value.clone());
         }
+        if let Some(holder_key) = &options.holder_key {
+            properties.insert(HOLDER_KEY_PROPERTY.to_string(), holder_key.to_lowercase());
+        }
+        if let Some(status) = &options.credential_status {
+            properties.insert(
+                CREDENTIAL_STATUS_PROPERTY.to_string(),
+                serde_json::to_string(status).expect("status entry serializes to JSON"),
+            );
+        }
 
         ProofMetadata {
             version: "1.0.0".to_string(),
@@ -386,16 +1038,28 @@ impl CertificateIssuer {
     }
 }
 
+/// Bytes covered by a certificate signature, the certificate without its signature
+///
+/// Encoded through `serde_json::Value` so the bytes do not depend on field order.
+fn certificate_signing_bytes(certificate: &CertificateData) -> Vec<u8> {
+    let mut value = serde_json::to_value(certificate).expect("certificate serializes to JSON");
+    if let Some(fields) = value.as_object_mut() {
+        fields.remove("signature");
+    }
+    serde_json::to_vec(&value).expect("certificate serializes to JSON")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::zk_proof::ISSUER_SIGNATURE_PROPERTY;
     use chrono::Utc;
 
     fn create_test_certificate() -> CertificateData {
         CertificateData::new(
             "German_B2_Complete".to_string(),
-            50,
-            47,
+            7,
+            7,
             "Test Student".to_string(),
             Utc::now(),
         )
@@ -406,6 +1070,7 @@ mod tests {
             "test_issuer_001".to_string(),
             "Web5 Claims Test Issuer".to_string(),
         )
+        .accept_unsigned_certificates()
     }
 
     #[test]
@@ -413,7 +1078,7 @@ mod tests {
         let issuer = create_test_issuer();
         let certificate = create_test_certificate();
 
-        assert!(issuer.validate_certificate(&certificate).is_ok());
+        assert!(issuer.validate_certificate(&certificate, None).is_ok());
     }
 
     #[test]
@@ -422,7 +1087,64 @@ mod tests {
         let mut certificate = create_test_certificate();
         certificate.profile_name = String::new();
 
-        assert!(issuer.validate_certificate(&certificate).is_err());
+        assert!(issuer.validate_certificate(&certificate, None).is_err());
+    }
+
+    #[test]
+    fn test_challenge_total_must_match_catalogue() {
+        let issuer = create_test_issuer();
+        let mut certificate = create_test_certificate();
+        certificate.total_challenges = 50;
+
+        assert!(matches!(
+            issuer.generate_proof(create_test_request(certificate)),
+            Err(IssuerError::InvalidCertificate(_))
+        ));
+        let mut result = create_test_game_result();
+        result.total_challenges = 50;
+        result.solved_challenges = 47;
+        assert!(matches!(
+            create_signed_issuer().mint_certificate(&result),
+            Err(IssuerError::InvalidCertificate(_))
+        ));
+    }
+
+    #[test]
+    fn test_unknown_game_path_rejected() {
+        let mut certificate = create_test_certificate();
+        certificate.game_path_name = "Klingon_C2".to_string();
+
+        assert!(matches!(
+            create_test_issuer().generate_proof(create_test_request(certificate.clone())),
+            Err(IssuerError::UnknownGamePath(path)) if path == "Klingon_C2"
+        ));
+        let mut result = create_test_game_result();
+        result.game_path_name = "Klingon_C2".to_string();
+        assert!(matches!(
+            create_signed_issuer().mint_certificate(&result),
+            Err(IssuerError::UnknownGamePath(_))
+        ));
+
+        let catalogue = GameCatalogue::parse(
+            r#"
+            [[game_paths]]
+            id = "German_C2_Mastery"
+            name = "German C2 - Mastery"
+            challenges = ["idioms-c2"]
+            "#,
+        )
+        .unwrap();
+        let issuer = create_test_issuer().with_game_catalogue(catalogue);
+        certificate.game_path_name = "German_C2_Mastery".to_string();
+        certificate.total_challenges = 1;
+        certificate.solved_challenges = 1;
+        assert!(issuer
+            .generate_proof(create_test_request(certificate))
+            .is_ok());
+        assert!(matches!(
+            issuer.generate_proof(create_test_request(create_test_certificate())),
+            Err(IssuerError::UnknownGamePath(_))
+        ));
     }
 
     #[test]
@@ -470,7 +1192,14 @@ mod tests {
     #[test]
     fn test_insufficient_performance() {
         let issuer = create_test_issuer();
-        let certificate = create_test_certificate();
+        let certificate = CertificateData::new(
+            "German_B2_Complete".to_string(),
+            7,
+            6,
+            "Test Student".to_string(),
+            Utc::now(),
+        );
+        let performance = certificate.performance_percentage;
 
         let request = ProofRequest {
             certificate,
@@ -485,7 +1214,7 @@ mod tests {
         match result.unwrap_err() {
             IssuerError::InsufficientPerformance { required, actual } => {
                 assert_eq!(required, 99);
-                assert_eq!(actual, 94); // From create_test_certificate
+                assert_eq!(actual, performance);
             }
             _ => panic!("Expected InsufficientPerformance error"),
         }
@@ -567,4 +1296,219 @@ mod tests {
         assert_eq!(hash1, hash2);
         assert!(!hash1.is_empty());
     }
+
+    #[test]
+    fn test_unknown_platform_rejected_at_request() {
+        let issuer = create_test_issuer();
+        let request = ProofRequest {
+            certificate: create_test_certificate(),
+            claim_type: ClaimType::PerformanceThreshold { min_percentage: 80 },
+            target_platform: "solana".to_string(),
+            options: ProofOptions::default(),
+        };
+
+        assert!(matches!(
+            issuer.generate_proof(request),
+            Err(IssuerError::Platform(PlatformError::UnknownPlatform(_)))
+        ));
+    }
+
+    #[test]
+    fn test_disabled_platform_rejected() {
+        let mut issuer = create_test_issuer();
+        issuer.supported_platforms = vec![Platform::Aleo];
+        let request = ProofRequest {
+            certificate: create_test_certificate(),
+            claim_type: ClaimType::PerformanceThreshold { min_percentage: 80 },
+            target_platform: "stylus".to_string(),
+            options: ProofOptions::default(),
+        };
+
+        assert!(matches!(
+            issuer.generate_proof(request),
+            Err(IssuerError::UnsupportedPlatform(_))
+        ));
+    }
+
+    #[test]
+    fn test_did_key_issuer_signs_proofs() {
+        let issuer = create_test_issuer().with_did_key(SigningKey::from_bytes(&[3u8; 32]));
+        assert!(issuer.did().unwrap().starts_with("did:key:z6Mk"));
+        assert_eq!(
+            issuer.verification_method().unwrap(),
+            format!(
+                "{}#{}",
+                issuer.issuer_id,
+                issuer.issuer_id.trim_start_matches("did:key:")
+            )
+        );
+
+        let request = ProofRequest {
+            certificate: create_test_certificate(),
+            claim_type: ClaimType::PerformanceThreshold { min_percentage: 80 },
+            target_platform: "test".to_string(),
+            options: ProofOptions::default(),
+        };
+        let proof = issuer.generate_proof(request.clone()).unwrap();
+        let properties = &proof.metadata.properties;
+        assert_eq!(properties["issuer_id"], issuer.issuer_id);
+        assert!(properties.contains_key(ISSUER_SIGNATURE_PROPERTY));
+
+        let unsigned = create_test_issuer().generate_proof(request).unwrap();
+        assert!(create_test_issuer().did().is_none());
+        assert!(!unsigned
+            .metadata
+            .properties
+            .contains_key(ISSUER_SIGNATURE_PROPERTY));
+    }
+
+    fn create_test_request(certificate: CertificateData) -> ProofRequest {
+        ProofRequest {
+            certificate,
+            claim_type: ClaimType::LanguageProficiency {
+                language: "German".to_string(),
+                min_level: CefrLevel::B1,
+            },
+            target_platform: "test".to_string(),
+            options: ProofOptions::default(),
+        }
+    }
+
+    fn create_test_game_result() -> GameResult {
+        GameResult {
+            game_path_name: "German_B2_Complete".to_string(),
+            profile_name: "Test Student".to_string(),
+            total_challenges: 7,
+            solved_challenges: 7,
+            completed_at: Utc::now(),
+        }
+    }
+
+    fn create_signed_issuer() -> CertificateIssuer {
+        CertificateIssuer::new("test_issuer".to_string(), "Test Issuer".to_string())
+            .accept_unsigned_certificates()
+            .with_signing_key("key-1".to_string(), SigningKey::from_bytes(&[21u8; 32]))
+    }
+
+    #[test]
+    fn test_mint_certificate() {
+        let issuer = create_signed_issuer().require_own_certificates().unwrap();
+        let minted = issuer.mint_certificate(&create_test_game_result()).unwrap();
+
+        assert_eq!(minted.certificate.performance_percentage, 100);
+        assert!(issuer.verify_certificate_signature(&minted).is_ok());
+        assert_eq!(
+            MintedCertificate::from_base64(&minted.to_base64()).unwrap(),
+            minted
+        );
+        assert!(issuer
+            .generate_proof(minted.proof_request(
+                ClaimType::PerformanceThreshold { min_percentage: 90 },
+                "test".to_string(),
+            ))
+            .is_ok());
+
+        let mut invalid = create_test_game_result();
+        invalid.solved_challenges = 51;
+        assert!(matches!(
+            issuer.mint_certificate(&invalid),
+            Err(IssuerError::InvalidCertificate(_))
+        ));
+        assert!(matches!(
+            create_test_issuer().mint_certificate(&create_test_game_result()),
+            Err(IssuerError::MissingSigningKey)
+        ));
+    }
+
+    #[test]
+    fn test_minted_certificate_passes_konnektoren_verification() {
+        let minted = create_signed_issuer()
+            .mint_certificate(&create_test_game_result())
+            .unwrap();
+
+        assert!(minted.certificate.signature.is_none());
+        assert!(minted.certificate.verify().is_ok());
+        assert!(create_test_issuer()
+            .generate_proof(create_test_request(minted.certificate))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_trusted_issuer_requires_minted_certificates() {
+        let issuer = create_signed_issuer().require_own_certificates().unwrap();
+        let claim_type = ClaimType::PerformanceThreshold { min_percentage: 90 };
+
+        // A certificate typed in by hand carries no signature
+        assert!(matches!(
+            issuer.generate_proof(create_test_request(create_test_certificate())),
+            Err(IssuerError::UntrustedCertificate)
+        ));
+
+        // Raising the challenge counts breaks the signature
+        let mut inflated = issuer.mint_certificate(&create_test_game_result()).unwrap();
+        inflated.certificate.solved_challenges = 50;
+        inflated.certificate.performance_percentage = 100;
+        assert!(matches!(
+            issuer.generate_proof(inflated.proof_request(claim_type.clone(), "test".to_string())),
+            Err(IssuerError::UntrustedCertificate)
+        ));
+
+        // A certificate that verifies under some other key is not enough
+        let other = CertificateIssuer::new("other".to_string(), "Other".to_string())
+            .accept_unsigned_certificates()
+            .with_signing_key("key-1".to_string(), SigningKey::from_bytes(&[22u8; 32]));
+        let foreign = other.mint_certificate(&create_test_game_result()).unwrap();
+        assert!(other
+            .require_own_certificates()
+            .unwrap()
+            .verify_certificate_signature(&foreign)
+            .is_ok());
+        assert!(matches!(
+            issuer.generate_proof(foreign.proof_request(claim_type, "test".to_string())),
+            Err(IssuerError::UntrustedCertificate)
+        ));
+    }
+
+    #[derive(Debug)]
+    struct TestProver;
+
+    impl ProvingBackend for TestProver {
+        fn prove(&self, circuit_id: &str, _: &PublicInputs) -> Result<Vec<u8>, String> {
+            Ok(circuit_id.as_bytes().to_vec())
+        }
+    }
+
+    #[test]
+    fn test_unsigned_certificates_rejected_by_default() {
+        let issuer = CertificateIssuer::new("test_issuer".to_string(), "Test Issuer".to_string());
+        assert!(matches!(
+            issuer.generate_proof(create_test_request(create_test_certificate())),
+            Err(IssuerError::MissingCertificateSigner)
+        ));
+
+        let issuer = issuer.accept_unsigned_certificates();
+        let proof = issuer
+            .generate_proof(create_test_request(create_test_certificate()))
+            .unwrap();
+        assert_eq!(proof.proof_data.mode, ProofMode::Simulated);
+    }
+
+    #[test]
+    fn test_real_proofs_require_certificate_signer() {
+        let issuer = create_signed_issuer().with_prover(TestProver);
+        assert!(matches!(
+            issuer.generate_proof(create_test_request(create_test_certificate())),
+            Err(IssuerError::MissingCertificateSigner)
+        ));
+
+        let issuer = issuer.require_own_certificates().unwrap();
+        let minted = issuer.mint_certificate(&create_test_game_result()).unwrap();
+        let proof = issuer
+            .generate_proof(minted.proof_request(
+                ClaimType::PerformanceThreshold { min_percentage: 90 },
+                "test".to_string(),
+            ))
+            .unwrap();
+        assert_eq!(proof.proof_data.mode, ProofMode::Real);
+    }
 }
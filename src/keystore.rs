@@ -0,0 +1,637 @@
+//! Issuer key management.
+//!
+//! A [`KeyStore`] holds the Ed25519 keys of one issuer: the active key signing new proofs and
+//! the retired keys that signed older ones. It is stored encrypted with a passphrase, using
+//! Argon2id to derive the key and XChaCha20-Poly1305 to encrypt the store.
+//!
+//! Signed proofs carry the id of their key in the `issuer_key_id` metadata property. Verifiers
+//! trust the public half of the store as a [`TrustedKeySet`], which tells which key was valid
+//! when a proof was generated.
+
+use crate::holder::signing_key_hex;
+use crate::issuer::CertificateIssuer;
+use crate::signature::{IssuerPublicKey, SignatureAlgorithm};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    XChaCha20Poly1305, XNonce,
+};
+use chrono::{DateTime, Duration, Utc};
+use ed25519_dalek::SigningKey;
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+/// Version of the encrypted keystore format
+pub const KEYSTORE_VERSION: u32 = 1;
+/// Key derivation function of encrypted keystores
+pub const KDF_ALGORITHM: &str = "argon2id";
+/// Cipher of encrypted keystores
+pub const CIPHER: &str = "xchacha20poly1305";
+
+#[derive(Error, Debug, PartialEq)]
+pub enum KeystoreError {
+    #[error("Keystore has no active key")]
+    NoActiveKey,
+    #[error("Unknown key: {0}")]
+    UnknownKey(String),
+    #[error("Duplicate key id: {0}")]
+    DuplicateKeyId(String),
+    #[error("Invalid key: {0}")]
+    InvalidKey(String),
+    #[error("Key derivation failed: {0}")]
+    KeyDerivation(String),
+    #[error("Wrong passphrase or corrupted keystore")]
+    DecryptionFailed,
+    #[error("Unsupported keystore format: {0}")]
+    UnsupportedFormat(String),
+    #[error("Serialization failed: {0}")]
+    Serialization(String),
+    #[error("IO error: {0}")]
+    Io(String),
+}
+
+/// Keys of an issuer
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct KeyStore {
+    /// Issuer the keys belong to
+    pub issuer_id: String,
+    /// Keys, oldest first; the last key without `retired_at` is active
+    keys: Vec<StoredKey>,
+    /// Age after which the active key is due for rotation, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rotation_period_secs: Option<i64>,
+}
+
+/// Key held by a keystore
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct StoredKey {
+    key_id: String,
+    #[serde(with = "signing_key_hex")]
+    signing_key: SigningKey,
+    created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    retired_at: Option<DateTime<Utc>>,
+}
+
+/// Argon2id parameters
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct KdfParams {
+    /// Memory cost in KiB
+    pub memory_kib: u32,
+    /// Number of passes
+    pub iterations: u32,
+    /// Degree of parallelism
+    pub parallelism: u32,
+}
+
+/// Keystore encrypted at rest
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EncryptedKeyStore {
+    /// Format version
+    pub version: u32,
+    /// Issuer the keys belong to, authenticated as associated data
+    pub issuer_id: String,
+    /// Key derivation function, always `argon2id`
+    pub kdf: String,
+    /// Key derivation parameters
+    pub kdf_params: KdfParams,
+    /// Base64 encoded salt
+    pub salt: String,
+    /// Cipher, always `xchacha20poly1305`
+    pub cipher: String,
+    /// Base64 encoded nonce
+    pub nonce: String,
+    /// Base64 encoded encrypted keystore
+    pub ciphertext: String,
+}
+
+/// Public key of an issuer with the period it signed proofs in
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TrustedKey {
+    /// Key identifier
+    pub key_id: String,
+    /// Signature algorithm of the key
+    #[serde(default)]
+    pub algorithm: SignatureAlgorithm,
+    /// Hex-encoded public key
+    pub public_key: String,
+    /// When the key started signing proofs
+    pub valid_from: DateTime<Utc>,
+    /// When the key was retired
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<DateTime<Utc>>,
+}
+
+/// Public keys an issuer signs proofs with, for verifiers
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TrustedKeySet {
+    /// Issuer the keys belong to
+    pub issuer_id: String,
+    /// Current and retired keys
+    pub keys: Vec<TrustedKey>,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            memory_kib: Params::DEFAULT_M_COST,
+            iterations: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+impl KeyStore {
+    /// Create an empty keystore
+    pub fn new(issuer_id: String) -> Self {
+        Self {
+            issuer_id,
+            keys: vec![],
+            rotation_period_secs: None,
+        }
+    }
+
+    /// Create a keystore with a fresh active key
+    pub fn generate(issuer_id: String) -> Self {
+        let mut keystore = Self::new(issuer_id);
+        keystore.rotate();
+        keystore
+    }
+
+    /// Set the age after which the active key is due for rotation
+    pub fn with_rotation_period(mut self, period: Duration) -> Self {
+        self.rotation_period_secs = Some(period.num_seconds());
+        self
+    }
+
+    /// Age after which the active key is due for rotation
+    pub fn rotation_period(&self) -> Option<Duration> {
+        self.rotation_period_secs.map(Duration::seconds)
+    }
+
+    /// Id of the active key
+    pub fn active_key_id(&self) -> Option<&str> {
+        self.active().map(|key| key.key_id.as_str())
+    }
+
+    /// Active signing key
+    pub fn active_key(&self) -> Option<&SigningKey> {
+        self.active().map(|key| &key.signing_key)
+    }
+
+    /// Ids of all keys, oldest first
+    pub fn key_ids(&self) -> Vec<&str> {
+        self.keys.iter().map(|key| key.key_id.as_str()).collect()
+    }
+
+    /// Generate a new active key, retiring the current one
+    ///
+    /// Returns the id of the new key.
+    pub fn rotate(&mut self) -> String {
+        let mut secret = Zeroizing::new([0u8; 32]);
+        OsRng.fill_bytes(&mut *secret);
+
+        let key_id = self.next_key_id();
+        self.activate(key_id.clone(), SigningKey::from_bytes(&secret));
+        key_id
+    }
+
+    /// Check if the active key is older than the rotation period
+    pub fn rotation_due(&self, now: DateTime<Utc>) -> bool {
+        match (self.active(), self.rotation_period()) {
+            (None, _) => true,
+            (Some(key), Some(period)) => now >= key.created_at + period,
+            (Some(_), None) => false,
+        }
+    }
+
+    /// Rotate if the active key is due, returning the id of the new key
+    pub fn rotate_if_due(&mut self, now: DateTime<Utc>) -> Option<String> {
+        self.rotation_due(now).then(|| self.rotate())
+    }
+
+    /// Import a key as the new active key, retiring the current one
+    pub fn import_key(
+        &mut self,
+        key_id: String,
+        signing_key: SigningKey,
+    ) -> Result<(), KeystoreError> {
+        if self.keys.iter().any(|key| key.key_id == key_id) {
+            return Err(KeystoreError::DuplicateKeyId(key_id));
+        }
+
+        self.activate(key_id, signing_key);
+        Ok(())
+    }
+
+    /// Import a hex-encoded secret key as the new active key
+    pub fn import_key_hex(
+        &mut self,
+        key_id: String,
+        secret_hex: &str,
+    ) -> Result<(), KeystoreError> {
+        let secret: Zeroizing<[u8; 32]> = hex::decode(secret_hex)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(Zeroizing::new)
+            .ok_or_else(|| KeystoreError::InvalidKey(key_id.clone()))?;
+
+        self.import_key(key_id, SigningKey::from_bytes(&secret))
+    }
+
+    /// Export a secret key as hex
+    pub fn export_key_hex(&self, key_id: &str) -> Result<Zeroizing<String>, KeystoreError> {
+        self.keys
+            .iter()
+            .find(|key| key.key_id == key_id)
+            .map(|key| Zeroizing::new(hex::encode(key.signing_key.to_bytes())))
+            .ok_or_else(|| KeystoreError::UnknownKey(key_id.to_string()))
+    }
+
+    /// Public keys of the store, for verifiers
+    pub fn trusted_keys(&self) -> TrustedKeySet {
+        TrustedKeySet {
+            issuer_id: self.issuer_id.clone(),
+            keys: self
+                .keys
+                .iter()
+                .map(|key| TrustedKey {
+                    valid_until: key.retired_at,
+                    ..TrustedKey::new(
+                        key.key_id.clone(),
+                        &IssuerPublicKey::Ed25519(key.signing_key.verifying_key()),
+                        key.created_at,
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Issuer signing proofs with the active key
+    pub fn issuer(&self, issuer_name: String) -> Result<CertificateIssuer, KeystoreError> {
+        let key = self.active().ok_or(KeystoreError::NoActiveKey)?;
+
+        Ok(CertificateIssuer::new(self.issuer_id.clone(), issuer_name)
+            .with_signing_key(key.key_id.clone(), key.signing_key.clone()))
+    }
+
+    /// Encrypt the keystore with a passphrase using the default Argon2id parameters
+    pub fn encrypt(&self, passphrase: &str) -> Result<EncryptedKeyStore, KeystoreError> {
+        self.encrypt_with(passphrase, KdfParams::default())
+    }
+
+    /// Encrypt the keystore with a passphrase and explicit Argon2id parameters
+    pub fn encrypt_with(
+        &self,
+        passphrase: &str,
+        kdf_params: KdfParams,
+    ) -> Result<EncryptedKeyStore, KeystoreError> {
+        let mut salt = [0u8; 16];
+        let mut nonce = [0u8; 24];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut nonce);
+
+        let plaintext = Zeroizing::new(
+            serde_json::to_vec(self).map_err(|e| KeystoreError::Serialization(e.to_string()))?,
+        );
+        let cipher = derive_cipher(passphrase, &salt, kdf_params)?;
+        let ciphertext = cipher
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: &plaintext,
+                    aad: self.issuer_id.as_bytes(),
+                },
+            )
+            .map_err(|e| KeystoreError::Serialization(e.to_string()))?;
+
+        Ok(EncryptedKeyStore {
+            version: KEYSTORE_VERSION,
+            issuer_id: self.issuer_id.clone(),
+            kdf: KDF_ALGORITHM.to_string(),
+            kdf_params,
+            salt: STANDARD.encode(salt),
+            cipher: CIPHER.to_string(),
+            nonce: STANDARD.encode(nonce),
+            ciphertext: STANDARD.encode(ciphertext),
+        })
+    }
+
+    /// Encrypt the keystore with the default Argon2id parameters and write it to a file
+    pub fn save(&self, path: impl AsRef<Path>, passphrase: &str) -> Result<(), KeystoreError> {
+        self.save_with(path, passphrase, KdfParams::default())
+    }
+
+    /// Encrypt the keystore with explicit Argon2id parameters and write it to a file
+    pub fn save_with(
+        &self,
+        path: impl AsRef<Path>,
+        passphrase: &str,
+        kdf_params: KdfParams,
+    ) -> Result<(), KeystoreError> {
+        let json = self.encrypt_with(passphrase, kdf_params)?.to_json()?;
+        std::fs::write(path, json).map_err(|e| KeystoreError::Io(e.to_string()))
+    }
+
+    /// Read and decrypt a keystore file
+    pub fn load(path: impl AsRef<Path>, passphrase: &str) -> Result<Self, KeystoreError> {
+        let json = std::fs::read_to_string(path).map_err(|e| KeystoreError::Io(e.to_string()))?;
+        EncryptedKeyStore::from_json(&json)?.decrypt(passphrase)
+    }
+
+    fn active(&self) -> Option<&StoredKey> {
+        self.keys.last().filter(|key| key.retired_at.is_none())
+    }
+
+    fn activate(&mut self, key_id: String, signing_key: SigningKey) {
+        let now = Utc::now();
+        for key in self.keys.iter_mut().filter(|key| key.retired_at.is_none()) {
+            key.retired_at = Some(now);
+        }
+
+        self.keys.push(StoredKey {
+            key_id,
+            signing_key,
+            created_at: now,
+            retired_at: None,
+        });
+    }
+
+    fn next_key_id(&self) -> String {
+        (self.keys.len() + 1..)
+            .map(|n| format!("key-{}", n))
+            .find(|key_id| self.keys.iter().all(|key| &key.key_id != key_id))
+            .expect("key ids are unbounded")
+    }
+}
+
+impl EncryptedKeyStore {
+    /// Decrypt the keystore with its passphrase
+    pub fn decrypt(&self, passphrase: &str) -> Result<KeyStore, KeystoreError> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(KeystoreError::UnsupportedFormat(format!(
+                "version {}",
+                self.version
+            )));
+        }
+        if self.kdf != KDF_ALGORITHM || self.cipher != CIPHER {
+            return Err(KeystoreError::UnsupportedFormat(format!(
+                "{} with {}",
+                self.kdf, self.cipher
+            )));
+        }
+
+        let decode = |field: &str| {
+            STANDARD
+                .decode(field)
+                .map_err(|e| KeystoreError::Serialization(e.to_string()))
+        };
+        let salt = decode(&self.salt)?;
+        let nonce = decode(&self.nonce)?;
+        if nonce.len() != 24 {
+            return Err(KeystoreError::Serialization(
+                "nonce must be 24 bytes".to_string(),
+            ));
+        }
+
+        let cipher = derive_cipher(passphrase, &salt, self.kdf_params)?;
+        let plaintext = Zeroizing::new(
+            cipher
+                .decrypt(
+                    XNonce::from_slice(&nonce),
+                    Payload {
+                        msg: &decode(&self.ciphertext)?,
+                        aad: self.issuer_id.as_bytes(),
+                    },
+                )
+                .map_err(|_| KeystoreError::DecryptionFailed)?,
+        );
+
+        serde_json::from_slice(&plaintext).map_err(|e| KeystoreError::Serialization(e.to_string()))
+    }
+
+    /// Serialize to JSON
+    pub fn to_json(&self) -> Result<String, KeystoreError> {
+        serde_json::to_string_pretty(self).map_err(|e| KeystoreError::Serialization(e.to_string()))
+    }
+
+    /// Deserialize from JSON
+    pub fn from_json(json: &str) -> Result<Self, KeystoreError> {
+        serde_json::from_str(json).map_err(|e| KeystoreError::Serialization(e.to_string()))
+    }
+}
+
+impl TrustedKey {
+    /// Trust a public key from `valid_from` on
+    pub fn new(key_id: String, public_key: &IssuerPublicKey, valid_from: DateTime<Utc>) -> Self {
+        Self {
+            key_id,
+            algorithm: public_key.algorithm(),
+            public_key: hex::encode(public_key.to_bytes()),
+            valid_from,
+            valid_until: None,
+        }
+    }
+}
+
+impl TrustedKeySet {
+    /// Public key that was valid for signing at `at`
+    pub fn key_at(&self, key_id: &str, at: DateTime<Utc>) -> Option<IssuerPublicKey> {
+        let key = self.keys.iter().find(|key| key.key_id == key_id)?;
+        if at < key.valid_from || key.valid_until.is_some_and(|until| at > until) {
+            return None;
+        }
+
+        let bytes = hex::decode(&key.public_key).ok()?;
+        IssuerPublicKey::from_bytes(key.algorithm, &bytes).ok()
+    }
+}
+
+/// Derive the cipher key from a passphrase with Argon2id
+fn derive_cipher(
+    passphrase: &str,
+    salt: &[u8],
+    kdf_params: KdfParams,
+) -> Result<XChaCha20Poly1305, KeystoreError> {
+    let params = Params::new(
+        kdf_params.memory_kib,
+        kdf_params.iterations,
+        kdf_params.parallelism,
+        Some(32),
+    )
+    .map_err(|e| KeystoreError::KeyDerivation(e.to_string()))?;
+
+    let mut key = Zeroizing::new([0u8; 32]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut *key)
+        .map_err(|e| KeystoreError::KeyDerivation(e.to_string()))?;
+
+    Ok(XChaCha20Poly1305::new((&*key).into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cheap parameters so tests do not spend seconds in Argon2
+    const TEST_KDF_PARAMS: KdfParams = KdfParams {
+        memory_kib: 64,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    fn create_test_keystore() -> KeyStore {
+        KeyStore::generate("test_issuer".to_string())
+    }
+
+    #[test]
+    fn test_generate_and_rotate() {
+        let mut keystore = create_test_keystore();
+        assert_eq!(keystore.active_key_id(), Some("key-1"));
+        let first_key = keystore.active_key().unwrap().clone();
+
+        assert_eq!(keystore.rotate(), "key-2");
+        assert_eq!(keystore.active_key_id(), Some("key-2"));
+        assert_ne!(keystore.active_key().unwrap(), &first_key);
+        assert_eq!(keystore.key_ids(), vec!["key-1", "key-2"]);
+
+        let trusted = keystore.trusted_keys();
+        assert!(trusted.keys[0].valid_until.is_some());
+        assert!(trusted.keys[1].valid_until.is_none());
+    }
+
+    #[test]
+    fn test_scheduled_rotation() {
+        let mut keystore = create_test_keystore().with_rotation_period(Duration::days(90));
+        let now = Utc::now();
+
+        assert!(!keystore.rotation_due(now));
+        assert_eq!(keystore.rotate_if_due(now), None);
+        assert_eq!(
+            keystore.rotate_if_due(now + Duration::days(91)),
+            Some("key-2".to_string())
+        );
+        assert!(KeyStore::new("empty".to_string()).rotation_due(now));
+        assert!(!KeyStore::generate("manual".to_string()).rotation_due(now + Duration::days(365)));
+    }
+
+    #[test]
+    fn test_import_and_export() {
+        let mut keystore = create_test_keystore();
+        let secret = keystore.export_key_hex("key-1").unwrap();
+
+        let mut other = KeyStore::new("test_issuer".to_string());
+        other
+            .import_key_hex("imported".to_string(), &secret)
+            .unwrap();
+        assert_eq!(other.active_key(), keystore.active_key());
+
+        assert_eq!(
+            keystore.import_key("key-1".to_string(), SigningKey::from_bytes(&[1u8; 32])),
+            Err(KeystoreError::DuplicateKeyId("key-1".to_string()))
+        );
+        assert!(matches!(
+            keystore.import_key_hex("bad".to_string(), "abcd"),
+            Err(KeystoreError::InvalidKey(_))
+        ));
+        assert!(matches!(
+            keystore.export_key_hex("missing"),
+            Err(KeystoreError::UnknownKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_encrypt_round_trip() {
+        let mut keystore = create_test_keystore().with_rotation_period(Duration::days(30));
+        keystore.rotate();
+
+        let encrypted = keystore
+            .encrypt_with("correct horse battery staple", TEST_KDF_PARAMS)
+            .unwrap();
+        let json = encrypted.to_json().unwrap();
+        assert!(!json.contains(keystore.export_key_hex("key-1").unwrap().as_str()));
+
+        let decrypted = EncryptedKeyStore::from_json(&json)
+            .unwrap()
+            .decrypt("correct horse battery staple")
+            .unwrap();
+        assert_eq!(decrypted, keystore);
+    }
+
+    #[test]
+    fn test_wrong_passphrase_and_tampering_rejected() {
+        let keystore = create_test_keystore();
+        let encrypted = keystore
+            .encrypt_with("passphrase", TEST_KDF_PARAMS)
+            .unwrap();
+        assert_eq!(
+            encrypted.decrypt("wrong passphrase"),
+            Err(KeystoreError::DecryptionFailed)
+        );
+
+        let mut relabelled = encrypted.clone();
+        relabelled.issuer_id = "other_issuer".to_string();
+        assert_eq!(
+            relabelled.decrypt("passphrase"),
+            Err(KeystoreError::DecryptionFailed)
+        );
+
+        let mut unsupported = encrypted;
+        unsupported.cipher = "aes-256-gcm".to_string();
+        assert!(matches!(
+            unsupported.decrypt("passphrase"),
+            Err(KeystoreError::UnsupportedFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let keystore = create_test_keystore();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keystore.json");
+
+        keystore
+            .save_with(&path, "passphrase", TEST_KDF_PARAMS)
+            .unwrap();
+        assert_eq!(KeyStore::load(&path, "passphrase").unwrap(), keystore);
+        assert!(KeyStore::load(&path, "other").is_err());
+    }
+
+    #[test]
+    fn test_trusted_key_validity() {
+        let mut keystore = create_test_keystore();
+        let signed_before_rotation = Utc::now();
+        keystore.rotate();
+        let trusted = keystore.trusted_keys();
+        let retired_at = trusted.keys[0].valid_until.unwrap();
+
+        assert!(trusted.key_at("key-1", signed_before_rotation).is_some());
+        assert!(trusted
+            .key_at("key-1", retired_at + Duration::seconds(1))
+            .is_none());
+        assert!(trusted.key_at("key-2", Utc::now()).is_some());
+        assert!(trusted.key_at("key-3", Utc::now()).is_none());
+    }
+
+    #[test]
+    fn test_issuer_signs_with_active_key() {
+        let keystore = create_test_keystore();
+        let issuer = keystore.issuer("Test Issuer".to_string()).unwrap();
+
+        assert_eq!(issuer.key_id(), Some("key-1"));
+        assert_eq!(
+            issuer.public_key(),
+            Some(keystore.active_key().unwrap().verifying_key())
+        );
+        assert_eq!(
+            KeyStore::new("empty".to_string())
+                .issuer("Empty".to_string())
+                .unwrap_err(),
+            KeystoreError::NoActiveKey
+        );
+    }
+}
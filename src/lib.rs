@@ -21,17 +21,18 @@
 //! // Create a certificate
 //! let certificate = CertificateData::new(
 //!     "German_B2_Complete".to_string(),
-//!     50,
-//!     47,
+//!     7,
+//!     7,
 //!     "Language Learner".to_string(),
 //!     Utc::now(),
 //! );
 //!
-//! // Create an issuer
+//! // Create a demo issuer, which accepts certificates no certificate signer signed
 //! let issuer = CertificateIssuer::new(
 //!     "web5_claims_issuer".to_string(),
 //!     "Web5 Claims Official".to_string(),
-//! );
+//! )
+//! .accept_unsigned_certificates();
 //!
 //! // Generate a proof
 //! let request = ProofRequest {
@@ -54,14 +55,76 @@
 //! assert!(verification_result.requirements_met);
 //! ```
 
+pub mod aleo;
+pub mod cosigning;
+pub mod country;
+pub mod did;
+#[cfg(feature = "evm")]
+pub mod eas;
+#[cfg(feature = "evm")]
+pub mod evm;
+pub mod game_session;
+pub mod handoff;
+pub mod holder;
+pub mod identity;
 pub mod issuer;
+pub mod keystore;
+pub mod name_binding;
+pub mod openid4vp;
+pub mod platform;
+pub mod presentation;
+pub mod presentation_exchange;
+pub mod registry;
+pub mod sd_jwt;
+pub mod signature;
+pub mod status_list;
+#[cfg(test)]
+mod testing;
+pub mod trust;
+pub mod vc;
 pub mod verifier;
 pub mod zk_proof;
 
 // Re-export key types for convenience
-pub use issuer::{CertificateIssuer, IssuerError, ProofOptions, ProofRequest};
-pub use verifier::{VerificationResult, VerifierError, ZkProofVerifier};
-pub use zk_proof::{CefrLevel, ClaimType, ProofData, ProofMetadata, PublicInputs, ZkProofClaim};
+pub use cosigning::{Cosigner, CosigningError, CosigningPolicy};
+pub use did::{DidDocument, DidError, DidResolver, DidWebPublisher, FileDidResolver};
+pub use game_session::{CatalogueEntry, CompletedChallenge, GameCatalogue, GameSessionError};
+pub use handoff::{HandoffError, HandoffSigner, HandoffVerifier, VerifiedHandoff};
+pub use holder::{ClaimRequest, Holder, HolderError};
+pub use identity::{
+    IdentityError, IdentityProofBundle, IdentityRequest, IdentityVerifier, VerifiedIdentity,
+};
+pub use issuer::{
+    CertificateIssuer, GameResult, IssuerError, MintedCertificate, ProofOptions, ProofRequest,
+};
+pub use keystore::{EncryptedKeyStore, KeyStore, KeystoreError, TrustedKeySet};
+pub use name_binding::{
+    NameBinding, NameBindingError, NameCommitment, NameMatch, SignedNameCommitment,
+};
+pub use openid4vp::{
+    AuthorizationRequest, AuthorizationResponse, OpenId4VpError, OpenId4VpVerifier,
+    PresentationQuery,
+};
+pub use platform::{Platform, PlatformCapabilities};
+pub use presentation::{Presentation, PresentationError};
+pub use presentation_exchange::{
+    DefinitionEvaluation, PresentationDefinition, PresentationExchangeError, PresentationSubmission,
+};
+pub use sd_jwt::{SdJwt, SdJwtError, SdJwtIssuer, SdJwtVerifier};
+pub use signature::{
+    IssuerPublicKey, IssuerSignature, IssuerSigningKey, SignatureAlgorithm, SignatureError,
+};
+pub use status_list::{SignedStatusList, StatusList, StatusListError};
+pub use trust::{TrustError, TrustFactor, TrustScore, TrustScoreConfig, TrustWeights};
+pub use vc::{VcError, VerifiableCredential};
+pub use verifier::{
+    PresentationVerificationResult, VerificationMode, VerificationResult, VerifierError,
+    ZkProofVerifier,
+};
+pub use zk_proof::{
+    CefrLevel, ClaimKind, ClaimType, ProofData, ProofMetadata, ProofMode, PublicInputs,
+    ZkProofClaim,
+};
 
 use chrono::Utc;
 use konnektoren_core::certificates::CertificateData;
@@ -70,8 +133,8 @@ use konnektoren_core::certificates::CertificateData;
 pub fn create_sample_certificate() -> CertificateData {
     CertificateData::new(
         "German_B2_Complete".to_string(),
-        50,
-        47,
+        7,
+        7,
         "Test Student".to_string(),
         Utc::now(),
     )
@@ -79,7 +142,8 @@ pub fn create_sample_certificate() -> CertificateData {
 
 /// Create a sample ZK proof for demonstration
 pub fn create_sample_proof() -> Result<ZkProofClaim, IssuerError> {
-    let issuer = CertificateIssuer::new("demo_issuer".to_string(), "Web5 Claims Demo".to_string());
+    let issuer = CertificateIssuer::new("demo_issuer".to_string(), "Web5 Claims Demo".to_string())
+        .accept_unsigned_certificates();
 
     let certificate = create_sample_certificate();
 
@@ -104,9 +168,9 @@ mod tests {
     fn test_create_sample_certificate() {
         let cert = create_sample_certificate();
         assert_eq!(cert.game_path_name, "German_B2_Complete");
-        assert_eq!(cert.total_challenges, 50);
-        assert_eq!(cert.solved_challenges, 47);
-        assert_eq!(cert.performance_percentage, 94);
+        assert_eq!(cert.total_challenges, 7);
+        assert_eq!(cert.solved_challenges, 7);
+        assert_eq!(cert.performance_percentage, 100);
     }
 
     #[test]
@@ -131,7 +195,8 @@ mod tests {
         let issuer = CertificateIssuer::new(
             "e2e_issuer".to_string(),
             "End-to-End Test Issuer".to_string(),
-        );
+        )
+        .accept_unsigned_certificates();
 
         // 3. Generate proof for language proficiency
         let language_proof_request = ProofRequest {
@@ -0,0 +1,643 @@
+//! Private binding of certificate names to passport names.
+//!
+//! A certificate's `profile_name` is typed in by the learner, while ZKPassport discloses the
+//! name printed in the passport. Both are normalized with [`normalize_name`] and committed to
+//! with Pedersen commitments on Ristretto. A [`NameBinding`] carries both commitments and a
+//! proof that they open to the same name, so a verifier learns that the names match without
+//! learning either.
+//!
+//! Neither commitment is chosen when proving. The certificate commitment is made when the
+//! certificate is minted and signed by its signer together with the certificate hash, see
+//! [`SignedNameCommitment`]. The passport commitment is derived from the public inputs of the
+//! passport proofs that disclosed the name, and the binding carries their digest.
+
+use crate::identity::PassportPublicInputs;
+use curve25519_dalek::{
+    constants::RISTRETTO_BASEPOINT_POINT,
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+use std::fmt;
+use thiserror::Error;
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+/// Public input holding the JSON-encoded [`NameBinding`] of an identity bound claim
+pub const NAME_BINDING_REQUIREMENT: &str = "name_binding";
+
+/// Domain separator of the second commitment generator
+const GENERATOR_DOMAIN: &[u8] = b"web5claims:name-binding:generator";
+/// Domain separator of committed names
+const NAME_DOMAIN: &[u8] = b"web5claims:name-binding:name";
+/// Domain separator of the equality proof challenge
+const CHALLENGE_DOMAIN: &[u8] = b"web5claims:name-binding:challenge";
+/// Domain separator of passport commitment blindings
+const PASSPORT_BLINDING_DOMAIN: &[u8] = b"web5claims:name-binding:passport-blinding";
+/// Domain separator of signed certificate commitments
+const CERTIFICATE_COMMITMENT_DOMAIN: &[u8] = b"web5claims:name-binding:certificate-commitment";
+
+#[derive(Error, Debug, PartialEq)]
+pub enum NameBindingError {
+    #[error("Name is empty after normalization")]
+    EmptyName,
+    #[error("Certificate name does not match the passport name")]
+    Mismatch,
+    #[error("Claim carries no name binding: {0}")]
+    MissingBinding(String),
+    #[error("Invalid name commitment: {0}")]
+    InvalidCommitment(String),
+    #[error("Name equality proof does not verify")]
+    InvalidProof,
+    #[error("Certificate name commitment is not signed by a trusted certificate signer")]
+    UntrustedCommitment,
+    #[error("Passport commitment is not derived from the passport proof public inputs")]
+    PassportInputsMismatch,
+}
+
+/// Which part of the names has to match
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum NameMatch {
+    /// First given name
+    #[default]
+    FirstName,
+    /// All name parts, in any order
+    FullName,
+}
+
+/// Commitment to a name together with its opening
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NameCommitment {
+    /// Committed name parts
+    pub name_match: NameMatch,
+    /// Hex-encoded commitment
+    pub commitment: String,
+    /// Hex-encoded blinding factor opening the commitment
+    pub blinding: String,
+}
+
+/// Commitment to a certificate name, signed by the certificate signer when minting
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SignedNameCommitment {
+    /// Commitment to the certificate's profile name
+    pub commitment: NameCommitment,
+    /// Hex-encoded Ed25519 key of the certificate signer
+    pub signer: String,
+    /// Hex-encoded signature over the certificate hash and the commitment
+    pub signature: String,
+}
+
+/// Proof that two name commitments open to the same name
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NameBinding {
+    /// Matched name parts
+    pub name_match: NameMatch,
+    /// Hex-encoded commitment to the certificate name
+    pub certificate_commitment: String,
+    /// Hex-encoded Ed25519 key of the certificate signer
+    pub certificate_signer: String,
+    /// Hex-encoded signature of the certificate signer over the certificate commitment
+    pub certificate_signature: String,
+    /// Hex-encoded commitment to the passport name
+    pub passport_commitment: String,
+    /// Hex-encoded SHA-256 digest of the passport proof public inputs
+    pub passport_inputs: String,
+    /// Hex-encoded nonce commitment of the equality proof
+    pub nonce_commitment: String,
+    /// Hex-encoded response of the equality proof
+    pub response: String,
+}
+
+impl NameMatch {
+    /// Identifier used in public inputs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NameMatch::FirstName => "first_name",
+            NameMatch::FullName => "full_name",
+        }
+    }
+
+    /// Normalized name parts compared under this rule
+    pub fn match_key(&self, name: &str) -> Result<String, NameBindingError> {
+        let normalized = normalize_name(name);
+        let mut parts: Vec<&str> = normalized.split(' ').filter(|p| !p.is_empty()).collect();
+        if parts.is_empty() {
+            return Err(NameBindingError::EmptyName);
+        }
+
+        match self {
+            NameMatch::FirstName => Ok(parts[0].to_string()),
+            NameMatch::FullName => {
+                // Passports list the surname first, profiles usually last
+                parts.sort_unstable();
+                Ok(parts.join(" "))
+            }
+        }
+    }
+}
+
+impl fmt::Display for NameMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl NameCommitment {
+    /// Commit to a name with a random blinding factor
+    pub fn commit(name: &str, name_match: NameMatch) -> Result<Self, NameBindingError> {
+        Self::with_blinding(name, name_match, Scalar::random(&mut OsRng))
+    }
+
+    /// Commit to a passport name, deriving the blinding factor from the public inputs of the
+    /// passport proofs that disclosed it
+    ///
+    /// The public inputs include the request nonce, so the blinding stays unknown to anyone
+    /// who has not seen the passport proofs.
+    pub fn for_passport(
+        passport_name: &str,
+        name_match: NameMatch,
+        public_inputs: &PassportPublicInputs,
+    ) -> Result<Self, NameBindingError> {
+        let mut hasher = Sha512::new();
+        hasher.update(PASSPORT_BLINDING_DOMAIN);
+        hasher.update(name_match.as_str().as_bytes());
+        hasher.update(passport_inputs_bytes(public_inputs));
+        Self::with_blinding(passport_name, name_match, Scalar::from_hash(hasher))
+    }
+
+    /// Whether the commitment opens to `name`
+    pub fn opens(&self, name: &str) -> bool {
+        let (Ok(key), Ok(blinding)) = (self.name_match.match_key(name), self.blinding()) else {
+            return false;
+        };
+        hex::encode(commit(&name_scalar(&key), &blinding).as_bytes()) == self.commitment
+    }
+
+    fn with_blinding(
+        name: &str,
+        name_match: NameMatch,
+        blinding: Scalar,
+    ) -> Result<Self, NameBindingError> {
+        let key = name_match.match_key(name)?;
+        Ok(Self {
+            name_match,
+            commitment: hex::encode(commit(&name_scalar(&key), &blinding).as_bytes()),
+            blinding: hex::encode(blinding.as_bytes()),
+        })
+    }
+
+    fn blinding(&self) -> Result<Scalar, NameBindingError> {
+        decode_scalar(&self.blinding, "blinding")
+    }
+}
+
+impl SignedNameCommitment {
+    /// Sign a commitment to the profile name of the certificate with `certificate_hash`
+    pub fn sign(
+        commitment: NameCommitment,
+        certificate_hash: &str,
+        signing_key: &SigningKey,
+    ) -> Self {
+        let message = certificate_commitment_message(
+            certificate_hash,
+            commitment.name_match,
+            &commitment.commitment,
+        );
+        Self {
+            signer: hex::encode(signing_key.verifying_key().as_bytes()),
+            signature: hex::encode(signing_key.sign(&message).to_bytes()),
+            commitment,
+        }
+    }
+
+    /// Check the signature for the certificate with `certificate_hash`, returning the signer
+    pub fn verify(&self, certificate_hash: &str) -> Result<VerifyingKey, NameBindingError> {
+        verify_certificate_commitment(
+            certificate_hash,
+            self.commitment.name_match,
+            &self.commitment.commitment,
+            &self.signer,
+            &self.signature,
+        )
+    }
+}
+
+impl NameBinding {
+    /// Prove that the signed certificate commitment and the passport name match
+    ///
+    /// The passport commitment is derived with [`NameCommitment::for_passport`]. `context` is
+    /// the certificate hash the certificate commitment was signed for and is bound into the
+    /// proof, so the binding cannot be moved to another claim.
+    pub fn prove(
+        certificate_name: &str,
+        certificate: &SignedNameCommitment,
+        passport_name: &str,
+        passport_inputs: &PassportPublicInputs,
+        context: &[u8],
+    ) -> Result<Self, NameBindingError> {
+        let name_match = certificate.commitment.name_match;
+        if !certificate.commitment.opens(certificate_name) {
+            return Err(NameBindingError::InvalidCommitment(
+                "certificate commitment does not open to the certificate name".to_string(),
+            ));
+        }
+        if name_match.match_key(certificate_name)? != name_match.match_key(passport_name)? {
+            return Err(NameBindingError::Mismatch);
+        }
+        let passport = NameCommitment::for_passport(passport_name, name_match, passport_inputs)?;
+
+        let certificate_commitment = decode_point(&certificate.commitment.commitment)?.compress();
+        let passport_commitment = decode_point(&passport.commitment)?.compress();
+        let passport_inputs = passport_inputs_digest(passport_inputs);
+
+        // Schnorr proof of knowledge of the blinding difference, the discrete log of the
+        // commitment difference to the second generator
+        let nonce = Scalar::random(&mut OsRng);
+        let nonce_commitment = (nonce * generator()).compress();
+        let challenge = challenge(
+            name_match,
+            &certificate_commitment,
+            &passport_commitment,
+            &nonce_commitment,
+            &passport_inputs,
+            context,
+        );
+        let response =
+            nonce + challenge * (certificate.commitment.blinding()? - passport.blinding()?);
+
+        Ok(Self {
+            name_match,
+            certificate_commitment: certificate.commitment.commitment.clone(),
+            certificate_signer: certificate.signer.clone(),
+            certificate_signature: certificate.signature.clone(),
+            passport_commitment: passport.commitment,
+            passport_inputs,
+            nonce_commitment: hex::encode(nonce_commitment.as_bytes()),
+            response: hex::encode(response.as_bytes()),
+        })
+    }
+
+    /// Verify that both commitments open to the same name and that the certificate
+    /// commitment is signed for the certificate hash `context`
+    ///
+    /// Returns the certificate signer, which the caller has to trust.
+    pub fn verify(&self, context: &[u8]) -> Result<VerifyingKey, NameBindingError> {
+        let certificate_hash =
+            std::str::from_utf8(context).map_err(|_| NameBindingError::UntrustedCommitment)?;
+        let signer = verify_certificate_commitment(
+            certificate_hash,
+            self.name_match,
+            &self.certificate_commitment,
+            &self.certificate_signer,
+            &self.certificate_signature,
+        )?;
+
+        let certificate_commitment = decode_point(&self.certificate_commitment)?;
+        let passport_commitment = decode_point(&self.passport_commitment)?;
+        let nonce_commitment = decode_point(&self.nonce_commitment)?;
+        let response = decode_scalar(&self.response, "response")?;
+
+        let challenge = challenge(
+            self.name_match,
+            &certificate_commitment.compress(),
+            &passport_commitment.compress(),
+            &nonce_commitment.compress(),
+            &self.passport_inputs,
+            context,
+        );
+        let expected =
+            nonce_commitment + challenge * (certificate_commitment - passport_commitment);
+        if response * generator() != expected {
+            return Err(NameBindingError::InvalidProof);
+        }
+
+        Ok(signer)
+    }
+
+    /// Check that the passport commitment was derived from the passport proofs with
+    /// `public_inputs`, e.g. when auditing a claim against the ZKPassport bundle
+    pub fn check_passport_inputs(
+        &self,
+        passport_name: &str,
+        public_inputs: &PassportPublicInputs,
+    ) -> Result<(), NameBindingError> {
+        let passport = NameCommitment::for_passport(passport_name, self.name_match, public_inputs)?;
+        if self.passport_inputs != passport_inputs_digest(public_inputs)
+            || self.passport_commitment != passport.commitment
+        {
+            return Err(NameBindingError::PassportInputsMismatch);
+        }
+        Ok(())
+    }
+}
+
+/// Hex-encoded SHA-256 digest of passport proof public inputs
+pub fn passport_inputs_digest(public_inputs: &PassportPublicInputs) -> String {
+    hex::encode(Sha256::digest(passport_inputs_bytes(public_inputs)))
+}
+
+/// Normalize a name for comparison
+///
+/// Applies Unicode NFKC, transliterates umlauts and ligatures as ICAO 9303 does for the
+/// passport machine readable zone (`ü` becomes `ue`, `ß` becomes `ss`), strips the remaining
+/// diacritics, transliterates other scripts to ASCII and lowercases. Hyphens, apostrophes and
+/// the MRZ filler `<` separate name parts.
+pub fn normalize_name(name: &str) -> String {
+    let composed: String = name.nfkc().collect::<String>().to_lowercase();
+
+    let mut transliterated = String::with_capacity(composed.len());
+    for c in composed.chars() {
+        match icao_transliteration(c) {
+            Some(replacement) => transliterated.push_str(replacement),
+            None => transliterated.push(c),
+        }
+    }
+
+    let stripped: String = transliterated
+        .nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .collect();
+
+    deunicode::deunicode(&stripped)
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Transliterations the passport MRZ uses where stripping the diacritic would lose a letter
+fn icao_transliteration(c: char) -> Option<&'static str> {
+    match c {
+        'ä' | 'æ' => Some("ae"),
+        'ö' | 'ø' | 'œ' => Some("oe"),
+        'ü' => Some("ue"),
+        'å' => Some("aa"),
+        'ß' => Some("ss"),
+        'þ' => Some("th"),
+        'ĳ' => Some("ij"),
+        _ => None,
+    }
+}
+
+fn generator() -> RistrettoPoint {
+    RistrettoPoint::hash_from_bytes::<Sha512>(GENERATOR_DOMAIN)
+}
+
+fn name_scalar(key: &str) -> Scalar {
+    let mut hasher = Sha512::new();
+    hasher.update(NAME_DOMAIN);
+    hasher.update(key.as_bytes());
+    Scalar::from_hash(hasher)
+}
+
+fn commit(name: &Scalar, blinding: &Scalar) -> CompressedRistretto {
+    (name * RISTRETTO_BASEPOINT_POINT + blinding * generator()).compress()
+}
+
+fn challenge(
+    name_match: NameMatch,
+    certificate_commitment: &CompressedRistretto,
+    passport_commitment: &CompressedRistretto,
+    nonce_commitment: &CompressedRistretto,
+    passport_inputs: &str,
+    context: &[u8],
+) -> Scalar {
+    let mut hasher = Sha512::new();
+    hasher.update(CHALLENGE_DOMAIN);
+    hasher.update(name_match.as_str().as_bytes());
+    hasher.update(certificate_commitment.as_bytes());
+    hasher.update(passport_commitment.as_bytes());
+    hasher.update(nonce_commitment.as_bytes());
+    hasher.update(passport_inputs.as_bytes());
+    hasher.update(context);
+    Scalar::from_hash(hasher)
+}
+
+fn passport_inputs_bytes(public_inputs: &PassportPublicInputs) -> Vec<u8> {
+    serde_json::to_vec(public_inputs).expect("passport public inputs serialize to JSON")
+}
+
+fn certificate_commitment_message(
+    certificate_hash: &str,
+    name_match: NameMatch,
+    commitment: &str,
+) -> Vec<u8> {
+    [
+        CERTIFICATE_COMMITMENT_DOMAIN,
+        certificate_hash.as_bytes(),
+        name_match.as_str().as_bytes(),
+        commitment.as_bytes(),
+    ]
+    .join(&b'|')
+}
+
+fn verify_certificate_commitment(
+    certificate_hash: &str,
+    name_match: NameMatch,
+    commitment: &str,
+    signer: &str,
+    signature: &str,
+) -> Result<VerifyingKey, NameBindingError> {
+    let signer = decode_bytes(signer)
+        .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+        .ok_or(NameBindingError::UntrustedCommitment)?;
+    let signature = hex::decode(signature)
+        .ok()
+        .and_then(|bytes| Signature::from_slice(&bytes).ok())
+        .ok_or(NameBindingError::UntrustedCommitment)?;
+    let message = certificate_commitment_message(certificate_hash, name_match, commitment);
+    signer
+        .verify(&message, &signature)
+        .map_err(|_| NameBindingError::UntrustedCommitment)?;
+    Ok(signer)
+}
+
+fn decode_bytes(value: &str) -> Option<[u8; 32]> {
+    hex::decode(value)
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+}
+
+fn decode_scalar(value: &str, field: &str) -> Result<Scalar, NameBindingError> {
+    decode_bytes(value)
+        .and_then(|bytes| Option::<Scalar>::from(Scalar::from_canonical_bytes(bytes)))
+        .ok_or_else(|| NameBindingError::InvalidCommitment(field.to_string()))
+}
+
+fn decode_point(value: &str) -> Result<RistrettoPoint, NameBindingError> {
+    decode_bytes(value)
+        .and_then(|bytes| CompressedRistretto(bytes).decompress())
+        .ok_or_else(|| NameBindingError::InvalidCommitment(value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_CONTEXT: &str = "certificate-hash";
+
+    fn create_passport_inputs(nonce: &str) -> PassportPublicInputs {
+        PassportPublicInputs {
+            scope: "web5claims.identity".to_string(),
+            nonce: nonce.to_string(),
+            query_result: serde_json::json!({
+                "firstname": {"disclose": {"result": "JUERGEN"}}
+            }),
+            unique_identifier: "nullifier-1".to_string(),
+        }
+    }
+
+    fn create_signed_commitment(name: &str, name_match: NameMatch) -> SignedNameCommitment {
+        SignedNameCommitment::sign(
+            NameCommitment::commit(name, name_match).unwrap(),
+            TEST_CONTEXT,
+            &SigningKey::from_bytes(&[7u8; 32]),
+        )
+    }
+
+    fn prove(
+        certificate_name: &str,
+        passport_name: &str,
+        name_match: NameMatch,
+    ) -> Result<NameBinding, NameBindingError> {
+        NameBinding::prove(
+            certificate_name,
+            &create_signed_commitment(certificate_name, name_match),
+            passport_name,
+            &create_passport_inputs("nonce-1"),
+            TEST_CONTEXT.as_bytes(),
+        )
+    }
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name("  Jürgen  Groß "), "juergen gross");
+        assert_eq!(normalize_name("JUERGEN<<GROSS"), "juergen gross");
+        assert_eq!(normalize_name("José-María O'Neill"), "jose maria o neill");
+        // Decomposed and compatibility forms normalize like their composed counterparts
+        assert_eq!(normalize_name("Ju\u{0308}rgen"), normalize_name("Jürgen"));
+        assert_eq!(normalize_name("ＪＡＮＥ"), "jane");
+        assert_eq!(normalize_name("Søren Åberg"), "soeren aaberg");
+        assert_eq!(normalize_name("Дмитрий"), "dmitrii");
+    }
+
+    #[test]
+    fn test_match_keys() {
+        assert_eq!(
+            NameMatch::FirstName.match_key("Jane Marie Doe").unwrap(),
+            "jane"
+        );
+        assert_eq!(
+            NameMatch::FullName.match_key("DOE<<JANE").unwrap(),
+            NameMatch::FullName.match_key("Jane Doe").unwrap()
+        );
+        assert_eq!(
+            NameMatch::FirstName.match_key(" - "),
+            Err(NameBindingError::EmptyName)
+        );
+    }
+
+    #[test]
+    fn test_binding_verifies() {
+        let binding = prove("Jürgen Schmidt", "JUERGEN", NameMatch::FirstName).unwrap();
+
+        let signer = binding.verify(TEST_CONTEXT.as_bytes()).unwrap();
+        assert_eq!(signer, SigningKey::from_bytes(&[7u8; 32]).verifying_key());
+        assert_ne!(binding.certificate_commitment, binding.passport_commitment);
+        let json = serde_json::to_string(&binding).unwrap();
+        assert!(!json.to_lowercase().contains("juergen"));
+    }
+
+    #[test]
+    fn test_mismatch_rejected() {
+        assert_eq!(
+            prove("Jane Doe", "JOHN", NameMatch::FirstName),
+            Err(NameBindingError::Mismatch)
+        );
+        assert_eq!(
+            prove("Jane Doe", "Jane Roe", NameMatch::FullName),
+            Err(NameBindingError::Mismatch)
+        );
+    }
+
+    #[test]
+    fn test_binding_bound_to_context() {
+        let binding = prove("Jane Doe", "DOE<<JANE", NameMatch::FullName).unwrap();
+
+        assert_eq!(
+            binding.verify(b"other-certificate"),
+            Err(NameBindingError::UntrustedCommitment)
+        );
+        let mut relabelled = binding.clone();
+        relabelled.name_match = NameMatch::FirstName;
+        assert!(relabelled.verify(TEST_CONTEXT.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_certificate_commitment_must_be_signed() {
+        // A commitment the issuer made up instead of the one signed at minting
+        let mut binding = prove("Jane Doe", "JANE", NameMatch::FirstName).unwrap();
+        let made_up = NameCommitment::commit("Jane", NameMatch::FirstName).unwrap();
+        binding.certificate_commitment = made_up.commitment;
+        assert_eq!(
+            binding.verify(TEST_CONTEXT.as_bytes()),
+            Err(NameBindingError::UntrustedCommitment)
+        );
+
+        // The signed commitment has to open to the certificate name
+        assert!(matches!(
+            NameBinding::prove(
+                "John Doe",
+                &create_signed_commitment("Jane Doe", NameMatch::FirstName),
+                "JOHN",
+                &create_passport_inputs("nonce-1"),
+                TEST_CONTEXT.as_bytes(),
+            ),
+            Err(NameBindingError::InvalidCommitment(_))
+        ));
+    }
+
+    #[test]
+    fn test_passport_commitment_bound_to_passport_inputs() {
+        let inputs = create_passport_inputs("nonce-1");
+        let binding = prove("Jürgen Schmidt", "JUERGEN", NameMatch::FirstName).unwrap();
+
+        assert_eq!(binding.passport_inputs, passport_inputs_digest(&inputs));
+        assert!(binding.check_passport_inputs("JUERGEN", &inputs).is_ok());
+        assert_eq!(
+            binding.check_passport_inputs("JUERGEN", &create_passport_inputs("nonce-2")),
+            Err(NameBindingError::PassportInputsMismatch)
+        );
+
+        let mut moved = binding;
+        moved.passport_inputs = passport_inputs_digest(&create_passport_inputs("nonce-2"));
+        assert_eq!(
+            moved.verify(TEST_CONTEXT.as_bytes()),
+            Err(NameBindingError::InvalidProof)
+        );
+    }
+
+    #[test]
+    fn test_swapped_commitment_rejected() {
+        let jane = prove("Jane", "JANE", NameMatch::FirstName).unwrap();
+        let john = prove("John", "JOHN", NameMatch::FirstName).unwrap();
+
+        let mut forged = jane.clone();
+        forged.passport_commitment = john.passport_commitment;
+        assert_eq!(
+            forged.verify(TEST_CONTEXT.as_bytes()),
+            Err(NameBindingError::InvalidProof)
+        );
+
+        let mut malformed = jane;
+        malformed.response = "zz".to_string();
+        assert!(matches!(
+            malformed.verify(TEST_CONTEXT.as_bytes()),
+            Err(NameBindingError::InvalidCommitment(_))
+        ));
+    }
+}
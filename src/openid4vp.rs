@@ -0,0 +1,1128 @@
+//! OpenID for Verifiable Presentations.
+//!
+//! The verifier sends an [`AuthorizationRequest`] carrying a DCQL query or a Presentation
+//! Exchange definition, the holder answers with an [`AuthorizationResponse`] whose `vp_token`
+//! holds one presentation per requested credential, and the verifier checks the response
+//! against the request it issued.
+//!
+//! Presentations use one of two formats: [`FORMAT_SD_JWT`] for SD-JWT VCs with key binding,
+//! and [`FORMAT_ZK_PROOF`] for holder-signed [`Presentation`]s of proof claims. Both are bound
+//! to the verifier's `client_id` and the request nonce.
+
+use crate::holder::{Holder, HolderError};
+use crate::presentation::Presentation;
+use crate::presentation_exchange::{
+    constraints_claim_type, AcceptedValues, DescriptorMapEntry, PresentationDefinition,
+    PresentationExchangeError, PresentationSubmission,
+};
+use crate::sd_jwt::{SdJwt, SdJwtError, SdJwtVerifier};
+use crate::verifier::{VerificationResult, VerifierError, ZkProofVerifier};
+use crate::zk_proof::ClaimType;
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use thiserror::Error;
+use url::{form_urlencoded, Url};
+
+/// Response type of presentation requests
+pub const RESPONSE_TYPE: &str = "vp_token";
+/// Credential format of SD-JWT VCs
+pub const FORMAT_SD_JWT: &str = "dc+sd-jwt";
+/// Credential format of holder-signed proof claim presentations
+pub const FORMAT_ZK_PROOF: &str = "web5claims_zkp";
+/// Authorization endpoint of wallets without a registered endpoint
+pub const DEFAULT_AUTHORIZATION_ENDPOINT: &str = "openid4vp://";
+
+#[derive(Error, Debug)]
+pub enum OpenId4VpError {
+    #[error("Invalid authorization request: {0}")]
+    InvalidRequest(String),
+    #[error("Unsupported query: {0}")]
+    UnsupportedQuery(String),
+    #[error("Unsupported credential format: {0}")]
+    UnsupportedFormat(String),
+    #[error("No credential matches query {0}")]
+    NoMatchingCredential(String),
+    #[error("Invalid authorization response: {0}")]
+    InvalidResponse(String),
+    #[error("Response does not answer a pending request")]
+    UnknownState,
+    #[error("Response has no presentation for query {0}")]
+    MissingPresentation(String),
+    #[error(transparent)]
+    PresentationExchange(#[from] PresentationExchangeError),
+    #[error(transparent)]
+    SdJwt(#[from] SdJwtError),
+    #[error(transparent)]
+    Verifier(#[from] VerifierError),
+    #[error(transparent)]
+    Holder(#[from] HolderError),
+}
+
+/// How the holder returns the response
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResponseMode {
+    /// Form POST to the verifier's `response_uri`
+    #[default]
+    DirectPost,
+    /// Redirect to the verifier's `redirect_uri` with the response in the URL fragment
+    Fragment,
+}
+
+/// Presentation request of a verifier
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthorizationRequest {
+    /// Verifier identifier, the audience of the presentations
+    pub client_id: String,
+    /// Requested response type, always `vp_token`
+    pub response_type: String,
+    /// How the holder returns the response
+    pub response_mode: ResponseMode,
+    /// POST endpoint (`direct_post`) or redirect URI (`fragment`) for the response
+    pub response_uri: String,
+    /// Challenge the presentations must be bound to
+    pub nonce: String,
+    /// Value the response echoes to identify the request
+    pub state: String,
+    /// What the verifier asks for
+    pub query: PresentationQuery,
+}
+
+/// Query language of a request
+#[derive(Debug, Clone, PartialEq)]
+pub enum PresentationQuery {
+    /// Digital Credentials Query Language
+    Dcql(DcqlQuery),
+    /// DIF Presentation Exchange
+    PresentationExchange(PresentationDefinition),
+}
+
+/// DCQL query
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DcqlQuery {
+    /// Requested credentials
+    pub credentials: Vec<CredentialQuery>,
+}
+
+/// DCQL credential query
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CredentialQuery {
+    /// Query identifier, the key of the presentation in the `vp_token`
+    pub id: String,
+    /// Requested credential format
+    pub format: String,
+    /// Format specific constraints, e.g. `vct_values`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Value>,
+    /// Requested claims
+    #[serde(default)]
+    pub claims: Vec<ClaimsQuery>,
+}
+
+/// DCQL claims query
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClaimsQuery {
+    /// Claims query identifier
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Path of the claim in the credential
+    pub path: Vec<Value>,
+    /// Accepted claim values
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub values: Option<Vec<Value>>,
+}
+
+/// Credential requested by a query, in terms of web5claims claims
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestedCredential {
+    /// Query or input descriptor identifier
+    pub id: String,
+    /// Accepted formats, in order of preference
+    pub formats: Vec<String>,
+    /// Claim the credential must prove
+    pub claim_type: ClaimType,
+    /// Attributes to disclose
+    pub attributes: Vec<String>,
+    /// Accepted SD-JWT credential types; empty accepts any type
+    pub vct_values: Vec<String>,
+}
+
+impl RequestedCredential {
+    /// Whether an SD-JWT of credential type `vct` answers the request
+    pub fn accepts_vct(&self, vct: &str) -> bool {
+        self.vct_values.is_empty() || self.vct_values.iter().any(|value| value == vct)
+    }
+}
+
+/// Response of the holder
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthorizationResponse {
+    /// Presentations; an object keyed by query id for DCQL, an array for Presentation Exchange
+    pub vp_token: Value,
+    /// Mapping of presentations to input descriptors (Presentation Exchange only)
+    pub presentation_submission: Option<PresentationSubmission>,
+    /// State of the request
+    pub state: String,
+}
+
+/// Verifier side of OpenID4VP
+#[derive(Debug, Clone)]
+pub struct OpenId4VpVerifier {
+    /// Verifier identifier
+    pub client_id: String,
+    /// Endpoint responses are returned to
+    pub response_uri: String,
+    /// How holders return responses
+    pub response_mode: ResponseMode,
+    /// Verifier for SD-JWT presentations
+    pub sd_jwt_verifier: SdJwtVerifier,
+    /// Verifier for proof claim presentations
+    pub proof_verifier: ZkProofVerifier,
+    /// Requests waiting for a response, by state
+    pending: HashMap<String, AuthorizationRequest>,
+}
+
+impl ResponseMode {
+    /// Parameter value of the mode
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResponseMode::DirectPost => "direct_post",
+            ResponseMode::Fragment => "fragment",
+        }
+    }
+
+    fn response_uri_parameter(&self) -> &'static str {
+        match self {
+            ResponseMode::DirectPost => "response_uri",
+            ResponseMode::Fragment => "redirect_uri",
+        }
+    }
+}
+
+impl std::str::FromStr for ResponseMode {
+    type Err = OpenId4VpError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "direct_post" => Ok(ResponseMode::DirectPost),
+            "fragment" => Ok(ResponseMode::Fragment),
+            other => Err(OpenId4VpError::InvalidRequest(format!(
+                "unsupported response_mode {}",
+                other
+            ))),
+        }
+    }
+}
+
+impl AuthorizationRequest {
+    /// Encode the request as a URL on the wallet's authorization endpoint
+    pub fn to_url(&self, authorization_endpoint: &str) -> String {
+        let mut parameters = form_urlencoded::Serializer::new(String::new());
+        parameters
+            .append_pair("client_id", &self.client_id)
+            .append_pair("response_type", &self.response_type)
+            .append_pair("response_mode", self.response_mode.as_str())
+            .append_pair(
+                self.response_mode.response_uri_parameter(),
+                &self.response_uri,
+            )
+            .append_pair("nonce", &self.nonce)
+            .append_pair("state", &self.state);
+
+        match &self.query {
+            PresentationQuery::Dcql(query) => parameters.append_pair(
+                "dcql_query",
+                &serde_json::to_string(query).expect("query serializes to JSON"),
+            ),
+            PresentationQuery::PresentationExchange(definition) => parameters.append_pair(
+                "presentation_definition",
+                &serde_json::to_string(definition).expect("definition serializes to JSON"),
+            ),
+        };
+
+        format!("{}?{}", authorization_endpoint, parameters.finish())
+    }
+
+    /// Parse and validate a request URL
+    pub fn from_url(request_url: &str) -> Result<Self, OpenId4VpError> {
+        let url =
+            Url::parse(request_url).map_err(|e| OpenId4VpError::InvalidRequest(e.to_string()))?;
+        let parameters: HashMap<String, String> = url.query_pairs().into_owned().collect();
+        let parameter = |name: &str| {
+            parameters
+                .get(name)
+                .filter(|value| !value.is_empty())
+                .cloned()
+                .ok_or_else(|| OpenId4VpError::InvalidRequest(format!("missing {}", name)))
+        };
+
+        let response_type = parameter("response_type")?;
+        if response_type != RESPONSE_TYPE {
+            return Err(OpenId4VpError::InvalidRequest(format!(
+                "unsupported response_type {}",
+                response_type
+            )));
+        }
+
+        let response_mode: ResponseMode = parameter("response_mode")?.parse()?;
+        let query = match (
+            parameters.get("dcql_query"),
+            parameters.get("presentation_definition"),
+        ) {
+            (Some(query), None) => PresentationQuery::Dcql(
+                serde_json::from_str(query)
+                    .map_err(|e| OpenId4VpError::InvalidRequest(e.to_string()))?,
+            ),
+            (None, Some(definition)) => PresentationQuery::PresentationExchange(
+                serde_json::from_str(definition)
+                    .map_err(|e| OpenId4VpError::InvalidRequest(e.to_string()))?,
+            ),
+            _ => {
+                return Err(OpenId4VpError::InvalidRequest(
+                    "expected exactly one of dcql_query and presentation_definition".to_string(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            client_id: parameter("client_id")?,
+            response_type,
+            response_mode,
+            response_uri: parameter(response_mode.response_uri_parameter())?,
+            nonce: parameter("nonce")?,
+            state: parameter("state")?,
+            query,
+        })
+    }
+}
+
+impl PresentationQuery {
+    /// Credentials the query asks for
+    pub fn requested_credentials(&self) -> Result<Vec<RequestedCredential>, OpenId4VpError> {
+        match self {
+            PresentationQuery::Dcql(query) => query
+                .credentials
+                .iter()
+                .map(|credential| {
+                    let constraints = credential
+                        .claims
+                        .iter()
+                        .map(|claim| {
+                            let attribute =
+                                claim.path.iter().rev().find_map(Value::as_str).ok_or_else(
+                                    || {
+                                        OpenId4VpError::UnsupportedQuery(format!(
+                                            "claim path of {}",
+                                            credential.id
+                                        ))
+                                    },
+                                )?;
+                            let values = match &claim.values {
+                                Some(values) => AcceptedValues::OneOf(values.clone()),
+                                None => AcceptedValues::Any,
+                            };
+                            Ok((attribute.to_string(), values))
+                        })
+                        .collect::<Result<Vec<_>, OpenId4VpError>>()?;
+                    let vct_values = match credential
+                        .meta
+                        .as_ref()
+                        .and_then(|meta| meta.get("vct_values"))
+                    {
+                        Some(values) => values
+                            .as_array()
+                            .and_then(|values| {
+                                values
+                                    .iter()
+                                    .map(|value| value.as_str().map(str::to_string))
+                                    .collect::<Option<Vec<_>>>()
+                            })
+                            .ok_or_else(|| {
+                                OpenId4VpError::UnsupportedQuery(format!(
+                                    "vct_values of {}",
+                                    credential.id
+                                ))
+                            })?,
+                        None => Vec::new(),
+                    };
+
+                    Ok(RequestedCredential {
+                        id: credential.id.clone(),
+                        formats: vec![credential.format.clone()],
+                        claim_type: constraints_claim_type(&credential.id, &constraints)?,
+                        attributes: constraints
+                            .into_iter()
+                            .map(|(attribute, _)| attribute)
+                            .collect(),
+                        vct_values,
+                    })
+                })
+                .collect(),
+            PresentationQuery::PresentationExchange(definition) => definition
+                .input_descriptors
+                .iter()
+                .map(|descriptor| {
+                    let formats = [FORMAT_SD_JWT, FORMAT_ZK_PROOF]
+                        .into_iter()
+                        .filter(|format| descriptor.accepts_format(format))
+                        .map(str::to_string)
+                        .collect();
+
+                    Ok(RequestedCredential {
+                        id: descriptor.id.clone(),
+                        formats,
+                        claim_type: descriptor.claim_type()?,
+                        attributes: descriptor.attributes()?,
+                        vct_values: Vec::new(),
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+impl AuthorizationResponse {
+    /// Encode the response as an `application/x-www-form-urlencoded` POST body
+    pub fn to_form_body(&self) -> String {
+        let mut body = form_urlencoded::Serializer::new(String::new());
+        body.append_pair("vp_token", &self.vp_token.to_string());
+        if let Some(submission) = &self.presentation_submission {
+            body.append_pair(
+                "presentation_submission",
+                &serde_json::to_string(submission).expect("submission serializes to JSON"),
+            );
+        }
+        body.append_pair("state", &self.state);
+        body.finish()
+    }
+
+    /// Parse a form POST body
+    pub fn from_form_body(body: &str) -> Result<Self, OpenId4VpError> {
+        let parameters: HashMap<String, String> = form_urlencoded::parse(body.as_bytes())
+            .into_owned()
+            .collect();
+
+        let vp_token = parameters
+            .get("vp_token")
+            .ok_or_else(|| OpenId4VpError::InvalidResponse("missing vp_token".to_string()))?;
+        let presentation_submission = parameters
+            .get("presentation_submission")
+            .map(|submission| serde_json::from_str(submission))
+            .transpose()
+            .map_err(|e| OpenId4VpError::InvalidResponse(e.to_string()))?;
+
+        Ok(Self {
+            vp_token: serde_json::from_str(vp_token)
+                .map_err(|e| OpenId4VpError::InvalidResponse(e.to_string()))?,
+            presentation_submission,
+            state: parameters
+                .get("state")
+                .cloned()
+                .ok_or_else(|| OpenId4VpError::InvalidResponse("missing state".to_string()))?,
+        })
+    }
+
+    /// Encode the response in the fragment of the verifier's redirect URI
+    pub fn to_redirect_url(&self, redirect_uri: &str) -> String {
+        format!("{}#{}", redirect_uri, self.to_form_body())
+    }
+
+    /// Parse a redirect URL carrying the response in its fragment
+    pub fn from_redirect_url(redirect_url: &str) -> Result<Self, OpenId4VpError> {
+        let (_, fragment) = redirect_url
+            .split_once('#')
+            .ok_or_else(|| OpenId4VpError::InvalidResponse("missing fragment".to_string()))?;
+        Self::from_form_body(fragment)
+    }
+}
+
+impl Holder {
+    /// Build the response to a presentation request from the holder's credentials
+    ///
+    /// Each requested credential is answered with the first accepted format the holder has a
+    /// matching credential for.
+    pub fn respond(
+        &self,
+        request: &AuthorizationRequest,
+    ) -> Result<AuthorizationResponse, OpenId4VpError> {
+        let mut presentations = Vec::new();
+
+        for requested in request.query.requested_credentials()? {
+            let presentation = requested
+                .formats
+                .iter()
+                .find_map(|format| {
+                    self.present_credential(&requested, format, request)
+                        .transpose()
+                        .map(|presentation| presentation.map(|p| (format.clone(), p)))
+                })
+                .transpose()?
+                .ok_or_else(|| OpenId4VpError::NoMatchingCredential(requested.id.clone()))?;
+            presentations.push((requested.id, presentation));
+        }
+
+        let (vp_token, presentation_submission) = match &request.query {
+            PresentationQuery::Dcql(_) => {
+                let vp_token = presentations
+                    .into_iter()
+                    .map(|(id, (_, presentation))| (id, Value::Array(vec![presentation])))
+                    .collect::<serde_json::Map<_, _>>();
+                (Value::Object(vp_token), None)
+            }
+            PresentationQuery::PresentationExchange(definition) => {
+                let descriptor_map = presentations
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (id, (format, _)))| DescriptorMapEntry {
+                        id: id.clone(),
+                        format: format.clone(),
+                        path: format!("$[{}]", i),
+                    })
+                    .collect();
+                let vp_token = presentations
+                    .into_iter()
+                    .map(|(_, (_, presentation))| presentation)
+                    .collect();
+                let submission = PresentationSubmission {
+                    id: random_token(),
+                    definition_id: definition.id.clone(),
+                    descriptor_map,
+                };
+                (Value::Array(vp_token), Some(submission))
+            }
+        };
+
+        Ok(AuthorizationResponse {
+            vp_token,
+            presentation_submission,
+            state: request.state.clone(),
+        })
+    }
+
+    /// Present a credential in the given format, if the holder has one that proves the claim
+    fn present_credential(
+        &self,
+        requested: &RequestedCredential,
+        format: &str,
+        request: &AuthorizationRequest,
+    ) -> Result<Option<Value>, OpenId4VpError> {
+        match format {
+            FORMAT_SD_JWT => {
+                let attributes: Vec<&str> =
+                    requested.attributes.iter().map(String::as_str).collect();
+                Ok(self
+                    .sd_jwts()
+                    .into_iter()
+                    .find(|sd_jwt| {
+                        sd_jwt.vct().is_ok_and(|vct| requested.accepts_vct(&vct))
+                            && sd_jwt
+                                .disclosed_certificate()
+                                .and_then(|certificate| {
+                                    certificate.satisfies(&requested.claim_type)
+                                })
+                                .unwrap_or(false)
+                    })
+                    .map(|sd_jwt| {
+                        let presentation = self.present_sd_jwt(
+                            &sd_jwt,
+                            &attributes,
+                            &request.client_id,
+                            &request.nonce,
+                        );
+                        Value::String(presentation.serialize())
+                    }))
+            }
+            FORMAT_ZK_PROOF => {
+                let holder_key = self.public_key_hex();
+                let Some(proof) = self.proofs().iter().find(|proof| {
+                    proof.claim_type.implies(&requested.claim_type)
+                        && proof.public_inputs.verification_result
+                        && proof.holder_key() == Some(holder_key.as_str())
+                }) else {
+                    return Ok(None);
+                };
+
+                let presentation = self.present(
+                    &[proof.proof_id.as_str()],
+                    &request.client_id,
+                    &request.nonce,
+                )?;
+                serde_json::to_value(presentation)
+                    .map(Some)
+                    .map_err(|e| OpenId4VpError::InvalidResponse(e.to_string()))
+            }
+            other => Err(OpenId4VpError::UnsupportedFormat(other.to_string())),
+        }
+    }
+}
+
+impl OpenId4VpVerifier {
+    /// Create a verifier receiving responses at `response_uri`
+    pub fn new(
+        client_id: String,
+        response_uri: String,
+        sd_jwt_verifier: SdJwtVerifier,
+        proof_verifier: ZkProofVerifier,
+    ) -> Self {
+        Self {
+            client_id,
+            response_uri,
+            response_mode: ResponseMode::default(),
+            sd_jwt_verifier,
+            proof_verifier,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Set how holders return responses
+    pub fn with_response_mode(mut self, response_mode: ResponseMode) -> Self {
+        self.response_mode = response_mode;
+        self
+    }
+
+    /// Create a request with a fresh nonce and remember it until the response arrives
+    pub fn create_request(&mut self, query: PresentationQuery) -> AuthorizationRequest {
+        let request = AuthorizationRequest {
+            client_id: self.client_id.clone(),
+            response_type: RESPONSE_TYPE.to_string(),
+            response_mode: self.response_mode,
+            response_uri: self.response_uri.clone(),
+            nonce: random_token(),
+            state: random_token(),
+            query,
+        };
+
+        self.pending.insert(request.state.clone(), request.clone());
+        request
+    }
+
+    /// Verify a response against its pending request
+    ///
+    /// Returns the verification result for every requested credential, keyed by query or input
+    /// descriptor id. The request is consumed, so a response cannot be replayed.
+    pub fn verify_response(
+        &mut self,
+        response: &AuthorizationResponse,
+    ) -> Result<HashMap<String, VerificationResult>, OpenId4VpError> {
+        let request = self
+            .pending
+            .remove(&response.state)
+            .ok_or(OpenId4VpError::UnknownState)?;
+
+        request
+            .query
+            .requested_credentials()?
+            .into_iter()
+            .map(|requested| {
+                let (format, presentation) = locate_presentation(&request, response, &requested)?;
+                let result =
+                    self.verify_presentation(&request, &requested, &format, presentation)?;
+                Ok((requested.id, result))
+            })
+            .collect()
+    }
+
+    fn verify_presentation(
+        &self,
+        request: &AuthorizationRequest,
+        requested: &RequestedCredential,
+        format: &str,
+        presentation: &Value,
+    ) -> Result<VerificationResult, OpenId4VpError> {
+        if !requested.formats.iter().any(|f| f == format) {
+            return Err(OpenId4VpError::UnsupportedFormat(format.to_string()));
+        }
+
+        match format {
+            FORMAT_SD_JWT => {
+                let sd_jwt = presentation.as_str().ok_or_else(|| {
+                    OpenId4VpError::InvalidResponse(format!("{} is not a string", requested.id))
+                })?;
+                let result = self.sd_jwt_verifier.verify(
+                    sd_jwt,
+                    &requested.claim_type,
+                    &request.client_id,
+                    &request.nonce,
+                )?;
+                let vct = SdJwt::parse(sd_jwt)?.vct()?;
+                if !requested.accepts_vct(&vct) {
+                    return Err(OpenId4VpError::InvalidResponse(format!(
+                        "{} presents credential type {}",
+                        requested.id, vct
+                    )));
+                }
+                Ok(result)
+            }
+            FORMAT_ZK_PROOF => {
+                let presentation: Presentation = serde_json::from_value(presentation.clone())
+                    .map_err(|e| OpenId4VpError::InvalidResponse(e.to_string()))?;
+                if presentation.claims.len() != 1
+                    || !presentation.claims[0]
+                        .claim_type
+                        .implies(&requested.claim_type)
+                {
+                    return Err(OpenId4VpError::InvalidResponse(format!(
+                        "presentation for {} does not prove the requested claim",
+                        requested.id
+                    )));
+                }
+
+                let mut result = self.proof_verifier.verify_presentation(
+                    &presentation,
+                    &request.client_id,
+                    &request.nonce,
+                )?;
+                Ok(result.results.remove(0))
+            }
+            other => Err(OpenId4VpError::UnsupportedFormat(other.to_string())),
+        }
+    }
+}
+
+/// Find the presentation answering a requested credential and its format
+fn locate_presentation<'a>(
+    request: &AuthorizationRequest,
+    response: &'a AuthorizationResponse,
+    requested: &RequestedCredential,
+) -> Result<(String, &'a Value), OpenId4VpError> {
+    let missing = || OpenId4VpError::MissingPresentation(requested.id.clone());
+
+    match &request.query {
+        PresentationQuery::Dcql(_) => {
+            let presentation = response.vp_token[&requested.id]
+                .as_array()
+                .and_then(|presentations| presentations.first())
+                .ok_or_else(missing)?;
+            Ok((requested.formats[0].clone(), presentation))
+        }
+        PresentationQuery::PresentationExchange(definition) => {
+            let submission = response.presentation_submission.as_ref().ok_or_else(|| {
+                OpenId4VpError::InvalidResponse("missing presentation_submission".to_string())
+            })?;
+            if submission.definition_id != definition.id {
+                return Err(OpenId4VpError::InvalidResponse(format!(
+                    "submission answers definition {}",
+                    submission.definition_id
+                )));
+            }
+
+            let entry = submission
+                .descriptor_map
+                .iter()
+                .find(|entry| entry.id == requested.id)
+                .ok_or_else(missing)?;
+            let presentation = match entry.path.as_str() {
+                "$" => Some(&response.vp_token),
+                path => path
+                    .strip_prefix("$[")
+                    .and_then(|index| index.strip_suffix(']'))
+                    .and_then(|index| index.parse::<usize>().ok())
+                    .and_then(|index| response.vp_token.get(index)),
+            }
+            .ok_or_else(missing)?;
+            Ok((entry.format.clone(), presentation))
+        }
+    }
+}
+
+fn random_token() -> String {
+    let mut bytes = [0u8; 16];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issuer::{CertificateIssuer, ProofOptions, ProofRequest};
+    use crate::sd_jwt::SdJwtIssuer;
+    use crate::zk_proof::{CefrLevel, ZkProofClaim};
+    use chrono::Utc;
+    use ed25519_dalek::SigningKey;
+    use konnektoren_core::certificates::CertificateData;
+    use serde_json::json;
+
+    const CLIENT_ID: &str = "https://verifier.example";
+    const RESPONSE_URI: &str = "https://verifier.example/response";
+
+    /// In-process stand-in for the verifier's HTTP endpoints
+    struct LocalVerifierEndpoints {
+        verifier: OpenId4VpVerifier,
+    }
+
+    impl LocalVerifierEndpoints {
+        /// Redirect that sends the wallet to its authorization endpoint with the request
+        fn redirect_to_wallet(&mut self, query: PresentationQuery) -> String {
+            self.verifier
+                .create_request(query)
+                .to_url(DEFAULT_AUTHORIZATION_ENDPOINT)
+        }
+
+        /// `POST response_uri` with a form body
+        fn post_response(
+            &mut self,
+            body: &str,
+        ) -> Result<HashMap<String, VerificationResult>, OpenId4VpError> {
+            self.verifier
+                .verify_response(&AuthorizationResponse::from_form_body(body)?)
+        }
+
+        /// Wallet redirect back to `redirect_uri` with the response in the fragment
+        fn receive_redirect(
+            &mut self,
+            url: &str,
+        ) -> Result<HashMap<String, VerificationResult>, OpenId4VpError> {
+            self.verifier
+                .verify_response(&AuthorizationResponse::from_redirect_url(url)?)
+        }
+    }
+
+    fn create_sd_jwt_issuer() -> SdJwtIssuer {
+        SdJwtIssuer::new(
+            "test_issuer".to_string(),
+            "Test Issuer".to_string(),
+            SigningKey::from_bytes(&[8u8; 32]),
+        )
+    }
+
+    fn create_test_certificate() -> CertificateData {
+        CertificateData::new(
+            "German_B2_Complete".to_string(),
+            7,
+            7,
+            "Test Student".to_string(),
+            Utc::now(),
+        )
+    }
+
+    fn create_test_holder() -> Holder {
+        let mut holder = Holder::generate();
+        let sd_jwt = create_sd_jwt_issuer()
+            .issue(&create_test_certificate(), &holder.public_key())
+            .unwrap();
+        holder.add_sd_jwt(&sd_jwt);
+        holder
+    }
+
+    fn create_endpoints(response_mode: ResponseMode) -> LocalVerifierEndpoints {
+        let issuer = create_sd_jwt_issuer();
+        let mut sd_jwt_verifier = SdJwtVerifier::new(CLIENT_ID.to_string());
+        sd_jwt_verifier.add_trusted_issuer(issuer.issuer_id.clone(), issuer.public_key());
+
+        LocalVerifierEndpoints {
+            verifier: OpenId4VpVerifier::new(
+                CLIENT_ID.to_string(),
+                RESPONSE_URI.to_string(),
+                sd_jwt_verifier,
+                ZkProofVerifier::new(CLIENT_ID.to_string()),
+            )
+            .with_response_mode(response_mode),
+        }
+    }
+
+    /// "German at B2 or above" as a DCQL query
+    fn german_b2_dcql() -> PresentationQuery {
+        PresentationQuery::Dcql(
+            serde_json::from_value(json!({
+                "credentials": [{
+                    "id": "german",
+                    "format": "dc+sd-jwt",
+                    "meta": { "vct_values": [crate::sd_jwt::LANGUAGE_CERTIFICATE_VCT] },
+                    "claims": [
+                        { "path": ["language"], "values": ["German"] },
+                        { "path": ["level"], "values": ["B2", "C1", "C2"] }
+                    ]
+                }]
+            }))
+            .unwrap(),
+        )
+    }
+
+    /// "German at B2 or above" as a Presentation Exchange definition
+    fn german_b2_definition(format: &str) -> PresentationQuery {
+        PresentationQuery::PresentationExchange(
+            serde_json::from_value(json!({
+                "id": "german_b2",
+                "input_descriptors": [{
+                    "id": "german",
+                    "format": { format: {} },
+                    "constraints": {
+                        "fields": [
+                            { "path": ["$.language"], "filter": { "type": "string", "const": "German" } },
+                            { "path": ["$.level"], "filter": { "type": "string", "enum": ["B2", "C1", "C2"] } }
+                        ]
+                    }
+                }]
+            }))
+            .unwrap(),
+        )
+    }
+
+    fn german_b2_claim() -> ClaimType {
+        ClaimType::LanguageProficiency {
+            language: "German".to_string(),
+            min_level: CefrLevel::B2,
+        }
+    }
+
+    /// Proof of a stronger claim than "German at B2 or above", bound to `holder_key`
+    fn create_bound_proof(holder_key: String) -> ZkProofClaim {
+        CertificateIssuer::new("test_issuer".to_string(), "Test Issuer".to_string())
+            .accept_unsigned_certificates()
+            .generate_proof(ProofRequest {
+                certificate: create_test_certificate(),
+                claim_type: ClaimType::Combined {
+                    criteria: vec![
+                        german_b2_claim(),
+                        ClaimType::PerformanceThreshold { min_percentage: 80 },
+                    ],
+                },
+                target_platform: "test".to_string(),
+                options: ProofOptions {
+                    holder_key: Some(holder_key),
+                    ..ProofOptions::default()
+                },
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_request_url_round_trip() {
+        let mut endpoints = create_endpoints(ResponseMode::DirectPost);
+        let request = endpoints.verifier.create_request(german_b2_dcql());
+
+        let url = request.to_url(DEFAULT_AUTHORIZATION_ENDPOINT);
+        assert!(url.starts_with("openid4vp://?client_id="));
+        assert_eq!(AuthorizationRequest::from_url(&url).unwrap(), request);
+    }
+
+    #[test]
+    fn test_invalid_request_rejected() {
+        let mut endpoints = create_endpoints(ResponseMode::DirectPost);
+        let url = endpoints.redirect_to_wallet(german_b2_dcql());
+
+        let without_nonce = url.replace("nonce=", "unused=");
+        assert!(matches!(
+            AuthorizationRequest::from_url(&without_nonce),
+            Err(OpenId4VpError::InvalidRequest(_))
+        ));
+
+        let wrong_type = url.replace("response_type=vp_token", "response_type=code");
+        assert!(matches!(
+            AuthorizationRequest::from_url(&wrong_type),
+            Err(OpenId4VpError::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_queries_map_to_claim() {
+        for query in [german_b2_dcql(), german_b2_definition(FORMAT_SD_JWT)] {
+            let requested = query.requested_credentials().unwrap();
+            assert_eq!(requested.len(), 1);
+            assert_eq!(requested[0].claim_type, german_b2_claim());
+            assert_eq!(requested[0].attributes, vec!["language", "level"]);
+        }
+    }
+
+    #[test]
+    fn test_dcql_flow_with_direct_post() {
+        let mut endpoints = create_endpoints(ResponseMode::DirectPost);
+        let holder = create_test_holder();
+
+        let request =
+            AuthorizationRequest::from_url(&endpoints.redirect_to_wallet(german_b2_dcql()))
+                .unwrap();
+        let response = holder.respond(&request).unwrap();
+
+        let sd_jwt =
+            crate::sd_jwt::SdJwt::parse(response.vp_token["german"][0].as_str().unwrap()).unwrap();
+        assert_eq!(sd_jwt.disclosures.len(), 2);
+
+        let results = endpoints.post_response(&response.to_form_body()).unwrap();
+        assert!(results["german"].is_valid);
+        assert!(results["german"].requirements_met);
+    }
+
+    #[test]
+    fn test_presentation_exchange_flow_with_proof() {
+        let mut endpoints = create_endpoints(ResponseMode::DirectPost);
+        let mut holder = Holder::generate();
+        holder
+            .store_proof(create_bound_proof(holder.public_key_hex()))
+            .unwrap();
+
+        let url = endpoints.redirect_to_wallet(german_b2_definition(FORMAT_ZK_PROOF));
+        let response = holder
+            .respond(&AuthorizationRequest::from_url(&url).unwrap())
+            .unwrap();
+
+        let submission = response.presentation_submission.as_ref().unwrap();
+        assert_eq!(submission.definition_id, "german_b2");
+        assert_eq!(submission.descriptor_map[0].format, FORMAT_ZK_PROOF);
+        assert_eq!(submission.descriptor_map[0].path, "$[0]");
+
+        let results = endpoints.post_response(&response.to_form_body()).unwrap();
+        assert!(results["german"].requirements_met);
+    }
+
+    #[test]
+    fn test_fragment_flow() {
+        let mut endpoints = create_endpoints(ResponseMode::Fragment);
+        let holder = create_test_holder();
+
+        let request = AuthorizationRequest::from_url(
+            &endpoints.redirect_to_wallet(german_b2_definition(FORMAT_SD_JWT)),
+        )
+        .unwrap();
+        assert_eq!(request.response_mode, ResponseMode::Fragment);
+
+        let redirect = holder
+            .respond(&request)
+            .unwrap()
+            .to_redirect_url(&request.response_uri);
+        let results = endpoints.receive_redirect(&redirect).unwrap();
+        assert!(results["german"].requirements_met);
+    }
+
+    #[test]
+    fn test_replayed_response_rejected() {
+        let mut endpoints = create_endpoints(ResponseMode::DirectPost);
+        let holder = create_test_holder();
+        let request =
+            AuthorizationRequest::from_url(&endpoints.redirect_to_wallet(german_b2_dcql()))
+                .unwrap();
+        let body = holder.respond(&request).unwrap().to_form_body();
+
+        assert!(endpoints.post_response(&body).is_ok());
+        assert!(matches!(
+            endpoints.post_response(&body),
+            Err(OpenId4VpError::UnknownState)
+        ));
+    }
+
+    #[test]
+    fn test_response_bound_to_request_nonce() {
+        let mut endpoints = create_endpoints(ResponseMode::DirectPost);
+        let holder = create_test_holder();
+        let first = endpoints.verifier.create_request(german_b2_dcql());
+        let second = endpoints.verifier.create_request(german_b2_dcql());
+
+        let mut response = holder.respond(&first).unwrap();
+        response.state = second.state;
+        assert!(matches!(
+            endpoints.verifier.verify_response(&response),
+            Err(OpenId4VpError::SdJwt(SdJwtError::KeyBindingMismatch(_)))
+        ));
+    }
+
+    #[test]
+    fn test_no_matching_credential() {
+        let mut endpoints = create_endpoints(ResponseMode::DirectPost);
+        let holder = create_test_holder();
+        let query = PresentationQuery::Dcql(DcqlQuery {
+            credentials: vec![CredentialQuery {
+                id: "german_c1".to_string(),
+                format: FORMAT_SD_JWT.to_string(),
+                meta: None,
+                claims: vec![
+                    ClaimsQuery {
+                        id: None,
+                        path: vec![json!("language")],
+                        values: Some(vec![json!("German")]),
+                    },
+                    ClaimsQuery {
+                        id: None,
+                        path: vec![json!("level")],
+                        values: Some(vec![json!("C1"), json!("C2")]),
+                    },
+                ],
+            }],
+        });
+
+        let request = endpoints.verifier.create_request(query);
+        assert!(matches!(
+            holder.respond(&request),
+            Err(OpenId4VpError::NoMatchingCredential(id)) if id == "german_c1"
+        ));
+    }
+
+    #[test]
+    fn test_holder_presents_only_bound_proofs() {
+        let mut endpoints = create_endpoints(ResponseMode::DirectPost);
+        let mut holder = Holder::generate();
+        holder
+            .store_proof(create_bound_proof(Holder::generate().public_key_hex()))
+            .unwrap();
+
+        let request = endpoints
+            .verifier
+            .create_request(german_b2_definition(FORMAT_ZK_PROOF));
+        assert!(matches!(
+            holder.respond(&request),
+            Err(OpenId4VpError::NoMatchingCredential(_))
+        ));
+
+        holder
+            .store_proof(create_bound_proof(holder.public_key_hex()))
+            .unwrap();
+        let response = holder.respond(&request).unwrap();
+        let results = endpoints.post_response(&response.to_form_body()).unwrap();
+        assert!(results["german"].requirements_met);
+    }
+
+    #[test]
+    fn test_dcql_vct_values_enforced() {
+        let mut endpoints = create_endpoints(ResponseMode::DirectPost);
+        let holder = create_test_holder();
+        let mut query = german_b2_dcql();
+        let PresentationQuery::Dcql(dcql) = &mut query else {
+            unreachable!()
+        };
+        dcql.credentials[0].meta = Some(json!({ "vct_values": ["https://example.com/other"] }));
+
+        // The holder has no credential of the requested type
+        let request = endpoints.verifier.create_request(query.clone());
+        assert!(matches!(
+            holder.respond(&request),
+            Err(OpenId4VpError::NoMatchingCredential(id)) if id == "german"
+        ));
+
+        // Nor does the verifier accept one
+        let answered = AuthorizationRequest {
+            query: german_b2_dcql(),
+            ..request
+        };
+        let response = holder.respond(&answered).unwrap();
+        assert!(matches!(
+            endpoints.verifier.verify_response(&response),
+            Err(OpenId4VpError::InvalidResponse(_))
+        ));
+
+        let PresentationQuery::Dcql(dcql) = &mut query else {
+            unreachable!()
+        };
+        dcql.credentials[0].meta = Some(json!({ "vct_values": "not a list" }));
+        assert!(matches!(
+            query.requested_credentials(),
+            Err(OpenId4VpError::UnsupportedQuery(_))
+        ));
+    }
+
+    #[test]
+    fn test_dcql_values_must_be_upward_closed() {
+        let mut query = german_b2_dcql();
+        let PresentationQuery::Dcql(dcql) = &mut query else {
+            unreachable!()
+        };
+        // Only B2 would admit a C1 holder under a B2 minimum
+        dcql.credentials[0].claims[1].values = Some(vec![json!("B2")]);
+
+        assert!(matches!(
+            query.requested_credentials(),
+            Err(OpenId4VpError::PresentationExchange(
+                PresentationExchangeError::UnsupportedConstraint { .. }
+            ))
+        ));
+    }
+}
@@ -0,0 +1,249 @@
+//! Platforms proofs can be issued for and verified on.
+//!
+//! Issuer and verifier share this model, so a proof requested for a platform that cannot
+//! carry its claim is rejected when the proof is requested rather than at verification.
+
+use crate::zk_proof::{ClaimKind, ClaimType};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum PlatformError {
+    #[error("Unknown platform: {0}")]
+    UnknownPlatform(String),
+    #[error("Platform {platform} does not support {claim:?} claims")]
+    UnsupportedClaim {
+        platform: Platform,
+        claim: ClaimKind,
+    },
+    #[error("Platform {platform} does not provide circuit {circuit_id}")]
+    UnsupportedCircuit {
+        platform: Platform,
+        circuit_id: String,
+    },
+}
+
+/// Target platform of a proof
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Platform {
+    /// Aleo network, proofs produced by the `web5claimsleo` program
+    #[serde(rename = "aleo")]
+    Aleo,
+    /// Arbitrum Stylus, proofs checked by the `web5claims-stylus` contract
+    #[serde(rename = "stylus")]
+    Stylus,
+    /// Proofs verified locally by the web app
+    #[serde(rename = "web5claims_local")]
+    Web5ClaimsLocal,
+    /// Test platform for development
+    #[serde(rename = "test")]
+    Test,
+}
+
+/// How proof bytes are encoded on a platform
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProofEncoding {
+    /// Leo program execution output
+    LeoExecution,
+    /// ABI-encoded calldata for an EVM verifier
+    EvmCalldata,
+    /// Simulated proof bytes checked off-chain
+    Simulated,
+}
+
+/// What a platform can do
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlatformCapabilities {
+    /// Claim types the platform can prove
+    pub claim_kinds: Vec<ClaimKind>,
+    /// Circuits deployed on the platform
+    pub circuits: Vec<String>,
+    /// Encoding of proof bytes
+    pub proof_encoding: ProofEncoding,
+    /// Whether verified proofs can be anchored on-chain
+    pub on_chain_anchoring: bool,
+}
+
+impl Platform {
+    /// All known platforms
+    pub const ALL: [Platform; 4] = [
+        Platform::Aleo,
+        Platform::Stylus,
+        Platform::Web5ClaimsLocal,
+        Platform::Test,
+    ];
+
+    /// Platform identifier as used in proof metadata
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Platform::Aleo => "aleo",
+            Platform::Stylus => "stylus",
+            Platform::Web5ClaimsLocal => "web5claims_local",
+            Platform::Test => "test",
+        }
+    }
+
+    /// Capabilities of the platform
+    pub fn capabilities(&self) -> PlatformCapabilities {
+        let all_claims = ClaimKind::ALL.to_vec();
+        // Identity claims are checked by the verifier, there are no on-chain circuits yet
+        let on_chain_claims: Vec<ClaimKind> = ClaimKind::ALL
+            .into_iter()
+            .filter(|kind| !kind.needs_identity())
+            .collect();
+        let circuits = |kinds: &[ClaimKind]| {
+            kinds
+                .iter()
+                .map(|kind| kind.circuit_id().to_string())
+                .collect()
+        };
+
+        match self {
+            Platform::Aleo => PlatformCapabilities {
+                circuits: circuits(&on_chain_claims),
+                claim_kinds: on_chain_claims,
+                proof_encoding: ProofEncoding::LeoExecution,
+                on_chain_anchoring: true,
+            },
+            Platform::Stylus => PlatformCapabilities {
+                circuits: circuits(&on_chain_claims),
+                claim_kinds: on_chain_claims,
+                proof_encoding: ProofEncoding::EvmCalldata,
+                on_chain_anchoring: true,
+            },
+            Platform::Web5ClaimsLocal | Platform::Test => PlatformCapabilities {
+                circuits: circuits(&all_claims),
+                claim_kinds: all_claims,
+                proof_encoding: ProofEncoding::Simulated,
+                on_chain_anchoring: false,
+            },
+        }
+    }
+
+    /// Check that the platform can prove a claim, including every criterion of combined claims
+    pub fn check_claim(&self, claim_type: &ClaimType) -> Result<(), PlatformError> {
+        let capabilities = self.capabilities();
+
+        if !capabilities.claim_kinds.contains(&claim_type.kind()) {
+            return Err(PlatformError::UnsupportedClaim {
+                platform: *self,
+                claim: claim_type.kind(),
+            });
+        }
+
+        let circuit_id = claim_type.circuit_id();
+        if !capabilities.circuits.iter().any(|c| c == circuit_id) {
+            return Err(PlatformError::UnsupportedCircuit {
+                platform: *self,
+                circuit_id: circuit_id.to_string(),
+            });
+        }
+
+        if let ClaimType::Combined { criteria } = claim_type {
+            for criterion in criteria {
+                self.check_claim(criterion)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Platform {
+    type Err = PlatformError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Platform::ALL
+            .into_iter()
+            .find(|platform| platform.as_str() == s)
+            .ok_or_else(|| PlatformError::UnknownPlatform(s.to_string()))
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::name_binding::NameMatch;
+    use crate::zk_proof::CefrLevel;
+
+    #[test]
+    fn test_platform_round_trip() {
+        for platform in Platform::ALL {
+            assert_eq!(platform.as_str().parse::<Platform>().unwrap(), platform);
+            assert_eq!(platform.to_string(), platform.as_str());
+
+            let json = serde_json::to_string(&platform).unwrap();
+            assert_eq!(json, format!("\"{}\"", platform.as_str()));
+        }
+    }
+
+    #[test]
+    fn test_unknown_platform() {
+        assert_eq!(
+            "solana".parse::<Platform>(),
+            Err(PlatformError::UnknownPlatform("solana".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_capabilities() {
+        assert!(Platform::Aleo.capabilities().on_chain_anchoring);
+        assert!(Platform::Stylus.capabilities().on_chain_anchoring);
+        assert!(!Platform::Web5ClaimsLocal.capabilities().on_chain_anchoring);
+        assert_eq!(
+            Platform::Stylus.capabilities().proof_encoding,
+            ProofEncoding::EvmCalldata
+        );
+        assert_eq!(
+            Platform::Test.capabilities().proof_encoding,
+            ProofEncoding::Simulated
+        );
+    }
+
+    #[test]
+    fn test_check_claim() {
+        let claim = ClaimType::Combined {
+            criteria: vec![
+                ClaimType::LanguageProficiency {
+                    language: "German".to_string(),
+                    min_level: CefrLevel::B1,
+                },
+                ClaimType::PerformanceThreshold { min_percentage: 80 },
+            ],
+        };
+
+        for platform in Platform::ALL {
+            assert!(platform.check_claim(&claim).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_identity_bound_claims_stay_off_chain() {
+        let claim = ClaimType::IdentityBound {
+            name_match: NameMatch::FirstName,
+        };
+
+        assert!(Platform::Web5ClaimsLocal.check_claim(&claim).is_ok());
+        assert_eq!(
+            Platform::Aleo.check_claim(&claim),
+            Err(PlatformError::UnsupportedClaim {
+                platform: Platform::Aleo,
+                claim: ClaimKind::IdentityBound,
+            })
+        );
+        assert!(Platform::Stylus
+            .check_claim(&ClaimType::Combined {
+                criteria: vec![claim],
+            })
+            .is_err());
+    }
+}
@@ -0,0 +1,417 @@
+//! Verifiable presentations.
+//!
+//! A [`Presentation`] bundles one or more proof claims for a single verifier. It carries the
+//! verifier's nonce and audience and is signed with the holder key, so a presentation cannot
+//! be replayed to another verifier or against a fresh challenge. Issuers bind every claim to
+//! the holder key (`cnf`), so a holder cannot present claims issued to someone else.
+
+use crate::zk_proof::ZkProofClaim;
+use chrono::{DateTime, Duration, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use thiserror::Error;
+
+/// How long a presentation is accepted after it was created, in minutes
+pub const DEFAULT_PRESENTATION_VALIDITY_MINUTES: i64 = 10;
+
+/// Seconds a presentation may be created ahead of the verifier's clock
+const PRESENTATION_CLOCK_SKEW_SECS: i64 = 60;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum PresentationError {
+    #[error("Presentation contains no claims")]
+    Empty,
+    #[error("Duplicate claim in presentation: {0}")]
+    DuplicateClaim(String),
+    #[error("Invalid holder key: {0}")]
+    InvalidHolderKey(String),
+    #[error("Invalid presentation signature: {0}")]
+    InvalidSignature(String),
+    #[error("Presentation intended for {actual}, expected {expected}")]
+    AudienceMismatch { expected: String, actual: String },
+    #[error("Presentation nonce does not match the challenge")]
+    NonceMismatch,
+    #[error("Presentation expired at {0}")]
+    Expired(DateTime<Utc>),
+    #[error("Presentation created in the future: {0}")]
+    NotYetValid(DateTime<Utc>),
+    #[error("Claim {0} is not bound to the presenting holder")]
+    UnboundClaim(String),
+}
+
+/// Proof claims presented by a holder to one verifier
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Presentation {
+    /// Unique identifier for this presentation
+    pub presentation_id: String,
+    /// Identifier of the presenting holder
+    pub holder_id: String,
+    /// Hex-encoded public key of the holder
+    pub holder_key: String,
+    /// Verifier the presentation is intended for
+    pub audience: String,
+    /// Challenge chosen by the verifier
+    pub nonce: String,
+    /// When the presentation was created
+    pub created_at: DateTime<Utc>,
+    /// When the presentation stops being accepted
+    pub expires_at: DateTime<Utc>,
+    /// Presented proof claims
+    pub claims: Vec<ZkProofClaim>,
+    /// Hex-encoded holder signature over the other fields
+    #[serde(default)]
+    pub signature: String,
+}
+
+/// Fields covered by the holder signature
+#[derive(Serialize)]
+struct UnsignedPresentation<'a> {
+    presentation_id: &'a str,
+    holder_id: &'a str,
+    holder_key: &'a str,
+    audience: &'a str,
+    nonce: &'a str,
+    created_at: &'a DateTime<Utc>,
+    expires_at: &'a DateTime<Utc>,
+    claims: &'a [ZkProofClaim],
+}
+
+impl Presentation {
+    /// Create an unsigned presentation for a holder key
+    pub fn new(
+        holder_id: String,
+        holder_key: &VerifyingKey,
+        claims: Vec<ZkProofClaim>,
+        audience: String,
+        nonce: String,
+    ) -> Self {
+        let created_at = Utc::now();
+
+        Self {
+            presentation_id: uuid::Uuid::new_v4().to_string(),
+            holder_id,
+            holder_key: hex::encode(holder_key.as_bytes()),
+            audience,
+            nonce,
+            created_at,
+            expires_at: created_at + Duration::minutes(DEFAULT_PRESENTATION_VALIDITY_MINUTES),
+            claims,
+            signature: String::new(),
+        }
+    }
+
+    /// Set how long the presentation is accepted
+    pub fn with_validity(mut self, validity: Duration) -> Self {
+        self.expires_at = self.created_at + validity;
+        self
+    }
+
+    /// Canonical bytes covered by the holder signature
+    ///
+    /// Encoded through `serde_json::Value` so map keys are sorted and the bytes do not depend
+    /// on `HashMap` iteration order.
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let unsigned = UnsignedPresentation {
+            presentation_id: &self.presentation_id,
+            holder_id: &self.holder_id,
+            holder_key: &self.holder_key,
+            audience: &self.audience,
+            nonce: &self.nonce,
+            created_at: &self.created_at,
+            expires_at: &self.expires_at,
+            claims: &self.claims,
+        };
+        let value = serde_json::to_value(unsigned).expect("presentation serializes to JSON");
+        serde_json::to_vec(&value).expect("presentation serializes to JSON")
+    }
+
+    /// Sign the presentation with the holder key
+    pub fn sign(mut self, signing_key: &SigningKey) -> Self {
+        self.holder_key = hex::encode(signing_key.verifying_key().as_bytes());
+        self.signature = hex::encode(signing_key.sign(&self.signing_bytes()).to_bytes());
+        self
+    }
+
+    /// Public key of the holder
+    pub fn holder_public_key(&self) -> Result<VerifyingKey, PresentationError> {
+        decode_holder_key(&self.holder_key)
+    }
+
+    /// Check that every claim is bound to the key the presentation is signed with
+    pub fn check_holder_binding(&self) -> Result<(), PresentationError> {
+        match self
+            .claims
+            .iter()
+            .find(|claim| claim.holder_key() != Some(self.holder_key.as_str()))
+        {
+            Some(claim) => Err(PresentationError::UnboundClaim(claim.proof_id.clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// Check the holder signature and that every claim appears once
+    pub fn verify_signature(&self) -> Result<(), PresentationError> {
+        if self.claims.is_empty() {
+            return Err(PresentationError::Empty);
+        }
+
+        let mut seen = HashSet::new();
+        for claim in &self.claims {
+            if !seen.insert(claim.proof_id.as_str()) {
+                return Err(PresentationError::DuplicateClaim(claim.proof_id.clone()));
+            }
+        }
+
+        let signature_bytes: [u8; 64] = hex::decode(&self.signature)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| {
+                PresentationError::InvalidSignature("malformed signature".to_string())
+            })?;
+
+        self.holder_public_key()?
+            .verify(
+                &self.signing_bytes(),
+                &Signature::from_bytes(&signature_bytes),
+            )
+            .map_err(|e| PresentationError::InvalidSignature(e.to_string()))
+    }
+
+    /// Check that the presentation answers the verifier's challenge and has not expired
+    pub fn check_challenge(
+        &self,
+        audience: &str,
+        nonce: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), PresentationError> {
+        if self.audience != audience {
+            return Err(PresentationError::AudienceMismatch {
+                expected: audience.to_string(),
+                actual: self.audience.clone(),
+            });
+        }
+
+        if self.nonce != nonce {
+            return Err(PresentationError::NonceMismatch);
+        }
+
+        if now > self.expires_at {
+            return Err(PresentationError::Expired(self.expires_at));
+        }
+
+        Ok(())
+    }
+
+    /// Check that the presentation is not older than the verifier accepts
+    ///
+    /// `expires_at` is chosen by the holder, so verifiers also bound the age of the
+    /// presentation from `created_at`.
+    pub fn check_age(
+        &self,
+        max_age: Duration,
+        now: DateTime<Utc>,
+    ) -> Result<(), PresentationError> {
+        if self.created_at > now + Duration::seconds(PRESENTATION_CLOCK_SKEW_SECS) {
+            return Err(PresentationError::NotYetValid(self.created_at));
+        }
+        if now > self.created_at + max_age {
+            return Err(PresentationError::Expired(self.created_at + max_age));
+        }
+        Ok(())
+    }
+}
+
+/// Parse a hex-encoded Ed25519 holder key
+pub(crate) fn decode_holder_key(holder_key: &str) -> Result<VerifyingKey, PresentationError> {
+    let bytes: [u8; 32] = hex::decode(holder_key)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| PresentationError::InvalidHolderKey(holder_key.to_string()))?;
+
+    VerifyingKey::from_bytes(&bytes).map_err(|e| PresentationError::InvalidHolderKey(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::holder::Holder;
+    use crate::issuer::{CertificateIssuer, ProofOptions, ProofRequest};
+    use crate::zk_proof::{CefrLevel, ClaimType};
+    use konnektoren_core::certificates::CertificateData;
+
+    fn create_test_claims() -> Vec<ZkProofClaim> {
+        let issuer = CertificateIssuer::new("test_issuer".to_string(), "Test Issuer".to_string())
+            .accept_unsigned_certificates();
+        let certificate = CertificateData::new(
+            "German_B2_Complete".to_string(),
+            7,
+            7,
+            "Test Student".to_string(),
+            Utc::now(),
+        );
+
+        [
+            ClaimType::LanguageProficiency {
+                language: "German".to_string(),
+                min_level: CefrLevel::B1,
+            },
+            ClaimType::PerformanceThreshold { min_percentage: 80 },
+        ]
+        .into_iter()
+        .map(|claim_type| {
+            issuer
+                .generate_proof(ProofRequest {
+                    certificate: certificate.clone(),
+                    claim_type,
+                    target_platform: "test".to_string(),
+                    options: ProofOptions::default(),
+                })
+                .unwrap()
+        })
+        .collect()
+    }
+
+    fn create_test_presentation(holder: &Holder) -> Presentation {
+        Presentation::new(
+            holder.holder_id.clone(),
+            &holder.public_key(),
+            create_test_claims(),
+            "verifier.example".to_string(),
+            "nonce-123".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let holder = Holder::generate();
+        let presentation = holder.sign_presentation(create_test_presentation(&holder));
+
+        assert_eq!(presentation.holder_key, holder.public_key_hex());
+        assert_eq!(presentation.claims.len(), 2);
+        assert!(presentation.verify_signature().is_ok());
+    }
+
+    #[test]
+    fn test_json_round_trip_keeps_signature_valid() {
+        let holder = Holder::generate();
+        let presentation = holder.sign_presentation(create_test_presentation(&holder));
+
+        let json = serde_json::to_string(&presentation).unwrap();
+        let parsed: Presentation = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, presentation);
+        assert!(parsed.verify_signature().is_ok());
+    }
+
+    #[test]
+    fn test_unsigned_presentation_rejected() {
+        let holder = Holder::generate();
+        let presentation = create_test_presentation(&holder);
+
+        assert!(matches!(
+            presentation.verify_signature(),
+            Err(PresentationError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn test_tampered_claims_rejected() {
+        let holder = Holder::generate();
+        let mut presentation = holder.sign_presentation(create_test_presentation(&holder));
+        presentation.claims.pop();
+
+        assert!(matches!(
+            presentation.verify_signature(),
+            Err(PresentationError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn test_other_holder_key_rejected() {
+        let holder = Holder::generate();
+        let mut presentation = holder.sign_presentation(create_test_presentation(&holder));
+        presentation.holder_key = Holder::generate().public_key_hex();
+
+        assert!(matches!(
+            presentation.verify_signature(),
+            Err(PresentationError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn test_empty_and_duplicate_claims_rejected() {
+        let holder = Holder::generate();
+        let mut presentation = create_test_presentation(&holder);
+        presentation.claims.clear();
+        let presentation = holder.sign_presentation(presentation);
+        assert_eq!(
+            presentation.verify_signature(),
+            Err(PresentationError::Empty)
+        );
+
+        let mut presentation = create_test_presentation(&holder);
+        presentation.claims.push(presentation.claims[0].clone());
+        let presentation = holder.sign_presentation(presentation);
+        assert!(matches!(
+            presentation.verify_signature(),
+            Err(PresentationError::DuplicateClaim(_))
+        ));
+    }
+
+    #[test]
+    fn test_check_challenge() {
+        let holder = Holder::generate();
+        let presentation = create_test_presentation(&holder);
+        let now = Utc::now();
+
+        assert!(presentation
+            .check_challenge("verifier.example", "nonce-123", now)
+            .is_ok());
+        assert!(matches!(
+            presentation.check_challenge("other.example", "nonce-123", now),
+            Err(PresentationError::AudienceMismatch { .. })
+        ));
+        assert_eq!(
+            presentation.check_challenge("verifier.example", "nonce-456", now),
+            Err(PresentationError::NonceMismatch)
+        );
+    }
+
+    #[test]
+    fn test_expired_presentation() {
+        let holder = Holder::generate();
+        let presentation = create_test_presentation(&holder).with_validity(Duration::minutes(1));
+        let later = presentation.created_at + Duration::minutes(2);
+
+        assert!(matches!(
+            presentation.check_challenge("verifier.example", "nonce-123", later),
+            Err(PresentationError::Expired(_))
+        ));
+    }
+
+    #[test]
+    fn test_check_age() {
+        let holder = Holder::generate();
+        let presentation = create_test_presentation(&holder).with_validity(Duration::days(1));
+        let max_age = Duration::minutes(DEFAULT_PRESENTATION_VALIDITY_MINUTES);
+
+        assert!(presentation
+            .check_age(max_age, presentation.created_at + Duration::minutes(5))
+            .is_ok());
+        // A long validity chosen by the holder does not extend the verifier's maximum age
+        let later = presentation.created_at + Duration::hours(1);
+        assert!(presentation
+            .check_challenge("verifier.example", "nonce-123", later)
+            .is_ok());
+        assert!(matches!(
+            presentation.check_age(max_age, later),
+            Err(PresentationError::Expired(_))
+        ));
+
+        let earlier = presentation.created_at - Duration::minutes(5);
+        assert!(matches!(
+            presentation.check_age(max_age, earlier),
+            Err(PresentationError::NotYetValid(_))
+        ));
+    }
+}
@@ -0,0 +1,828 @@
+//! DIF Presentation Exchange.
+//!
+//! A verifier describes what it wants in a [`PresentationDefinition`]; the holder answers with
+//! a [`PresentationSubmission`] mapping each input descriptor to a presented credential.
+//!
+//! Input descriptors constrain the certificate attributes `language`, `level`, `score` and
+//! `date` (and may ask for `name` to be disclosed). Their filters are mapped to a [`ClaimType`],
+//! so [`PresentationDefinition::evaluate`] decides which certificates and proofs satisfy a
+//! descriptor with the same claim evaluation the issuer uses for proofs.
+
+use crate::issuer::CertificateIssuer;
+use crate::openid4vp::FORMAT_ZK_PROOF;
+use crate::verifier::ZkProofVerifier;
+use crate::zk_proof::{CefrLevel, ClaimType, ZkProofClaim};
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use konnektoren_core::certificates::CertificateData;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+/// Format of konnektoren certificates presented as they are
+pub const FORMAT_CERTIFICATE: &str = "konnektoren_certificate";
+
+/// Filter keywords the evaluation understands
+const SUPPORTED_FILTER_KEYWORDS: [&str; 6] = [
+    "type",
+    "format",
+    "const",
+    "enum",
+    "minimum",
+    "formatMinimum",
+];
+
+/// Highest score a certificate records, in percent
+const MAX_SCORE: u8 = 100;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum PresentationExchangeError {
+    #[error("Unsupported constraint in {id}: {reason}")]
+    UnsupportedConstraint { id: String, reason: String },
+    #[error("{0} constrains no claim attribute")]
+    Unconstrained(String),
+}
+
+/// What a verifier asks a holder to present
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PresentationDefinition {
+    /// Definition identifier
+    pub id: String,
+    /// Human readable name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Why the verifier asks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<String>,
+    /// Credentials the verifier asks for
+    pub input_descriptors: Vec<InputDescriptor>,
+}
+
+/// One requested credential
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InputDescriptor {
+    /// Descriptor identifier
+    pub id: String,
+    /// Human readable name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Why the credential is requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<String>,
+    /// Accepted credential formats, keyed by format identifier
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<serde_json::Map<String, Value>>,
+    /// Constraints on the credential
+    #[serde(default)]
+    pub constraints: Constraints,
+}
+
+/// Constraints of an input descriptor
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Constraints {
+    /// Required credential fields
+    #[serde(default)]
+    pub fields: Vec<Field>,
+    /// Whether only the constrained fields may be disclosed (`required` or `preferred`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit_disclosure: Option<String>,
+}
+
+/// Credential field selected by JSONPath and checked against a JSON Schema filter
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Field {
+    /// JSONPath expressions, the first one that resolves is used
+    pub path: Vec<String>,
+    /// JSON Schema the field value must match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Value>,
+    /// Whether the field may be left out
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optional: Option<bool>,
+}
+
+/// How presented credentials answer a presentation definition
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PresentationSubmission {
+    /// Submission identifier
+    pub id: String,
+    /// Definition the submission answers
+    pub definition_id: String,
+    /// Presented credential for each input descriptor
+    pub descriptor_map: Vec<DescriptorMapEntry>,
+}
+
+/// Location of the credential answering an input descriptor
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DescriptorMapEntry {
+    /// Input descriptor identifier
+    pub id: String,
+    /// Format of the presented credential
+    pub format: String,
+    /// JSONPath of the credential in the `vp_token`
+    pub path: String,
+}
+
+/// Holder credential that satisfies an input descriptor
+#[derive(Debug, Clone, PartialEq)]
+pub enum CandidateCredential {
+    /// Proof claim, by proof id
+    Proof(String),
+    /// Certificate, by index in the evaluated certificates
+    Certificate(usize),
+}
+
+/// Evaluation of one input descriptor
+#[derive(Debug, Clone, PartialEq)]
+pub struct DescriptorEvaluation {
+    /// Input descriptor identifier
+    pub descriptor_id: String,
+    /// Claim the descriptor asks for
+    pub claim_type: ClaimType,
+    /// Credentials satisfying the descriptor, proofs before certificates
+    pub candidates: Vec<CandidateCredential>,
+}
+
+/// Evaluation of a presentation definition against a holder's credentials
+#[derive(Debug, Clone, PartialEq)]
+pub struct DefinitionEvaluation {
+    /// Definition identifier
+    pub definition_id: String,
+    /// Evaluation of each input descriptor, in definition order
+    pub descriptors: Vec<DescriptorEvaluation>,
+}
+
+impl PresentationDefinition {
+    /// Work out which certificates and proofs satisfy each input descriptor
+    ///
+    /// Proofs satisfy a descriptor when `verifier` accepts them and their claim implies the
+    /// requested claim; certificates when the requested claim holds for them.
+    pub fn evaluate(
+        &self,
+        certificates: &[CertificateData],
+        proofs: &[ZkProofClaim],
+        verifier: &ZkProofVerifier,
+    ) -> Result<DefinitionEvaluation, PresentationExchangeError> {
+        let evaluator =
+            CertificateIssuer::new(self.id.clone(), "Presentation Exchange".to_string());
+        let verified_proofs: Vec<&ZkProofClaim> = proofs
+            .iter()
+            .filter(|proof| {
+                verifier
+                    .verify_proof(proof)
+                    .is_ok_and(|result| result.is_valid && result.requirements_met)
+            })
+            .collect();
+
+        let descriptors = self
+            .input_descriptors
+            .iter()
+            .map(|descriptor| {
+                let claim_type = descriptor.claim_type()?;
+                let mut candidates = Vec::new();
+
+                if descriptor.accepts_format(FORMAT_ZK_PROOF) {
+                    candidates.extend(
+                        verified_proofs
+                            .iter()
+                            .filter(|proof| proof.claim_type.implies(&claim_type))
+                            .map(|proof| CandidateCredential::Proof(proof.proof_id.clone())),
+                    );
+                }
+
+                if descriptor.accepts_format(FORMAT_CERTIFICATE) {
+                    candidates.extend(
+                        certificates
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, certificate)| {
+                                evaluator
+                                    .evaluate_claim(certificate, &claim_type)
+                                    .unwrap_or(false)
+                            })
+                            .map(|(index, _)| CandidateCredential::Certificate(index)),
+                    );
+                }
+
+                Ok(DescriptorEvaluation {
+                    descriptor_id: descriptor.id.clone(),
+                    claim_type,
+                    candidates,
+                })
+            })
+            .collect::<Result<_, PresentationExchangeError>>()?;
+
+        Ok(DefinitionEvaluation {
+            definition_id: self.id.clone(),
+            descriptors,
+        })
+    }
+}
+
+impl InputDescriptor {
+    /// Check if the descriptor accepts a credential format (no format list accepts all)
+    pub fn accepts_format(&self, format: &str) -> bool {
+        self.format
+            .as_ref()
+            .map(|formats| formats.contains_key(format))
+            .unwrap_or(true)
+    }
+
+    /// Attributes the descriptor asks for, in field order
+    pub fn attributes(&self) -> Result<Vec<String>, PresentationExchangeError> {
+        self.constraints
+            .fields
+            .iter()
+            .map(|field| {
+                field
+                    .attribute()
+                    .map(str::to_string)
+                    .ok_or_else(|| self.unsupported(format!("field path {:?}", field.path)))
+            })
+            .collect()
+    }
+
+    /// Claim a credential must prove to satisfy the descriptor
+    pub fn claim_type(&self) -> Result<ClaimType, PresentationExchangeError> {
+        let constraints = self
+            .constraints
+            .fields
+            .iter()
+            .zip(self.attributes()?)
+            .map(|(field, attribute)| {
+                let values = field
+                    .accepted_values()
+                    .map_err(|reason| self.unsupported(reason))?;
+                Ok((attribute, values))
+            })
+            .collect::<Result<Vec<_>, PresentationExchangeError>>()?;
+
+        constraints_claim_type(&self.id, &constraints)
+    }
+
+    fn unsupported(&self, reason: String) -> PresentationExchangeError {
+        PresentationExchangeError::UnsupportedConstraint {
+            id: self.id.clone(),
+            reason,
+        }
+    }
+}
+
+impl Field {
+    /// Attribute name the field selects, the last segment of its first path
+    pub fn attribute(&self) -> Option<&str> {
+        self.path
+            .first()
+            .and_then(|path| path.rsplit(['.', '$']).find(|segment| !segment.is_empty()))
+    }
+
+    /// Field asking for a certificate issued within `period` of now
+    pub fn issued_within(period: Duration) -> Self {
+        let earliest = (Utc::now() - period).to_rfc3339_opts(SecondsFormat::Secs, true);
+
+        Self {
+            path: vec!["$.date".to_string()],
+            filter: Some(serde_json::json!({
+                "type": "string",
+                "format": "date-time",
+                "formatMinimum": earliest,
+            })),
+            optional: None,
+        }
+    }
+
+    /// Values the filter accepts
+    ///
+    /// Optional fields and fields without filter only ask for the attribute to be disclosed.
+    fn accepted_values(&self) -> Result<AcceptedValues, String> {
+        let Some(filter) = self.filter.as_ref().filter(|_| self.optional != Some(true)) else {
+            return Ok(AcceptedValues::Any);
+        };
+
+        let filter = filter
+            .as_object()
+            .ok_or_else(|| "filter is not an object".to_string())?;
+        if let Some(keyword) = filter
+            .keys()
+            .find(|keyword| !SUPPORTED_FILTER_KEYWORDS.contains(&keyword.as_str()))
+        {
+            return Err(format!("filter keyword {}", keyword));
+        }
+
+        if let Some(values) = filter.get("enum") {
+            return values
+                .as_array()
+                .cloned()
+                .map(AcceptedValues::OneOf)
+                .ok_or_else(|| "enum is not an array".to_string());
+        }
+        if let Some(value) = filter.get("const") {
+            return Ok(AcceptedValues::OneOf(vec![value.clone()]));
+        }
+        Ok(["minimum", "formatMinimum"]
+            .into_iter()
+            .find_map(|keyword| filter.get(keyword).cloned())
+            .map_or(AcceptedValues::Any, AcceptedValues::AtLeast))
+    }
+}
+
+/// Values a field filter or DCQL claim accepts for an attribute
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum AcceptedValues {
+    /// Every value, the attribute only has to be disclosed
+    Any,
+    /// The value and every value above it (`minimum`, `formatMinimum`)
+    AtLeast(Value),
+    /// Exactly these values (`enum`, `const`, DCQL `values`)
+    OneOf(Vec<Value>),
+}
+
+impl AcceptedValues {
+    /// Minimum a claim can prove, provided the accepted values are exactly the values from it
+    /// up to the top of `scale`, parsed with `parse`
+    ///
+    /// A proof shows a value is at least its minimum, so a set missing a higher value, such
+    /// as `const: "B2"` or `enum: ["A1", "C2"]`, cannot be mapped to a claim.
+    fn minimum<T: PartialOrd + Clone>(
+        &self,
+        scale: &[T],
+        parse: impl Fn(&Value) -> Option<T>,
+    ) -> Result<Option<T>, ()> {
+        match self {
+            AcceptedValues::Any => Ok(None),
+            AcceptedValues::AtLeast(value) => parse(value).map(Some).ok_or(()),
+            AcceptedValues::OneOf(values) => {
+                let values = values
+                    .iter()
+                    .map(&parse)
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or(())?;
+                let minimum = scale.iter().find(|step| values.contains(step)).ok_or(())?;
+                let closed = values.iter().all(|value| scale.contains(value))
+                    && scale
+                        .iter()
+                        .filter(|step| *step >= minimum)
+                        .all(|step| values.contains(step));
+                match closed {
+                    true => Ok(Some(minimum.clone())),
+                    false => Err(()),
+                }
+            }
+        }
+    }
+}
+
+impl CandidateCredential {
+    /// Presentation format of the credential
+    pub fn format(&self) -> &'static str {
+        match self {
+            CandidateCredential::Proof(_) => FORMAT_ZK_PROOF,
+            CandidateCredential::Certificate(_) => FORMAT_CERTIFICATE,
+        }
+    }
+}
+
+impl DescriptorEvaluation {
+    /// Check if any credential satisfies the descriptor
+    pub fn is_satisfied(&self) -> bool {
+        !self.candidates.is_empty()
+    }
+}
+
+impl DefinitionEvaluation {
+    /// Check if every input descriptor is satisfied
+    pub fn is_satisfied(&self) -> bool {
+        self.descriptors
+            .iter()
+            .all(DescriptorEvaluation::is_satisfied)
+    }
+
+    /// Credential presented for each input descriptor, the first candidate of each
+    pub fn selection(&self) -> Option<Vec<&CandidateCredential>> {
+        self.descriptors
+            .iter()
+            .map(|descriptor| descriptor.candidates.first())
+            .collect()
+    }
+
+    /// Submission presenting the [`selection`](Self::selection) as a `vp_token` array
+    pub fn submission(&self) -> Option<PresentationSubmission> {
+        let selection = self.selection()?;
+
+        Some(PresentationSubmission {
+            id: uuid::Uuid::new_v4().to_string(),
+            definition_id: self.definition_id.clone(),
+            descriptor_map: self
+                .descriptors
+                .iter()
+                .zip(selection)
+                .enumerate()
+                .map(|(i, (descriptor, candidate))| DescriptorMapEntry {
+                    id: descriptor.descriptor_id.clone(),
+                    format: candidate.format().to_string(),
+                    path: format!("$[{}]", i),
+                })
+                .collect(),
+        })
+    }
+}
+
+/// Map attribute constraints to the claim a credential must prove
+///
+/// Minimums become the minimum of the claim, and so does the lowest of a set of accepted
+/// levels or scores that holds every value above it. Other sets cannot be proven and are
+/// unsupported. Constraints accepting every value only ask for the attribute to be disclosed.
+pub(crate) fn constraints_claim_type(
+    id: &str,
+    constraints: &[(String, AcceptedValues)],
+) -> Result<ClaimType, PresentationExchangeError> {
+    let unsupported = |reason: &str| PresentationExchangeError::UnsupportedConstraint {
+        id: id.to_string(),
+        reason: reason.to_string(),
+    };
+    let mut language = None;
+    let mut min_level = None;
+    let mut min_percentage = None;
+    let mut after_date = None;
+
+    for (attribute, values) in constraints {
+        match attribute.as_str() {
+            "language" => {
+                let values = match values {
+                    AcceptedValues::Any => &vec![],
+                    AcceptedValues::OneOf(values) => values,
+                    AcceptedValues::AtLeast(_) => return Err(unsupported("language minimum")),
+                };
+                let mut languages = values
+                    .iter()
+                    .map(Value::as_str)
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| unsupported("language values"))?;
+                languages.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+                // A claim names one language, so alternatives cannot be mapped to it
+                if languages.len() > 1 {
+                    return Err(unsupported("several accepted languages"));
+                }
+                language = languages.first().map(|language| language.to_string());
+            }
+            "level" => {
+                min_level = values
+                    .minimum(&CefrLevel::ALL, |value| {
+                        serde_json::from_value(value.clone()).ok()
+                    })
+                    .map_err(|_| unsupported("level values"))?;
+            }
+            "score" => {
+                let scores: Vec<u8> = (0..=MAX_SCORE).collect();
+                min_percentage = values
+                    .minimum(&scores, |value| {
+                        value.as_u64().and_then(|score| u8::try_from(score).ok())
+                    })
+                    .map_err(|_| unsupported("score values"))?;
+            }
+            "date" => {
+                after_date = match values {
+                    AcceptedValues::Any => None,
+                    AcceptedValues::AtLeast(value) => Some(
+                        serde_json::from_value::<DateTime<Utc>>(value.clone())
+                            .map_err(|_| unsupported("date values"))?,
+                    ),
+                    // Dates have no highest value, so no set of them holds every later date
+                    AcceptedValues::OneOf(_) => return Err(unsupported("date values")),
+                };
+            }
+            "name" if *values == AcceptedValues::Any => {}
+            other => return Err(unsupported(&format!("attribute {}", other))),
+        }
+    }
+
+    let mut criteria = Vec::new();
+    match (language, min_level) {
+        (Some(language), min_level) => criteria.push(ClaimType::LanguageProficiency {
+            language,
+            min_level: min_level.unwrap_or(CefrLevel::A1),
+        }),
+        (None, Some(_)) => return Err(unsupported("level without language")),
+        (None, None) => {}
+    }
+    if let Some(min_percentage) = min_percentage {
+        criteria.push(ClaimType::PerformanceThreshold { min_percentage });
+    }
+    if let Some(after_date) = after_date {
+        criteria.push(ClaimType::CompletionDate { after_date });
+    }
+
+    match criteria.len() {
+        0 => Err(PresentationExchangeError::Unconstrained(id.to_string())),
+        1 => Ok(criteria.remove(0)),
+        _ => Ok(ClaimType::Combined { criteria }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_session::GameCatalogue;
+    use crate::testing::{create_real_proof_setup, generate_real_proof};
+    use serde_json::json;
+
+    fn create_test_certificate(game_path_name: &str, date: DateTime<Utc>) -> CertificateData {
+        let total_challenges = GameCatalogue::builtin()
+            .game_path(game_path_name)
+            .map_or(1, |game_path| game_path.challenges.len());
+        CertificateData::new(
+            game_path_name.to_string(),
+            total_challenges,
+            total_challenges,
+            "Test Student".to_string(),
+            date,
+        )
+    }
+
+    fn create_test_verifier() -> ZkProofVerifier {
+        ZkProofVerifier::new("test_verifier".to_string())
+    }
+
+    /// "language=German, level>=B2, issued within 2 years"
+    fn create_recent_german_b2_definition() -> PresentationDefinition {
+        let mut definition = create_test_definition();
+        let descriptor = &mut definition.input_descriptors[0];
+        descriptor.format = None;
+        descriptor
+            .constraints
+            .fields
+            .push(Field::issued_within(Duration::days(2 * 365)));
+        definition
+    }
+
+    fn create_test_definition() -> PresentationDefinition {
+        serde_json::from_value(serde_json::json!({
+            "id": "german_b2",
+            "input_descriptors": [{
+                "id": "language_certificate",
+                "format": { "dc+sd-jwt": {} },
+                "constraints": {
+                    "limit_disclosure": "required",
+                    "fields": [
+                        { "path": ["$.language"], "filter": { "type": "string", "const": "German" } },
+                        { "path": ["$.level"], "filter": { "type": "string", "enum": ["B2", "C1", "C2"] } }
+                    ]
+                }
+            }]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_definition() {
+        let definition = create_test_definition();
+        let descriptor = &definition.input_descriptors[0];
+
+        assert_eq!(definition.id, "german_b2");
+        assert_eq!(descriptor.constraints.fields.len(), 2);
+        assert_eq!(
+            descriptor.constraints.limit_disclosure.as_deref(),
+            Some("required")
+        );
+    }
+
+    #[test]
+    fn test_accepts_format() {
+        let mut descriptor = create_test_definition().input_descriptors[0].clone();
+        assert!(descriptor.accepts_format("dc+sd-jwt"));
+        assert!(!descriptor.accepts_format("ldp_vc"));
+
+        descriptor.format = None;
+        assert!(descriptor.accepts_format("ldp_vc"));
+    }
+
+    #[test]
+    fn test_field_attribute() {
+        let field = |path: &str| Field {
+            path: vec![path.to_string()],
+            filter: None,
+            optional: None,
+        };
+
+        assert_eq!(field("$.language").attribute(), Some("language"));
+        assert_eq!(
+            field("$.credentialSubject.level").attribute(),
+            Some("level")
+        );
+        assert_eq!(field("$").attribute(), None);
+    }
+
+    #[test]
+    fn test_descriptor_claim_type() {
+        let definition = create_recent_german_b2_definition();
+        let claim_type = definition.input_descriptors[0].claim_type().unwrap();
+
+        let ClaimType::Combined { criteria } = claim_type else {
+            panic!("expected a combined claim");
+        };
+        assert_eq!(
+            criteria[0],
+            ClaimType::LanguageProficiency {
+                language: "German".to_string(),
+                min_level: CefrLevel::B2,
+            }
+        );
+        assert!(matches!(
+            criteria[1],
+            ClaimType::CompletionDate { after_date }
+                if after_date < Utc::now() - Duration::days(729)
+        ));
+    }
+
+    #[test]
+    fn test_unsupported_constraints_rejected() {
+        let mut descriptor = create_test_definition().input_descriptors[0].clone();
+        descriptor.constraints.fields[1].filter =
+            Some(json!({ "type": "string", "pattern": "^B" }));
+        assert!(matches!(
+            descriptor.claim_type(),
+            Err(PresentationExchangeError::UnsupportedConstraint { .. })
+        ));
+
+        descriptor.constraints.fields[1].filter =
+            Some(json!({ "type": "string", "enum": ["B2", "C1"] }));
+        descriptor.constraints.fields[0].filter =
+            Some(json!({ "type": "string", "enum": ["German", "Spanish"] }));
+        assert_eq!(
+            descriptor.claim_type(),
+            Err(PresentationExchangeError::UnsupportedConstraint {
+                id: "language_certificate".to_string(),
+                reason: "several accepted languages".to_string(),
+            })
+        );
+
+        descriptor.constraints.fields = vec![Field {
+            path: vec!["$.name".to_string()],
+            filter: None,
+            optional: None,
+        }];
+        assert_eq!(
+            descriptor.claim_type(),
+            Err(PresentationExchangeError::Unconstrained(
+                "language_certificate".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_accepted_value_sets_must_be_upward_closed() {
+        let mut descriptor = create_test_definition().input_descriptors[0].clone();
+        let claim_type = |descriptor: &InputDescriptor, filter: Value| {
+            let mut descriptor = descriptor.clone();
+            descriptor.constraints.fields.last_mut().unwrap().filter = Some(filter);
+            descriptor.claim_type()
+        };
+
+        assert_eq!(
+            claim_type(&descriptor, json!({ "enum": ["C2", "B2", "C1"] })).unwrap(),
+            ClaimType::LanguageProficiency {
+                language: "German".to_string(),
+                min_level: CefrLevel::B2,
+            }
+        );
+        assert_eq!(
+            claim_type(&descriptor, json!({ "const": "C2" })).unwrap(),
+            ClaimType::LanguageProficiency {
+                language: "German".to_string(),
+                min_level: CefrLevel::C2,
+            }
+        );
+        // A proof of a minimum level cannot show the level is exactly B2 or one of A1 and C2
+        for filter in [json!({ "const": "B2" }), json!({ "enum": ["A1", "C2"] })] {
+            assert_eq!(
+                claim_type(&descriptor, filter),
+                Err(PresentationExchangeError::UnsupportedConstraint {
+                    id: "language_certificate".to_string(),
+                    reason: "level values".to_string(),
+                })
+            );
+        }
+
+        descriptor.constraints.fields[0].path = vec!["$.score".to_string()];
+        descriptor.constraints.fields.truncate(1);
+        let scores: Vec<u8> = (80..=100).collect();
+        assert_eq!(
+            claim_type(&descriptor, json!({ "enum": scores })),
+            Ok(ClaimType::PerformanceThreshold { min_percentage: 80 })
+        );
+        for filter in [json!({ "const": 80 }), json!({ "enum": [80, 90, 100] })] {
+            assert!(matches!(
+                claim_type(&descriptor, filter),
+                Err(PresentationExchangeError::UnsupportedConstraint { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_optional_field_only_disclosed() {
+        let mut descriptor = create_test_definition().input_descriptors[0].clone();
+        descriptor.constraints.fields[1].optional = Some(true);
+
+        assert_eq!(
+            descriptor.claim_type().unwrap(),
+            ClaimType::LanguageProficiency {
+                language: "German".to_string(),
+                min_level: CefrLevel::A1,
+            }
+        );
+        assert_eq!(descriptor.attributes().unwrap(), vec!["language", "level"]);
+    }
+
+    #[test]
+    fn test_evaluate_certificates() {
+        let definition = create_recent_german_b2_definition();
+        let certificates = [
+            create_test_certificate("German_A2_Elementary", Utc::now()),
+            create_test_certificate("German_B2_Complete", Utc::now() - Duration::days(3 * 365)),
+            create_test_certificate("French_C1_Advanced", Utc::now()),
+            create_test_certificate("German_C1_Advanced", Utc::now() - Duration::days(30)),
+        ];
+
+        let evaluation = definition
+            .evaluate(&certificates, &[], &create_test_verifier())
+            .unwrap();
+        assert_eq!(
+            evaluation.descriptors[0].candidates,
+            vec![CandidateCredential::Certificate(3)]
+        );
+        assert!(evaluation.is_satisfied());
+    }
+
+    #[test]
+    fn test_evaluate_prefers_proofs() {
+        let (issuer, verifier) = create_real_proof_setup();
+        let definition = create_recent_german_b2_definition();
+        let claim_type = definition.input_descriptors[0].claim_type().unwrap();
+        let weaker = generate_real_proof(
+            &issuer,
+            ClaimType::LanguageProficiency {
+                language: "German".to_string(),
+                min_level: CefrLevel::B1,
+            },
+            "test",
+        );
+        let matching = generate_real_proof(&issuer, claim_type, "test");
+        let certificates = [create_test_certificate("German_B2_Complete", Utc::now())];
+
+        let evaluation = definition
+            .evaluate(&certificates, &[weaker, matching.clone()], &verifier)
+            .unwrap();
+        assert_eq!(
+            evaluation.descriptors[0].candidates,
+            vec![
+                CandidateCredential::Proof(matching.proof_id),
+                CandidateCredential::Certificate(0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_evaluate_skips_unverified_proofs() {
+        let (issuer, verifier) = create_real_proof_setup();
+        let definition = create_recent_german_b2_definition();
+        let claim_type = definition.input_descriptors[0].claim_type().unwrap();
+        let mut forged = generate_real_proof(&issuer, claim_type, "test");
+        forged.proof_data.proof_bytes = vec![0u8; 32];
+        assert!(verifier.verify_proof(&forged).is_err());
+
+        let evaluation = definition.evaluate(&[], &[forged], &verifier).unwrap();
+        assert!(!evaluation.is_satisfied());
+    }
+
+    #[test]
+    fn test_evaluate_respects_formats() {
+        let definition = create_test_definition();
+        let certificates = [create_test_certificate("German_B2_Complete", Utc::now())];
+
+        let evaluation = definition
+            .evaluate(&certificates, &[], &create_test_verifier())
+            .unwrap();
+        assert!(!evaluation.is_satisfied());
+        assert_eq!(evaluation.submission(), None);
+    }
+
+    #[test]
+    fn test_submission() {
+        let definition = create_recent_german_b2_definition();
+        let certificates = [create_test_certificate("German_B2_Complete", Utc::now())];
+
+        let submission = definition
+            .evaluate(&certificates, &[], &create_test_verifier())
+            .unwrap()
+            .submission()
+            .unwrap();
+        assert_eq!(submission.definition_id, "german_b2");
+        assert_eq!(
+            submission.descriptor_map,
+            vec![DescriptorMapEntry {
+                id: "language_certificate".to_string(),
+                format: FORMAT_CERTIFICATE.to_string(),
+                path: "$[0]".to_string(),
+            }]
+        );
+    }
+}
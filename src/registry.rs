@@ -0,0 +1,350 @@
+//! Trusted circuit registry manifests.
+//!
+//! A [`CircuitManifest`] lists the circuits a verifier trusts. Manifests are distributed as
+//! TOML or JSON and signed with an Ed25519 key; the signature covers the canonical JSON
+//! encoding of the manifest, so a manifest can be converted between formats without
+//! re-signing.
+
+use crate::platform::Platform;
+use crate::verifier::CircuitInfo;
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+use thiserror::Error;
+
+/// Built-in manifest shipped with the crate
+const BUILTIN_MANIFEST: &str = include_str!("../circuits.toml");
+
+#[derive(Error, Debug)]
+pub enum RegistryError {
+    #[error("Failed to read manifest: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to parse manifest: {0}")]
+    Parse(String),
+    #[error("Unsupported manifest format: {0}")]
+    UnsupportedFormat(String),
+    #[error("Invalid manifest signature: {0}")]
+    InvalidSignature(String),
+    #[error("Manifest signed by untrusted key: {0}")]
+    UntrustedSigner(String),
+    #[error("Duplicate circuit in manifest: {0}")]
+    DuplicateCircuit(String),
+    #[error("Invalid verification key hash for circuit {0}")]
+    InvalidVkHash(String),
+}
+
+/// Status of a circuit in the registry at a point in time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CircuitStatus {
+    /// Circuit is trusted
+    Active,
+    /// Circuit is still trusted but should be replaced
+    Deprecated { since: DateTime<Utc> },
+    /// Circuit is no longer trusted
+    Revoked,
+}
+
+/// List of trusted circuits
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CircuitManifest {
+    /// Manifest name
+    pub name: String,
+    /// When the manifest was issued
+    pub issued_at: DateTime<Utc>,
+    /// Trusted circuits
+    pub circuits: Vec<CircuitInfo>,
+}
+
+/// Manifest together with the Ed25519 signature of its publisher
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SignedCircuitManifest {
+    /// Hex-encoded public key of the signer
+    pub signer: String,
+    /// Hex-encoded signature over the canonical manifest encoding
+    pub signature: String,
+    /// The signed manifest
+    pub manifest: CircuitManifest,
+}
+
+/// Manifest file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestFormat {
+    Toml,
+    Json,
+}
+
+impl ManifestFormat {
+    /// Detect the format from a file extension
+    pub fn from_path(path: &Path) -> Result<Self, RegistryError> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Ok(Self::Toml),
+            Some("json") => Ok(Self::Json),
+            other => Err(RegistryError::UnsupportedFormat(
+                other.unwrap_or_default().to_string(),
+            )),
+        }
+    }
+}
+
+impl CircuitInfo {
+    /// Status of the circuit at the given time
+    pub fn status_at(&self, now: DateTime<Utc>) -> CircuitStatus {
+        if self.revoked {
+            return CircuitStatus::Revoked;
+        }
+
+        match self.deprecated_at {
+            Some(since) if since <= now => CircuitStatus::Deprecated { since },
+            _ => CircuitStatus::Active,
+        }
+    }
+
+    /// Check if proofs for the given platform may use this circuit
+    ///
+    /// An empty platform list allows every platform.
+    pub fn supports_platform(&self, platform: Platform) -> bool {
+        self.platforms.is_empty() || self.platforms.contains(&platform)
+    }
+}
+
+impl CircuitManifest {
+    /// Manifest with the circuits shipped with the crate
+    pub fn builtin() -> Self {
+        Self::parse(BUILTIN_MANIFEST, ManifestFormat::Toml)
+            .expect("built-in circuit manifest is valid")
+    }
+
+    /// Parse an unsigned manifest
+    pub fn parse(content: &str, format: ManifestFormat) -> Result<Self, RegistryError> {
+        let manifest: Self = parse(content, format)?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    /// Check the manifest for duplicate circuits and malformed vk hashes
+    pub fn validate(&self) -> Result<(), RegistryError> {
+        let mut seen = HashSet::new();
+
+        for circuit in &self.circuits {
+            if !seen.insert(circuit.circuit_id.as_str()) {
+                return Err(RegistryError::DuplicateCircuit(circuit.circuit_id.clone()));
+            }
+
+            let valid_hash = hex::decode(&circuit.vk_hash)
+                .map(|bytes| bytes.len() == 32)
+                .unwrap_or(false);
+            if !valid_hash {
+                return Err(RegistryError::InvalidVkHash(circuit.circuit_id.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Canonical bytes covered by the manifest signature
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).expect("manifest serializes to JSON")
+    }
+
+    /// Sign the manifest
+    pub fn sign(self, signing_key: &SigningKey) -> SignedCircuitManifest {
+        let signature = signing_key.sign(&self.signing_bytes());
+
+        SignedCircuitManifest {
+            signer: hex::encode(signing_key.verifying_key().as_bytes()),
+            signature: hex::encode(signature.to_bytes()),
+            manifest: self,
+        }
+    }
+}
+
+impl SignedCircuitManifest {
+    /// Parse a signed manifest
+    pub fn parse(content: &str, format: ManifestFormat) -> Result<Self, RegistryError> {
+        parse(content, format)
+    }
+
+    /// Load a signed manifest from a `.toml` or `.json` file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RegistryError> {
+        let path = path.as_ref();
+        let format = ManifestFormat::from_path(path)?;
+        let content = std::fs::read_to_string(path)?;
+        Self::parse(&content, format)
+    }
+
+    /// Serialize the signed manifest
+    pub fn encode(&self, format: ManifestFormat) -> Result<String, RegistryError> {
+        match format {
+            ManifestFormat::Toml => {
+                toml::to_string(self).map_err(|e| RegistryError::Parse(e.to_string()))
+            }
+            ManifestFormat::Json => {
+                serde_json::to_string_pretty(self).map_err(|e| RegistryError::Parse(e.to_string()))
+            }
+        }
+    }
+
+    /// Verify the signature against a trusted publisher key and return the manifest
+    pub fn verify(&self, trusted_key: &VerifyingKey) -> Result<&CircuitManifest, RegistryError> {
+        if self.signer != hex::encode(trusted_key.as_bytes()) {
+            return Err(RegistryError::UntrustedSigner(self.signer.clone()));
+        }
+
+        let signature_bytes: [u8; 64] = hex::decode(&self.signature)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| RegistryError::InvalidSignature("malformed signature".to_string()))?;
+        let signature = Signature::from_bytes(&signature_bytes);
+
+        trusted_key
+            .verify(&self.manifest.signing_bytes(), &signature)
+            .map_err(|e| RegistryError::InvalidSignature(e.to_string()))?;
+
+        self.manifest.validate()?;
+        Ok(&self.manifest)
+    }
+}
+
+fn parse<T: serde::de::DeserializeOwned>(
+    content: &str,
+    format: ManifestFormat,
+) -> Result<T, RegistryError> {
+    match format {
+        ManifestFormat::Toml => {
+            toml::from_str(content).map_err(|e| RegistryError::Parse(e.to_string()))
+        }
+        ManifestFormat::Json => {
+            serde_json::from_str(content).map_err(|e| RegistryError::Parse(e.to_string()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issuer::CertificateIssuer;
+    use chrono::Duration;
+
+    fn create_test_key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32])
+    }
+
+    fn create_test_manifest() -> CircuitManifest {
+        let mut manifest = CircuitManifest::builtin();
+        manifest.name = "test-registry".to_string();
+        manifest
+    }
+
+    #[test]
+    fn test_builtin_manifest() {
+        let manifest = CircuitManifest::builtin();
+        assert_eq!(manifest.circuits.len(), 7);
+
+        for circuit in &manifest.circuits {
+            assert_eq!(
+                circuit.vk_hash,
+                CertificateIssuer::compute_verification_key_hash(&circuit.circuit_id)
+            );
+            assert_eq!(circuit.status_at(Utc::now()), CircuitStatus::Active);
+        }
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let key = create_test_key();
+        let signed = create_test_manifest().sign(&key);
+
+        let manifest = signed.verify(&key.verifying_key()).unwrap();
+        assert_eq!(manifest.name, "test-registry");
+    }
+
+    #[test]
+    fn test_toml_and_json_round_trip() {
+        let key = create_test_key();
+        let signed = create_test_manifest().sign(&key);
+
+        for format in [ManifestFormat::Toml, ManifestFormat::Json] {
+            let content = signed.encode(format).unwrap();
+            let parsed = SignedCircuitManifest::parse(&content, format).unwrap();
+            assert_eq!(parsed, signed);
+            assert!(parsed.verify(&key.verifying_key()).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_tampered_manifest_rejected() {
+        let key = create_test_key();
+        let mut signed = create_test_manifest().sign(&key);
+        signed.manifest.circuits[0].vk_hash = "00".repeat(32);
+
+        assert!(matches!(
+            signed.verify(&key.verifying_key()),
+            Err(RegistryError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn test_untrusted_signer_rejected() {
+        let signed = create_test_manifest().sign(&SigningKey::from_bytes(&[9u8; 32]));
+
+        assert!(matches!(
+            signed.verify(&create_test_key().verifying_key()),
+            Err(RegistryError::UntrustedSigner(_))
+        ));
+    }
+
+    #[test]
+    fn test_duplicate_circuit_rejected() {
+        let mut manifest = create_test_manifest();
+        manifest.circuits.push(manifest.circuits[0].clone());
+
+        assert!(matches!(
+            manifest.validate(),
+            Err(RegistryError::DuplicateCircuit(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_vk_hash_rejected() {
+        let mut manifest = create_test_manifest();
+        manifest.circuits[0].vk_hash = "not-a-hash".to_string();
+
+        assert!(matches!(
+            manifest.validate(),
+            Err(RegistryError::InvalidVkHash(_))
+        ));
+    }
+
+    #[test]
+    fn test_circuit_status() {
+        let now = Utc::now();
+        let mut circuit = create_test_manifest().circuits[0].clone();
+
+        circuit.deprecated_at = Some(now + Duration::days(1));
+        assert_eq!(circuit.status_at(now), CircuitStatus::Active);
+
+        circuit.deprecated_at = Some(now - Duration::days(1));
+        assert!(matches!(
+            circuit.status_at(now),
+            CircuitStatus::Deprecated { .. }
+        ));
+
+        circuit.revoked = true;
+        assert_eq!(circuit.status_at(now), CircuitStatus::Revoked);
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            ManifestFormat::from_path(Path::new("circuits.toml")).unwrap(),
+            ManifestFormat::Toml
+        );
+        assert_eq!(
+            ManifestFormat::from_path(Path::new("circuits.json")).unwrap(),
+            ManifestFormat::Json
+        );
+        assert!(ManifestFormat::from_path(Path::new("circuits.yaml")).is_err());
+    }
+}
@@ -0,0 +1,1028 @@
+//! SD-JWT VC encoding of language certificates.
+//!
+//! The issuer signs a JWT that holds only salted digests of the certificate attributes
+//! (`language`, `level`, `score`, `date`, `name`). The attributes themselves travel as
+//! disclosures, so the holder chooses which of them a verifier sees. Presentations end with
+//! a key-binding JWT signed by the holder key named in the `cnf` claim, tied to the
+//! verifier's audience and nonce.
+
+use crate::trust::{
+    IdentityStatus, RevocationStatus, SignatureStatus, TrustError, TrustInputs, TrustScore,
+    TrustScoreConfig,
+};
+use crate::verifier::{unmet_warning, VerificationDetails, VerificationResult};
+use crate::zk_proof::{CefrLevel, ClaimType, ProofMode};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Duration, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use konnektoren_core::certificates::CertificateData;
+use rand::{rngs::OsRng, RngCore};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+/// Verifiable credential type of language certificates
+pub const LANGUAGE_CERTIFICATE_VCT: &str =
+    "https://web5.claims/credentials/language-certificate/v1";
+/// Platform name reported for verified SD-JWT presentations
+pub const SD_JWT_PLATFORM: &str = "sd_jwt";
+/// Selectively disclosable certificate attributes
+pub const CERTIFICATE_ATTRIBUTES: [&str; 5] = ["language", "level", "score", "date", "name"];
+
+/// Default age after which a key-binding JWT is no longer accepted
+pub const DEFAULT_KEY_BINDING_MAX_AGE_SECS: i64 = 300;
+/// How far a key-binding JWT may be issued in the verifier's future, for clock skew
+const KEY_BINDING_CLOCK_SKEW_SECS: i64 = 60;
+
+const SD_JWT_TYP: &str = "dc+sd-jwt";
+const KB_JWT_TYP: &str = "kb+jwt";
+
+#[derive(Error, Debug, PartialEq)]
+pub enum SdJwtError {
+    #[error("Malformed SD-JWT: {0}")]
+    Malformed(String),
+    #[error("Invalid certificate: {0}")]
+    InvalidCertificate(String),
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
+    #[error("Issuer is not trusted: {0}")]
+    UntrustedIssuer(String),
+    #[error("SD-JWT expired at {0}")]
+    Expired(i64),
+    #[error("Disclosure not covered by the issuer signature: {0}")]
+    UnknownDisclosure(String),
+    #[error("Attribute disclosed more than once: {0}")]
+    DuplicateDisclosure(String),
+    #[error("Claim needs undisclosed attribute: {0}")]
+    MissingDisclosure(String),
+    #[error("Presentation has no key-binding JWT")]
+    MissingKeyBinding,
+    #[error("Key-binding JWT does not match: {0}")]
+    KeyBindingMismatch(String),
+    #[error("Key-binding JWT issued at {0} is not fresh")]
+    StaleKeyBinding(i64),
+    #[error("Unsupported credential type: {0}")]
+    UnsupportedType(String),
+    #[error("Trust score computation failed: {0}")]
+    Trust(#[from] TrustError),
+}
+
+/// Salted attribute disclosure
+#[derive(Debug, Clone, PartialEq)]
+pub struct Disclosure {
+    /// Random salt
+    pub salt: String,
+    /// Attribute name
+    pub name: String,
+    /// Attribute value
+    pub value: Value,
+    /// Base64url-encoded JSON array `[salt, name, value]`
+    encoded: String,
+}
+
+/// SD-JWT with its disclosures and optional key-binding JWT
+#[derive(Debug, Clone, PartialEq)]
+pub struct SdJwt {
+    /// Issuer-signed JWT
+    pub issuer_jwt: String,
+    /// Disclosed attributes
+    pub disclosures: Vec<Disclosure>,
+    /// Holder key-binding JWT
+    pub key_binding_jwt: Option<String>,
+}
+
+/// Issues certificates as SD-JWT VCs
+#[derive(Debug, Clone)]
+pub struct SdJwtIssuer {
+    /// Issuer identifier, used as `iss`
+    pub issuer_id: String,
+    /// Issuer name
+    pub issuer_name: String,
+    /// Issuer signing key
+    signing_key: SigningKey,
+    /// How long issued credentials are valid
+    pub validity: Option<Duration>,
+}
+
+/// Verifies SD-JWT VC presentations against claims
+#[derive(Debug, Clone)]
+pub struct SdJwtVerifier {
+    /// Verifier identifier
+    pub verifier_id: String,
+    /// Public keys of trusted issuers by issuer id
+    pub trusted_issuers: HashMap<String, VerifyingKey>,
+    /// How long after its `iat` a key-binding JWT is accepted
+    pub key_binding_max_age: Duration,
+    /// Weights and thresholds of the trust score
+    pub trust_config: TrustScoreConfig,
+}
+
+/// Certificate attributes disclosed in a presentation
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DisclosedCertificate {
+    /// Course language
+    pub language: Option<String>,
+    /// CEFR level of the course
+    pub level: Option<CefrLevel>,
+    /// Performance percentage
+    pub score: Option<u8>,
+    /// Completion date
+    pub date: Option<DateTime<Utc>>,
+    /// Learner name
+    pub name: Option<String>,
+}
+
+impl Disclosure {
+    /// Create a disclosure with a random salt
+    pub fn new(name: &str, value: Value) -> Self {
+        let mut salt = [0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+        let salt = URL_SAFE_NO_PAD.encode(salt);
+        let encoded = URL_SAFE_NO_PAD.encode(json!([salt, name, value]).to_string());
+
+        Self {
+            salt,
+            name: name.to_string(),
+            value,
+            encoded,
+        }
+    }
+
+    /// Parse an encoded disclosure
+    pub fn parse(encoded: &str) -> Result<Self, SdJwtError> {
+        let array: Value = URL_SAFE_NO_PAD
+            .decode(encoded)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .ok_or_else(|| SdJwtError::Malformed(format!("disclosure {}", encoded)))?;
+
+        match array.as_array().map(Vec::as_slice) {
+            Some([Value::String(salt), Value::String(name), value]) => Ok(Self {
+                salt: salt.clone(),
+                name: name.clone(),
+                value: value.clone(),
+                encoded: encoded.to_string(),
+            }),
+            _ => Err(SdJwtError::Malformed(format!("disclosure {}", encoded))),
+        }
+    }
+
+    /// Encoded disclosure as it appears in the SD-JWT
+    pub fn encoded(&self) -> &str {
+        &self.encoded
+    }
+
+    /// Digest listed in the issuer JWT `_sd` array
+    pub fn digest(&self) -> String {
+        URL_SAFE_NO_PAD.encode(Sha256::digest(self.encoded.as_bytes()))
+    }
+}
+
+impl SdJwt {
+    /// Parse the compact `<issuer-jwt>~<disclosure>~...~<kb-jwt>` serialization
+    pub fn parse(serialized: &str) -> Result<Self, SdJwtError> {
+        let mut parts: Vec<&str> = serialized.split('~').collect();
+        if parts.len() < 2 {
+            return Err(SdJwtError::Malformed(
+                "missing disclosure separator".to_string(),
+            ));
+        }
+
+        let issuer_jwt = parts.remove(0).to_string();
+        let key_binding_jwt = parts.pop().filter(|kb| !kb.is_empty()).map(str::to_string);
+        let disclosures = parts
+            .into_iter()
+            .map(Disclosure::parse)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            issuer_jwt,
+            disclosures,
+            key_binding_jwt,
+        })
+    }
+
+    /// Compact serialization
+    pub fn serialize(&self) -> String {
+        let mut serialized = self.serialize_without_key_binding();
+        if let Some(kb) = &self.key_binding_jwt {
+            serialized.push_str(kb);
+        }
+        serialized
+    }
+
+    /// Credential type claimed by the issuer JWT, without checking its signature
+    pub fn vct(&self) -> Result<String, SdJwtError> {
+        jwt_payload(&self.issuer_jwt)?["vct"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| SdJwtError::Malformed("vct".to_string()))
+    }
+
+    /// Attributes revealed by the disclosures
+    pub fn disclosed_certificate(&self) -> Result<DisclosedCertificate, SdJwtError> {
+        DisclosedCertificate::from_disclosures(&self.disclosures)
+    }
+
+    /// Keep only the named attributes, dropping any key binding
+    pub fn disclose(&self, names: &[&str]) -> Self {
+        Self {
+            issuer_jwt: self.issuer_jwt.clone(),
+            disclosures: self
+                .disclosures
+                .iter()
+                .filter(|disclosure| names.contains(&disclosure.name.as_str()))
+                .cloned()
+                .collect(),
+            key_binding_jwt: None,
+        }
+    }
+
+    /// Append a key-binding JWT for a verifier's audience and nonce
+    pub fn bind(mut self, holder_key: &SigningKey, audience: &str, nonce: &str) -> Self {
+        let payload = json!({
+            "iat": Utc::now().timestamp(),
+            "aud": audience,
+            "nonce": nonce,
+            "sd_hash": self.sd_hash(),
+        });
+        self.key_binding_jwt = Some(encode_jwt(KB_JWT_TYP, &payload, holder_key));
+        self
+    }
+
+    /// Hash of the presentation the key-binding JWT signs
+    fn sd_hash(&self) -> String {
+        URL_SAFE_NO_PAD.encode(Sha256::digest(
+            self.serialize_without_key_binding().as_bytes(),
+        ))
+    }
+
+    fn serialize_without_key_binding(&self) -> String {
+        let mut serialized = format!("{}~", self.issuer_jwt);
+        for disclosure in &self.disclosures {
+            serialized.push_str(disclosure.encoded());
+            serialized.push('~');
+        }
+        serialized
+    }
+}
+
+impl SdJwtIssuer {
+    /// Create an issuer with its signing key
+    pub fn new(issuer_id: String, issuer_name: String, signing_key: SigningKey) -> Self {
+        Self {
+            issuer_id,
+            issuer_name,
+            signing_key,
+            validity: None,
+        }
+    }
+
+    /// Set how long issued credentials are valid
+    pub fn with_validity(mut self, validity: Duration) -> Self {
+        self.validity = Some(validity);
+        self
+    }
+
+    /// Public key of the issuer
+    pub fn public_key(&self) -> VerifyingKey {
+        self.signing_key.verifying_key()
+    }
+
+    /// Issue a certificate bound to the holder key, every attribute selectively disclosable
+    pub fn issue(
+        &self,
+        certificate: &CertificateData,
+        holder_key: &VerifyingKey,
+    ) -> Result<SdJwt, SdJwtError> {
+        if certificate.verify().is_err() {
+            return Err(SdJwtError::InvalidCertificate(
+                "Certificate signature verification failed".to_string(),
+            ));
+        }
+
+        let language = certificate
+            .game_path_name
+            .split('_')
+            .next()
+            .filter(|language| !language.is_empty())
+            .ok_or_else(|| SdJwtError::InvalidCertificate("Cannot extract language".to_string()))?;
+        let level = CefrLevel::from_course_name(&certificate.game_path_name).ok_or_else(|| {
+            SdJwtError::InvalidCertificate(format!(
+                "Invalid CEFR level: {}",
+                certificate.game_path_name
+            ))
+        })?;
+
+        let disclosures = vec![
+            Disclosure::new("language", json!(language)),
+            Disclosure::new("level", json!(level)),
+            Disclosure::new("score", json!(certificate.performance_percentage)),
+            Disclosure::new("date", json!(certificate.date.to_rfc3339())),
+            Disclosure::new("name", json!(certificate.profile_name)),
+        ];
+        let mut digests: Vec<String> = disclosures.iter().map(Disclosure::digest).collect();
+        digests.sort();
+
+        let now = Utc::now();
+        let mut payload = json!({
+            "iss": self.issuer_id,
+            "issuer_name": self.issuer_name,
+            "iat": now.timestamp(),
+            "vct": LANGUAGE_CERTIFICATE_VCT,
+            "cnf": { "jwk": ed25519_jwk(holder_key) },
+            "_sd_alg": "sha-256",
+            "_sd": digests,
+        });
+        if let Some(validity) = self.validity {
+            payload["exp"] = json!((now + validity).timestamp());
+        }
+
+        Ok(SdJwt {
+            issuer_jwt: encode_jwt(SD_JWT_TYP, &payload, &self.signing_key),
+            disclosures,
+            key_binding_jwt: None,
+        })
+    }
+}
+
+impl DisclosedCertificate {
+    /// Check the disclosed attributes against a claim
+    pub fn satisfies(&self, claim_type: &ClaimType) -> Result<bool, SdJwtError> {
+        match claim_type {
+            ClaimType::LanguageProficiency {
+                language,
+                min_level,
+            } => {
+                let disclosed_language = required(&self.language, "language")?;
+                if !disclosed_language.eq_ignore_ascii_case(language) {
+                    return Err(SdJwtError::InvalidCertificate(format!(
+                        "Certificate language {} does not match requested language {}",
+                        disclosed_language, language
+                    )));
+                }
+                Ok(required(&self.level, "level")? >= min_level)
+            }
+            ClaimType::PerformanceThreshold { min_percentage } => {
+                Ok(*required(&self.score, "score")? >= *min_percentage)
+            }
+            ClaimType::CompletionDate { after_date } => {
+                Ok(required(&self.date, "date")? >= after_date)
+            }
+            ClaimType::Combined { criteria } => {
+                let mut all_pass = true;
+                for criterion in criteria {
+                    all_pass &= self.satisfies(criterion)?;
+                }
+                Ok(all_pass)
+            }
+            // The disclosed certificate attributes carry no passport data
+            ClaimType::IdentityBound { .. }
+            | ClaimType::NationalityNotIn { .. }
+            | ClaimType::DocumentValid { .. } => {
+                Err(SdJwtError::MissingDisclosure("identity".to_string()))
+            }
+        }
+    }
+
+    fn from_disclosures(disclosures: &[Disclosure]) -> Result<Self, SdJwtError> {
+        let mut certificate = Self::default();
+
+        for disclosure in disclosures {
+            let invalid = || SdJwtError::Malformed(format!("attribute {}", disclosure.name));
+            let value = disclosure.value.clone();
+
+            match disclosure.name.as_str() {
+                "language" => {
+                    certificate.language =
+                        Some(serde_json::from_value(value).map_err(|_| invalid())?)
+                }
+                "level" => {
+                    certificate.level = Some(serde_json::from_value(value).map_err(|_| invalid())?)
+                }
+                "score" => {
+                    certificate.score = Some(serde_json::from_value(value).map_err(|_| invalid())?)
+                }
+                "date" => {
+                    certificate.date = Some(serde_json::from_value(value).map_err(|_| invalid())?)
+                }
+                "name" => {
+                    certificate.name = Some(serde_json::from_value(value).map_err(|_| invalid())?)
+                }
+                _ => {}
+            }
+        }
+
+        Ok(certificate)
+    }
+}
+
+impl SdJwtVerifier {
+    /// Create a verifier without trusted issuers
+    pub fn new(verifier_id: String) -> Self {
+        Self {
+            verifier_id,
+            trusted_issuers: HashMap::new(),
+            key_binding_max_age: Duration::seconds(DEFAULT_KEY_BINDING_MAX_AGE_SECS),
+            trust_config: TrustScoreConfig::default(),
+        }
+    }
+
+    /// Set how long after its issuance a key-binding JWT is accepted
+    pub fn with_key_binding_max_age(mut self, max_age: Duration) -> Self {
+        self.key_binding_max_age = max_age;
+        self
+    }
+
+    /// Set the trust score configuration
+    pub fn with_trust_config(mut self, config: TrustScoreConfig) -> Self {
+        self.trust_config = config;
+        self
+    }
+
+    /// Trust credentials issued by `issuer_id` and signed with `key`
+    pub fn add_trusted_issuer(&mut self, issuer_id: String, key: VerifyingKey) {
+        self.trusted_issuers.insert(issuer_id, key);
+    }
+
+    /// Verify a presentation for the given audience and nonce and check it against a claim
+    ///
+    /// The result has the same shape as [`crate::ZkProofVerifier::verify_proof`] for a native
+    /// proof of the same claim.
+    pub fn verify(
+        &self,
+        presentation: &str,
+        claim_type: &ClaimType,
+        audience: &str,
+        nonce: &str,
+    ) -> Result<VerificationResult, SdJwtError> {
+        let sd_jwt = SdJwt::parse(presentation)?;
+
+        let issuer_id = jwt_payload(&sd_jwt.issuer_jwt)?["iss"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| SdJwtError::Malformed("missing iss".to_string()))?;
+        let issuer_key = self
+            .trusted_issuers
+            .get(&issuer_id)
+            .ok_or_else(|| SdJwtError::UntrustedIssuer(issuer_id.clone()))?;
+        let payload = decode_jwt(&sd_jwt.issuer_jwt, SD_JWT_TYP, issuer_key)?;
+
+        let vct = payload["vct"].as_str().unwrap_or_default();
+        if vct != LANGUAGE_CERTIFICATE_VCT {
+            return Err(SdJwtError::UnsupportedType(vct.to_string()));
+        }
+        if let Some(exp) = payload["exp"].as_i64() {
+            if exp < Utc::now().timestamp() {
+                return Err(SdJwtError::Expired(exp));
+            }
+        }
+
+        let digests: HashSet<&str> = payload["_sd"]
+            .as_array()
+            .map(|digests| digests.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        let mut disclosed = HashSet::new();
+        for disclosure in &sd_jwt.disclosures {
+            if !digests.contains(disclosure.digest().as_str()) {
+                return Err(SdJwtError::UnknownDisclosure(disclosure.name.clone()));
+            }
+            if !disclosed.insert(disclosure.name.as_str()) {
+                return Err(SdJwtError::DuplicateDisclosure(disclosure.name.clone()));
+            }
+        }
+
+        self.verify_key_binding(&sd_jwt, &payload, audience, nonce)?;
+
+        let certificate = sd_jwt.disclosed_certificate()?;
+        let requirements_met = certificate.satisfies(claim_type)?;
+
+        let mut metadata = HashMap::new();
+        metadata.insert("issuer_id".to_string(), issuer_id);
+        if let Some(name) = payload["issuer_name"].as_str() {
+            metadata.insert("issuer_name".to_string(), name.to_string());
+        }
+        metadata.insert("vct".to_string(), LANGUAGE_CERTIFICATE_VCT.to_string());
+        // The issuer signature itself is checked; there is no simulated proof to fall back to
+        metadata.insert("issuer_authenticated".to_string(), true.to_string());
+        metadata.insert("proof_mode".to_string(), ProofMode::Real.to_string());
+
+        let issued_at = payload["iat"]
+            .as_i64()
+            .and_then(|iat| DateTime::from_timestamp(iat, 0))
+            .ok_or_else(|| SdJwtError::Malformed("missing iat".to_string()))?;
+        let trust_inputs = TrustInputs {
+            signature: SignatureStatus::Verified,
+            identity: IdentityStatus::None,
+            issuer_accredited: true,
+            proof_mode: ProofMode::Real,
+            issued_at,
+            revocation: RevocationStatus::Unknown,
+        };
+        let trust_score = TrustScore::compute(&trust_inputs, &self.trust_config, Utc::now())?;
+
+        Ok(VerificationResult {
+            is_valid: true,
+            requirements_met,
+            details: VerificationDetails {
+                platform: SD_JWT_PLATFORM.to_string(),
+                circuit_id: claim_type.circuit_id().to_string(),
+                verified_at: Utc::now(),
+                verified_inputs: claim_type.requirements(),
+                metadata,
+                signers: Vec::new(),
+            },
+            warnings: if requirements_met {
+                vec![]
+            } else {
+                vec![unmet_warning(claim_type.kind()).to_string()]
+            },
+            trust_score: Some(trust_score),
+        })
+    }
+
+    /// Check the key-binding JWT against the `cnf` key, audience, nonce, freshness window and
+    /// disclosures
+    fn verify_key_binding(
+        &self,
+        sd_jwt: &SdJwt,
+        payload: &Value,
+        audience: &str,
+        nonce: &str,
+    ) -> Result<(), SdJwtError> {
+        let kb_jwt = sd_jwt
+            .key_binding_jwt
+            .as_deref()
+            .ok_or(SdJwtError::MissingKeyBinding)?;
+        let holder_key = jwk_to_key(&payload["cnf"]["jwk"])?;
+        let kb_payload = decode_jwt(kb_jwt, KB_JWT_TYP, &holder_key)?;
+
+        if kb_payload["aud"].as_str() != Some(audience) {
+            return Err(SdJwtError::KeyBindingMismatch("aud".to_string()));
+        }
+        if kb_payload["nonce"].as_str() != Some(nonce) {
+            return Err(SdJwtError::KeyBindingMismatch("nonce".to_string()));
+        }
+        let iat = kb_payload["iat"]
+            .as_i64()
+            .ok_or_else(|| SdJwtError::Malformed("missing key-binding iat".to_string()))?;
+        let now = Utc::now().timestamp();
+        if iat > now + KEY_BINDING_CLOCK_SKEW_SECS
+            || iat < now - self.key_binding_max_age.num_seconds()
+        {
+            return Err(SdJwtError::StaleKeyBinding(iat));
+        }
+        if kb_payload["sd_hash"].as_str() != Some(sd_jwt.sd_hash().as_str()) {
+            return Err(SdJwtError::KeyBindingMismatch("sd_hash".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+fn required<'a, T>(value: &'a Option<T>, name: &str) -> Result<&'a T, SdJwtError> {
+    value
+        .as_ref()
+        .ok_or_else(|| SdJwtError::MissingDisclosure(name.to_string()))
+}
+
+fn ed25519_jwk(key: &VerifyingKey) -> Value {
+    json!({
+        "kty": "OKP",
+        "crv": "Ed25519",
+        "x": URL_SAFE_NO_PAD.encode(key.as_bytes()),
+    })
+}
+
+fn jwk_to_key(jwk: &Value) -> Result<VerifyingKey, SdJwtError> {
+    if jwk["kty"] != "OKP" || jwk["crv"] != "Ed25519" {
+        return Err(SdJwtError::Malformed(
+            "cnf key is not an Ed25519 JWK".to_string(),
+        ));
+    }
+
+    let bytes: [u8; 32] = jwk["x"]
+        .as_str()
+        .and_then(|x| URL_SAFE_NO_PAD.decode(x).ok())
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| SdJwtError::Malformed("cnf key".to_string()))?;
+    VerifyingKey::from_bytes(&bytes).map_err(|e| SdJwtError::Malformed(e.to_string()))
+}
+
+pub(crate) fn encode_jwt(typ: &str, payload: &Value, key: &SigningKey) -> String {
+    let header = json!({ "alg": "EdDSA", "typ": typ });
+    let signing_input = format!(
+        "{}.{}",
+        URL_SAFE_NO_PAD.encode(header.to_string()),
+        URL_SAFE_NO_PAD.encode(payload.to_string())
+    );
+    let signature = key.sign(signing_input.as_bytes());
+    format!(
+        "{}.{}",
+        signing_input,
+        URL_SAFE_NO_PAD.encode(signature.to_bytes())
+    )
+}
+
+/// Payload of a JWT without checking its signature
+fn jwt_payload(jwt: &str) -> Result<Value, SdJwtError> {
+    let payload = jwt
+        .split('.')
+        .nth(1)
+        .ok_or_else(|| SdJwtError::Malformed("JWT".to_string()))?;
+    decode_segment(payload)
+}
+
+/// Check the header type and signature of a JWT and return its payload
+pub(crate) fn decode_jwt(jwt: &str, typ: &str, key: &VerifyingKey) -> Result<Value, SdJwtError> {
+    let (signing_input, signature) = jwt
+        .rsplit_once('.')
+        .ok_or_else(|| SdJwtError::Malformed("JWT".to_string()))?;
+    let (header, payload) = signing_input
+        .split_once('.')
+        .ok_or_else(|| SdJwtError::Malformed("JWT".to_string()))?;
+
+    let header = decode_segment(header)?;
+    if header["alg"] != "EdDSA" || header["typ"] != typ {
+        return Err(SdJwtError::Malformed(format!("expected {} JWT", typ)));
+    }
+
+    let signature: [u8; 64] = URL_SAFE_NO_PAD
+        .decode(signature)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| SdJwtError::InvalidSignature("malformed signature".to_string()))?;
+    key.verify(signing_input.as_bytes(), &Signature::from_bytes(&signature))
+        .map_err(|e| SdJwtError::InvalidSignature(e.to_string()))?;
+
+    decode_segment(payload)
+}
+
+fn decode_segment(segment: &str) -> Result<Value, SdJwtError> {
+    URL_SAFE_NO_PAD
+        .decode(segment)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .ok_or_else(|| SdJwtError::Malformed("JWT segment".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::holder::Holder;
+    use crate::issuer::{CertificateIssuer, ProofOptions, ProofRequest};
+    use crate::verifier::ZkProofVerifier;
+
+    const AUDIENCE: &str = "verifier.example";
+    const NONCE: &str = "nonce-1";
+
+    fn create_test_certificate() -> CertificateData {
+        CertificateData::new(
+            "German_B2_Complete".to_string(),
+            7,
+            7,
+            "Test Student".to_string(),
+            Utc::now(),
+        )
+    }
+
+    fn create_test_issuer() -> SdJwtIssuer {
+        SdJwtIssuer::new(
+            "test_issuer".to_string(),
+            "Test Issuer".to_string(),
+            SigningKey::from_bytes(&[5u8; 32]),
+        )
+    }
+
+    fn create_test_verifier(issuer: &SdJwtIssuer) -> SdJwtVerifier {
+        let mut verifier = SdJwtVerifier::new("test_verifier".to_string());
+        verifier.add_trusted_issuer(issuer.issuer_id.clone(), issuer.public_key());
+        verifier
+    }
+
+    fn language_claim(min_level: CefrLevel) -> ClaimType {
+        ClaimType::LanguageProficiency {
+            language: "German".to_string(),
+            min_level,
+        }
+    }
+
+    fn present(holder: &Holder, names: &[&str]) -> String {
+        let sd_jwt = create_test_issuer()
+            .issue(&create_test_certificate(), &holder.public_key())
+            .unwrap();
+        holder
+            .present_sd_jwt(&sd_jwt, names, AUDIENCE, NONCE)
+            .serialize()
+    }
+
+    #[test]
+    fn test_issue_discloses_every_attribute() {
+        let holder = Holder::generate();
+        let sd_jwt = create_test_issuer()
+            .issue(&create_test_certificate(), &holder.public_key())
+            .unwrap();
+
+        let names: Vec<&str> = sd_jwt.disclosures.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, CERTIFICATE_ATTRIBUTES);
+
+        let payload = jwt_payload(&sd_jwt.issuer_jwt).unwrap();
+        assert_eq!(payload["vct"], LANGUAGE_CERTIFICATE_VCT);
+        assert_eq!(payload["_sd"].as_array().unwrap().len(), 5);
+        assert!(payload.get("language").is_none());
+        assert_eq!(payload["cnf"]["jwk"], ed25519_jwk(&holder.public_key()));
+    }
+
+    #[test]
+    fn test_serialization_round_trip() {
+        let holder = Holder::generate();
+        let sd_jwt = create_test_issuer()
+            .issue(&create_test_certificate(), &holder.public_key())
+            .unwrap();
+
+        let serialized = sd_jwt.serialize();
+        assert!(serialized.ends_with('~'));
+        assert_eq!(SdJwt::parse(&serialized).unwrap(), sd_jwt);
+
+        let bound = sd_jwt.bind(&SigningKey::from_bytes(&[6u8; 32]), AUDIENCE, NONCE);
+        assert_eq!(SdJwt::parse(&bound.serialize()).unwrap(), bound);
+    }
+
+    #[test]
+    fn test_disclosure_digest() {
+        let disclosure = Disclosure::new("level", json!("B2"));
+        let parsed = Disclosure::parse(disclosure.encoded()).unwrap();
+
+        assert_eq!(parsed, disclosure);
+        assert_eq!(parsed.digest(), disclosure.digest());
+        assert_ne!(
+            Disclosure::new("level", json!("B2")).digest(),
+            disclosure.digest()
+        );
+    }
+
+    #[test]
+    fn test_same_result_as_native_proof() {
+        let claim_type = ClaimType::Combined {
+            criteria: vec![
+                language_claim(CefrLevel::B1),
+                ClaimType::PerformanceThreshold { min_percentage: 80 },
+            ],
+        };
+        let native = CertificateIssuer::new("test_issuer".to_string(), "Test Issuer".to_string())
+            .accept_unsigned_certificates()
+            .generate_proof(ProofRequest {
+                certificate: create_test_certificate(),
+                claim_type: claim_type.clone(),
+                target_platform: "test".to_string(),
+                options: ProofOptions::default(),
+            })
+            .unwrap();
+        let native_result = ZkProofVerifier::new("test_verifier".to_string())
+            .verify_proof(&native)
+            .unwrap();
+
+        let holder = Holder::generate();
+        let result = create_test_verifier(&create_test_issuer())
+            .verify(
+                &present(&holder, &CERTIFICATE_ATTRIBUTES),
+                &claim_type,
+                AUDIENCE,
+                NONCE,
+            )
+            .unwrap();
+
+        assert_eq!(result.is_valid, native_result.is_valid);
+        assert_eq!(result.requirements_met, native_result.requirements_met);
+        assert_eq!(result.details.circuit_id, native_result.details.circuit_id);
+        assert_eq!(
+            result.details.verified_inputs,
+            native_result.details.verified_inputs
+        );
+        assert_eq!(
+            result.details.metadata["issuer_id"],
+            native_result.details.metadata["issuer_id"]
+        );
+        assert_eq!(result.details.metadata["proof_mode"], "real");
+        assert_eq!(result.details.metadata["issuer_authenticated"], "true");
+        assert!(result.trust_score.unwrap().score > native_result.trust_score.unwrap().score);
+    }
+
+    #[test]
+    fn test_selective_disclosure() {
+        let holder = Holder::generate();
+        let presentation = present(&holder, &["language", "level"]);
+        let verifier = create_test_verifier(&create_test_issuer());
+
+        assert_eq!(SdJwt::parse(&presentation).unwrap().disclosures.len(), 2);
+        assert!(
+            verifier
+                .verify(
+                    &presentation,
+                    &language_claim(CefrLevel::B2),
+                    AUDIENCE,
+                    NONCE
+                )
+                .unwrap()
+                .requirements_met
+        );
+        assert_eq!(
+            verifier.verify(
+                &presentation,
+                &ClaimType::PerformanceThreshold { min_percentage: 80 },
+                AUDIENCE,
+                NONCE
+            ),
+            Err(SdJwtError::MissingDisclosure("score".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_unmet_claim() {
+        let holder = Holder::generate();
+        let result = create_test_verifier(&create_test_issuer())
+            .verify(
+                &present(&holder, &["language", "level"]),
+                &language_claim(CefrLevel::C1),
+                AUDIENCE,
+                NONCE,
+            )
+            .unwrap();
+
+        assert!(result.is_valid);
+        assert!(!result.requirements_met);
+        assert_eq!(
+            result.warnings,
+            vec!["Language proficiency requirements not met".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_key_binding_required_and_checked() {
+        let holder = Holder::generate();
+        let issuer = create_test_issuer();
+        let verifier = create_test_verifier(&issuer);
+        let claim = language_claim(CefrLevel::B1);
+        let sd_jwt = issuer
+            .issue(&create_test_certificate(), &holder.public_key())
+            .unwrap();
+
+        assert_eq!(
+            verifier.verify(&sd_jwt.serialize(), &claim, AUDIENCE, NONCE),
+            Err(SdJwtError::MissingKeyBinding)
+        );
+
+        let presentation = present(&holder, &CERTIFICATE_ATTRIBUTES);
+        assert_eq!(
+            verifier.verify(&presentation, &claim, AUDIENCE, "nonce-2"),
+            Err(SdJwtError::KeyBindingMismatch("nonce".to_string()))
+        );
+        assert_eq!(
+            verifier.verify(&presentation, &claim, "other.example", NONCE),
+            Err(SdJwtError::KeyBindingMismatch("aud".to_string()))
+        );
+
+        let stolen = sd_jwt.bind(&SigningKey::from_bytes(&[6u8; 32]), AUDIENCE, NONCE);
+        assert!(matches!(
+            verifier.verify(&stolen.serialize(), &claim, AUDIENCE, NONCE),
+            Err(SdJwtError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn test_stale_key_binding_rejected() {
+        let holder_key = SigningKey::from_bytes(&[8u8; 32]);
+        let holder = Holder::from_signing_key("test_holder".to_string(), holder_key.clone());
+        let issuer = create_test_issuer();
+        let verifier = create_test_verifier(&issuer);
+        let claim = language_claim(CefrLevel::B1);
+        let mut sd_jwt = issuer
+            .issue(&create_test_certificate(), &holder.public_key())
+            .unwrap();
+
+        let issued_at = (Utc::now() - Duration::minutes(10)).timestamp();
+        let payload = json!({
+            "iat": issued_at,
+            "aud": AUDIENCE,
+            "nonce": NONCE,
+            "sd_hash": sd_jwt.sd_hash(),
+        });
+        sd_jwt.key_binding_jwt = Some(encode_jwt(KB_JWT_TYP, &payload, &holder_key));
+        let presentation = sd_jwt.serialize();
+
+        assert_eq!(
+            verifier.verify(&presentation, &claim, AUDIENCE, NONCE),
+            Err(SdJwtError::StaleKeyBinding(issued_at))
+        );
+        assert!(verifier
+            .with_key_binding_max_age(Duration::minutes(15))
+            .verify(&presentation, &claim, AUDIENCE, NONCE)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_forged_disclosure_rejected() {
+        let holder = Holder::generate();
+        let issuer = create_test_issuer();
+        let mut sd_jwt = issuer
+            .issue(&create_test_certificate(), &holder.public_key())
+            .unwrap()
+            .disclose(&["language"]);
+        sd_jwt
+            .disclosures
+            .push(Disclosure::new("level", json!("C2")));
+        let presentation = holder.present_sd_jwt(&sd_jwt, &["language", "level"], AUDIENCE, NONCE);
+
+        assert_eq!(
+            create_test_verifier(&issuer).verify(
+                &presentation.serialize(),
+                &language_claim(CefrLevel::C2),
+                AUDIENCE,
+                NONCE
+            ),
+            Err(SdJwtError::UnknownDisclosure("level".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_untrusted_issuer_rejected() {
+        let holder = Holder::generate();
+        let verifier = SdJwtVerifier::new("test_verifier".to_string());
+
+        assert_eq!(
+            verifier.verify(
+                &present(&holder, &CERTIFICATE_ATTRIBUTES),
+                &language_claim(CefrLevel::B1),
+                AUDIENCE,
+                NONCE
+            ),
+            Err(SdJwtError::UntrustedIssuer("test_issuer".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_expired_credential_rejected() {
+        let holder = Holder::generate();
+        let issuer = create_test_issuer().with_validity(Duration::seconds(-1));
+        let sd_jwt = issuer
+            .issue(&create_test_certificate(), &holder.public_key())
+            .unwrap();
+        let presentation = holder.present_sd_jwt(&sd_jwt, &CERTIFICATE_ATTRIBUTES, AUDIENCE, NONCE);
+
+        assert!(matches!(
+            create_test_verifier(&issuer).verify(
+                &presentation.serialize(),
+                &language_claim(CefrLevel::B1),
+                AUDIENCE,
+                NONCE
+            ),
+            Err(SdJwtError::Expired(_))
+        ));
+    }
+
+    #[test]
+    fn test_language_mismatch_rejected() {
+        let holder = Holder::generate();
+
+        assert_eq!(
+            create_test_verifier(&create_test_issuer()).verify(
+                &present(&holder, &["language", "level"]),
+                &ClaimType::LanguageProficiency {
+                    language: "French".to_string(),
+                    min_level: CefrLevel::A1,
+                },
+                AUDIENCE,
+                NONCE
+            ),
+            Err(SdJwtError::InvalidCertificate(
+                "Certificate language German does not match requested language French".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_unsupported_type_rejected() {
+        let holder = Holder::generate();
+        let issuer = create_test_issuer();
+        let mut sd_jwt = issuer
+            .issue(&create_test_certificate(), &holder.public_key())
+            .unwrap();
+        let mut payload = jwt_payload(&sd_jwt.issuer_jwt).unwrap();
+        payload["vct"] = json!("https://example.com/other");
+        sd_jwt.issuer_jwt = encode_jwt(SD_JWT_TYP, &payload, &issuer.signing_key);
+        let presentation = holder.present_sd_jwt(&sd_jwt, &CERTIFICATE_ATTRIBUTES, AUDIENCE, NONCE);
+
+        assert_eq!(
+            create_test_verifier(&issuer).verify(
+                &presentation.serialize(),
+                &language_claim(CefrLevel::B1),
+                AUDIENCE,
+                NONCE
+            ),
+            Err(SdJwtError::UnsupportedType(
+                "https://example.com/other".to_string()
+            ))
+        );
+    }
+}
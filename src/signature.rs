@@ -0,0 +1,510 @@
+//! Agile issuer signatures.
+//!
+//! Issuers sign with Ed25519 by default. With the `post-quantum` feature they can sign with
+//! ML-DSA-65 (FIPS 204), or with both in a hybrid mode that holds as long as either scheme
+//! does. The algorithm is recorded in the `issuer_signature_algorithm` metadata property,
+//! which the signature covers, so a hybrid signature cannot be stripped down to one half.
+//! Credential status lists are signed with the same keys, see [`crate::status_list`].
+
+use crate::zk_proof::{
+    ISSUER_PQ_SIGNATURE_PROPERTY, ISSUER_SIGNATURE_ALGORITHM_PROPERTY, ISSUER_SIGNATURE_PROPERTY,
+};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[cfg(feature = "post-quantum")]
+use mysten_mldsa_native_rs as ml_dsa;
+#[cfg(feature = "post-quantum")]
+use rand::{rngs::OsRng, RngCore};
+#[cfg(feature = "post-quantum")]
+use zeroize::Zeroizing;
+
+/// FIPS 204 context string of ML-DSA issuer signatures
+pub const ML_DSA_CONTEXT: &[u8] = b"web5claims";
+
+#[derive(Error, Debug, PartialEq)]
+pub enum SignatureError {
+    #[error("Unknown signature algorithm: {0}")]
+    UnknownAlgorithm(String),
+    #[error("Signature algorithm not supported by this build: {0}")]
+    UnsupportedAlgorithm(SignatureAlgorithm),
+    #[error("Algorithm mismatch: signed with {signed}, key is {key}")]
+    AlgorithmMismatch {
+        signed: SignatureAlgorithm,
+        key: SignatureAlgorithm,
+    },
+    #[error("Invalid public key: {0}")]
+    InvalidKey(String),
+    #[error("Missing {0} signature")]
+    MissingSignature(String),
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
+}
+
+/// Signature algorithm of an issuer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum SignatureAlgorithm {
+    /// Classical Ed25519
+    #[default]
+    #[serde(rename = "Ed25519")]
+    Ed25519,
+    /// Post-quantum ML-DSA-65
+    #[serde(rename = "ML-DSA-65")]
+    MlDsa65,
+    /// Ed25519 and ML-DSA-65, both of which must verify
+    #[serde(rename = "Ed25519+ML-DSA-65")]
+    Ed25519MlDsa65,
+}
+
+/// ML-DSA-65 signing key, kept as its 32-byte FIPS 204 seed
+#[cfg(feature = "post-quantum")]
+#[derive(Clone)]
+pub struct MlDsaSigningKey {
+    seed: Zeroizing<[u8; 32]>,
+}
+
+/// Key an issuer signs with
+#[derive(Debug, Clone)]
+pub enum IssuerSigningKey {
+    Ed25519(SigningKey),
+    #[cfg(feature = "post-quantum")]
+    MlDsa65(MlDsaSigningKey),
+    #[cfg(feature = "post-quantum")]
+    Ed25519MlDsa65(SigningKey, MlDsaSigningKey),
+}
+
+/// Public key verifying issuer signatures
+#[derive(Debug, Clone, PartialEq)]
+pub enum IssuerPublicKey {
+    Ed25519(VerifyingKey),
+    #[cfg(feature = "post-quantum")]
+    MlDsa65(ml_dsa::VerifyingKey),
+    #[cfg(feature = "post-quantum")]
+    Ed25519MlDsa65(VerifyingKey, ml_dsa::VerifyingKey),
+}
+
+/// Issuer signature with its algorithm
+#[derive(Debug, Clone, PartialEq)]
+pub struct IssuerSignature {
+    /// Algorithm the signature was made with
+    pub algorithm: SignatureAlgorithm,
+    /// Ed25519 signature, for Ed25519 and hybrid signatures
+    pub ed25519: Option<Vec<u8>>,
+    /// ML-DSA-65 signature, for ML-DSA and hybrid signatures
+    pub ml_dsa: Option<Vec<u8>>,
+}
+
+impl SignatureAlgorithm {
+    /// Identifier recorded in proof metadata
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SignatureAlgorithm::Ed25519 => "Ed25519",
+            SignatureAlgorithm::MlDsa65 => "ML-DSA-65",
+            SignatureAlgorithm::Ed25519MlDsa65 => "Ed25519+ML-DSA-65",
+        }
+    }
+}
+
+impl fmt::Display for SignatureAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SignatureAlgorithm {
+    type Err = SignatureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Ed25519" => Ok(SignatureAlgorithm::Ed25519),
+            "ML-DSA-65" => Ok(SignatureAlgorithm::MlDsa65),
+            "Ed25519+ML-DSA-65" => Ok(SignatureAlgorithm::Ed25519MlDsa65),
+            _ => Err(SignatureError::UnknownAlgorithm(s.to_string())),
+        }
+    }
+}
+
+#[cfg(feature = "post-quantum")]
+impl MlDsaSigningKey {
+    /// Generate a random key
+    pub fn generate() -> Self {
+        let mut seed = Zeroizing::new([0u8; 32]);
+        OsRng.fill_bytes(&mut *seed);
+        Self { seed }
+    }
+
+    /// Key expanded from a FIPS 204 seed
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        Self {
+            seed: Zeroizing::new(seed),
+        }
+    }
+
+    /// FIPS 204 seed of the key
+    pub fn seed(&self) -> &[u8; 32] {
+        &self.seed
+    }
+
+    /// Public key
+    pub fn verifying_key(&self) -> ml_dsa::VerifyingKey {
+        ml_dsa::SigningKeySeed::from(*self.seed).expand().1
+    }
+
+    /// Sign with hedged randomness
+    fn sign(&self, message: &[u8]) -> Vec<u8> {
+        let (signing_key, _) = ml_dsa::SigningKeySeed::from(*self.seed).expand();
+        let mut rnd = [0u8; ml_dsa::RND_LENGTH];
+        OsRng.fill_bytes(&mut rnd);
+
+        signing_key
+            .sign(message, ML_DSA_CONTEXT, &rnd)
+            .expect("context string fits")
+            .as_bytes()
+            .to_vec()
+    }
+}
+
+#[cfg(feature = "post-quantum")]
+impl fmt::Debug for MlDsaSigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MlDsaSigningKey(<redacted>)")
+    }
+}
+
+impl IssuerSigningKey {
+    /// Algorithm the key signs with
+    pub fn algorithm(&self) -> SignatureAlgorithm {
+        match self {
+            IssuerSigningKey::Ed25519(_) => SignatureAlgorithm::Ed25519,
+            #[cfg(feature = "post-quantum")]
+            IssuerSigningKey::MlDsa65(_) => SignatureAlgorithm::MlDsa65,
+            #[cfg(feature = "post-quantum")]
+            IssuerSigningKey::Ed25519MlDsa65(..) => SignatureAlgorithm::Ed25519MlDsa65,
+        }
+    }
+
+    /// Ed25519 part of the key, if any
+    pub fn ed25519(&self) -> Option<&SigningKey> {
+        match self {
+            IssuerSigningKey::Ed25519(key) => Some(key),
+            #[cfg(feature = "post-quantum")]
+            IssuerSigningKey::MlDsa65(_) => None,
+            #[cfg(feature = "post-quantum")]
+            IssuerSigningKey::Ed25519MlDsa65(key, _) => Some(key),
+        }
+    }
+
+    /// Public key
+    pub fn public_key(&self) -> IssuerPublicKey {
+        match self {
+            IssuerSigningKey::Ed25519(key) => IssuerPublicKey::Ed25519(key.verifying_key()),
+            #[cfg(feature = "post-quantum")]
+            IssuerSigningKey::MlDsa65(key) => IssuerPublicKey::MlDsa65(key.verifying_key()),
+            #[cfg(feature = "post-quantum")]
+            IssuerSigningKey::Ed25519MlDsa65(ed25519, ml_dsa) => {
+                IssuerPublicKey::Ed25519MlDsa65(ed25519.verifying_key(), ml_dsa.verifying_key())
+            }
+        }
+    }
+
+    /// Sign a message
+    pub fn sign(&self, message: &[u8]) -> IssuerSignature {
+        let ed25519 = self
+            .ed25519()
+            .map(|key| key.sign(message).to_bytes().to_vec());
+        let ml_dsa = match self {
+            #[cfg(feature = "post-quantum")]
+            IssuerSigningKey::MlDsa65(key) | IssuerSigningKey::Ed25519MlDsa65(_, key) => {
+                Some(key.sign(message))
+            }
+            _ => None,
+        };
+
+        IssuerSignature {
+            algorithm: self.algorithm(),
+            ed25519,
+            ml_dsa,
+        }
+    }
+}
+
+impl From<SigningKey> for IssuerSigningKey {
+    fn from(signing_key: SigningKey) -> Self {
+        IssuerSigningKey::Ed25519(signing_key)
+    }
+}
+
+impl IssuerPublicKey {
+    /// Algorithm the key verifies
+    pub fn algorithm(&self) -> SignatureAlgorithm {
+        match self {
+            IssuerPublicKey::Ed25519(_) => SignatureAlgorithm::Ed25519,
+            #[cfg(feature = "post-quantum")]
+            IssuerPublicKey::MlDsa65(_) => SignatureAlgorithm::MlDsa65,
+            #[cfg(feature = "post-quantum")]
+            IssuerPublicKey::Ed25519MlDsa65(..) => SignatureAlgorithm::Ed25519MlDsa65,
+        }
+    }
+
+    /// Ed25519 part of the key, if any
+    pub fn ed25519(&self) -> Option<&VerifyingKey> {
+        match self {
+            IssuerPublicKey::Ed25519(key) => Some(key),
+            #[cfg(feature = "post-quantum")]
+            IssuerPublicKey::MlDsa65(_) => None,
+            #[cfg(feature = "post-quantum")]
+            IssuerPublicKey::Ed25519MlDsa65(key, _) => Some(key),
+        }
+    }
+
+    /// Key bytes; hybrid keys are the Ed25519 key followed by the ML-DSA key
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            IssuerPublicKey::Ed25519(key) => key.as_bytes().to_vec(),
+            #[cfg(feature = "post-quantum")]
+            IssuerPublicKey::MlDsa65(key) => key.as_bytes().to_vec(),
+            #[cfg(feature = "post-quantum")]
+            IssuerPublicKey::Ed25519MlDsa65(ed25519, ml_dsa) => {
+                [ed25519.as_bytes().as_slice(), ml_dsa.as_bytes()].concat()
+            }
+        }
+    }
+
+    /// Parse key bytes of an algorithm
+    pub fn from_bytes(algorithm: SignatureAlgorithm, bytes: &[u8]) -> Result<Self, SignatureError> {
+        let invalid = |e: String| SignatureError::InvalidKey(e);
+        let ed25519 = |bytes: &[u8]| {
+            let bytes: [u8; 32] = bytes
+                .try_into()
+                .map_err(|_| invalid("Ed25519 key must be 32 bytes".to_string()))?;
+            VerifyingKey::from_bytes(&bytes).map_err(|e| invalid(e.to_string()))
+        };
+
+        match algorithm {
+            SignatureAlgorithm::Ed25519 => Ok(IssuerPublicKey::Ed25519(ed25519(bytes)?)),
+            #[cfg(feature = "post-quantum")]
+            SignatureAlgorithm::MlDsa65 => ml_dsa::VerifyingKey::from_bytes(bytes)
+                .map(IssuerPublicKey::MlDsa65)
+                .map_err(|e| invalid(e.to_string())),
+            #[cfg(feature = "post-quantum")]
+            SignatureAlgorithm::Ed25519MlDsa65 => {
+                if bytes.len() < 32 {
+                    return Err(invalid("hybrid key is too short".to_string()));
+                }
+                let (ed25519_bytes, ml_dsa_bytes) = bytes.split_at(32);
+                Ok(IssuerPublicKey::Ed25519MlDsa65(
+                    ed25519(ed25519_bytes)?,
+                    ml_dsa::VerifyingKey::from_bytes(ml_dsa_bytes)
+                        .map_err(|e| invalid(e.to_string()))?,
+                ))
+            }
+            #[cfg(not(feature = "post-quantum"))]
+            _ => Err(SignatureError::UnsupportedAlgorithm(algorithm)),
+        }
+    }
+
+    /// Verify a signature, requiring every part of a hybrid signature to hold
+    pub fn verify(
+        &self,
+        message: &[u8],
+        signature: &IssuerSignature,
+    ) -> Result<(), SignatureError> {
+        if signature.algorithm != self.algorithm() {
+            return Err(SignatureError::AlgorithmMismatch {
+                signed: signature.algorithm,
+                key: self.algorithm(),
+            });
+        }
+
+        if let Some(key) = self.ed25519() {
+            let bytes: [u8; 64] = signature
+                .ed25519
+                .as_deref()
+                .and_then(|bytes| bytes.try_into().ok())
+                .ok_or_else(|| SignatureError::MissingSignature("Ed25519".to_string()))?;
+            key.verify(message, &Signature::from_bytes(&bytes))
+                .map_err(|e| SignatureError::InvalidSignature(e.to_string()))?;
+        }
+
+        #[cfg(feature = "post-quantum")]
+        if let IssuerPublicKey::MlDsa65(key) | IssuerPublicKey::Ed25519MlDsa65(_, key) = self {
+            let pq_signature = signature
+                .ml_dsa
+                .as_deref()
+                .and_then(|bytes| ml_dsa::Signature::from_bytes(bytes).ok())
+                .ok_or_else(|| SignatureError::MissingSignature("ML-DSA-65".to_string()))?;
+            key.verify(message, ML_DSA_CONTEXT, &pq_signature)
+                .map_err(|e| SignatureError::InvalidSignature(e.to_string()))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl IssuerSignature {
+    /// Read the signature from claim metadata; claims without an algorithm are Ed25519
+    pub fn from_properties(properties: &HashMap<String, String>) -> Result<Self, SignatureError> {
+        let algorithm = properties
+            .get(ISSUER_SIGNATURE_ALGORITHM_PROPERTY)
+            .map(|algorithm| algorithm.parse())
+            .transpose()?
+            .unwrap_or_default();
+        let decode = |property: &str| {
+            properties
+                .get(property)
+                .and_then(|signature| hex::decode(signature).ok())
+        };
+
+        Ok(Self {
+            algorithm,
+            ed25519: decode(ISSUER_SIGNATURE_PROPERTY),
+            ml_dsa: decode(ISSUER_PQ_SIGNATURE_PROPERTY),
+        })
+    }
+
+    /// Write the signature into claim metadata
+    ///
+    /// The algorithm property must already be in place when signing, since it is covered by
+    /// the signature.
+    pub fn write_properties(&self, properties: &mut HashMap<String, String>) {
+        if let Some(signature) = &self.ed25519 {
+            properties.insert(
+                ISSUER_SIGNATURE_PROPERTY.to_string(),
+                hex::encode(signature),
+            );
+        }
+        if let Some(signature) = &self.ml_dsa {
+            properties.insert(
+                ISSUER_PQ_SIGNATURE_PROPERTY.to_string(),
+                hex::encode(signature),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_ed25519_key() -> IssuerSigningKey {
+        SigningKey::from_bytes(&[7u8; 32]).into()
+    }
+
+    #[cfg(feature = "post-quantum")]
+    fn create_test_hybrid_key() -> IssuerSigningKey {
+        IssuerSigningKey::Ed25519MlDsa65(
+            SigningKey::from_bytes(&[7u8; 32]),
+            MlDsaSigningKey::from_seed([8u8; 32]),
+        )
+    }
+
+    #[test]
+    fn test_algorithm_identifiers() {
+        for algorithm in [
+            SignatureAlgorithm::Ed25519,
+            SignatureAlgorithm::MlDsa65,
+            SignatureAlgorithm::Ed25519MlDsa65,
+        ] {
+            assert_eq!(
+                algorithm.as_str().parse::<SignatureAlgorithm>(),
+                Ok(algorithm)
+            );
+            assert_eq!(
+                serde_json::to_value(algorithm).unwrap(),
+                serde_json::json!(algorithm.as_str())
+            );
+        }
+        assert_eq!(
+            "RSA".parse::<SignatureAlgorithm>(),
+            Err(SignatureError::UnknownAlgorithm("RSA".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_ed25519_sign_and_verify() {
+        let key = create_test_ed25519_key();
+        let signature = key.sign(b"claim");
+
+        assert_eq!(signature.algorithm, SignatureAlgorithm::Ed25519);
+        assert!(signature.ml_dsa.is_none());
+        assert!(key.public_key().verify(b"claim", &signature).is_ok());
+        assert!(matches!(
+            key.public_key().verify(b"other claim", &signature),
+            Err(SignatureError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn test_properties_round_trip() {
+        let key = create_test_ed25519_key();
+        let mut properties = HashMap::new();
+        key.sign(b"claim").write_properties(&mut properties);
+
+        // Claims signed before algorithm agility carry no algorithm and are Ed25519
+        let signature = IssuerSignature::from_properties(&properties).unwrap();
+        assert_eq!(signature, key.sign(b"claim"));
+
+        properties.insert(
+            ISSUER_SIGNATURE_ALGORITHM_PROPERTY.to_string(),
+            "RSA".to_string(),
+        );
+        assert!(IssuerSignature::from_properties(&properties).is_err());
+    }
+
+    #[cfg(feature = "post-quantum")]
+    #[test]
+    fn test_ml_dsa_sign_and_verify() {
+        let key = IssuerSigningKey::MlDsa65(MlDsaSigningKey::from_seed([9u8; 32]));
+        let signature = key.sign(b"claim");
+
+        assert!(signature.ed25519.is_none());
+        assert_eq!(
+            signature.ml_dsa.as_ref().map(Vec::len),
+            Some(ml_dsa::SIGNATURE_LENGTH)
+        );
+        assert!(key.public_key().verify(b"claim", &signature).is_ok());
+        assert!(key.public_key().verify(b"other claim", &signature).is_err());
+        assert!(key.public_key().ed25519().is_none());
+    }
+
+    #[cfg(feature = "post-quantum")]
+    #[test]
+    fn test_hybrid_requires_both_signatures() {
+        let key = create_test_hybrid_key();
+        let public_key = key.public_key();
+        let signature = key.sign(b"claim");
+        assert!(public_key.verify(b"claim", &signature).is_ok());
+
+        let mut classical_only = signature.clone();
+        classical_only.ml_dsa = None;
+        assert_eq!(
+            public_key.verify(b"claim", &classical_only),
+            Err(SignatureError::MissingSignature("ML-DSA-65".to_string()))
+        );
+
+        let mut downgraded = classical_only;
+        downgraded.algorithm = SignatureAlgorithm::Ed25519;
+        assert!(matches!(
+            public_key.verify(b"claim", &downgraded),
+            Err(SignatureError::AlgorithmMismatch { .. })
+        ));
+    }
+
+    #[cfg(feature = "post-quantum")]
+    #[test]
+    fn test_public_key_bytes_round_trip() {
+        let public_key = create_test_hybrid_key().public_key();
+        let bytes = public_key.to_bytes();
+
+        assert_eq!(bytes.len(), 32 + ml_dsa::PUBLIC_KEY_LENGTH);
+        assert_eq!(
+            IssuerPublicKey::from_bytes(SignatureAlgorithm::Ed25519MlDsa65, &bytes),
+            Ok(public_key)
+        );
+        assert!(IssuerPublicKey::from_bytes(SignatureAlgorithm::MlDsa65, &bytes).is_err());
+    }
+}
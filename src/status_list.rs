@@ -0,0 +1,361 @@
+//! Signed credential status lists.
+//!
+//! A [`StatusList`] holds one revocation bit per credential and is published at the URL the
+//! [`CredentialStatus`] entries of credentials point to. Issuers sign it with their issuer
+//! key, so a status list carries the same Ed25519, ML-DSA or hybrid signature as a claim, see
+//! [`CertificateIssuer::sign_status_list`](crate::issuer::CertificateIssuer::sign_status_list).
+//!
+//! The bitstring is ordered like a W3C Bitstring Status List, index 0 being the most
+//! significant bit of the first byte, but encoded as plain base64url without GZIP.
+
+use crate::keystore::TrustedKeySet;
+use crate::signature::{
+    IssuerPublicKey, IssuerSignature, IssuerSigningKey, SignatureAlgorithm, SignatureError,
+};
+use crate::vc::CredentialStatus;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Entries of a status list, the W3C minimum that keeps single credentials from standing out
+pub const DEFAULT_STATUS_LIST_SIZE: usize = 131_072;
+
+/// Status purpose of revocation lists
+pub const REVOCATION_PURPOSE: &str = "revocation";
+
+/// Default age after which verifiers no longer rely on a status list
+pub const DEFAULT_STATUS_LIST_MAX_AGE_HOURS: i64 = 24;
+/// How far a status list may be signed in the verifier's future, for clock skew
+const STATUS_LIST_CLOCK_SKEW_SECS: i64 = 60;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum StatusListError {
+    #[error("Status index out of range: {0}")]
+    IndexOutOfRange(String),
+    #[error("Status entry belongs to another list: {0}")]
+    ListMismatch(String),
+    #[error("Invalid encoded status list: {0}")]
+    InvalidEncoding(String),
+    #[error("Status list key {0} is not trusted")]
+    UntrustedKey(String),
+    #[error("Status list {0} is outdated")]
+    Stale(String),
+    #[error("Status list {0} is signed in the future")]
+    NotYetValid(String),
+    #[error(transparent)]
+    Signature(#[from] SignatureError),
+}
+
+/// Bitstring of credential statuses
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusList {
+    /// URL the list is published at
+    pub id: String,
+    /// Issuer of the list
+    pub issuer: String,
+    /// What a set bit means, e.g. `revocation`
+    pub status_purpose: String,
+    /// When the list was signed
+    pub valid_from: DateTime<Utc>,
+    /// Base64url-encoded bitstring
+    pub encoded_list: String,
+}
+
+/// Issuer signature over a status list
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusListSignature {
+    /// Issuer key the list is signed with
+    pub key_id: String,
+    /// Signature algorithm, covered by the signature
+    pub algorithm: SignatureAlgorithm,
+    /// Hex-encoded Ed25519 signature, for Ed25519 and hybrid signatures
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Hex-encoded ML-DSA-65 signature, for ML-DSA and hybrid signatures
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pq_signature: Option<String>,
+}
+
+/// Status list with the signature of its issuer
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SignedStatusList {
+    /// The signed list
+    pub list: StatusList,
+    /// Signature over the list
+    pub proof: StatusListSignature,
+}
+
+impl StatusList {
+    /// Revocation list of `size` entries, none of them revoked
+    pub fn revocation(id: String, issuer: String, size: usize) -> Self {
+        Self {
+            id,
+            issuer,
+            status_purpose: REVOCATION_PURPOSE.to_string(),
+            valid_from: Utc::now(),
+            encoded_list: URL_SAFE_NO_PAD.encode(vec![0u8; size.div_ceil(8)]),
+        }
+    }
+
+    /// Status bit at `index`
+    pub fn status(&self, index: u64) -> Result<bool, StatusListError> {
+        let bits = self.bits()?;
+        let (byte, mask) = bit_position(&bits, index)?;
+        Ok(bits[byte] & mask != 0)
+    }
+
+    /// Set or clear the status bit at `index`
+    pub fn set_status(&mut self, index: u64, status: bool) -> Result<(), StatusListError> {
+        let mut bits = self.bits()?;
+        let (byte, mask) = bit_position(&bits, index)?;
+        if status {
+            bits[byte] |= mask;
+        } else {
+            bits[byte] &= !mask;
+        }
+        self.encoded_list = URL_SAFE_NO_PAD.encode(bits);
+        Ok(())
+    }
+
+    /// Status bit of a credential's status entry, which must point at this list
+    pub fn entry_status(&self, entry: &CredentialStatus) -> Result<bool, StatusListError> {
+        if entry.status_list_credential != self.id || entry.status_purpose != self.status_purpose {
+            return Err(StatusListError::ListMismatch(entry.id.clone()));
+        }
+        let index = entry
+            .status_list_index
+            .parse()
+            .map_err(|_| StatusListError::IndexOutOfRange(entry.status_list_index.clone()))?;
+        self.status(index)
+    }
+
+    /// Check that the list was signed at most `max_age` before `now`
+    pub fn check_freshness(
+        &self,
+        max_age: Duration,
+        now: DateTime<Utc>,
+    ) -> Result<(), StatusListError> {
+        if self.valid_from > now + Duration::seconds(STATUS_LIST_CLOCK_SKEW_SECS) {
+            return Err(StatusListError::NotYetValid(self.id.clone()));
+        }
+        if self.valid_from + max_age < now {
+            return Err(StatusListError::Stale(self.id.clone()));
+        }
+        Ok(())
+    }
+
+    /// Sign the list as of now with an issuer key
+    pub fn sign(mut self, key_id: String, signing_key: &IssuerSigningKey) -> SignedStatusList {
+        self.valid_from = Utc::now();
+        let algorithm = signing_key.algorithm();
+        let signature = signing_key.sign(&signing_bytes(&self, &key_id, algorithm));
+
+        SignedStatusList {
+            list: self,
+            proof: StatusListSignature {
+                key_id,
+                algorithm,
+                signature: signature.ed25519.map(hex::encode),
+                pq_signature: signature.ml_dsa.map(hex::encode),
+            },
+        }
+    }
+
+    fn bits(&self) -> Result<Vec<u8>, StatusListError> {
+        URL_SAFE_NO_PAD
+            .decode(&self.encoded_list)
+            .map_err(|e| StatusListError::InvalidEncoding(e.to_string()))
+    }
+}
+
+impl SignedStatusList {
+    /// Verify the signature with the issuer's public key
+    pub fn verify(&self, public_key: &IssuerPublicKey) -> Result<&StatusList, StatusListError> {
+        let decode = |signature: &Option<String>| {
+            signature
+                .as_ref()
+                .and_then(|signature| hex::decode(signature).ok())
+        };
+        let signature = IssuerSignature {
+            algorithm: self.proof.algorithm,
+            ed25519: decode(&self.proof.signature),
+            ml_dsa: decode(&self.proof.pq_signature),
+        };
+
+        public_key.verify(
+            &signing_bytes(&self.list, &self.proof.key_id, self.proof.algorithm),
+            &signature,
+        )?;
+        Ok(&self.list)
+    }
+
+    /// Verify the signature with the key the issuer's trusted key set held when the list
+    /// was signed
+    pub fn verify_with(&self, key_set: &TrustedKeySet) -> Result<&StatusList, StatusListError> {
+        if key_set.issuer_id != self.list.issuer {
+            return Err(StatusListError::UntrustedKey(self.proof.key_id.clone()));
+        }
+        let public_key = key_set
+            .key_at(&self.proof.key_id, self.list.valid_from)
+            .ok_or_else(|| StatusListError::UntrustedKey(self.proof.key_id.clone()))?;
+        self.verify(&public_key)
+    }
+}
+
+/// Byte and bit mask of a status index
+fn bit_position(bits: &[u8], index: u64) -> Result<(usize, u8), StatusListError> {
+    let byte = usize::try_from(index / 8)
+        .ok()
+        .filter(|byte| *byte < bits.len())
+        .ok_or_else(|| StatusListError::IndexOutOfRange(index.to_string()))?;
+    Ok((byte, 0x80 >> (index % 8)))
+}
+
+/// Bytes covered by a status list signature: the list, the key id and the algorithm
+fn signing_bytes(list: &StatusList, key_id: &str, algorithm: SignatureAlgorithm) -> Vec<u8> {
+    serde_json::to_vec(&serde_json::json!({
+        "list": list,
+        "keyId": key_id,
+        "algorithm": algorithm,
+    }))
+    .expect("status list serializes to JSON")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keystore::KeyStore;
+    use ed25519_dalek::SigningKey;
+
+    const TEST_LIST_URL: &str = "https://web5.claims/status/1";
+
+    fn create_test_list() -> StatusList {
+        StatusList::revocation(
+            TEST_LIST_URL.to_string(),
+            "test_issuer".to_string(),
+            DEFAULT_STATUS_LIST_SIZE,
+        )
+    }
+
+    fn create_test_key() -> IssuerSigningKey {
+        SigningKey::from_bytes(&[11u8; 32]).into()
+    }
+
+    #[test]
+    fn test_set_and_read_status() {
+        let mut list = create_test_list();
+        assert!(!list.status(7).unwrap());
+
+        list.set_status(7, true).unwrap();
+        assert!(list.status(7).unwrap());
+        assert!(!list.status(6).unwrap());
+        // Index 0 is the most significant bit of the first byte
+        assert_eq!(list.bits().unwrap()[0], 0x01);
+
+        list.set_status(7, false).unwrap();
+        assert!(!list.status(7).unwrap());
+        assert!(matches!(
+            list.status(DEFAULT_STATUS_LIST_SIZE as u64),
+            Err(StatusListError::IndexOutOfRange(_))
+        ));
+    }
+
+    #[test]
+    fn test_entry_status() {
+        let mut list = create_test_list();
+        list.set_status(42, true).unwrap();
+
+        assert!(list
+            .entry_status(&CredentialStatus::revocation(TEST_LIST_URL, 42))
+            .unwrap());
+        assert!(matches!(
+            list.entry_status(&CredentialStatus::revocation(
+                "https://web5.claims/status/2",
+                42
+            )),
+            Err(StatusListError::ListMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn test_check_freshness() {
+        let list = create_test_list();
+        let max_age = Duration::hours(DEFAULT_STATUS_LIST_MAX_AGE_HOURS);
+
+        assert!(list.check_freshness(max_age, list.valid_from).is_ok());
+        assert!(matches!(
+            list.check_freshness(max_age, list.valid_from + Duration::hours(25)),
+            Err(StatusListError::Stale(_))
+        ));
+        assert!(matches!(
+            list.check_freshness(max_age, list.valid_from - Duration::hours(1)),
+            Err(StatusListError::NotYetValid(_))
+        ));
+    }
+
+    #[test]
+    fn test_signed_status_list() {
+        let key = create_test_key();
+        let mut list = create_test_list();
+        list.set_status(3, true).unwrap();
+        let signed = list.sign("key-1".to_string(), &key);
+
+        let json = serde_json::to_string(&signed).unwrap();
+        let parsed: SignedStatusList = serde_json::from_str(&json).unwrap();
+        assert!(parsed.verify(&key.public_key()).unwrap().status(3).unwrap());
+
+        // Unrevoking a credential breaks the signature
+        let mut tampered = parsed.clone();
+        tampered.list.set_status(3, false).unwrap();
+        assert!(matches!(
+            tampered.verify(&key.public_key()),
+            Err(StatusListError::Signature(
+                SignatureError::InvalidSignature(_)
+            ))
+        ));
+
+        let other_key: IssuerSigningKey = SigningKey::from_bytes(&[12u8; 32]).into();
+        assert!(parsed.verify(&other_key.public_key()).is_err());
+    }
+
+    #[test]
+    fn test_verify_with_trusted_keys() {
+        let keystore = KeyStore::generate("test_issuer".to_string());
+        let issuer = keystore.issuer("Test Issuer".to_string()).unwrap();
+        let signed = issuer.sign_status_list(create_test_list()).unwrap();
+
+        assert!(signed.verify_with(&keystore.trusted_keys()).is_ok());
+        let other = KeyStore::generate("other_issuer".to_string());
+        assert!(matches!(
+            signed.verify_with(&other.trusted_keys()),
+            Err(StatusListError::UntrustedKey(_))
+        ));
+    }
+
+    #[cfg(feature = "post-quantum")]
+    #[test]
+    fn test_hybrid_signed_status_list() {
+        use crate::signature::MlDsaSigningKey;
+
+        let key = IssuerSigningKey::Ed25519MlDsa65(
+            SigningKey::from_bytes(&[11u8; 32]),
+            MlDsaSigningKey::from_seed([13u8; 32]),
+        );
+        let signed = create_test_list().sign("key-1".to_string(), &key);
+        assert_eq!(signed.proof.algorithm, SignatureAlgorithm::Ed25519MlDsa65);
+        assert!(signed.verify(&key.public_key()).is_ok());
+
+        // Dropping the post-quantum half fails verification
+        let mut stripped = signed;
+        stripped.proof.pq_signature = None;
+        assert!(matches!(
+            stripped.verify(&key.public_key()),
+            Err(StatusListError::Signature(
+                SignatureError::MissingSignature(_)
+            ))
+        ));
+    }
+}
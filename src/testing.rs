@@ -0,0 +1,71 @@
+//! Test support shared by the module tests.
+
+use crate::issuer::{CertificateIssuer, GameResult, ProvingBackend};
+use crate::keystore::KeyStore;
+use crate::verifier::{ProofBackend, ZkProofVerifier};
+use crate::zk_proof::{ClaimType, PublicInputs, ZkProofClaim};
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+
+/// Proving system whose proofs hash the circuit and public inputs
+#[derive(Debug)]
+pub(crate) struct TestProvingSystem;
+
+impl TestProvingSystem {
+    fn proof_bytes(circuit_id: &str, public_inputs: &PublicInputs) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update(circuit_id.as_bytes());
+        hasher.update(public_inputs.certificate_hash.as_bytes());
+        hasher.update([public_inputs.verification_result as u8]);
+        hasher.finalize().to_vec()
+    }
+}
+
+impl ProvingBackend for TestProvingSystem {
+    fn prove(&self, circuit_id: &str, public_inputs: &PublicInputs) -> Result<Vec<u8>, String> {
+        Ok(Self::proof_bytes(circuit_id, public_inputs))
+    }
+}
+
+impl ProofBackend for TestProvingSystem {
+    fn verify(&self, proof: &ZkProofClaim) -> Result<bool, String> {
+        let expected = Self::proof_bytes(&proof.proof_data.circuit_id, &proof.public_inputs);
+        Ok(proof.proof_data.proof_bytes == expected)
+    }
+}
+
+/// Issuer of real proofs and a verifier that trusts its keys and checks its proofs
+pub(crate) fn create_real_proof_setup() -> (CertificateIssuer, ZkProofVerifier) {
+    let keystore = KeyStore::generate("test_issuer".to_string());
+    let issuer = keystore
+        .issuer("Test Issuer".to_string())
+        .unwrap()
+        .with_prover(TestProvingSystem)
+        .require_own_certificates()
+        .unwrap();
+
+    let mut verifier =
+        ZkProofVerifier::new("test_verifier".to_string()).with_proof_backend(TestProvingSystem);
+    verifier.add_trusted_keys(keystore.trusted_keys());
+    (issuer, verifier)
+}
+
+/// Real proof of a claim about a certificate the issuer minted
+pub(crate) fn generate_real_proof(
+    issuer: &CertificateIssuer,
+    claim_type: ClaimType,
+    target_platform: &str,
+) -> ZkProofClaim {
+    let minted = issuer
+        .mint_certificate(&GameResult {
+            game_path_name: "German_B2_Complete".to_string(),
+            profile_name: "Test Student".to_string(),
+            total_challenges: 7,
+            solved_challenges: 7,
+            completed_at: Utc::now(),
+        })
+        .unwrap();
+    issuer
+        .generate_proof(minted.proof_request(claim_type, target_platform.to_string()))
+        .unwrap()
+}
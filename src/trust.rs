@@ -0,0 +1,530 @@
+//! Trust scores of verified claims.
+//!
+//! A [`TrustScore`] rates how much a relying party can rely on a claim, from 0 to 100. It is
+//! a weighted sum of [`TrustFactor`] scores: the issuer signature, ZKPassport identity binding,
+//! issuer accreditation, proof mode, the age of the claim and its revocation status. The
+//! weights are configurable with [`TrustWeights`]; a revoked claim always scores 0.
+
+use crate::zk_proof::ProofMode;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum TrustError {
+    #[error("Invalid trust weights: {0}")]
+    InvalidWeights(String),
+    #[error("Invalid trust score configuration: {0}")]
+    InvalidConfig(String),
+}
+
+/// Input of a trust score
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TrustFactor {
+    /// Presence and validity of the issuer signature
+    Signature,
+    /// ZKPassport identity bound into the claim
+    IdentityBinding,
+    /// Issuer known to the verifier through trusted keys, trusted DIDs or co-signers
+    IssuerAccreditation,
+    /// Real or simulated proof
+    ProofMode,
+    /// Time since the issuer issued the claim
+    ///
+    /// Proofs keep the certificate completion date private, so this rates how recently the
+    /// issuer vouched for the claim, not how recently the certificate was earned.
+    ClaimAge,
+    /// Revocation status of the claim
+    Revocation,
+}
+
+/// State of the issuer signature
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureStatus {
+    /// Claim carries no issuer signature
+    Missing,
+    /// Claim is signed, but the verifier has no key to check the signature with
+    Unchecked,
+    /// Signature verified against a trusted key or the issuer's DID
+    Verified,
+}
+
+/// Identity bound into a claim
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IdentityStatus {
+    /// Claim carries no identity
+    None,
+    /// Claim carries identity attributes
+    Verified {
+        /// Whether the passport proofs were verified cryptographically
+        mode: ProofMode,
+        /// Whether the certificate name is proven to match the passport name
+        name_bound: bool,
+    },
+}
+
+/// Revocation status of a claim
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RevocationStatus {
+    /// Verifier has no revocation list
+    #[default]
+    Unknown,
+    /// Claim is not on the revocation list
+    Valid,
+    /// Claim has been revoked
+    Revoked,
+}
+
+/// Weight of each factor in the score
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TrustWeights {
+    pub signature: f64,
+    pub identity_binding: f64,
+    pub issuer_accreditation: f64,
+    pub proof_mode: f64,
+    pub claim_age: f64,
+    pub revocation: f64,
+}
+
+/// Configuration of trust scores
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TrustScoreConfig {
+    /// Weight of each factor
+    pub weights: TrustWeights,
+    /// Age in days up to which a claim keeps its full age score
+    pub full_trust_days: i64,
+    /// Age in days at which the age score reaches zero
+    pub max_age_days: i64,
+}
+
+/// Facts about a verified claim the score is computed from
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrustInputs {
+    /// State of the issuer signature
+    pub signature: SignatureStatus,
+    /// Identity bound into the claim
+    pub identity: IdentityStatus,
+    /// Whether the issuer is accredited with the verifier
+    pub issuer_accredited: bool,
+    /// Whether the proof is real or simulated
+    pub proof_mode: ProofMode,
+    /// When the issuer issued the claim: the proof generation time or the SD-JWT `iat`
+    pub issued_at: DateTime<Utc>,
+    /// Revocation status of the claim
+    pub revocation: RevocationStatus,
+}
+
+/// Score of one factor
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FactorScore {
+    /// Rated factor
+    pub factor: TrustFactor,
+    /// Weight of the factor
+    pub weight: f64,
+    /// Factor score between 0 and 1
+    pub score: f64,
+    /// Why the factor scored as it did
+    pub detail: String,
+}
+
+/// Trust score of a claim with its per-factor breakdown
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TrustScore {
+    /// Score between 0 and 100
+    pub score: u8,
+    /// Score of every factor
+    pub factors: Vec<FactorScore>,
+}
+
+impl TrustFactor {
+    /// All trust factors
+    pub const ALL: [TrustFactor; 6] = [
+        TrustFactor::Signature,
+        TrustFactor::IdentityBinding,
+        TrustFactor::IssuerAccreditation,
+        TrustFactor::ProofMode,
+        TrustFactor::ClaimAge,
+        TrustFactor::Revocation,
+    ];
+
+    /// Identifier of the factor
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TrustFactor::Signature => "signature",
+            TrustFactor::IdentityBinding => "identity_binding",
+            TrustFactor::IssuerAccreditation => "issuer_accreditation",
+            TrustFactor::ProofMode => "proof_mode",
+            TrustFactor::ClaimAge => "claim_age",
+            TrustFactor::Revocation => "revocation",
+        }
+    }
+}
+
+impl fmt::Display for TrustFactor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Default for TrustWeights {
+    fn default() -> Self {
+        Self {
+            signature: 25.0,
+            identity_binding: 20.0,
+            issuer_accreditation: 20.0,
+            proof_mode: 15.0,
+            claim_age: 10.0,
+            revocation: 10.0,
+        }
+    }
+}
+
+impl TrustWeights {
+    /// Weight of a factor
+    pub fn weight(&self, factor: TrustFactor) -> f64 {
+        match factor {
+            TrustFactor::Signature => self.signature,
+            TrustFactor::IdentityBinding => self.identity_binding,
+            TrustFactor::IssuerAccreditation => self.issuer_accreditation,
+            TrustFactor::ProofMode => self.proof_mode,
+            TrustFactor::ClaimAge => self.claim_age,
+            TrustFactor::Revocation => self.revocation,
+        }
+    }
+
+    /// Check that weights are finite, non-negative and not all zero
+    pub fn validate(&self) -> Result<(), TrustError> {
+        if let Some(factor) = TrustFactor::ALL.into_iter().find(|factor| {
+            let weight = self.weight(*factor);
+            !weight.is_finite() || weight < 0.0
+        }) {
+            return Err(TrustError::InvalidWeights(format!(
+                "weight of {} must be a non-negative number",
+                factor
+            )));
+        }
+        if self.total() <= 0.0 {
+            return Err(TrustError::InvalidWeights(
+                "at least one weight must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn total(&self) -> f64 {
+        TrustFactor::ALL
+            .into_iter()
+            .map(|factor| self.weight(factor))
+            .sum()
+    }
+}
+
+impl Default for TrustScoreConfig {
+    fn default() -> Self {
+        Self {
+            weights: TrustWeights::default(),
+            full_trust_days: 365,
+            max_age_days: 3 * 365,
+        }
+    }
+}
+
+impl TrustScoreConfig {
+    /// Use other factor weights
+    pub fn with_weights(mut self, weights: TrustWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Check the weights and the age window
+    pub fn validate(&self) -> Result<(), TrustError> {
+        self.weights.validate()?;
+        if self.full_trust_days < 0 || self.max_age_days <= self.full_trust_days {
+            return Err(TrustError::InvalidConfig(format!(
+                "full trust period of {} days must end before the maximum age of {} days",
+                self.full_trust_days, self.max_age_days
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl TrustScore {
+    /// Score a claim at `now`
+    pub fn compute(
+        inputs: &TrustInputs,
+        config: &TrustScoreConfig,
+        now: DateTime<Utc>,
+    ) -> Result<Self, TrustError> {
+        config.validate()?;
+
+        let factors: Vec<FactorScore> = TrustFactor::ALL
+            .into_iter()
+            .map(|factor| {
+                let (score, detail) = factor_score(factor, inputs, config, now);
+                FactorScore {
+                    factor,
+                    weight: config.weights.weight(factor),
+                    score,
+                    detail,
+                }
+            })
+            .collect();
+
+        let score = if inputs.revocation == RevocationStatus::Revoked {
+            0
+        } else {
+            let weighted: f64 = factors.iter().map(|f| f.weight * f.score).sum();
+            (weighted / config.weights.total() * 100.0).round() as u8
+        };
+
+        Ok(Self { score, factors })
+    }
+
+    /// Score of a factor
+    pub fn factor(&self, factor: TrustFactor) -> Option<&FactorScore> {
+        self.factors.iter().find(|f| f.factor == factor)
+    }
+}
+
+fn factor_score(
+    factor: TrustFactor,
+    inputs: &TrustInputs,
+    config: &TrustScoreConfig,
+    now: DateTime<Utc>,
+) -> (f64, String) {
+    match factor {
+        TrustFactor::Signature => match inputs.signature {
+            SignatureStatus::Missing => (0.0, "claim is not signed by its issuer".to_string()),
+            SignatureStatus::Unchecked => (
+                0.5,
+                "issuer signature present but not checked against a trusted key".to_string(),
+            ),
+            SignatureStatus::Verified => (1.0, "issuer signature verified".to_string()),
+        },
+        TrustFactor::IdentityBinding => match inputs.identity {
+            IdentityStatus::None => (0.0, "no verified identity".to_string()),
+            // Identity attributes are only as trustworthy as the signature covering them
+            IdentityStatus::Verified { .. } if inputs.signature != SignatureStatus::Verified => (
+                0.0,
+                "identity attributes are not covered by a verified issuer signature".to_string(),
+            ),
+            IdentityStatus::Verified { mode, name_bound } => {
+                let (mut score, mut detail) = match mode {
+                    ProofMode::Real => (1.0, "passport proofs verified".to_string()),
+                    ProofMode::Simulated => (0.5, "passport proofs not verified".to_string()),
+                };
+                if !name_bound {
+                    score *= 0.75;
+                    detail.push_str(", certificate name not bound to the passport");
+                }
+                (score, detail)
+            }
+        },
+        TrustFactor::IssuerAccreditation => {
+            if inputs.issuer_accredited {
+                (1.0, "issuer is accredited with the verifier".to_string())
+            } else {
+                (
+                    0.0,
+                    "issuer is not accredited with the verifier".to_string(),
+                )
+            }
+        }
+        TrustFactor::ProofMode => match inputs.proof_mode {
+            ProofMode::Real => (1.0, "proof from a real proving system".to_string()),
+            ProofMode::Simulated => (0.0, "simulated proof".to_string()),
+        },
+        TrustFactor::ClaimAge => {
+            let age_days = (now - inputs.issued_at).num_days().max(0);
+            let score = if age_days <= config.full_trust_days {
+                1.0
+            } else if age_days >= config.max_age_days {
+                0.0
+            } else {
+                (config.max_age_days - age_days) as f64
+                    / (config.max_age_days - config.full_trust_days) as f64
+            };
+            (score, format!("issued {} days ago", age_days))
+        }
+        TrustFactor::Revocation => match inputs.revocation {
+            RevocationStatus::Unknown => (0.5, "revocation status not checked".to_string()),
+            RevocationStatus::Valid => (1.0, "not revoked".to_string()),
+            RevocationStatus::Revoked => (0.0, "revoked".to_string()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn create_test_inputs() -> TrustInputs {
+        TrustInputs {
+            signature: SignatureStatus::Verified,
+            identity: IdentityStatus::Verified {
+                mode: ProofMode::Real,
+                name_bound: true,
+            },
+            issuer_accredited: true,
+            proof_mode: ProofMode::Real,
+            issued_at: Utc::now() - Duration::days(30),
+            revocation: RevocationStatus::Valid,
+        }
+    }
+
+    fn score(inputs: &TrustInputs) -> TrustScore {
+        TrustScore::compute(inputs, &TrustScoreConfig::default(), Utc::now()).unwrap()
+    }
+
+    #[test]
+    fn test_fully_trusted_claim() {
+        let trust = score(&create_test_inputs());
+
+        assert_eq!(trust.score, 100);
+        assert_eq!(trust.factors.len(), TrustFactor::ALL.len());
+        assert!(trust.factors.iter().all(|factor| factor.score == 1.0));
+    }
+
+    #[test]
+    fn test_unsigned_simulated_claim() {
+        let inputs = TrustInputs {
+            signature: SignatureStatus::Missing,
+            identity: IdentityStatus::None,
+            issuer_accredited: false,
+            proof_mode: ProofMode::Simulated,
+            revocation: RevocationStatus::Unknown,
+            ..create_test_inputs()
+        };
+        let trust = score(&inputs);
+
+        // Only the age and half of the revocation weight remain
+        assert_eq!(trust.score, 15);
+        assert_eq!(trust.factor(TrustFactor::Signature).unwrap().score, 0.0);
+        assert_eq!(trust.factor(TrustFactor::Revocation).unwrap().score, 0.5);
+    }
+
+    #[test]
+    fn test_identity_needs_verified_signature() {
+        let mut inputs = create_test_inputs();
+        inputs.signature = SignatureStatus::Unchecked;
+        assert_eq!(
+            score(&inputs)
+                .factor(TrustFactor::IdentityBinding)
+                .unwrap()
+                .score,
+            0.0
+        );
+
+        inputs.signature = SignatureStatus::Verified;
+        inputs.identity = IdentityStatus::Verified {
+            mode: ProofMode::Simulated,
+            name_bound: false,
+        };
+        assert_eq!(
+            score(&inputs)
+                .factor(TrustFactor::IdentityBinding)
+                .unwrap()
+                .score,
+            0.375
+        );
+    }
+
+    #[test]
+    fn test_claim_age_decays() {
+        let config = TrustScoreConfig::default();
+        let now = Utc::now();
+        let age_score = |days| {
+            let inputs = TrustInputs {
+                issued_at: now - Duration::days(days),
+                ..create_test_inputs()
+            };
+            TrustScore::compute(&inputs, &config, now)
+                .unwrap()
+                .factor(TrustFactor::ClaimAge)
+                .unwrap()
+                .score
+        };
+
+        assert_eq!(age_score(365), 1.0);
+        assert_eq!(age_score(730), 0.5);
+        assert_eq!(age_score(2000), 0.0);
+    }
+
+    #[test]
+    fn test_revoked_claim_scores_zero() {
+        let inputs = TrustInputs {
+            revocation: RevocationStatus::Revoked,
+            ..create_test_inputs()
+        };
+
+        assert_eq!(score(&inputs).score, 0);
+    }
+
+    #[test]
+    fn test_custom_weights() {
+        let weights = TrustWeights {
+            signature: 1.0,
+            identity_binding: 0.0,
+            issuer_accreditation: 0.0,
+            proof_mode: 1.0,
+            claim_age: 0.0,
+            revocation: 0.0,
+        };
+        let config = TrustScoreConfig::default().with_weights(weights);
+        let inputs = TrustInputs {
+            identity: IdentityStatus::None,
+            proof_mode: ProofMode::Simulated,
+            ..create_test_inputs()
+        };
+
+        let trust = TrustScore::compute(&inputs, &config, Utc::now()).unwrap();
+        assert_eq!(trust.score, 50);
+        assert_eq!(
+            trust.factor(TrustFactor::IdentityBinding).unwrap().weight,
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let zero = TrustWeights {
+            signature: 0.0,
+            identity_binding: 0.0,
+            issuer_accreditation: 0.0,
+            proof_mode: 0.0,
+            claim_age: 0.0,
+            revocation: 0.0,
+        };
+        assert!(matches!(
+            TrustScoreConfig::default().with_weights(zero).validate(),
+            Err(TrustError::InvalidWeights(_))
+        ));
+
+        let negative = TrustWeights {
+            signature: -1.0,
+            ..TrustWeights::default()
+        };
+        assert_eq!(
+            negative.validate(),
+            Err(TrustError::InvalidWeights(
+                "weight of signature must be a non-negative number".to_string()
+            ))
+        );
+
+        let config = TrustScoreConfig {
+            max_age_days: 30,
+            ..TrustScoreConfig::default()
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(TrustError::InvalidConfig(_))
+        ));
+    }
+}
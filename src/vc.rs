@@ -0,0 +1,714 @@
+//! W3C Verifiable Credentials Data Model 2.0 mapping.
+//!
+//! A [`ZkProofClaim`] maps to a `ZkProofCredential`: the issuer metadata becomes the credential
+//! `issuer`, the claim and its public inputs become the `credentialSubject`, and the proof
+//! bytes travel in `credentialSubject.zkProof`. Terms are defined by the JSON-LD context in
+//! `contexts/web5claims-v1.jsonld`, published at [`WEB5CLAIMS_CONTEXT_URL`].
+//!
+//! Credentials are secured with a Data Integrity proof using the `eddsa-jcs-2022`
+//! cryptosuite.
+
+use crate::name_binding::NameMatch;
+use crate::zk_proof::{
+    CefrLevel, ClaimType, ProofData, ProofMetadata, ProofMode, PublicInputs, ZkProofClaim,
+};
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Base context of VC Data Model 2.0
+pub const VC_CONTEXT_V2: &str = "https://www.w3.org/ns/credentials/v2";
+/// URL the web5claims context is published at
+pub const WEB5CLAIMS_CONTEXT_URL: &str = "https://web5.claims/contexts/web5claims-v1.jsonld";
+/// JSON-LD context defining the web5claims terms
+pub const WEB5CLAIMS_CONTEXT: &str = include_str!("../contexts/web5claims-v1.jsonld");
+/// Credential type of exported proof claims
+pub const CREDENTIAL_TYPE: &str = "ZkProofCredential";
+/// Data Integrity cryptosuite used to sign credentials
+pub const CRYPTOSUITE: &str = "eddsa-jcs-2022";
+
+/// Prefix of issuer ids that are not URLs or DIDs
+const ISSUER_URN_PREFIX: &str = "urn:web5claims:issuer:";
+/// Prefix of proof ids that are not UUIDs
+const PROOF_URN_PREFIX: &str = "urn:web5claims:proof:";
+
+#[derive(Error, Debug, PartialEq)]
+pub enum VcError {
+    #[error("Serialization failed: {0}")]
+    Serialization(String),
+    #[error("Missing context: {0}")]
+    MissingContext(String),
+    #[error("Missing credential type: {0}")]
+    MissingType(String),
+    #[error("Proof claim has no issuer metadata")]
+    MissingIssuer,
+    #[error("Credential is not signed")]
+    MissingProof,
+    #[error("Unsupported cryptosuite: {0}")]
+    UnsupportedCryptosuite(String),
+    #[error("Invalid credential signature: {0}")]
+    InvalidSignature(String),
+    #[error("Invalid proof bytes: {0}")]
+    InvalidProofBytes(String),
+}
+
+/// Verifiable credential carrying a zero-knowledge proof claim
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiableCredential {
+    /// JSON-LD contexts
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    /// Credential identifier, derived from the proof id
+    pub id: String,
+    /// Credential types
+    #[serde(rename = "type")]
+    pub types: Vec<String>,
+    /// Issuer of the proof
+    pub issuer: CredentialIssuer,
+    /// When the proof was generated
+    pub valid_from: DateTime<Utc>,
+    /// The proven claim
+    pub credential_subject: CredentialSubject,
+    /// Where to check whether the credential was revoked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credential_status: Option<CredentialStatus>,
+    /// Data Integrity proof of the issuer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof: Option<DataIntegrityProof>,
+}
+
+/// Credential issuer
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CredentialIssuer {
+    /// Issuer URL, DID or URN
+    pub id: String,
+    /// Human readable issuer name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// Subject of a proof credential
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialSubject {
+    /// Holder the claim is about
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Claim proven by the zero-knowledge proof
+    pub claim: CredentialClaim,
+    /// Minimum requirements that were checked
+    pub requirements: HashMap<String, Value>,
+    /// Whether the requirements are met
+    pub requirements_met: bool,
+    /// Hash of the certificate the proof was generated from
+    pub certificate_hash: String,
+    /// Zero-knowledge proof of the claim
+    pub zk_proof: ZkProofEvidence,
+}
+
+/// JSON-LD form of [`ClaimType`]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all_fields = "camelCase")]
+pub enum CredentialClaim {
+    LanguageProficiency {
+        language: String,
+        min_level: CefrLevel,
+    },
+    PerformanceThreshold {
+        min_percentage: u8,
+    },
+    CompletionDate {
+        after_date: DateTime<Utc>,
+    },
+    Combined {
+        criteria: Vec<CredentialClaim>,
+    },
+    IdentityBound {
+        name_match: NameMatch,
+    },
+    NationalityNotIn {
+        countries: Vec<String>,
+    },
+    DocumentValid {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        document_type: Option<String>,
+    },
+}
+
+/// Zero-knowledge proof carried by a credential
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ZkProofEvidence {
+    /// Circuit the proof was generated with
+    pub circuit_id: String,
+    /// Verification key hash of the circuit
+    pub vk_hash: String,
+    /// Whether the proof is simulated or real
+    pub proof_mode: ProofMode,
+    /// Base64-encoded proof bytes
+    pub proof_bytes: String,
+    /// Platform the proof targets
+    pub platform: String,
+    /// Version of the proof system
+    pub proof_version: String,
+    /// Additional proof properties, without the issuer metadata
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub properties: HashMap<String, String>,
+}
+
+/// Bitstring status list entry of a credential
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialStatus {
+    /// Status entry identifier
+    pub id: String,
+    /// Status entry type, `BitstringStatusListEntry`
+    #[serde(rename = "type")]
+    pub status_type: String,
+    /// What the status bit means, e.g. `revocation`
+    pub status_purpose: String,
+    /// Index of the credential in the status list
+    pub status_list_index: String,
+    /// URL of the status list credential
+    pub status_list_credential: String,
+}
+
+/// Data Integrity proof
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DataIntegrityProof {
+    /// Proof type, `DataIntegrityProof`
+    #[serde(rename = "type")]
+    pub proof_type: String,
+    /// Cryptosuite used to create the proof
+    pub cryptosuite: String,
+    /// When the proof was created
+    pub created: DateTime<Utc>,
+    /// Key the proof was created with
+    pub verification_method: String,
+    /// Purpose of the proof, `assertionMethod`
+    pub proof_purpose: String,
+    /// Multibase-encoded signature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof_value: Option<String>,
+}
+
+impl CredentialStatus {
+    /// Revocation entry at `index` of the status list published at `status_list_credential`
+    pub fn revocation(status_list_credential: &str, index: u64) -> Self {
+        Self {
+            id: format!("{}#{}", status_list_credential, index),
+            status_type: "BitstringStatusListEntry".to_string(),
+            status_purpose: "revocation".to_string(),
+            status_list_index: index.to_string(),
+            status_list_credential: status_list_credential.to_string(),
+        }
+    }
+}
+
+impl From<&ClaimType> for CredentialClaim {
+    fn from(claim_type: &ClaimType) -> Self {
+        match claim_type {
+            ClaimType::LanguageProficiency {
+                language,
+                min_level,
+            } => CredentialClaim::LanguageProficiency {
+                language: language.clone(),
+                min_level: min_level.clone(),
+            },
+            ClaimType::PerformanceThreshold { min_percentage } => {
+                CredentialClaim::PerformanceThreshold {
+                    min_percentage: *min_percentage,
+                }
+            }
+            ClaimType::CompletionDate { after_date } => CredentialClaim::CompletionDate {
+                after_date: *after_date,
+            },
+            ClaimType::Combined { criteria } => CredentialClaim::Combined {
+                criteria: criteria.iter().map(CredentialClaim::from).collect(),
+            },
+            ClaimType::IdentityBound { name_match } => CredentialClaim::IdentityBound {
+                name_match: *name_match,
+            },
+            ClaimType::NationalityNotIn { countries } => CredentialClaim::NationalityNotIn {
+                countries: countries.clone(),
+            },
+            ClaimType::DocumentValid { document_type } => CredentialClaim::DocumentValid {
+                document_type: document_type.clone(),
+            },
+        }
+    }
+}
+
+impl From<CredentialClaim> for ClaimType {
+    fn from(claim: CredentialClaim) -> Self {
+        match claim {
+            CredentialClaim::LanguageProficiency {
+                language,
+                min_level,
+            } => ClaimType::LanguageProficiency {
+                language,
+                min_level,
+            },
+            CredentialClaim::PerformanceThreshold { min_percentage } => {
+                ClaimType::PerformanceThreshold { min_percentage }
+            }
+            CredentialClaim::CompletionDate { after_date } => {
+                ClaimType::CompletionDate { after_date }
+            }
+            CredentialClaim::Combined { criteria } => ClaimType::Combined {
+                criteria: criteria.into_iter().map(ClaimType::from).collect(),
+            },
+            CredentialClaim::IdentityBound { name_match } => {
+                ClaimType::IdentityBound { name_match }
+            }
+            CredentialClaim::NationalityNotIn { countries } => {
+                ClaimType::NationalityNotIn { countries }
+            }
+            CredentialClaim::DocumentValid { document_type } => {
+                ClaimType::DocumentValid { document_type }
+            }
+        }
+    }
+}
+
+impl VerifiableCredential {
+    /// Map a proof claim to an unsigned credential
+    ///
+    /// The issuer is taken from the `issuer_id` and `issuer_name` metadata properties set by
+    /// [`crate::issuer::CertificateIssuer`].
+    pub fn from_claim(claim: &ZkProofClaim) -> Result<Self, VcError> {
+        let mut properties = claim.metadata.properties.clone();
+        let issuer_id = properties
+            .remove("issuer_id")
+            .ok_or(VcError::MissingIssuer)?;
+        let issuer_name = properties.remove("issuer_name");
+
+        Ok(Self {
+            context: vec![
+                VC_CONTEXT_V2.to_string(),
+                WEB5CLAIMS_CONTEXT_URL.to_string(),
+            ],
+            id: proof_id_to_uri(&claim.proof_id),
+            types: vec![
+                "VerifiableCredential".to_string(),
+                CREDENTIAL_TYPE.to_string(),
+            ],
+            issuer: CredentialIssuer {
+                id: issuer_id_to_uri(&issuer_id),
+                name: issuer_name,
+            },
+            valid_from: claim.generated_at,
+            credential_subject: CredentialSubject {
+                id: None,
+                claim: CredentialClaim::from(&claim.claim_type),
+                requirements: claim.public_inputs.requirements.clone(),
+                requirements_met: claim.public_inputs.verification_result,
+                certificate_hash: claim.public_inputs.certificate_hash.clone(),
+                zk_proof: ZkProofEvidence {
+                    circuit_id: claim.proof_data.circuit_id.clone(),
+                    vk_hash: claim.proof_data.vk_hash.clone(),
+                    proof_mode: claim.proof_data.mode,
+                    proof_bytes: general_purpose::STANDARD.encode(&claim.proof_data.proof_bytes),
+                    platform: claim.metadata.platform.clone(),
+                    proof_version: claim.metadata.version.clone(),
+                    properties,
+                },
+            },
+            credential_status: claim.credential_status(),
+            proof: None,
+        })
+    }
+
+    /// Set the holder the credential is about
+    pub fn with_subject_id(mut self, subject_id: String) -> Self {
+        self.credential_subject.id = Some(subject_id);
+        self
+    }
+
+    /// Set the status list entry of the credential
+    pub fn with_status(mut self, status: CredentialStatus) -> Self {
+        self.credential_status = Some(status);
+        self
+    }
+
+    /// Map the credential back to the proof claim it was created from
+    pub fn to_claim(&self) -> Result<ZkProofClaim, VcError> {
+        for context in [VC_CONTEXT_V2, WEB5CLAIMS_CONTEXT_URL] {
+            if !self.context.iter().any(|c| c == context) {
+                return Err(VcError::MissingContext(context.to_string()));
+            }
+        }
+        if !self.types.iter().any(|t| t == CREDENTIAL_TYPE) {
+            return Err(VcError::MissingType(CREDENTIAL_TYPE.to_string()));
+        }
+
+        let subject = &self.credential_subject;
+        let evidence = &subject.zk_proof;
+        let proof_bytes = general_purpose::STANDARD
+            .decode(&evidence.proof_bytes)
+            .map_err(|e| VcError::InvalidProofBytes(e.to_string()))?;
+
+        let mut properties = evidence.properties.clone();
+        properties.insert("issuer_id".to_string(), uri_to_issuer_id(&self.issuer.id));
+        if let Some(name) = &self.issuer.name {
+            properties.insert("issuer_name".to_string(), name.clone());
+        }
+
+        Ok(ZkProofClaim {
+            proof_id: uri_to_proof_id(&self.id),
+            claim_type: ClaimType::from(subject.claim.clone()),
+            public_inputs: PublicInputs {
+                requirements: subject.requirements.clone(),
+                verification_result: subject.requirements_met,
+                certificate_hash: subject.certificate_hash.clone(),
+            },
+            proof_data: ProofData {
+                proof_bytes,
+                circuit_id: evidence.circuit_id.clone(),
+                vk_hash: evidence.vk_hash.clone(),
+                mode: evidence.proof_mode,
+            },
+            generated_at: self.valid_from,
+            metadata: ProofMetadata {
+                version: evidence.proof_version.clone(),
+                platform: evidence.platform.clone(),
+                properties,
+            },
+        })
+    }
+
+    /// Add an `eddsa-jcs-2022` Data Integrity proof, replacing any existing proof
+    pub fn sign(mut self, signing_key: &SigningKey, verification_method: &str) -> Self {
+        self.proof = None;
+        let mut proof = DataIntegrityProof {
+            proof_type: "DataIntegrityProof".to_string(),
+            cryptosuite: CRYPTOSUITE.to_string(),
+            created: Utc::now(),
+            verification_method: verification_method.to_string(),
+            proof_purpose: "assertionMethod".to_string(),
+            proof_value: None,
+        };
+
+        let signature = signing_key.sign(&self.hash_data(&proof));
+        proof.proof_value = Some(format!(
+            "z{}",
+            bs58::encode(signature.to_bytes()).into_string()
+        ));
+        self.proof = Some(proof);
+        self
+    }
+
+    /// Verify the Data Integrity proof against the issuer key
+    pub fn verify_signature(&self, issuer_key: &VerifyingKey) -> Result<(), VcError> {
+        let proof = self.proof.as_ref().ok_or(VcError::MissingProof)?;
+        if proof.cryptosuite != CRYPTOSUITE {
+            return Err(VcError::UnsupportedCryptosuite(proof.cryptosuite.clone()));
+        }
+
+        let signature_bytes: [u8; 64] = proof
+            .proof_value
+            .as_deref()
+            .and_then(|value| value.strip_prefix('z'))
+            .and_then(|encoded| bs58::decode(encoded).into_vec().ok())
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| VcError::InvalidSignature("malformed proof value".to_string()))?;
+
+        let mut unsigned = self.clone();
+        unsigned.proof = None;
+        let mut proof_config = proof.clone();
+        proof_config.proof_value = None;
+
+        issuer_key
+            .verify(
+                &unsigned.hash_data(&proof_config),
+                &Signature::from_bytes(&signature_bytes),
+            )
+            .map_err(|e| VcError::InvalidSignature(e.to_string()))
+    }
+
+    /// Serialize the credential
+    pub fn to_json(&self) -> Result<String, VcError> {
+        serde_json::to_string_pretty(self).map_err(|e| VcError::Serialization(e.to_string()))
+    }
+
+    /// Parse a credential
+    pub fn from_json(json: &str) -> Result<Self, VcError> {
+        serde_json::from_str(json).map_err(|e| VcError::Serialization(e.to_string()))
+    }
+
+    /// Hash of the proof configuration followed by the hash of the unsigned document
+    fn hash_data(&self, proof_config: &DataIntegrityProof) -> Vec<u8> {
+        let mut config = serde_json::to_value(proof_config).expect("proof serializes to JSON");
+        config["@context"] = serde_json::json!(self.context);
+        let document = serde_json::to_value(self).expect("credential serializes to JSON");
+
+        [
+            Sha256::digest(canonicalize(&config).as_bytes()),
+            Sha256::digest(canonicalize(&document).as_bytes()),
+        ]
+        .concat()
+    }
+}
+
+/// JSON Canonicalization Scheme (RFC 8785) serialization
+///
+/// Keys are sorted by byte order, which matches the UTF-16 order JCS requires for the ASCII
+/// keys used in credentials.
+fn canonicalize(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            let members: Vec<String> = entries
+                .into_iter()
+                .map(|(key, value)| {
+                    format!("{}:{}", Value::from(key.as_str()), canonicalize(value))
+                })
+                .collect();
+            format!("{{{}}}", members.join(","))
+        }
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(canonicalize).collect();
+            format!("[{}]", items.join(","))
+        }
+        other => other.to_string(),
+    }
+}
+
+fn issuer_id_to_uri(issuer_id: &str) -> String {
+    if issuer_id.contains(':') {
+        issuer_id.to_string()
+    } else {
+        format!("{}{}", ISSUER_URN_PREFIX, issuer_id)
+    }
+}
+
+fn uri_to_issuer_id(uri: &str) -> String {
+    uri.strip_prefix(ISSUER_URN_PREFIX)
+        .unwrap_or(uri)
+        .to_string()
+}
+
+fn proof_id_to_uri(proof_id: &str) -> String {
+    match uuid::Uuid::parse_str(proof_id) {
+        Ok(uuid) if uuid.hyphenated().to_string() == proof_id => format!("urn:uuid:{}", proof_id),
+        _ => format!("{}{}", PROOF_URN_PREFIX, proof_id),
+    }
+}
+
+fn uri_to_proof_id(uri: &str) -> String {
+    uri.strip_prefix("urn:uuid:")
+        .or_else(|| uri.strip_prefix(PROOF_URN_PREFIX))
+        .unwrap_or(uri)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::issuer::{CertificateIssuer, ProofOptions, ProofRequest};
+    use crate::verifier::ZkProofVerifier;
+    use konnektoren_core::certificates::CertificateData;
+
+    fn create_test_key() -> SigningKey {
+        SigningKey::from_bytes(&[3u8; 32])
+    }
+
+    fn create_test_claim(claim_type: ClaimType) -> ZkProofClaim {
+        let issuer = CertificateIssuer::new("test_issuer".to_string(), "Test Issuer".to_string())
+            .accept_unsigned_certificates();
+        let certificate = CertificateData::new(
+            "German_B2_Complete".to_string(),
+            7,
+            7,
+            "Test Student".to_string(),
+            Utc::now(),
+        );
+
+        issuer
+            .generate_proof(ProofRequest {
+                certificate,
+                claim_type,
+                target_platform: "test".to_string(),
+                options: ProofOptions::default(),
+            })
+            .unwrap()
+    }
+
+    fn create_language_claim() -> ZkProofClaim {
+        create_test_claim(ClaimType::LanguageProficiency {
+            language: "German".to_string(),
+            min_level: CefrLevel::B2,
+        })
+    }
+
+    #[test]
+    fn test_credential_shape() {
+        let credential = VerifiableCredential::from_claim(&create_language_claim()).unwrap();
+        let json: Value = serde_json::from_str(&credential.to_json().unwrap()).unwrap();
+
+        assert_eq!(json["@context"][0], VC_CONTEXT_V2);
+        assert_eq!(json["type"][1], CREDENTIAL_TYPE);
+        assert_eq!(json["issuer"]["id"], "urn:web5claims:issuer:test_issuer");
+        assert_eq!(json["issuer"]["name"], "Test Issuer");
+        assert!(json["id"].as_str().unwrap().starts_with("urn:uuid:"));
+        assert_eq!(
+            json["credentialSubject"]["claim"],
+            serde_json::json!({
+                "type": "LanguageProficiency",
+                "language": "German",
+                "minLevel": "B2"
+            })
+        );
+    }
+
+    #[test]
+    fn test_round_trip_keeps_claim() {
+        let claim = create_test_claim(ClaimType::Combined {
+            criteria: vec![
+                ClaimType::LanguageProficiency {
+                    language: "German".to_string(),
+                    min_level: CefrLevel::B1,
+                },
+                ClaimType::PerformanceThreshold { min_percentage: 80 },
+            ],
+        });
+
+        let credential = VerifiableCredential::from_claim(&claim).unwrap();
+        let parsed = VerifiableCredential::from_json(&credential.to_json().unwrap()).unwrap();
+        assert_eq!(parsed.to_claim().unwrap(), claim);
+    }
+
+    #[test]
+    fn test_round_trip_keeps_verification_result() {
+        let verifier = ZkProofVerifier::new("test_verifier".to_string());
+        let claim = create_language_claim();
+        let credential = VerifiableCredential::from_claim(&claim)
+            .unwrap()
+            .sign(&create_test_key(), "did:example:issuer#key-1");
+        let imported = VerifiableCredential::from_json(&credential.to_json().unwrap())
+            .unwrap()
+            .to_claim()
+            .unwrap();
+
+        let expected = verifier.verify_proof(&claim).unwrap();
+        let mut actual = verifier.verify_proof(&imported).unwrap();
+        actual.details.verified_at = expected.details.verified_at;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let key = create_test_key();
+        let credential = VerifiableCredential::from_claim(&create_language_claim())
+            .unwrap()
+            .with_status(CredentialStatus::revocation(
+                "https://web5.claims/status/1",
+                42,
+            ))
+            .sign(&key, "did:example:issuer#key-1");
+
+        let proof = credential.proof.as_ref().unwrap();
+        assert_eq!(proof.cryptosuite, CRYPTOSUITE);
+        assert!(proof.proof_value.as_ref().unwrap().starts_with('z'));
+
+        let parsed = VerifiableCredential::from_json(&credential.to_json().unwrap()).unwrap();
+        assert!(parsed.verify_signature(&key.verifying_key()).is_ok());
+    }
+
+    #[test]
+    fn test_tampered_credential_rejected() {
+        let key = create_test_key();
+        let mut credential = VerifiableCredential::from_claim(&create_language_claim())
+            .unwrap()
+            .sign(&key, "did:example:issuer#key-1");
+        credential.credential_subject.claim = CredentialClaim::LanguageProficiency {
+            language: "German".to_string(),
+            min_level: CefrLevel::C2,
+        };
+
+        assert!(matches!(
+            credential.verify_signature(&key.verifying_key()),
+            Err(VcError::InvalidSignature(_))
+        ));
+        assert!(matches!(
+            credential.verify_signature(&SigningKey::from_bytes(&[4u8; 32]).verifying_key()),
+            Err(VcError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn test_unsigned_credential_rejected() {
+        let credential = VerifiableCredential::from_claim(&create_language_claim()).unwrap();
+        assert_eq!(
+            credential.verify_signature(&create_test_key().verifying_key()),
+            Err(VcError::MissingProof)
+        );
+    }
+
+    #[test]
+    fn test_credential_status() {
+        let status = CredentialStatus::revocation("https://web5.claims/status/1", 7);
+        let json = serde_json::to_value(&status).unwrap();
+
+        assert_eq!(json["type"], "BitstringStatusListEntry");
+        assert_eq!(json["statusPurpose"], "revocation");
+        assert_eq!(json["statusListIndex"], "7");
+        assert_eq!(json["id"], "https://web5.claims/status/1#7");
+    }
+
+    #[test]
+    fn test_missing_context_or_issuer() {
+        let mut claim = create_language_claim();
+        let mut credential = VerifiableCredential::from_claim(&claim).unwrap();
+        credential.context.pop();
+        assert!(matches!(
+            credential.to_claim(),
+            Err(VcError::MissingContext(_))
+        ));
+
+        claim.metadata.properties.remove("issuer_id");
+        assert_eq!(
+            VerifiableCredential::from_claim(&claim),
+            Err(VcError::MissingIssuer)
+        );
+    }
+
+    #[test]
+    fn test_context_document_defines_claim_terms() {
+        let context: Value = serde_json::from_str(WEB5CLAIMS_CONTEXT).unwrap();
+        let terms = &context["@context"];
+
+        for term in [
+            CREDENTIAL_TYPE,
+            "LanguageProficiency",
+            "PerformanceThreshold",
+            "CompletionDate",
+            "Combined",
+            "IdentityBound",
+            "NationalityNotIn",
+            "DocumentValid",
+            "minLevel",
+            "B2",
+            "zkProof",
+        ] {
+            assert!(!terms[term].is_null(), "missing term {}", term);
+        }
+    }
+
+    #[test]
+    fn test_canonicalize_sorts_keys() {
+        let value = serde_json::json!({"b": 1, "a": {"d": [true, null], "c": "x"}});
+        assert_eq!(
+            canonicalize(&value),
+            r#"{"a":{"c":"x","d":[true,null]},"b":1}"#
+        );
+    }
+}
@@ -1,8 +1,29 @@
+use crate::cosigning::{CosigningError, CosigningPolicy};
+use crate::did::DidResolver;
+use crate::identity::{IdentityAttributes, UNVERIFIED_PASSPORT_PROOF_WARNING};
 use crate::issuer::CertificateIssuer;
-use crate::zk_proof::{ClaimType, ZkProofClaim};
+use crate::keystore::TrustedKeySet;
+use crate::name_binding::{NameBinding, NameBindingError, NameMatch, NAME_BINDING_REQUIREMENT};
+use crate::platform::Platform;
+use crate::presentation::{Presentation, PresentationError, DEFAULT_PRESENTATION_VALIDITY_MINUTES};
+use crate::registry::{CircuitManifest, CircuitStatus, RegistryError, SignedCircuitManifest};
+use crate::signature::{IssuerPublicKey, IssuerSignature};
+use crate::status_list::{
+    SignedStatusList, StatusList, StatusListError, DEFAULT_STATUS_LIST_MAX_AGE_HOURS,
+};
+use crate::trust::{
+    IdentityStatus, RevocationStatus, SignatureStatus, TrustError, TrustInputs, TrustScore,
+    TrustScoreConfig,
+};
+use crate::zk_proof::{
+    ClaimKind, ClaimType, ProofMode, ZkProofClaim, ISSUER_KEY_ID_PROPERTY,
+    ISSUER_PQ_SIGNATURE_PROPERTY, ISSUER_SIGNATURE_PROPERTY, ISSUER_VERIFICATION_METHOD_PROPERTY,
+};
+use ed25519_dalek::VerifyingKey;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -17,6 +38,77 @@ pub enum VerifierError {
     IntegrityCheckFailed,
     #[error("Circuit verification failed: {0}")]
     CircuitVerificationFailed(String),
+    #[error("Circuit has been revoked: {0}")]
+    RevokedCircuit(String),
+    #[error("Proof has been revoked: {0}")]
+    RevokedProof(String),
+    #[error("Simulated proofs are not accepted in strict mode")]
+    SimulatedProofRejected,
+    #[error("Strict mode needs a proof backend to verify real proofs")]
+    NoProofBackend,
+    #[error("Proof rejected by the proof backend: {0}")]
+    ProofRejected(String),
+    #[error("Invalid presentation: {0}")]
+    InvalidPresentation(#[from] PresentationError),
+    #[error("Issuer authentication failed: {0}")]
+    IssuerAuthenticationFailed(String),
+    #[error("Untrusted issuer: {0}")]
+    UntrustedIssuer(String),
+    #[error("Co-signature check failed: {0}")]
+    Cosigning(#[from] CosigningError),
+    #[error("Claim requires a verified identity the proof does not carry")]
+    MissingIdentity,
+    #[error("Name binding check failed: {0}")]
+    NameBinding(#[from] NameBindingError),
+    #[error(transparent)]
+    Trust(#[from] TrustError),
+    #[error("Status list check failed: {0}")]
+    StatusList(#[from] StatusListError),
+}
+
+/// Warning attached to simulated proofs accepted in lenient mode
+pub const SIMULATED_PROOF_WARNING: &str =
+    "SIMULATED PROOF: this proof was not produced by a real proving system and carries no cryptographic guarantee";
+
+/// Warning attached to identity attributes of a proof whose issuer was not authenticated
+pub const UNAUTHENTICATED_IDENTITY_WARNING: &str =
+    "identity attributes are not backed by an authenticated issuer signature";
+
+/// Warning attached to proofs whose DID issuer signed them but is not a trusted issuer
+pub const UNTRUSTED_ISSUER_WARNING: &str =
+    "issuer signature is valid but the issuer is not trusted";
+
+/// Warning attached to presented claims whose holder binding no authenticated issuer signed
+pub const UNAUTHENTICATED_HOLDER_BINDING_WARNING: &str =
+    "holder binding is not backed by an authenticated issuer signature";
+
+/// Warning reported when a claim of this kind is not met
+pub fn unmet_warning(kind: ClaimKind) -> &'static str {
+    match kind {
+        ClaimKind::LanguageProficiency => "Language proficiency requirements not met",
+        ClaimKind::PerformanceThreshold => "Performance threshold not met",
+        ClaimKind::CompletionDate => "Completion date requirements not met",
+        ClaimKind::Combined => "Combined criteria requirements not met",
+        ClaimKind::IdentityBound => "Identity binding requirements not met",
+        ClaimKind::NationalityNotIn => "Nationality requirements not met",
+        ClaimKind::DocumentValid => "Document validity requirements not met",
+    }
+}
+
+/// How the verifier treats simulated proofs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VerificationMode {
+    /// Refuse every proof a proof backend did not verify (production)
+    Strict,
+    /// Accept simulated proofs with a warning (development and demos)
+    #[default]
+    Lenient,
+}
+
+/// Proving system checking proof bytes against the verifying key of their circuit
+pub trait ProofBackend: std::fmt::Debug + Send + Sync {
+    /// Verify the proof bytes against the public inputs of the claim
+    fn verify(&self, proof: &ZkProofClaim) -> Result<bool, String>;
 }
 
 /// Result of proof verification
@@ -30,6 +122,26 @@ pub struct VerificationResult {
     pub details: VerificationDetails,
     /// Any warnings or additional information
     pub warnings: Vec<String>,
+    /// How much the claim can be relied on, with a per-factor breakdown
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trust_score: Option<TrustScore>,
+}
+
+/// Result of presentation verification
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PresentationVerificationResult {
+    /// Identifier of the presenting holder
+    pub holder_id: String,
+    /// Hex-encoded public key the presentation was signed with
+    pub holder_key: String,
+    /// Whether every presented proof is valid
+    pub is_valid: bool,
+    /// Whether every presented claim meets its requirements
+    pub requirements_met: bool,
+    /// Whether an authenticated issuer signature binds every claim to the holder key
+    pub holder_binding_authenticated: bool,
+    /// Verification results of the presented claims, in presentation order
+    pub results: Vec<VerificationResult>,
 }
 
 /// Detailed information about the verification process
@@ -45,6 +157,9 @@ pub struct VerificationDetails {
     pub verified_inputs: HashMap<String, serde_json::Value>,
     /// Additional verification metadata
     pub metadata: HashMap<String, String>,
+    /// Co-signers whose signatures met the co-signing quorum
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signers: Vec<String>,
 }
 
 /// Zero-knowledge proof verifier
@@ -53,13 +168,48 @@ pub struct ZkProofVerifier {
     /// Verifier identifier
     pub verifier_id: String,
     /// Supported platforms for verification
-    pub supported_platforms: Vec<String>,
+    pub supported_platforms: Vec<Platform>,
     /// Trusted circuit registry
     pub trusted_circuits: HashMap<String, CircuitInfo>,
+    /// Handling of simulated proofs
+    pub mode: VerificationMode,
+    /// Resolver for issuer DIDs; when set, every proof must be signed by its issuer
+    pub did_resolver: Option<Arc<dyn DidResolver>>,
+    /// DIDs of trusted issuers; other DID issuers with a valid signature are reported as
+    /// untrusted, and rejected once the list is not empty
+    pub trusted_issuers: Vec<String>,
+    /// Rotating key sets of trusted issuers, by issuer id
+    pub trusted_keys: HashMap<String, TrustedKeySet>,
+    /// Quorum of co-signers every proof needs
+    pub cosigning_policy: Option<CosigningPolicy>,
+    /// Weights and age window of trust scores
+    pub trust_config: TrustScoreConfig,
+    /// Ids of revoked proofs (`None` when revocation is not checked)
+    pub revoked_proofs: Option<HashSet<String>>,
+    /// Verified status lists of trusted issuers, by the URL they are published at
+    pub status_lists: HashMap<String, StatusList>,
+    /// How long after it was signed a status list is relied on
+    pub status_list_max_age: chrono::Duration,
+    /// Proving system verifying real proofs (`None` treats every proof as simulated)
+    pub proof_backend: Option<Arc<dyn ProofBackend>>,
+    /// Keys whose certificate name commitments identity bound claims are accepted with
+    pub certificate_signers: Vec<VerifyingKey>,
+    /// How long after their creation presentations are accepted, whatever their expiry
+    pub presentation_max_age: chrono::Duration,
+}
+
+/// Outcome of authenticating the issuer of a proof
+enum IssuerAuthentication {
+    /// Neither trusted keys nor a DID resolver are configured
+    Unchecked,
+    /// Signed by the DID issuer, which is not a trusted issuer
+    Untrusted(String),
+    /// Signed by a trusted issuer, with the issuer details for the result metadata
+    Authenticated(HashMap<String, String>),
 }
 
 /// Information about a trusted circuit
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CircuitInfo {
     /// Circuit identifier
     pub circuit_id: String,
@@ -69,84 +219,210 @@ pub struct CircuitInfo {
     pub vk_hash: String,
     /// Circuit description
     pub description: String,
+    /// Platforms the circuit is deployed on (empty for all platforms)
+    #[serde(default)]
+    pub platforms: Vec<Platform>,
+    /// Date from which the circuit is deprecated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecated_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Whether the circuit has been revoked
+    #[serde(default)]
+    pub revoked: bool,
 }
 
 impl ZkProofVerifier {
-    /// Create a new verifier with default trusted circuits
+    /// Create a new verifier with the built-in trusted circuits
     pub fn new(verifier_id: String) -> Self {
-        let mut trusted_circuits = HashMap::new();
-
-        // Add default trusted circuits using the same VK hash computation as issuer
-        trusted_circuits.insert(
-            "language_proficiency_v1".to_string(),
-            CircuitInfo {
-                circuit_id: "language_proficiency_v1".to_string(),
-                version: "1.0.0".to_string(),
-                vk_hash: CertificateIssuer::compute_verification_key_hash(
-                    "language_proficiency_v1",
-                ),
-                description: "Verifies language proficiency level claims".to_string(),
-            },
-        );
-
-        trusted_circuits.insert(
-            "performance_threshold_v1".to_string(),
-            CircuitInfo {
-                circuit_id: "performance_threshold_v1".to_string(),
-                version: "1.0.0".to_string(),
-                vk_hash: CertificateIssuer::compute_verification_key_hash(
-                    "performance_threshold_v1",
-                ),
-                description: "Verifies performance threshold claims".to_string(),
-            },
-        );
-
-        trusted_circuits.insert(
-            "completion_date_v1".to_string(),
-            CircuitInfo {
-                circuit_id: "completion_date_v1".to_string(),
-                version: "1.0.0".to_string(),
-                vk_hash: CertificateIssuer::compute_verification_key_hash("completion_date_v1"),
-                description: "Verifies completion date claims".to_string(),
-            },
-        );
+        Self::from_manifest(verifier_id, &CircuitManifest::builtin())
+    }
 
-        trusted_circuits.insert(
-            "combined_criteria_v1".to_string(),
-            CircuitInfo {
-                circuit_id: "combined_criteria_v1".to_string(),
-                version: "1.0.0".to_string(),
-                vk_hash: CertificateIssuer::compute_verification_key_hash("combined_criteria_v1"),
-                description: "Verifies combined criteria claims".to_string(),
-            },
-        );
+    /// Create a verifier trusting the circuits of a manifest
+    pub fn from_manifest(verifier_id: String, manifest: &CircuitManifest) -> Self {
+        let trusted_circuits = manifest
+            .circuits
+            .iter()
+            .map(|circuit| (circuit.circuit_id.clone(), circuit.clone()))
+            .collect();
 
         Self {
             verifier_id,
-            supported_platforms: vec!["aleo".to_string(), "stylus".to_string(), "test".to_string()],
+            supported_platforms: Platform::ALL.to_vec(),
             trusted_circuits,
+            mode: VerificationMode::default(),
+            did_resolver: None,
+            trusted_issuers: Vec::new(),
+            trusted_keys: HashMap::new(),
+            cosigning_policy: None,
+            trust_config: TrustScoreConfig::default(),
+            revoked_proofs: None,
+            status_lists: HashMap::new(),
+            status_list_max_age: chrono::Duration::hours(DEFAULT_STATUS_LIST_MAX_AGE_HOURS),
+            proof_backend: None,
+            certificate_signers: Vec::new(),
+            presentation_max_age: chrono::Duration::minutes(DEFAULT_PRESENTATION_VALIDITY_MINUTES),
+        }
+    }
+
+    /// Set how simulated proofs are handled
+    pub fn with_mode(mut self, mode: VerificationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Verify real proofs cryptographically with a proving system
+    pub fn with_proof_backend(mut self, backend: impl ProofBackend + 'static) -> Self {
+        self.proof_backend = Some(Arc::new(backend));
+        self
+    }
+
+    /// Accept identity bound claims whose certificate name commitment this key signed
+    pub fn with_certificate_signer(mut self, public_key: VerifyingKey) -> Self {
+        if !self.certificate_signers.contains(&public_key) {
+            self.certificate_signers.push(public_key);
+        }
+        self
+    }
+
+    /// Accept presentations for at most `max_age` after their creation
+    pub fn with_presentation_max_age(mut self, max_age: chrono::Duration) -> Self {
+        self.presentation_max_age = max_age;
+        self
+    }
+
+    /// Authenticate the issuer of every proof by resolving its DID
+    pub fn with_did_resolver(mut self, resolver: impl DidResolver + 'static) -> Self {
+        self.did_resolver = Some(Arc::new(resolver));
+        self
+    }
+
+    /// Require every proof to be co-signed by a quorum of signers
+    pub fn with_cosigning_policy(mut self, policy: CosigningPolicy) -> Self {
+        self.cosigning_policy = Some(policy);
+        self
+    }
+
+    /// Score trust with other weights or another age window
+    pub fn with_trust_config(mut self, config: TrustScoreConfig) -> Self {
+        self.trust_config = config;
+        self
+    }
+
+    /// Reject proofs on a list of revoked proof ids
+    pub fn with_revocation_list(mut self, proof_ids: impl IntoIterator<Item = String>) -> Self {
+        self.revoked_proofs = Some(proof_ids.into_iter().collect());
+        self
+    }
+
+    /// Rely on status lists for at most `max_age` after they were signed
+    pub fn with_status_list_max_age(mut self, max_age: chrono::Duration) -> Self {
+        self.status_list_max_age = max_age;
+        self
+    }
+
+    /// Check proofs against a status list signed by a trusted issuer
+    ///
+    /// The list replaces the previous list published at the same URL. Lists are verified with
+    /// the issuer's trusted key set or, for trusted DID issuers, the key of its DID document.
+    pub fn add_status_list(&mut self, signed: &SignedStatusList) -> Result<(), VerifierError> {
+        let issuer_id = &signed.list.issuer;
+        let list = match (self.trusted_keys.get(issuer_id), &self.did_resolver) {
+            (Some(key_set), _) => signed.verify_with(key_set)?,
+            (None, Some(resolver)) if self.trusted_issuers.contains(issuer_id) => {
+                let public_key = resolver
+                    .resolve(issuer_id)
+                    .and_then(|document| {
+                        document.assertion_key_at(
+                            &format!("#{}", signed.proof.key_id),
+                            signed.list.valid_from,
+                        )
+                    })
+                    .map_err(|e| VerifierError::IssuerAuthenticationFailed(e.to_string()))?;
+                signed.verify(&public_key)?
+            }
+            _ => return Err(VerifierError::UntrustedIssuer(issuer_id.clone())),
+        };
+        list.check_freshness(self.status_list_max_age, chrono::Utc::now())?;
+
+        self.status_lists.insert(list.id.clone(), list.clone());
+        Ok(())
+    }
+
+    /// Accept proofs of an issuer DID, restricting verification to trusted issuers
+    pub fn add_trusted_issuer(&mut self, did: String) {
+        if !self.trusted_issuers.contains(&did) {
+            self.trusted_issuers.push(did);
         }
     }
 
+    /// Trust the keys of an issuer, replacing its previously trusted set after a rotation
+    pub fn add_trusted_keys(&mut self, key_set: TrustedKeySet) {
+        self.trusted_keys.insert(key_set.issuer_id.clone(), key_set);
+    }
+
+    /// Create a verifier from a signed manifest published by a trusted key
+    pub fn from_signed_manifest(
+        verifier_id: String,
+        manifest: &SignedCircuitManifest,
+        trusted_key: &VerifyingKey,
+    ) -> Result<Self, RegistryError> {
+        let manifest = manifest.verify(trusted_key)?;
+        Ok(Self::from_manifest(verifier_id, manifest))
+    }
+
+    /// Create a verifier from a signed `.toml` or `.json` manifest file
+    pub fn load_manifest(
+        verifier_id: String,
+        path: impl AsRef<std::path::Path>,
+        trusted_key: &VerifyingKey,
+    ) -> Result<Self, RegistryError> {
+        let manifest = SignedCircuitManifest::load(path)?;
+        Self::from_signed_manifest(verifier_id, &manifest, trusted_key)
+    }
+
     /// Verify a zero-knowledge proof claim
     pub fn verify_proof(&self, proof: &ZkProofClaim) -> Result<VerificationResult, VerifierError> {
         // Step 1: Basic integrity checks
         if !proof.verify_integrity() {
             return Err(VerifierError::IntegrityCheckFailed);
         }
+        if self
+            .revoked_proofs
+            .as_ref()
+            .is_some_and(|revoked| revoked.contains(&proof.proof_id))
+        {
+            return Err(VerifierError::RevokedProof(proof.proof_id.clone()));
+        }
+        let status_checked = self.check_credential_status(proof)?;
+
+        let issuer = self.authenticate_issuer(proof)?;
+        let issuer_authenticated = matches!(issuer, IssuerAuthentication::Authenticated(_));
+        let proof_mode = self.check_proof_mode(proof, issuer_authenticated)?;
+        let signers = match &self.cosigning_policy {
+            Some(policy) => policy.verify(proof)?,
+            None => Vec::new(),
+        };
 
         // Step 2: Platform support check
-        if !self.supported_platforms.contains(&proof.metadata.platform) {
-            return Err(VerifierError::UnsupportedPlatform(
-                proof.metadata.platform.clone(),
-            ));
-        }
+        let platform = proof
+            .metadata
+            .platform
+            .parse::<Platform>()
+            .ok()
+            .filter(|platform| self.supported_platforms.contains(platform))
+            .ok_or_else(|| VerifierError::UnsupportedPlatform(proof.metadata.platform.clone()))?;
+        platform
+            .check_claim(&proof.claim_type)
+            .map_err(|e| VerifierError::VerificationFailed(e.to_string()))?;
 
         // Step 3: Circuit verification
-        self.verify_circuit(&proof.proof_data.circuit_id, &proof.proof_data.vk_hash)?;
+        let circuit_warnings = self.verify_circuit(
+            &proof.proof_data.circuit_id,
+            &proof.proof_data.vk_hash,
+            platform,
+        )?;
 
         // Step 4: Proof verification based on claim type
-        let verification_result = match &proof.claim_type {
+        let mut verification_result = match &proof.claim_type {
             ClaimType::LanguageProficiency {
                 language,
                 min_level,
@@ -158,17 +434,329 @@ impl ZkProofVerifier {
                 self.verify_completion_date_claim(proof, after_date)?
             }
             ClaimType::Combined { criteria } => self.verify_combined_claim(proof, criteria)?,
+            ClaimType::IdentityBound { name_match } => {
+                self.verify_identity_bound_claim(proof, *name_match)?
+            }
+            ClaimType::NationalityNotIn { .. } | ClaimType::DocumentValid { .. } => {
+                self.verify_identity_attribute_claim(proof)?
+            }
         };
+        // Met identity claims must carry the passport attributes they were evaluated against
+        if proof.claim_type.needs_identity()
+            && proof.public_inputs.verification_result
+            && IdentityAttributes::from_claim(proof).is_none()
+        {
+            return Err(VerifierError::MissingIdentity);
+        }
+        self.verify_name_bindings(proof, &proof.claim_type, "")?;
+
+        verification_result.warnings.extend(circuit_warnings);
+        if let Some(identity) = IdentityAttributes::from_claim(proof) {
+            let metadata = &mut verification_result.details.metadata;
+            metadata.insert("identity_verified".to_string(), "true".to_string());
+            if let Some(age) = identity.age_over {
+                metadata.insert("identity_age_over".to_string(), age.to_string());
+            }
+            if !issuer_authenticated {
+                verification_result
+                    .warnings
+                    .push(UNAUTHENTICATED_IDENTITY_WARNING.to_string());
+            }
+            if identity.mode == ProofMode::Simulated {
+                if self.mode == VerificationMode::Strict {
+                    return Err(VerifierError::SimulatedProofRejected);
+                }
+                verification_result
+                    .warnings
+                    .push(UNVERIFIED_PASSPORT_PROOF_WARNING.to_string());
+            }
+        }
+        let trust_inputs = self.trust_inputs(
+            proof,
+            proof_mode,
+            issuer_authenticated,
+            status_checked,
+            &signers,
+        );
+        verification_result.trust_score = Some(TrustScore::compute(
+            &trust_inputs,
+            &self.trust_config,
+            chrono::Utc::now(),
+        )?);
+        verification_result.details.metadata.insert(
+            "issuer_authenticated".to_string(),
+            issuer_authenticated.to_string(),
+        );
+        match issuer {
+            IssuerAuthentication::Authenticated(metadata) => {
+                verification_result.details.metadata.extend(metadata)
+            }
+            IssuerAuthentication::Untrusted(did) => {
+                verification_result
+                    .details
+                    .metadata
+                    .insert("untrusted_issuer_did".to_string(), did);
+                verification_result
+                    .warnings
+                    .push(UNTRUSTED_ISSUER_WARNING.to_string());
+            }
+            IssuerAuthentication::Unchecked => {}
+        }
+        verification_result.details.signers = signers;
+        verification_result
+            .details
+            .metadata
+            .insert("proof_mode".to_string(), proof_mode.to_string());
+        if proof_mode == ProofMode::Simulated {
+            verification_result
+                .warnings
+                .insert(0, SIMULATED_PROOF_WARNING.to_string());
+        }
 
         Ok(verification_result)
     }
 
-    /// Verify circuit integrity and trust
+    /// Verify a presentation against the challenge the verifier issued
+    ///
+    /// Checks audience, nonce, expiry, the verifier's maximum age, the holder signature and
+    /// that every claim is bound to the holder key before verifying every presented claim.
+    /// A claim that fails verification fails the whole presentation. The holder key of a
+    /// claim is only as trustworthy as its issuer signature, so claims of unauthenticated
+    /// issuers are flagged and clear `holder_binding_authenticated`.
+    pub fn verify_presentation(
+        &self,
+        presentation: &Presentation,
+        audience: &str,
+        nonce: &str,
+    ) -> Result<PresentationVerificationResult, VerifierError> {
+        let now = chrono::Utc::now();
+        presentation.check_challenge(audience, nonce, now)?;
+        presentation.check_age(self.presentation_max_age, now)?;
+        presentation.verify_signature()?;
+        presentation.check_holder_binding()?;
+
+        let mut results = presentation
+            .claims
+            .iter()
+            .map(|claim| self.verify_proof(claim))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut holder_binding_authenticated = true;
+        for result in &mut results {
+            if result.details.metadata.get("issuer_authenticated") != Some(&true.to_string()) {
+                holder_binding_authenticated = false;
+                result
+                    .warnings
+                    .push(UNAUTHENTICATED_HOLDER_BINDING_WARNING.to_string());
+            }
+        }
+
+        Ok(PresentationVerificationResult {
+            holder_id: presentation.holder_id.clone(),
+            holder_key: presentation.holder_key.clone(),
+            is_valid: results.iter().all(|result| result.is_valid),
+            requirements_met: results.iter().all(|result| result.requirements_met),
+            holder_binding_authenticated,
+            results,
+        })
+    }
+
+    /// Establish how the proof bytes were produced
+    ///
+    /// The stated mode is only trusted when an authenticated issuer signature covers it,
+    /// and a proof only counts as real once the proof backend verified it. Strict mode
+    /// refuses every other proof.
+    fn check_proof_mode(
+        &self,
+        proof: &ZkProofClaim,
+        issuer_authenticated: bool,
+    ) -> Result<ProofMode, VerifierError> {
+        let stated_mode = match issuer_authenticated {
+            true => proof.proof_data.mode,
+            false => ProofMode::Simulated,
+        };
+
+        let verified = match (stated_mode, &self.proof_backend) {
+            (ProofMode::Real, Some(backend)) => {
+                let circuit_id = &proof.proof_data.circuit_id;
+                match backend.verify(proof) {
+                    Ok(true) => true,
+                    Ok(false) => return Err(VerifierError::ProofRejected(circuit_id.clone())),
+                    Err(e) => {
+                        return Err(VerifierError::ProofRejected(format!(
+                            "{}: {}",
+                            circuit_id, e
+                        )))
+                    }
+                }
+            }
+            _ => false,
+        };
+
+        if !verified && self.mode == VerificationMode::Strict {
+            return Err(match stated_mode {
+                ProofMode::Real => VerifierError::NoProofBackend,
+                ProofMode::Simulated => VerifierError::SimulatedProofRejected,
+            });
+        }
+
+        Ok(match verified {
+            true => ProofMode::Real,
+            false => ProofMode::Simulated,
+        })
+    }
+
+    /// Facts about a verified proof its trust score is computed from
+    fn trust_inputs(
+        &self,
+        proof: &ZkProofClaim,
+        proof_mode: ProofMode,
+        issuer_authenticated: bool,
+        status_checked: bool,
+        signers: &[String],
+    ) -> TrustInputs {
+        let properties = &proof.metadata.properties;
+        let signature = if issuer_authenticated {
+            SignatureStatus::Verified
+        } else if properties.contains_key(ISSUER_SIGNATURE_PROPERTY)
+            || properties.contains_key(ISSUER_PQ_SIGNATURE_PROPERTY)
+        {
+            SignatureStatus::Unchecked
+        } else {
+            SignatureStatus::Missing
+        };
+
+        let identity = match IdentityAttributes::from_claim(proof) {
+            Some(identity) => IdentityStatus::Verified {
+                mode: identity.mode,
+                name_bound: binds_name(&proof.claim_type),
+            },
+            None => IdentityStatus::None,
+        };
+
+        let issuer_id = properties
+            .get("issuer_id")
+            .map(String::as_str)
+            .unwrap_or("");
+        let issuer_accredited = !signers.is_empty()
+            || (issuer_authenticated
+                && (self.trusted_keys.contains_key(issuer_id)
+                    || self.trusted_issuers.iter().any(|did| did == issuer_id)));
+
+        let revocation = match &self.revoked_proofs {
+            Some(revoked) if revoked.contains(&proof.proof_id) => RevocationStatus::Revoked,
+            Some(_) => RevocationStatus::Valid,
+            None if status_checked => RevocationStatus::Valid,
+            None => RevocationStatus::Unknown,
+        };
+
+        TrustInputs {
+            signature,
+            identity,
+            issuer_accredited,
+            proof_mode,
+            issued_at: proof.generated_at,
+            revocation,
+        }
+    }
+
+    /// Look the proof up in the status list its issuer recorded for it
+    ///
+    /// Returns whether a fresh list of the issuer covered the proof.
+    fn check_credential_status(&self, proof: &ZkProofClaim) -> Result<bool, VerifierError> {
+        let Some(entry) = proof.credential_status() else {
+            return Ok(false);
+        };
+        let Some(list) = self.status_lists.get(&entry.status_list_credential) else {
+            return Ok(false);
+        };
+        if proof.metadata.properties.get("issuer_id") != Some(&list.issuer) {
+            return Err(StatusListError::ListMismatch(entry.id).into());
+        }
+        list.check_freshness(self.status_list_max_age, chrono::Utc::now())?;
+
+        if list.entry_status(&entry)? {
+            return Err(VerifierError::RevokedProof(proof.proof_id.clone()));
+        }
+        Ok(true)
+    }
+
+    /// Check the issuer signature against a trusted key set or the issuer's DID document
+    ///
+    /// A DID issuer only counts as authenticated when it is a trusted issuer; a valid
+    /// signature of any other DID, such as a freshly generated `did:key`, proves nothing
+    /// about who issued the claim.
+    fn authenticate_issuer(
+        &self,
+        proof: &ZkProofClaim,
+    ) -> Result<IssuerAuthentication, VerifierError> {
+        let failed = |reason: String| VerifierError::IssuerAuthenticationFailed(reason);
+        let properties = &proof.metadata.properties;
+        let issuer_id = properties.get("issuer_id").cloned().unwrap_or_default();
+        let key_id = properties.get(ISSUER_KEY_ID_PROPERTY);
+        let mut metadata = HashMap::new();
+        if let Some(key_id) = key_id {
+            metadata.insert("issuer_key_id".to_string(), key_id.clone());
+        }
+
+        if let Some(key_set) = self.trusted_keys.get(&issuer_id) {
+            let key_id = key_id.ok_or_else(|| failed("proof names no issuer key".to_string()))?;
+            let public_key = key_set.key_at(key_id, proof.generated_at).ok_or_else(|| {
+                failed(format!(
+                    "key {} of {} was not valid when the proof was generated",
+                    key_id, issuer_id
+                ))
+            })?;
+            verify_issuer_signature(proof, &public_key)?;
+            return Ok(IssuerAuthentication::Authenticated(metadata));
+        }
+
+        let Some(resolver) = &self.did_resolver else {
+            if !self.trusted_keys.is_empty() {
+                return Err(VerifierError::UntrustedIssuer(issuer_id));
+            }
+            return Ok(IssuerAuthentication::Unchecked);
+        };
+
+        if !issuer_id.starts_with("did:") {
+            return Err(failed(
+                "proof issuer is not identified by a DID".to_string(),
+            ));
+        }
+        let verification_method = properties
+            .get(ISSUER_VERIFICATION_METHOD_PROPERTY)
+            .filter(|method| method.starts_with(&format!("{}#", issuer_id)))
+            .ok_or_else(|| {
+                failed(format!(
+                    "proof names no verification method of {}",
+                    issuer_id
+                ))
+            })?;
+
+        let public_key = resolver
+            .resolve(&issuer_id)
+            .and_then(|document| document.assertion_key_at(verification_method, proof.generated_at))
+            .map_err(|e| failed(e.to_string()))?;
+        verify_issuer_signature(proof, &public_key)?;
+
+        if !self.trusted_issuers.contains(&issuer_id) {
+            if !self.trusted_issuers.is_empty() {
+                return Err(VerifierError::UntrustedIssuer(issuer_id));
+            }
+            return Ok(IssuerAuthentication::Untrusted(issuer_id));
+        }
+
+        metadata.insert("issuer_did".to_string(), issuer_id);
+        Ok(IssuerAuthentication::Authenticated(metadata))
+    }
+
+    /// Verify circuit integrity and trust, returning warnings for deprecated circuits
     fn verify_circuit(
         &self,
         circuit_id: &str,
         provided_vk_hash: &str,
-    ) -> Result<(), VerifierError> {
+        platform: Platform,
+    ) -> Result<Vec<String>, VerifierError> {
         let circuit_info = self.trusted_circuits.get(circuit_id).ok_or_else(|| {
             VerifierError::CircuitVerificationFailed(format!("Unknown circuit: {}", circuit_id))
         })?;
@@ -182,7 +770,22 @@ impl ZkProofVerifier {
             )));
         }
 
-        Ok(())
+        if !circuit_info.supports_platform(platform) {
+            return Err(VerifierError::CircuitVerificationFailed(format!(
+                "Circuit '{}' is not deployed on platform '{}'",
+                circuit_id, platform
+            )));
+        }
+
+        match circuit_info.status_at(chrono::Utc::now()) {
+            CircuitStatus::Active => Ok(vec![]),
+            CircuitStatus::Deprecated { since } => Ok(vec![format!(
+                "Circuit '{}' is deprecated since {}",
+                circuit_id,
+                since.format("%Y-%m-%d")
+            )]),
+            CircuitStatus::Revoked => Err(VerifierError::RevokedCircuit(circuit_id.to_string())),
+        }
     }
 
     /// Verify language proficiency claim
@@ -204,10 +807,11 @@ impl ZkProofVerifier {
             verified_at: chrono::Utc::now(),
             verified_inputs: proof.public_inputs.requirements.clone(),
             metadata: proof.metadata.properties.clone(),
+            signers: Vec::new(),
         };
 
         let warnings = if !requirements_met {
-            vec!["Language proficiency requirements not met".to_string()]
+            vec![unmet_warning(ClaimKind::LanguageProficiency).to_string()]
         } else {
             vec![]
         };
@@ -217,6 +821,7 @@ impl ZkProofVerifier {
             requirements_met,
             details,
             warnings,
+            trust_score: None,
         })
     }
 
@@ -235,10 +840,11 @@ impl ZkProofVerifier {
             verified_at: chrono::Utc::now(),
             verified_inputs: proof.public_inputs.requirements.clone(),
             metadata: proof.metadata.properties.clone(),
+            signers: Vec::new(),
         };
 
         let warnings = if !requirements_met {
-            vec!["Performance threshold not met".to_string()]
+            vec![unmet_warning(ClaimKind::PerformanceThreshold).to_string()]
         } else {
             vec![]
         };
@@ -248,6 +854,7 @@ impl ZkProofVerifier {
             requirements_met,
             details,
             warnings,
+            trust_score: None,
         })
     }
 
@@ -266,10 +873,11 @@ impl ZkProofVerifier {
             verified_at: chrono::Utc::now(),
             verified_inputs: proof.public_inputs.requirements.clone(),
             metadata: proof.metadata.properties.clone(),
+            signers: Vec::new(),
         };
 
         let warnings = if !requirements_met {
-            vec!["Completion date requirements not met".to_string()]
+            vec![unmet_warning(ClaimKind::CompletionDate).to_string()]
         } else {
             vec![]
         };
@@ -279,6 +887,7 @@ impl ZkProofVerifier {
             requirements_met,
             details,
             warnings,
+            trust_score: None,
         })
     }
 
@@ -297,10 +906,44 @@ impl ZkProofVerifier {
             verified_at: chrono::Utc::now(),
             verified_inputs: proof.public_inputs.requirements.clone(),
             metadata: proof.metadata.properties.clone(),
+            signers: Vec::new(),
+        };
+
+        let warnings = if !requirements_met {
+            vec![unmet_warning(ClaimKind::Combined).to_string()]
+        } else {
+            vec![]
+        };
+
+        Ok(VerificationResult {
+            is_valid,
+            requirements_met,
+            details,
+            warnings,
+            trust_score: None,
+        })
+    }
+
+    /// Verify identity binding claim
+    fn verify_identity_bound_claim(
+        &self,
+        proof: &ZkProofClaim,
+        _name_match: NameMatch,
+    ) -> Result<VerificationResult, VerifierError> {
+        let is_valid = self.simulate_proof_verification(&proof.proof_data.proof_bytes);
+        let requirements_met = proof.public_inputs.verification_result;
+
+        let details = VerificationDetails {
+            platform: proof.metadata.platform.clone(),
+            circuit_id: proof.proof_data.circuit_id.clone(),
+            verified_at: chrono::Utc::now(),
+            verified_inputs: proof.public_inputs.requirements.clone(),
+            metadata: proof.metadata.properties.clone(),
+            signers: Vec::new(),
         };
 
         let warnings = if !requirements_met {
-            vec!["Combined criteria requirements not met".to_string()]
+            vec![unmet_warning(ClaimKind::IdentityBound).to_string()]
         } else {
             vec![]
         };
@@ -310,13 +953,87 @@ impl ZkProofVerifier {
             requirements_met,
             details,
             warnings,
+            trust_score: None,
+        })
+    }
+
+    /// Verify nationality or document validity claim
+    fn verify_identity_attribute_claim(
+        &self,
+        proof: &ZkProofClaim,
+    ) -> Result<VerificationResult, VerifierError> {
+        let is_valid = self.simulate_proof_verification(&proof.proof_data.proof_bytes);
+        let requirements_met = proof.public_inputs.verification_result;
+
+        let details = VerificationDetails {
+            platform: proof.metadata.platform.clone(),
+            circuit_id: proof.proof_data.circuit_id.clone(),
+            verified_at: chrono::Utc::now(),
+            verified_inputs: proof.public_inputs.requirements.clone(),
+            metadata: proof.metadata.properties.clone(),
+            signers: Vec::new(),
+        };
+
+        let warnings = match (&proof.claim_type, requirements_met) {
+            (_, true) => vec![],
+            (ClaimType::NationalityNotIn { .. }, false) => {
+                vec![unmet_warning(ClaimKind::NationalityNotIn).to_string()]
+            }
+            (_, false) => vec![unmet_warning(ClaimKind::DocumentValid).to_string()],
+        };
+
+        Ok(VerificationResult {
+            is_valid,
+            requirements_met,
+            details,
+            warnings,
+            trust_score: None,
         })
     }
 
-    /// Simulate proof verification (replace with real cryptographic verification)
+    /// Check the name equality proofs of identity bound claims, including criteria of
+    /// combined claims, against the certificate hash and the trusted certificate signers
+    fn verify_name_bindings(
+        &self,
+        proof: &ZkProofClaim,
+        claim_type: &ClaimType,
+        prefix: &str,
+    ) -> Result<(), VerifierError> {
+        match claim_type {
+            ClaimType::IdentityBound { name_match } => {
+                let key = format!("{}{}", prefix, NAME_BINDING_REQUIREMENT);
+                let binding: NameBinding = proof
+                    .public_inputs
+                    .requirements
+                    .get(&key)
+                    .and_then(|value| serde_json::from_value(value.clone()).ok())
+                    .ok_or(NameBindingError::MissingBinding(key))?;
+                if binding.name_match != *name_match {
+                    return Err(NameBindingError::InvalidProof.into());
+                }
+                let signer = binding.verify(proof.public_inputs.certificate_hash.as_bytes())?;
+                if !self.certificate_signers.contains(&signer) {
+                    return Err(NameBindingError::UntrustedCommitment.into());
+                }
+                Ok(())
+            }
+            // Criteria that failed at issuance carry no binding and leave the claim unmet
+            ClaimType::Combined { criteria } if proof.public_inputs.verification_result => {
+                for (i, criterion) in criteria.iter().enumerate() {
+                    let prefix = format!("{}criterion_{}_", prefix, i);
+                    self.verify_name_bindings(proof, criterion, &prefix)?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Structural check of the proof bytes
+    ///
+    /// Cryptographic verification is the proof backend's job (see `check_proof_mode`); this
+    /// only rejects truncated proofs.
     fn simulate_proof_verification(&self, proof_bytes: &[u8]) -> bool {
-        // In a real implementation, this would perform actual ZK proof verification
-        // For this demo, we simulate by checking if proof bytes are non-empty and have valid structure
         !proof_bytes.is_empty() && proof_bytes.len() >= 32
     }
 
@@ -368,12 +1085,44 @@ pub struct VerificationStats {
     pub verifier_id: String,
 }
 
+/// Check the issuer signature of a proof against the issuer's public key
+fn verify_issuer_signature(
+    proof: &ZkProofClaim,
+    public_key: &IssuerPublicKey,
+) -> Result<(), VerifierError> {
+    let failed = |reason: String| VerifierError::IssuerAuthenticationFailed(reason);
+    let signature = IssuerSignature::from_properties(&proof.metadata.properties)
+        .map_err(|e| failed(e.to_string()))?;
+    if signature.ed25519.is_none() && signature.ml_dsa.is_none() {
+        return Err(failed("proof is not signed by its issuer".to_string()));
+    }
+
+    public_key
+        .verify(&proof.issuer_signing_bytes(), &signature)
+        .map_err(|e| failed(e.to_string()))
+}
+
+/// Whether a claim, or one of its criteria, binds the certificate name to the passport name
+fn binds_name(claim_type: &ClaimType) -> bool {
+    match claim_type {
+        ClaimType::IdentityBound { .. } => true,
+        ClaimType::Combined { criteria } => criteria.iter().any(binds_name),
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::issuer::{CertificateIssuer, ProofOptions, ProofRequest};
-    use crate::zk_proof::{CefrLevel, ClaimType};
-    use chrono::Utc;
+    use crate::did::FileDidResolver;
+    use crate::identity::{PassportPublicInputs, VerifiedIdentity};
+    use crate::issuer::{
+        CertificateIssuer, GameResult, ProofOptions, ProofRequest, ProvingBackend,
+    };
+    use crate::testing::{create_real_proof_setup, generate_real_proof, TestProvingSystem};
+    use crate::trust::{TrustFactor, TrustWeights};
+    use crate::zk_proof::{CefrLevel, ClaimType, PublicInputs};
+    use chrono::{NaiveDate, Utc};
     use konnektoren_core::certificates::CertificateData;
 
     fn create_test_verifier() -> ZkProofVerifier {
@@ -381,27 +1130,41 @@ mod tests {
     }
 
     fn create_test_proof() -> ZkProofClaim {
-        let issuer = CertificateIssuer::new("test_issuer".to_string(), "Test Issuer".to_string());
+        create_test_proof_from(
+            &CertificateIssuer::new("test_issuer".to_string(), "Test Issuer".to_string())
+                .accept_unsigned_certificates(),
+        )
+    }
 
-        let certificate = CertificateData::new(
-            "German_B2_Complete".to_string(),
-            50,
-            47,
-            "Test Student".to_string(),
-            Utc::now(),
-        );
+    fn create_test_proof_from(issuer: &CertificateIssuer) -> ZkProofClaim {
+        issuer.generate_proof(create_test_request()).unwrap()
+    }
 
-        let request = ProofRequest {
-            certificate,
+    fn create_holder_proof(holder: &crate::holder::Holder) -> ZkProofClaim {
+        let mut request = create_test_request();
+        request.options.holder_key = Some(holder.public_key_hex());
+        CertificateIssuer::new("test_issuer".to_string(), "Test Issuer".to_string())
+            .accept_unsigned_certificates()
+            .generate_proof(request)
+            .unwrap()
+    }
+
+    fn create_test_request() -> ProofRequest {
+        ProofRequest {
+            certificate: CertificateData::new(
+                "German_B2_Complete".to_string(),
+                7,
+                7,
+                "Test Student".to_string(),
+                Utc::now(),
+            ),
             claim_type: ClaimType::LanguageProficiency {
                 language: "German".to_string(),
                 min_level: CefrLevel::B1,
             },
             target_platform: "test".to_string(),
             options: ProofOptions::default(),
-        };
-
-        issuer.generate_proof(request).unwrap()
+        }
     }
 
     #[test]
@@ -423,7 +1186,10 @@ mod tests {
         let verification_result = result.unwrap();
         assert!(verification_result.is_valid);
         assert!(verification_result.requirements_met);
-        assert!(verification_result.warnings.is_empty());
+        assert_eq!(
+            verification_result.warnings,
+            vec![SIMULATED_PROOF_WARNING.to_string()]
+        );
     }
 
     #[test]
@@ -479,12 +1245,13 @@ mod tests {
 
     #[test]
     fn test_performance_threshold_verification() {
-        let issuer = CertificateIssuer::new("test_issuer".to_string(), "Test Issuer".to_string());
+        let issuer = CertificateIssuer::new("test_issuer".to_string(), "Test Issuer".to_string())
+            .accept_unsigned_certificates();
 
         let certificate = CertificateData::new(
             "Spanish_B2_Complete".to_string(),
-            50,
-            45, // 90% performance
+            6,
+            6,
             "Test Student".to_string(),
             Utc::now(),
         );
@@ -513,6 +1280,9 @@ mod tests {
             version: "1.0.0".to_string(),
             vk_hash: "custom_hash".to_string(),
             description: "Custom circuit for testing".to_string(),
+            platforms: vec![],
+            deprecated_at: None,
+            revoked: false,
         };
 
         verifier.add_trusted_circuit(new_circuit);
@@ -550,8 +1320,8 @@ mod tests {
         let stats = verifier.get_verification_stats();
 
         assert_eq!(stats.verifier_id, "test_verifier_001");
-        assert_eq!(stats.supported_platforms, 3); // aleo, stylus, test
-        assert_eq!(stats.trusted_circuits, 4); // 4 default circuits
+        assert_eq!(stats.supported_platforms, 4); // aleo, stylus, web5claims_local, test
+        assert_eq!(stats.trusted_circuits, 7); // 7 default circuits
     }
 
     #[test]
@@ -572,11 +1342,12 @@ mod tests {
     #[test]
     fn test_end_to_end_verification() {
         // Create issuer and generate proof
-        let issuer = CertificateIssuer::new("e2e_issuer".to_string(), "E2E Test".to_string());
+        let issuer = CertificateIssuer::new("e2e_issuer".to_string(), "E2E Test".to_string())
+            .accept_unsigned_certificates();
         let certificate = CertificateData::new(
             "French_C1_Advanced".to_string(),
-            60,
-            58,
+            6,
+            6,
             "Advanced Student".to_string(),
             Utc::now(),
         );
@@ -610,4 +1381,1285 @@ mod tests {
             "combined_criteria_v1"
         );
     }
+
+    #[test]
+    fn test_deprecated_circuit_warning() {
+        let mut manifest = CircuitManifest::builtin();
+        for circuit in &mut manifest.circuits {
+            circuit.deprecated_at = Some(Utc::now() - chrono::Duration::days(1));
+        }
+        let verifier = ZkProofVerifier::from_manifest("test_verifier".to_string(), &manifest);
+
+        let result = verifier.verify_proof(&create_test_proof()).unwrap();
+        assert!(result.is_valid);
+        assert!(result
+            .warnings
+            .iter()
+            .any(|warning| warning.contains("language_proficiency_v1")
+                && warning.contains("deprecated")));
+    }
+
+    #[test]
+    fn test_revoked_circuit_rejected() {
+        let mut manifest = CircuitManifest::builtin();
+        for circuit in &mut manifest.circuits {
+            circuit.revoked = true;
+        }
+        let verifier = ZkProofVerifier::from_manifest("test_verifier".to_string(), &manifest);
+
+        match verifier.verify_proof(&create_test_proof()) {
+            Err(VerifierError::RevokedCircuit(circuit_id)) => {
+                assert_eq!(circuit_id, "language_proficiency_v1");
+            }
+            other => panic!("Expected RevokedCircuit error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_circuit_platform_restriction() {
+        let mut manifest = CircuitManifest::builtin();
+        for circuit in &mut manifest.circuits {
+            circuit.platforms = vec![Platform::Aleo];
+        }
+        let verifier = ZkProofVerifier::from_manifest("test_verifier".to_string(), &manifest);
+
+        assert!(matches!(
+            verifier.verify_proof(&create_test_proof()),
+            Err(VerifierError::CircuitVerificationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_load_signed_manifest() {
+        let key = ed25519_dalek::SigningKey::from_bytes(&[3u8; 32]);
+        let signed = CircuitManifest::builtin().sign(&key);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("circuits.toml");
+        std::fs::write(
+            &path,
+            signed
+                .encode(crate::registry::ManifestFormat::Toml)
+                .unwrap(),
+        )
+        .unwrap();
+
+        let verifier = ZkProofVerifier::load_manifest(
+            "test_verifier".to_string(),
+            &path,
+            &key.verifying_key(),
+        );
+
+        let verifier = verifier.unwrap();
+        assert_eq!(verifier.trusted_circuits.len(), 7);
+        assert!(verifier.verify_proof(&create_test_proof()).is_ok());
+
+        let other_key = ed25519_dalek::SigningKey::from_bytes(&[4u8; 32]);
+        assert!(matches!(
+            ZkProofVerifier::from_signed_manifest(
+                "test_verifier".to_string(),
+                &signed,
+                &other_key.verifying_key()
+            ),
+            Err(RegistryError::UntrustedSigner(_))
+        ));
+    }
+
+    #[test]
+    fn test_local_platform_proof_verifies() {
+        let issuer = CertificateIssuer::new("test_issuer".to_string(), "Test Issuer".to_string())
+            .accept_unsigned_certificates();
+        let verifier = create_test_verifier();
+
+        for platform in issuer.supported_platforms.clone() {
+            assert!(verifier.supported_platforms.contains(&platform));
+
+            let request = ProofRequest {
+                certificate: CertificateData::new(
+                    "German_B2_Complete".to_string(),
+                    7,
+                    7,
+                    "Test Student".to_string(),
+                    Utc::now(),
+                ),
+                claim_type: ClaimType::PerformanceThreshold { min_percentage: 80 },
+                target_platform: platform.to_string(),
+                options: ProofOptions::default(),
+            };
+
+            let proof = issuer.generate_proof(request).unwrap();
+            assert!(verifier.verify_proof(&proof).unwrap().is_valid);
+        }
+    }
+
+    #[test]
+    fn test_strict_mode_rejects_simulated_proof() {
+        let verifier = create_test_verifier().with_mode(VerificationMode::Strict);
+
+        assert!(matches!(
+            verifier.verify_proof(&create_test_proof()),
+            Err(VerifierError::SimulatedProofRejected)
+        ));
+    }
+
+    #[test]
+    fn test_lenient_mode_flags_simulated_proof() {
+        let verifier = create_test_verifier();
+        assert_eq!(verifier.mode, VerificationMode::Lenient);
+
+        let result = verifier.verify_proof(&create_test_proof()).unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.warnings[0], SIMULATED_PROOF_WARNING);
+        assert_eq!(result.details.metadata["proof_mode"], "simulated");
+    }
+
+    #[test]
+    fn test_relabelled_simulated_proof_rejected() {
+        let verifier = create_test_verifier().with_mode(VerificationMode::Strict);
+        let mut proof = create_test_proof();
+        proof.proof_data.mode = ProofMode::Real;
+
+        assert!(matches!(
+            verifier.verify_proof(&proof),
+            Err(VerifierError::IntegrityCheckFailed)
+        ));
+    }
+
+    /// Prover emitting proof bytes no proving system would accept
+    #[derive(Debug)]
+    struct ForgingProver;
+
+    impl ProvingBackend for ForgingProver {
+        fn prove(&self, _: &str, _: &PublicInputs) -> Result<Vec<u8>, String> {
+            Ok(vec![7u8; 32])
+        }
+    }
+
+    #[test]
+    fn test_strict_mode_accepts_verified_real_proof() {
+        let (issuer, verifier) = create_real_proof_setup();
+        let verifier = verifier.with_mode(VerificationMode::Strict);
+
+        let proof = generate_real_proof(
+            &issuer,
+            ClaimType::PerformanceThreshold { min_percentage: 80 },
+            "test",
+        );
+        let result = verifier.verify_proof(&proof).unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.details.metadata["proof_mode"], "real");
+        assert!(!result
+            .warnings
+            .contains(&SIMULATED_PROOF_WARNING.to_string()));
+    }
+
+    #[test]
+    fn test_strict_mode_needs_proof_backend() {
+        let dids = tempfile::tempdir().unwrap();
+        let issuer = create_real_issuer(TestProvingSystem);
+        let verifier = create_did_verifier(&dids).with_mode(VerificationMode::Strict);
+        let proof = issuer
+            .generate_proof(mint_request(&issuer, create_test_request()))
+            .unwrap();
+
+        assert!(matches!(
+            verifier.verify_proof(&proof),
+            Err(VerifierError::NoProofBackend)
+        ));
+    }
+
+    #[test]
+    fn test_unsigned_real_mode_not_trusted() {
+        // Without the simulated tag the relabelled proof passes the integrity check, but no
+        // issuer signature vouches for its mode
+        let mut proof = create_test_proof();
+        proof.proof_data.proof_bytes = vec![7u8; 32];
+        proof.proof_data.mode = ProofMode::Real;
+
+        let strict = create_test_verifier()
+            .with_mode(VerificationMode::Strict)
+            .with_proof_backend(TestProvingSystem);
+        assert!(matches!(
+            strict.verify_proof(&proof),
+            Err(VerifierError::SimulatedProofRejected)
+        ));
+
+        let result = create_test_verifier().verify_proof(&proof).unwrap();
+        assert_eq!(result.details.metadata["proof_mode"], "simulated");
+        assert_eq!(result.warnings[0], SIMULATED_PROOF_WARNING);
+    }
+
+    #[test]
+    fn test_proof_backend_rejects_forged_proof() {
+        let dids = tempfile::tempdir().unwrap();
+        let issuer = create_real_issuer(ForgingProver);
+        let verifier = create_did_verifier(&dids).with_proof_backend(TestProvingSystem);
+        let proof = issuer
+            .generate_proof(mint_request(&issuer, create_test_request()))
+            .unwrap();
+
+        assert!(matches!(
+            verifier.verify_proof(&proof),
+            Err(VerifierError::ProofRejected(_))
+        ));
+    }
+
+    #[test]
+    fn test_real_proofs_must_be_signed() {
+        let minter = create_did_issuer();
+        let issuer = CertificateIssuer::new("test_issuer".to_string(), "Test Issuer".to_string())
+            .accept_unsigned_certificates()
+            .with_prover(TestProvingSystem)
+            .with_certificate_signer(minter.public_key().unwrap());
+        let mut request = mint_request(&minter, create_test_request());
+        request.claim_type = ClaimType::PerformanceThreshold { min_percentage: 80 };
+        request.target_platform = "aleo".to_string();
+
+        assert!(matches!(
+            issuer.generate_proof(request),
+            Err(crate::issuer::IssuerError::ProofGenerationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_presentation_verification() {
+        let verifier = create_test_verifier();
+        let holder = crate::holder::Holder::generate();
+        let presentation = holder.sign_presentation(Presentation::new(
+            holder.holder_id.clone(),
+            &holder.public_key(),
+            vec![create_holder_proof(&holder), create_holder_proof(&holder)],
+            "test_verifier_001".to_string(),
+            "nonce-1".to_string(),
+        ));
+
+        let result = verifier
+            .verify_presentation(&presentation, "test_verifier_001", "nonce-1")
+            .unwrap();
+        assert!(result.is_valid);
+        assert!(result.requirements_met);
+        assert_eq!(result.results.len(), 2);
+        assert_eq!(result.holder_key, holder.public_key_hex());
+        // Without issuer authentication the holder binding is only flagged
+        assert!(!result.holder_binding_authenticated);
+        assert!(result.results[0]
+            .warnings
+            .contains(&UNAUTHENTICATED_HOLDER_BINDING_WARNING.to_string()));
+
+        assert!(matches!(
+            verifier.verify_presentation(&presentation, "test_verifier_001", "nonce-2"),
+            Err(VerifierError::InvalidPresentation(
+                PresentationError::NonceMismatch
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_presentation_with_invalid_claim_rejected() {
+        let verifier = create_test_verifier();
+        let holder = crate::holder::Holder::generate();
+        let mut invalid = create_holder_proof(&holder);
+        invalid.proof_data.vk_hash = "invalid_hash".to_string();
+        let presentation = holder.sign_presentation(Presentation::new(
+            holder.holder_id.clone(),
+            &holder.public_key(),
+            vec![create_holder_proof(&holder), invalid],
+            "test_verifier_001".to_string(),
+            "nonce-1".to_string(),
+        ));
+
+        assert!(matches!(
+            verifier.verify_presentation(&presentation, "test_verifier_001", "nonce-1"),
+            Err(VerifierError::CircuitVerificationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_presentation_of_unbound_claims_rejected() {
+        let verifier = create_test_verifier();
+        let holder = crate::holder::Holder::generate();
+        let other = crate::holder::Holder::generate();
+
+        for claim in [create_test_proof(), create_holder_proof(&other)] {
+            let proof_id = claim.proof_id.clone();
+            let presentation = holder.sign_presentation(Presentation::new(
+                holder.holder_id.clone(),
+                &holder.public_key(),
+                vec![create_holder_proof(&holder), claim],
+                "test_verifier_001".to_string(),
+                "nonce-1".to_string(),
+            ));
+
+            assert_eq!(
+                verifier
+                    .verify_presentation(&presentation, "test_verifier_001", "nonce-1")
+                    .unwrap_err()
+                    .to_string(),
+                format!(
+                    "Invalid presentation: {}",
+                    PresentationError::UnboundClaim(proof_id)
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_presentation_of_authenticated_claims() {
+        let dids = tempfile::tempdir().unwrap();
+        let issuer = create_did_issuer();
+        let verifier = create_did_verifier(&dids);
+        let holder = crate::holder::Holder::generate();
+        let mut request = create_test_request();
+        request.options.holder_key = Some(holder.public_key_hex());
+        let presentation = holder.sign_presentation(Presentation::new(
+            holder.holder_id.clone(),
+            &holder.public_key(),
+            vec![issuer.generate_proof(request).unwrap()],
+            "test_verifier_001".to_string(),
+            "nonce-1".to_string(),
+        ));
+
+        let result = verifier
+            .verify_presentation(&presentation, "test_verifier_001", "nonce-1")
+            .unwrap();
+        assert!(result.holder_binding_authenticated);
+        assert!(!result.results[0]
+            .warnings
+            .contains(&UNAUTHENTICATED_HOLDER_BINDING_WARNING.to_string()));
+    }
+
+    #[test]
+    fn test_old_presentation_rejected() {
+        let verifier = create_test_verifier();
+        let holder = crate::holder::Holder::generate();
+        let mut presentation = Presentation::new(
+            holder.holder_id.clone(),
+            &holder.public_key(),
+            vec![create_holder_proof(&holder)],
+            "test_verifier_001".to_string(),
+            "nonce-1".to_string(),
+        );
+        // The holder backdates the presentation and extends its validity
+        presentation.created_at = Utc::now() - chrono::Duration::hours(1);
+        let presentation =
+            holder.sign_presentation(presentation.with_validity(chrono::Duration::days(1)));
+
+        assert!(matches!(
+            verifier.verify_presentation(&presentation, "test_verifier_001", "nonce-1"),
+            Err(VerifierError::InvalidPresentation(
+                PresentationError::Expired(_)
+            ))
+        ));
+        assert!(verifier
+            .with_presentation_max_age(chrono::Duration::hours(2))
+            .verify_presentation(&presentation, "test_verifier_001", "nonce-1")
+            .is_ok());
+    }
+
+    fn create_did_issuer() -> CertificateIssuer {
+        CertificateIssuer::new("test_issuer".to_string(), "Test Issuer".to_string())
+            .accept_unsigned_certificates()
+            .with_did_key(ed25519_dalek::SigningKey::from_bytes(&[5u8; 32]))
+    }
+
+    /// Issuer generating real proofs from certificates it minted
+    fn create_real_issuer(prover: impl ProvingBackend + 'static) -> CertificateIssuer {
+        create_did_issuer()
+            .with_prover(prover)
+            .require_own_certificates()
+            .unwrap()
+    }
+
+    /// Mint the certificate of a request through the issuer
+    fn mint_request(issuer: &CertificateIssuer, request: ProofRequest) -> ProofRequest {
+        let certificate = &request.certificate;
+        let minted = issuer
+            .mint_certificate(&GameResult {
+                game_path_name: certificate.game_path_name.clone(),
+                profile_name: certificate.profile_name.clone(),
+                total_challenges: certificate.total_challenges,
+                solved_challenges: certificate.solved_challenges,
+                completed_at: certificate.date,
+            })
+            .unwrap();
+        minted.proof_request(request.claim_type, request.target_platform)
+    }
+
+    /// Verifier resolving DIDs from a directory and trusting the issuer of `create_did_issuer`
+    fn create_did_verifier(dids: &tempfile::TempDir) -> ZkProofVerifier {
+        let mut verifier =
+            create_test_verifier().with_did_resolver(FileDidResolver::new(dids.path()));
+        verifier.add_trusted_issuer(create_did_issuer().issuer_id);
+        verifier
+    }
+
+    #[test]
+    fn test_did_key_issuer_authenticated() {
+        let dids = tempfile::tempdir().unwrap();
+        let issuer = create_did_issuer();
+        let result = create_did_verifier(&dids)
+            .verify_proof(&create_test_proof_from(&issuer))
+            .unwrap();
+
+        assert!(result.is_valid);
+        assert_eq!(result.details.metadata["issuer_did"], issuer.issuer_id);
+
+        // The issuer signature survives the mapping to a W3C credential and back
+        let proof = create_test_proof_from(&issuer);
+        let credential = crate::vc::VerifiableCredential::from_claim(&proof).unwrap();
+        assert_eq!(credential.issuer.id, issuer.issuer_id);
+        assert!(create_did_verifier(&dids)
+            .verify_proof(&credential.to_claim().unwrap())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_did_key_issuer_without_trusted_issuers() {
+        let dids = tempfile::tempdir().unwrap();
+        let issuer = create_did_issuer();
+        // Anyone can create a did:key, so a valid signature alone does not authenticate it
+        let verifier = create_test_verifier().with_did_resolver(FileDidResolver::new(dids.path()));
+        let result = verifier
+            .verify_proof(&create_test_proof_from(&issuer))
+            .unwrap();
+
+        assert_eq!(result.details.metadata["issuer_authenticated"], "false");
+        assert_eq!(
+            result.details.metadata["untrusted_issuer_did"],
+            issuer.issuer_id
+        );
+        assert!(!result.details.metadata.contains_key("issuer_did"));
+        assert!(result
+            .warnings
+            .contains(&UNTRUSTED_ISSUER_WARNING.to_string()));
+        assert_eq!(
+            result
+                .trust_score
+                .unwrap()
+                .factor(TrustFactor::Signature)
+                .unwrap()
+                .score,
+            0.5
+        );
+    }
+
+    #[test]
+    fn test_did_web_issuer_authenticated() {
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[6u8; 32]);
+        let did = "did:web:web5.claims:issuers:konnektoren".to_string();
+        let issuer = CertificateIssuer::new(String::new(), "Konnektoren".to_string())
+            .accept_unsigned_certificates()
+            .with_did_web(did.clone(), "key-1", signing_key.clone());
+
+        let dids = tempfile::tempdir().unwrap();
+        let resolver = FileDidResolver::new(dids.path());
+        let path = resolver.document_path(&did).unwrap();
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let document = crate::did::DidDocument::for_key(
+            did.clone(),
+            "key-1".to_string(),
+            &signing_key.verifying_key(),
+        );
+        std::fs::write(&path, serde_json::to_string(&document).unwrap()).unwrap();
+
+        let mut verifier = create_test_verifier().with_did_resolver(resolver);
+        verifier.add_trusted_issuer(did.clone());
+        let result = verifier
+            .verify_proof(&create_test_proof_from(&issuer))
+            .unwrap();
+        assert_eq!(result.details.metadata["issuer_did"], did);
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            verifier.verify_proof(&create_test_proof_from(&issuer)),
+            Err(VerifierError::IssuerAuthenticationFailed(_))
+        ));
+    }
+
+    #[cfg(feature = "post-quantum")]
+    #[test]
+    fn test_did_web_hybrid_issuer_authenticated() {
+        use crate::did::{DidDocument, VerificationMethod};
+        use crate::signature::{IssuerSigningKey, MlDsaSigningKey};
+
+        let signing_key = IssuerSigningKey::Ed25519MlDsa65(
+            ed25519_dalek::SigningKey::from_bytes(&[6u8; 32]),
+            MlDsaSigningKey::from_seed([7u8; 32]),
+        );
+        let did = "did:web:web5.claims:issuers:konnektoren".to_string();
+        let method_id = format!("{}#key-1", did);
+        let document = DidDocument {
+            context: vec![crate::did::DID_CONTEXT.to_string()],
+            id: did.clone(),
+            verification_method: vec![VerificationMethod::for_issuer_key(
+                method_id.clone(),
+                did.clone(),
+                &signing_key.public_key(),
+            )],
+            authentication: vec![method_id.clone()],
+            assertion_method: vec![method_id],
+            service: vec![],
+        };
+
+        let dids = tempfile::tempdir().unwrap();
+        let resolver = FileDidResolver::new(dids.path());
+        let path = resolver.document_path(&did).unwrap();
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, serde_json::to_string(&document).unwrap()).unwrap();
+
+        let issuer = CertificateIssuer::new(String::new(), "Konnektoren".to_string())
+            .accept_unsigned_certificates()
+            .with_did_web(did.clone(), "key-1", signing_key);
+        let mut verifier = create_test_verifier().with_did_resolver(resolver);
+        verifier.add_trusted_issuer(did.clone());
+
+        let result = verifier
+            .verify_proof(&create_test_proof_from(&issuer))
+            .unwrap();
+        assert_eq!(result.details.metadata["issuer_did"], did);
+        assert_eq!(result.details.metadata["issuer_authenticated"], "true");
+    }
+
+    #[test]
+    fn test_tampered_proof_fails_issuer_authentication() {
+        let dids = tempfile::tempdir().unwrap();
+        let mut proof = create_test_proof_from(&create_did_issuer());
+        proof.public_inputs.verification_result = false;
+
+        assert!(matches!(
+            create_did_verifier(&dids).verify_proof(&proof),
+            Err(VerifierError::IssuerAuthenticationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_unsigned_proof_rejected_with_did_resolver() {
+        let dids = tempfile::tempdir().unwrap();
+        assert!(create_test_verifier()
+            .verify_proof(&create_test_proof())
+            .is_ok());
+        assert!(matches!(
+            create_did_verifier(&dids).verify_proof(&create_test_proof()),
+            Err(VerifierError::IssuerAuthenticationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_untrusted_issuer_rejected() {
+        let dids = tempfile::tempdir().unwrap();
+        let issuer = create_did_issuer();
+        let other = CertificateIssuer::new("other".to_string(), "Other".to_string())
+            .accept_unsigned_certificates()
+            .with_did_key(ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]));
+        let mut verifier = create_did_verifier(&dids);
+        verifier.add_trusted_issuer(issuer.issuer_id.clone());
+
+        assert!(verifier
+            .verify_proof(&create_test_proof_from(&issuer))
+            .is_ok());
+        assert_eq!(
+            verifier
+                .verify_proof(&create_test_proof_from(&other))
+                .unwrap_err()
+                .to_string(),
+            format!("Untrusted issuer: {}", other.issuer_id)
+        );
+    }
+
+    #[test]
+    fn test_rotating_trusted_keys() {
+        let mut keystore = crate::keystore::KeyStore::generate("test_issuer".to_string());
+        let old_proof = create_test_proof_from(
+            &keystore
+                .issuer("Test Issuer".to_string())
+                .unwrap()
+                .accept_unsigned_certificates(),
+        );
+        keystore.rotate();
+        let new_proof = create_test_proof_from(
+            &keystore
+                .issuer("Test Issuer".to_string())
+                .unwrap()
+                .accept_unsigned_certificates(),
+        );
+
+        let mut verifier = create_test_verifier();
+        verifier.add_trusted_keys(keystore.trusted_keys());
+
+        let result = verifier.verify_proof(&new_proof).unwrap();
+        assert_eq!(result.details.metadata["issuer_key_id"], "key-2");
+        let result = verifier.verify_proof(&old_proof).unwrap();
+        assert_eq!(result.details.metadata["issuer_key_id"], "key-1");
+
+        // A proof claiming a retired key after its retirement is rejected
+        let mut backdated = old_proof.clone();
+        backdated.generated_at = Utc::now() + chrono::Duration::days(1);
+        assert!(matches!(
+            verifier.verify_proof(&backdated),
+            Err(VerifierError::IssuerAuthenticationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_trusted_keys_reject_other_issuers() {
+        let keystore = crate::keystore::KeyStore::generate("test_issuer".to_string());
+        let mut verifier = create_test_verifier();
+        verifier.add_trusted_keys(keystore.trusted_keys());
+
+        let impostor = crate::keystore::KeyStore::generate("test_issuer".to_string());
+        assert!(matches!(
+            verifier.verify_proof(&create_test_proof_from(
+                &impostor
+                    .issuer("Test Issuer".to_string())
+                    .unwrap()
+                    .accept_unsigned_certificates()
+            )),
+            Err(VerifierError::IssuerAuthenticationFailed(_))
+        ));
+        assert_eq!(
+            verifier
+                .verify_proof(&create_test_proof())
+                .unwrap_err()
+                .to_string(),
+            VerifierError::IssuerAuthenticationFailed("proof names no issuer key".to_string())
+                .to_string()
+        );
+        assert!(matches!(
+            verifier.verify_proof(&create_test_proof_from(
+                &CertificateIssuer::new("other_issuer".to_string(), "Other".to_string())
+                    .accept_unsigned_certificates()
+            )),
+            Err(VerifierError::UntrustedIssuer(_))
+        ));
+    }
+
+    #[test]
+    fn test_cosigning_quorum() {
+        let platform = crate::cosigning::Cosigner::new(
+            "platform".to_string(),
+            ed25519_dalek::SigningKey::from_bytes(&[11u8; 32]),
+        );
+        let examiner = crate::cosigning::Cosigner::new(
+            "examiner".to_string(),
+            ed25519_dalek::SigningKey::from_bytes(&[12u8; 32]),
+        );
+        let verifier = create_test_verifier().with_cosigning_policy(
+            CosigningPolicy::new(2)
+                .with_signer("platform".to_string(), &platform.public_key())
+                .with_signer("examiner".to_string(), &examiner.public_key()),
+        );
+
+        let mut proof = create_test_proof();
+        platform.cosign(&mut proof).unwrap();
+        assert!(matches!(
+            verifier.verify_proof(&proof),
+            Err(VerifierError::Cosigning(
+                CosigningError::QuorumNotMet { .. }
+            ))
+        ));
+
+        examiner.cosign(&mut proof).unwrap();
+        let result = verifier.verify_proof(&proof).unwrap();
+        assert_eq!(result.details.signers, vec!["platform", "examiner"]);
+        assert!(create_test_verifier()
+            .verify_proof(&proof)
+            .unwrap()
+            .details
+            .signers
+            .is_empty());
+    }
+
+    #[cfg(feature = "post-quantum")]
+    #[test]
+    fn test_hybrid_issuer_signature() {
+        use crate::keystore::{TrustedKey, TrustedKeySet};
+        use crate::signature::{IssuerSigningKey, MlDsaSigningKey};
+        use crate::zk_proof::{ISSUER_PQ_SIGNATURE_PROPERTY, ISSUER_SIGNATURE_ALGORITHM_PROPERTY};
+
+        let issuer = CertificateIssuer::new("test_issuer".to_string(), "Test Issuer".to_string())
+            .accept_unsigned_certificates()
+            .with_signing_key(
+                "hybrid-1".to_string(),
+                IssuerSigningKey::Ed25519MlDsa65(
+                    ed25519_dalek::SigningKey::from_bytes(&[13u8; 32]),
+                    MlDsaSigningKey::from_seed([14u8; 32]),
+                ),
+            );
+        let mut verifier = create_test_verifier();
+        verifier.add_trusted_keys(TrustedKeySet {
+            issuer_id: "test_issuer".to_string(),
+            keys: vec![TrustedKey::new(
+                "hybrid-1".to_string(),
+                &issuer.issuer_public_key().unwrap(),
+                Utc::now() - chrono::Duration::days(1),
+            )],
+        });
+
+        let proof = create_test_proof_from(&issuer);
+        assert_eq!(
+            proof.metadata.properties[ISSUER_SIGNATURE_ALGORITHM_PROPERTY],
+            "Ed25519+ML-DSA-65"
+        );
+        assert!(verifier.verify_proof(&proof).is_ok());
+
+        // Dropping the post-quantum half cannot pass as a classical signature
+        let mut downgraded = proof;
+        downgraded
+            .metadata
+            .properties
+            .remove(ISSUER_PQ_SIGNATURE_PROPERTY);
+        downgraded.metadata.properties.insert(
+            ISSUER_SIGNATURE_ALGORITHM_PROPERTY.to_string(),
+            "Ed25519".to_string(),
+        );
+        assert!(matches!(
+            verifier.verify_proof(&downgraded),
+            Err(VerifierError::IssuerAuthenticationFailed(_))
+        ));
+    }
+
+    fn create_test_identity() -> VerifiedIdentity {
+        VerifiedIdentity {
+            attributes: IdentityAttributes {
+                unique_identifier: "nullifier-1".to_string(),
+                scope: "web5claims.identity".to_string(),
+                age_over: Some(18),
+                verified_at: Utc::now(),
+                mode: ProofMode::Real,
+            },
+            first_name: Some("STUDENT".to_string()),
+            excluded_nationalities: Vec::new(),
+            document_valid_until: NaiveDate::from_ymd_opt(2099, 12, 31),
+            disclosed: HashMap::from([
+                ("lastname".to_string(), "TEST".to_string()),
+                ("nationality".to_string(), "FRA".to_string()),
+                ("document_type".to_string(), "passport".to_string()),
+            ]),
+            passport_inputs: PassportPublicInputs {
+                scope: "web5claims.identity".to_string(),
+                nonce: "nonce-1".to_string(),
+                query_result: serde_json::json!({
+                    "firstname": {"disclose": {"result": "STUDENT"}}
+                }),
+                unique_identifier: "nullifier-1".to_string(),
+            },
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Verifier accepting the name commitments the issuer signed when minting
+    fn create_name_binding_verifier(issuer: &CertificateIssuer) -> ZkProofVerifier {
+        create_test_verifier().with_certificate_signer(issuer.public_key().unwrap())
+    }
+
+    fn create_identity_bound_request(name_match: NameMatch) -> ProofRequest {
+        ProofRequest {
+            certificate: CertificateData::new(
+                "German_B2_Complete".to_string(),
+                7,
+                7,
+                "Student Test".to_string(),
+                Utc::now(),
+            ),
+            claim_type: ClaimType::Combined {
+                criteria: vec![
+                    ClaimType::LanguageProficiency {
+                        language: "German".to_string(),
+                        min_level: CefrLevel::B2,
+                    },
+                    ClaimType::IdentityBound { name_match },
+                ],
+            },
+            target_platform: "test".to_string(),
+            options: ProofOptions::default(),
+        }
+    }
+
+    #[test]
+    fn test_identity_bound_claim_verified() {
+        let issuer = create_did_issuer();
+        let request = ProofRequest {
+            claim_type: ClaimType::IdentityBound {
+                name_match: NameMatch::FullName,
+            },
+            ..create_identity_bound_request(NameMatch::FullName)
+        };
+        let proof = issuer
+            .generate_proof_with_identity(mint_request(&issuer, request), &create_test_identity())
+            .unwrap();
+
+        let result = create_name_binding_verifier(&issuer)
+            .verify_proof(&proof)
+            .unwrap();
+        assert!(result.is_valid);
+        assert!(result.requirements_met);
+        assert_eq!(result.details.verified_inputs["name_match"], "full_name");
+        assert!(!serde_json::to_string(&proof).unwrap().contains("Student"));
+
+        let combined = issuer
+            .generate_proof_with_identity(
+                mint_request(&issuer, create_identity_bound_request(NameMatch::FirstName)),
+                &create_test_identity(),
+            )
+            .unwrap();
+        assert!(combined.public_inputs.verification_result);
+        assert!(create_name_binding_verifier(&issuer)
+            .verify_proof(&combined)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_identity_bound_claim_needs_signed_name_commitment() {
+        let issuer = create_did_issuer();
+        let request = ProofRequest {
+            claim_type: ClaimType::IdentityBound {
+                name_match: NameMatch::FirstName,
+            },
+            ..create_identity_bound_request(NameMatch::FirstName)
+        };
+
+        // Certificates that were not minted carry no commitment to prove against
+        assert!(matches!(
+            issuer.generate_proof_with_identity(request.clone(), &create_test_identity()),
+            Err(crate::issuer::IssuerError::NameBinding(
+                NameBindingError::UntrustedCommitment
+            ))
+        ));
+
+        // Commitments of another certificate do not verify for this one
+        let minted = mint_request(&issuer, request.clone());
+        let mut other_certificate = request.clone();
+        other_certificate.certificate.solved_challenges -= 1;
+        let mut moved = mint_request(&issuer, other_certificate);
+        moved.options.name_commitments = minted.options.name_commitments.clone();
+        assert!(matches!(
+            issuer.generate_proof_with_identity(moved, &create_test_identity()),
+            Err(crate::issuer::IssuerError::NameBinding(
+                NameBindingError::UntrustedCommitment
+            ))
+        ));
+
+        // Verifiers only accept commitments of the certificate signers they trust
+        let proof = issuer
+            .generate_proof_with_identity(minted, &create_test_identity())
+            .unwrap();
+        assert!(matches!(
+            create_test_verifier().verify_proof(&proof),
+            Err(VerifierError::NameBinding(
+                NameBindingError::UntrustedCommitment
+            ))
+        ));
+        let binding: NameBinding = serde_json::from_value(
+            proof.public_inputs.requirements[NAME_BINDING_REQUIREMENT].clone(),
+        )
+        .unwrap();
+        assert!(binding
+            .check_passport_inputs("STUDENT", &create_test_identity().passport_inputs)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_strict_mode_rejects_unverified_identity() {
+        let dids = tempfile::tempdir().unwrap();
+        let issuer = create_real_issuer(TestProvingSystem);
+        let verifier = create_did_verifier(&dids)
+            .with_mode(VerificationMode::Strict)
+            .with_proof_backend(TestProvingSystem)
+            .with_certificate_signer(issuer.public_key().unwrap());
+        let request = mint_request(&issuer, create_identity_bound_request(NameMatch::FirstName));
+
+        let proof = issuer
+            .generate_proof_with_identity(request.clone(), &create_test_identity())
+            .unwrap();
+        assert!(verifier.verify_proof(&proof).is_ok());
+
+        let mut identity = create_test_identity();
+        identity.attributes.mode = ProofMode::Simulated;
+        let proof = issuer
+            .generate_proof_with_identity(request, &identity)
+            .unwrap();
+        assert!(matches!(
+            verifier.verify_proof(&proof),
+            Err(VerifierError::SimulatedProofRejected)
+        ));
+    }
+
+    #[test]
+    fn test_identity_bound_claim_needs_identity() {
+        let issuer = create_did_issuer();
+        let request = mint_request(
+            &issuer,
+            ProofRequest {
+                claim_type: ClaimType::IdentityBound {
+                    name_match: NameMatch::FirstName,
+                },
+                ..create_identity_bound_request(NameMatch::FirstName)
+            },
+        );
+        assert!(matches!(
+            issuer.generate_proof(request.clone()),
+            Err(crate::issuer::IssuerError::IdentityRequired)
+        ));
+
+        let mut other_name = create_test_identity();
+        other_name.first_name = Some("Erika".to_string());
+        assert!(matches!(
+            issuer.generate_proof_with_identity(request, &other_name),
+            Err(crate::issuer::IssuerError::NameBinding(
+                NameBindingError::Mismatch
+            ))
+        ));
+
+        // A failed identity criterion leaves the combined claim unmet
+        let combined = issuer
+            .generate_proof_with_identity(
+                mint_request(&issuer, create_identity_bound_request(NameMatch::FirstName)),
+                &other_name,
+            )
+            .unwrap();
+        let result = create_name_binding_verifier(&issuer)
+            .verify_proof(&combined)
+            .unwrap();
+        assert!(!result.requirements_met);
+    }
+
+    #[test]
+    fn test_tampered_name_binding_rejected() {
+        let issuer = create_did_issuer();
+        let verifier = create_name_binding_verifier(&issuer);
+        let proof = issuer
+            .generate_proof_with_identity(
+                mint_request(&issuer, create_identity_bound_request(NameMatch::FirstName)),
+                &create_test_identity(),
+            )
+            .unwrap();
+        let binding_key = format!("criterion_1_{}", NAME_BINDING_REQUIREMENT);
+
+        let mut moved = proof.clone();
+        moved.public_inputs.certificate_hash = "0".repeat(64);
+        assert!(matches!(
+            verifier.verify_proof(&moved),
+            Err(VerifierError::NameBinding(
+                NameBindingError::UntrustedCommitment
+            ))
+        ));
+
+        let mut swapped = proof.clone();
+        let mut binding: NameBinding =
+            serde_json::from_value(swapped.public_inputs.requirements[&binding_key].clone())
+                .unwrap();
+        binding.passport_commitment = binding.certificate_commitment.clone();
+        swapped
+            .public_inputs
+            .requirements
+            .insert(binding_key.clone(), serde_json::to_value(&binding).unwrap());
+        assert!(matches!(
+            verifier.verify_proof(&swapped),
+            Err(VerifierError::NameBinding(NameBindingError::InvalidProof))
+        ));
+
+        let mut stripped = proof.clone();
+        stripped.public_inputs.requirements.remove(&binding_key);
+        assert!(matches!(
+            verifier.verify_proof(&stripped),
+            Err(VerifierError::NameBinding(
+                NameBindingError::MissingBinding(_)
+            ))
+        ));
+
+        let mut anonymous = proof;
+        anonymous
+            .metadata
+            .properties
+            .retain(|key, _| !key.starts_with("identity_"));
+        assert!(matches!(
+            verifier.verify_proof(&anonymous),
+            Err(VerifierError::MissingIdentity)
+        ));
+    }
+
+    fn create_identity_attribute_request(countries: &[&str]) -> ProofRequest {
+        ProofRequest {
+            claim_type: ClaimType::Combined {
+                criteria: vec![
+                    ClaimType::LanguageProficiency {
+                        language: "German".to_string(),
+                        min_level: CefrLevel::B2,
+                    },
+                    ClaimType::NationalityNotIn {
+                        countries: countries.iter().map(|c| c.to_string()).collect(),
+                    },
+                    ClaimType::DocumentValid {
+                        document_type: Some("passport".to_string()),
+                    },
+                ],
+            },
+            ..create_identity_bound_request(NameMatch::FirstName)
+        }
+    }
+
+    #[test]
+    fn test_nationality_and_document_claims() {
+        let issuer = CertificateIssuer::new("test_issuer".to_string(), "Test Issuer".to_string())
+            .accept_unsigned_certificates();
+        let proof = issuer
+            .generate_proof_with_identity(
+                create_identity_attribute_request(&["DEU"]),
+                &create_test_identity(),
+            )
+            .unwrap();
+
+        let result = create_test_verifier().verify_proof(&proof).unwrap();
+        assert!(result.is_valid);
+        assert!(result.requirements_met);
+        assert_eq!(
+            result.details.verified_inputs["criterion_1_excluded_nationalities"],
+            serde_json::json!(["DEU"])
+        );
+        assert_eq!(
+            result.details.verified_inputs["criterion_2_document_type"],
+            "passport"
+        );
+        // The nationality itself is never disclosed in the claim
+        assert!(!serde_json::to_string(&proof).unwrap().contains("FRA"));
+
+        let unmet = issuer
+            .generate_proof_with_identity(
+                create_identity_attribute_request(&["FRA"]),
+                &create_test_identity(),
+            )
+            .unwrap();
+        let result = create_test_verifier().verify_proof(&unmet).unwrap();
+        assert!(result.is_valid);
+        assert!(!result.requirements_met);
+    }
+
+    #[test]
+    fn test_german_national_against_alpha2_code() {
+        let issuer = CertificateIssuer::new("test_issuer".to_string(), "Test Issuer".to_string())
+            .accept_unsigned_certificates();
+        // German passports carry `D` as nationality in the machine readable zone
+        let mut identity = create_test_identity();
+        identity
+            .disclosed
+            .insert("nationality".to_string(), "D".to_string());
+
+        let proof = issuer
+            .generate_proof_with_identity(create_identity_attribute_request(&["DE"]), &identity)
+            .unwrap();
+        let result = create_test_verifier().verify_proof(&proof).unwrap();
+        assert!(result.is_valid);
+        assert!(!result.requirements_met);
+
+        // Codes that are not ISO 3166-1 are rejected instead of never matching
+        let request = ProofRequest {
+            claim_type: ClaimType::NationalityNotIn {
+                countries: vec!["GER".to_string()],
+            },
+            ..create_identity_bound_request(NameMatch::FirstName)
+        };
+        assert!(matches!(
+            issuer.generate_proof_with_identity(request, &identity),
+            Err(crate::issuer::IssuerError::InvalidCountryCode(code)) if code == "GER"
+        ));
+    }
+
+    #[test]
+    fn test_identity_attribute_claims_need_identity() {
+        let issuer = CertificateIssuer::new("test_issuer".to_string(), "Test Issuer".to_string())
+            .accept_unsigned_certificates();
+        let request = ProofRequest {
+            claim_type: ClaimType::NationalityNotIn {
+                countries: vec!["DEU".to_string()],
+            },
+            ..create_identity_bound_request(NameMatch::FirstName)
+        };
+        assert!(matches!(
+            issuer.generate_proof(request.clone()),
+            Err(crate::issuer::IssuerError::IdentityRequired)
+        ));
+
+        let mut identity = create_test_identity();
+        identity.disclosed.remove("nationality");
+        assert!(matches!(
+            issuer.generate_proof_with_identity(request.clone(), &identity),
+            Err(crate::issuer::IssuerError::MissingIdentityAttribute(_))
+        ));
+
+        // A document that expired before the certificate was issued does not count
+        identity.document_valid_until = NaiveDate::from_ymd_opt(2000, 1, 1);
+        let request = ProofRequest {
+            claim_type: ClaimType::DocumentValid {
+                document_type: None,
+            },
+            ..request
+        };
+        let proof = issuer
+            .generate_proof_with_identity(request, &identity)
+            .unwrap();
+        assert!(!proof.public_inputs.verification_result);
+
+        let mut anonymous = issuer
+            .generate_proof_with_identity(
+                create_identity_attribute_request(&["DEU"]),
+                &create_test_identity(),
+            )
+            .unwrap();
+        anonymous
+            .metadata
+            .properties
+            .retain(|key, _| !key.starts_with("identity_"));
+        assert!(matches!(
+            create_test_verifier().verify_proof(&anonymous),
+            Err(VerifierError::MissingIdentity)
+        ));
+    }
+
+    #[test]
+    fn test_trust_score_breakdown() {
+        let dids = tempfile::tempdir().unwrap();
+        let result = create_test_verifier()
+            .verify_proof(&create_test_proof())
+            .unwrap();
+        let trust = result.trust_score.unwrap();
+
+        // An unsigned simulated claim only scores for its age and unknown revocation status
+        assert_eq!(trust.score, 15);
+        assert_eq!(trust.factors.len(), TrustFactor::ALL.len());
+        assert_eq!(
+            trust.factor(TrustFactor::Signature).unwrap().detail,
+            "claim is not signed by its issuer"
+        );
+
+        let issuer = create_did_issuer();
+        let mut verifier = create_did_verifier(&dids);
+        verifier.add_trusted_issuer(issuer.issuer_id.clone());
+        let trust = verifier
+            .verify_proof(&create_test_proof_from(&issuer))
+            .unwrap()
+            .trust_score
+            .unwrap();
+        assert_eq!(trust.factor(TrustFactor::Signature).unwrap().score, 1.0);
+        assert_eq!(
+            trust
+                .factor(TrustFactor::IssuerAccreditation)
+                .unwrap()
+                .score,
+            1.0
+        );
+        assert_eq!(trust.score, 60);
+    }
+
+    #[test]
+    fn test_trust_score_identity_and_revocation() {
+        let dids = tempfile::tempdir().unwrap();
+        let issuer = create_did_issuer();
+        let proof = issuer
+            .generate_proof_with_identity(
+                mint_request(&issuer, create_identity_bound_request(NameMatch::FirstName)),
+                &create_test_identity(),
+            )
+            .unwrap();
+
+        let trust = create_did_verifier(&dids)
+            .with_certificate_signer(issuer.public_key().unwrap())
+            .verify_proof(&proof)
+            .unwrap()
+            .trust_score
+            .unwrap();
+        assert_eq!(
+            trust.factor(TrustFactor::IdentityBinding).unwrap().score,
+            1.0
+        );
+
+        let revoked = create_did_verifier(&dids)
+            .with_revocation_list([proof.proof_id.clone()])
+            .verify_proof(&proof);
+        assert!(matches!(
+            revoked,
+            Err(VerifierError::RevokedProof(proof_id)) if proof_id == proof.proof_id
+        ));
+    }
+
+    #[test]
+    fn test_signed_status_list_revocation() {
+        use crate::status_list::DEFAULT_STATUS_LIST_SIZE;
+        use crate::vc::CredentialStatus;
+
+        const LIST_URL: &str = "https://web5.claims/status/1";
+        let keystore = crate::keystore::KeyStore::generate("test_issuer".to_string());
+        let issuer = keystore
+            .issuer("Test Issuer".to_string())
+            .unwrap()
+            .accept_unsigned_certificates();
+        let mut request = create_test_request();
+        request.options.credential_status = Some(CredentialStatus::revocation(LIST_URL, 7));
+        let proof = issuer.generate_proof(request).unwrap();
+
+        let mut verifier = create_test_verifier();
+        verifier.add_trusted_keys(keystore.trusted_keys());
+        let mut list = StatusList::revocation(
+            LIST_URL.to_string(),
+            String::new(),
+            DEFAULT_STATUS_LIST_SIZE,
+        );
+        verifier
+            .add_status_list(&issuer.sign_status_list(list.clone()).unwrap())
+            .unwrap();
+        let trust = verifier.verify_proof(&proof).unwrap().trust_score.unwrap();
+        assert_eq!(trust.factor(TrustFactor::Revocation).unwrap().score, 1.0);
+
+        list.set_status(7, true).unwrap();
+        verifier
+            .add_status_list(&issuer.sign_status_list(list.clone()).unwrap())
+            .unwrap();
+        assert!(matches!(
+            verifier.verify_proof(&proof),
+            Err(VerifierError::RevokedProof(proof_id)) if proof_id == proof.proof_id
+        ));
+
+        // An outdated list no longer vouches for the proof either way
+        verifier.status_lists.get_mut(LIST_URL).unwrap().valid_from -=
+            chrono::Duration::hours(DEFAULT_STATUS_LIST_MAX_AGE_HOURS + 1);
+        assert!(matches!(
+            verifier.verify_proof(&proof),
+            Err(VerifierError::StatusList(StatusListError::Stale(_)))
+        ));
+
+        // Lists of issuers the verifier does not trust are refused
+        let other = crate::keystore::KeyStore::generate("other_issuer".to_string())
+            .issuer("Other".to_string())
+            .unwrap();
+        assert!(matches!(
+            verifier.add_status_list(&other.sign_status_list(list).unwrap()),
+            Err(VerifierError::UntrustedIssuer(_))
+        ));
+    }
+
+    #[test]
+    fn test_trust_score_custom_weights() {
+        let weights = TrustWeights {
+            signature: 0.0,
+            identity_binding: 0.0,
+            issuer_accreditation: 0.0,
+            proof_mode: 0.0,
+            claim_age: 1.0,
+            revocation: 0.0,
+        };
+        let verifier = create_test_verifier()
+            .with_trust_config(TrustScoreConfig::default().with_weights(weights.clone()));
+        let result = verifier.verify_proof(&create_test_proof()).unwrap();
+        assert_eq!(result.trust_score.unwrap().score, 100);
+
+        let invalid = create_test_verifier().with_trust_config(
+            TrustScoreConfig::default().with_weights(TrustWeights {
+                claim_age: -1.0,
+                ..weights
+            }),
+        );
+        assert!(matches!(
+            invalid.verify_proof(&create_test_proof()),
+            Err(VerifierError::Trust(_))
+        ));
+    }
 }
@@ -1,3 +1,6 @@
+use crate::country::normalize_country;
+use crate::name_binding::NameMatch;
+use crate::vc::CredentialStatus;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
@@ -34,6 +37,24 @@ pub enum ClaimType {
     CompletionDate { after_date: DateTime<Utc> },
     /// Prove multiple criteria simultaneously
     Combined { criteria: Vec<ClaimType> },
+    /// Prove the certificate name matches the verified passport name without revealing either
+    IdentityBound { name_match: NameMatch },
+    /// Prove the verified nationality is none of the ISO 3166-1 alpha-3 `countries`
+    NationalityNotIn { countries: Vec<String> },
+    /// Prove the identity document, optionally of a given type, was valid at certification
+    DocumentValid { document_type: Option<String> },
+}
+
+/// Kind of a claim, without its parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ClaimKind {
+    LanguageProficiency,
+    PerformanceThreshold,
+    CompletionDate,
+    Combined,
+    IdentityBound,
+    NationalityNotIn,
+    DocumentValid,
 }
 
 /// CEFR (Common European Framework of Reference) levels
@@ -58,6 +79,40 @@ pub struct PublicInputs {
     pub certificate_hash: String,
 }
 
+/// Metadata property holding the id of the key the issuer signed the proof with
+pub const ISSUER_KEY_ID_PROPERTY: &str = "issuer_key_id";
+/// Metadata property naming the verification method the issuer signed the proof with
+pub const ISSUER_VERIFICATION_METHOD_PROPERTY: &str = "issuer_verification_method";
+/// Metadata property naming the algorithm of the issuer signature (Ed25519 when absent)
+pub const ISSUER_SIGNATURE_ALGORITHM_PROPERTY: &str = "issuer_signature_algorithm";
+/// Metadata property holding the hex-encoded Ed25519 issuer signature
+pub const ISSUER_SIGNATURE_PROPERTY: &str = "issuer_signature";
+/// Metadata property holding the hex-encoded ML-DSA issuer signature
+pub const ISSUER_PQ_SIGNATURE_PROPERTY: &str = "issuer_pq_signature";
+/// Metadata property holding the JSON-encoded co-signatures of the proof
+pub const COSIGNATURES_PROPERTY: &str = "issuer_cosignatures";
+/// Metadata property holding the hex-encoded Ed25519 key of the holder the claim is bound to
+pub const HOLDER_KEY_PROPERTY: &str = "cnf";
+/// Metadata property holding the JSON-encoded status list entry of the claim
+pub const CREDENTIAL_STATUS_PROPERTY: &str = "credential_status";
+
+/// Prefix of simulated proof bytes, catching simulated proofs relabelled as real
+///
+/// Not a security boundary: verifiers only treat a proof as real once a proof backend
+/// verified it.
+pub const SIMULATED_PROOF_TAG: &[u8] = b"web5claims:simulated:";
+
+/// How the proof bytes were produced
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ProofMode {
+    /// Proof bytes are a simulation without cryptographic guarantees
+    #[default]
+    Simulated,
+    /// Proof bytes come from a real proving system
+    Real,
+}
+
 /// The actual proof data (simplified for this demo)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ProofData {
@@ -67,6 +122,9 @@ pub struct ProofData {
     pub circuit_id: String,
     /// Verification key hash
     pub vk_hash: String,
+    /// Whether the proof is simulated or real (proofs without a mode predate it and are simulated)
+    #[serde(default)]
+    pub mode: ProofMode,
 }
 
 /// Additional metadata about the proof
@@ -105,6 +163,44 @@ impl ZkProofClaim {
             && !self.public_inputs.certificate_hash.is_empty()
             && !self.proof_data.proof_bytes.is_empty()
             && !self.proof_data.circuit_id.is_empty()
+            && self.proof_data.mode_matches_bytes()
+    }
+
+    /// Hex-encoded key of the holder the claim is bound to
+    pub fn holder_key(&self) -> Option<&str> {
+        self.metadata
+            .properties
+            .get(HOLDER_KEY_PROPERTY)
+            .map(String::as_str)
+    }
+
+    /// Status list entry the issuer recorded for the claim
+    pub fn credential_status(&self) -> Option<CredentialStatus> {
+        self.metadata
+            .properties
+            .get(CREDENTIAL_STATUS_PROPERTY)
+            .and_then(|status| serde_json::from_str(status).ok())
+    }
+
+    /// Bytes covered by the issuer signature and co-signatures, the claim without its
+    /// signature properties
+    ///
+    /// Encoded through `serde_json::Value` so map keys are sorted and the bytes do not depend
+    /// on `HashMap` iteration order.
+    pub fn issuer_signing_bytes(&self) -> Vec<u8> {
+        let mut unsigned = self.clone();
+        unsigned
+            .metadata
+            .properties
+            .remove(ISSUER_SIGNATURE_PROPERTY);
+        unsigned
+            .metadata
+            .properties
+            .remove(ISSUER_PQ_SIGNATURE_PROPERTY);
+        unsigned.metadata.properties.remove(COSIGNATURES_PROPERTY);
+
+        let value = serde_json::to_value(unsigned).expect("proof serializes to JSON");
+        serde_json::to_vec(&value).expect("proof serializes to JSON")
     }
 
     /// Get a hash of this proof for uniqueness verification
@@ -115,7 +211,223 @@ impl ZkProofClaim {
     }
 }
 
+impl ClaimType {
+    /// Kind of this claim
+    pub fn kind(&self) -> ClaimKind {
+        match self {
+            ClaimType::LanguageProficiency { .. } => ClaimKind::LanguageProficiency,
+            ClaimType::PerformanceThreshold { .. } => ClaimKind::PerformanceThreshold,
+            ClaimType::CompletionDate { .. } => ClaimKind::CompletionDate,
+            ClaimType::Combined { .. } => ClaimKind::Combined,
+            ClaimType::IdentityBound { .. } => ClaimKind::IdentityBound,
+            ClaimType::NationalityNotIn { .. } => ClaimKind::NationalityNotIn,
+            ClaimType::DocumentValid { .. } => ClaimKind::DocumentValid,
+        }
+    }
+
+    /// Circuit that proves this claim
+    pub fn circuit_id(&self) -> &'static str {
+        self.kind().circuit_id()
+    }
+
+    /// Whether this claim, or one of its criteria, is about a verified identity
+    pub fn needs_identity(&self) -> bool {
+        match self {
+            ClaimType::Combined { criteria } => criteria.iter().any(ClaimType::needs_identity),
+            other => other.kind().needs_identity(),
+        }
+    }
+
+    /// Requirements revealed as public inputs, criteria of combined claims prefixed with
+    /// `criterion_<index>_`
+    pub fn requirements(&self) -> HashMap<String, serde_json::Value> {
+        let mut requirements = HashMap::new();
+
+        match self {
+            ClaimType::LanguageProficiency {
+                language,
+                min_level,
+            } => {
+                requirements.insert(
+                    "min_level".to_string(),
+                    serde_json::Value::String(min_level.to_string()),
+                );
+                requirements.insert(
+                    "language".to_string(),
+                    serde_json::Value::String(language.to_string()),
+                );
+            }
+            ClaimType::PerformanceThreshold { min_percentage } => {
+                requirements.insert(
+                    "min_percentage".to_string(),
+                    serde_json::Value::Number((*min_percentage).into()),
+                );
+            }
+            ClaimType::CompletionDate { after_date } => {
+                requirements.insert(
+                    "after_date".to_string(),
+                    serde_json::Value::String(after_date.to_rfc3339()),
+                );
+            }
+            ClaimType::Combined { criteria } => {
+                for (i, criterion) in criteria.iter().enumerate() {
+                    for (key, value) in criterion.requirements() {
+                        requirements.insert(format!("criterion_{}_{}", i, key), value);
+                    }
+                }
+            }
+            ClaimType::IdentityBound { name_match } => {
+                requirements.insert(
+                    "name_match".to_string(),
+                    serde_json::Value::String(name_match.to_string()),
+                );
+            }
+            ClaimType::NationalityNotIn { countries } => {
+                requirements.insert(
+                    "excluded_nationalities".to_string(),
+                    serde_json::json!(countries),
+                );
+            }
+            ClaimType::DocumentValid { document_type } => {
+                if let Some(document_type) = document_type {
+                    requirements.insert(
+                        "document_type".to_string(),
+                        serde_json::Value::String(document_type.clone()),
+                    );
+                }
+            }
+        }
+
+        requirements
+    }
+
+    /// Check if proving this claim also proves `other`
+    ///
+    /// A claim implies a weaker claim of the same kind, e.g. B2 proficiency implies B1
+    /// proficiency in the same language. A combined claim implies every claim one of its
+    /// criteria implies, and a claim implies a combined claim whose criteria it all implies.
+    pub fn implies(&self, other: &ClaimType) -> bool {
+        match (self, other) {
+            (_, ClaimType::Combined { criteria }) => {
+                criteria.iter().all(|criterion| self.implies(criterion))
+            }
+            (ClaimType::Combined { criteria }, _) => {
+                criteria.iter().any(|criterion| criterion.implies(other))
+            }
+            (
+                ClaimType::LanguageProficiency {
+                    language,
+                    min_level,
+                },
+                ClaimType::LanguageProficiency {
+                    language: other_language,
+                    min_level: other_min_level,
+                },
+            ) => language.eq_ignore_ascii_case(other_language) && min_level >= other_min_level,
+            (
+                ClaimType::PerformanceThreshold { min_percentage },
+                ClaimType::PerformanceThreshold {
+                    min_percentage: other_min_percentage,
+                },
+            ) => min_percentage >= other_min_percentage,
+            (
+                ClaimType::CompletionDate { after_date },
+                ClaimType::CompletionDate {
+                    after_date: other_after_date,
+                },
+            ) => after_date >= other_after_date,
+            (
+                ClaimType::IdentityBound { name_match },
+                ClaimType::IdentityBound {
+                    name_match: other_name_match,
+                },
+            ) => name_match == other_name_match,
+            (
+                ClaimType::NationalityNotIn { countries },
+                ClaimType::NationalityNotIn {
+                    countries: other_countries,
+                },
+            ) => other_countries.iter().all(|country| {
+                let country = normalize_country(country);
+                countries
+                    .iter()
+                    .any(|excluded| normalize_country(excluded) == country)
+            }),
+            (
+                ClaimType::DocumentValid { document_type },
+                ClaimType::DocumentValid {
+                    document_type: other_document_type,
+                },
+            ) => other_document_type.is_none() || document_type == other_document_type,
+            _ => false,
+        }
+    }
+}
+
+impl ClaimKind {
+    /// All claim kinds
+    pub const ALL: [ClaimKind; 7] = [
+        ClaimKind::LanguageProficiency,
+        ClaimKind::PerformanceThreshold,
+        ClaimKind::CompletionDate,
+        ClaimKind::Combined,
+        ClaimKind::IdentityBound,
+        ClaimKind::NationalityNotIn,
+        ClaimKind::DocumentValid,
+    ];
+
+    /// Circuit that proves claims of this kind
+    pub fn circuit_id(&self) -> &'static str {
+        match self {
+            ClaimKind::LanguageProficiency => "language_proficiency_v1",
+            ClaimKind::PerformanceThreshold => "performance_threshold_v1",
+            ClaimKind::CompletionDate => "completion_date_v1",
+            ClaimKind::Combined => "combined_criteria_v1",
+            ClaimKind::IdentityBound => "identity_binding_v1",
+            ClaimKind::NationalityNotIn => "nationality_exclusion_v1",
+            ClaimKind::DocumentValid => "document_validity_v1",
+        }
+    }
+
+    /// Whether claims of this kind are about a verified identity
+    pub fn needs_identity(&self) -> bool {
+        matches!(
+            self,
+            ClaimKind::IdentityBound | ClaimKind::NationalityNotIn | ClaimKind::DocumentValid
+        )
+    }
+}
+
+impl ProofData {
+    /// Check that simulated proof bytes are not labelled as real
+    pub fn mode_matches_bytes(&self) -> bool {
+        match self.mode {
+            ProofMode::Simulated => true,
+            ProofMode::Real => !self.proof_bytes.starts_with(SIMULATED_PROOF_TAG),
+        }
+    }
+}
+
+impl std::fmt::Display for ProofMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProofMode::Simulated => write!(f, "simulated"),
+            ProofMode::Real => write!(f, "real"),
+        }
+    }
+}
+
 impl CefrLevel {
+    /// All levels, from lowest to highest
+    pub const ALL: [CefrLevel; 6] = [
+        CefrLevel::A1,
+        CefrLevel::A2,
+        CefrLevel::B1,
+        CefrLevel::B2,
+        CefrLevel::C1,
+        CefrLevel::C2,
+    ];
+
     /// Convert from string representation
     pub fn from_course_name(course_name: &str) -> Option<CefrLevel> {
         if course_name.contains("A1") {
@@ -210,6 +522,7 @@ mod tests {
             proof_bytes: vec![1, 2, 3, 4],
             circuit_id: "language_verification_v1".to_string(),
             vk_hash: "vk_hash_123".to_string(),
+            mode: ProofMode::Simulated,
         };
 
         let metadata = ProofMetadata {
@@ -247,6 +560,7 @@ mod tests {
                 proof_bytes: vec![1, 2, 3],
                 circuit_id: "test_circuit".to_string(),
                 vk_hash: "vk123".to_string(),
+                mode: ProofMode::Simulated,
             },
             ProofMetadata {
                 version: "1.0.0".to_string(),
@@ -255,4 +569,108 @@ mod tests {
             },
         )
     }
+
+    #[test]
+    fn test_proof_mode_integrity() {
+        let mut claim = create_test_claim();
+        claim.proof_data.proof_bytes = [SIMULATED_PROOF_TAG, &[7u8; 32]].concat();
+        assert!(claim.verify_integrity());
+
+        // Relabelling simulated proof bytes as real breaks integrity
+        claim.proof_data.mode = ProofMode::Real;
+        assert!(!claim.verify_integrity());
+
+        claim.proof_data.proof_bytes = vec![7u8; 32];
+        assert!(claim.verify_integrity());
+    }
+
+    #[test]
+    fn test_proof_mode_defaults_to_simulated() {
+        let claim = create_test_claim();
+        let mut json = serde_json::to_value(&claim).unwrap();
+        json["proof_data"].as_object_mut().unwrap().remove("mode");
+
+        let parsed: ZkProofClaim = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.proof_data.mode, ProofMode::Simulated);
+    }
+
+    #[test]
+    fn test_claim_requirements() {
+        let claim = ClaimType::Combined {
+            criteria: vec![
+                ClaimType::LanguageProficiency {
+                    language: "German".to_string(),
+                    min_level: CefrLevel::B2,
+                },
+                ClaimType::PerformanceThreshold { min_percentage: 80 },
+            ],
+        };
+
+        let requirements = claim.requirements();
+        assert_eq!(requirements.len(), 3);
+        assert_eq!(requirements["criterion_0_language"], "German");
+        assert_eq!(requirements["criterion_0_min_level"], "B2");
+        assert_eq!(requirements["criterion_1_min_percentage"], 80);
+    }
+
+    #[test]
+    fn test_claim_implies() {
+        let german = |min_level| ClaimType::LanguageProficiency {
+            language: "German".to_string(),
+            min_level,
+        };
+        let performance = |min_percentage| ClaimType::PerformanceThreshold { min_percentage };
+
+        assert!(german(CefrLevel::B2).implies(&german(CefrLevel::B1)));
+        assert!(!german(CefrLevel::B1).implies(&german(CefrLevel::B2)));
+        assert!(
+            !german(CefrLevel::B2).implies(&ClaimType::LanguageProficiency {
+                language: "Spanish".to_string(),
+                min_level: CefrLevel::A1,
+            })
+        );
+        assert!(!performance(90).implies(&german(CefrLevel::A1)));
+
+        let combined = ClaimType::Combined {
+            criteria: vec![german(CefrLevel::C1), performance(90)],
+        };
+        assert!(combined.implies(&performance(80)));
+        assert!(combined.implies(&ClaimType::Combined {
+            criteria: vec![german(CefrLevel::B2), performance(90)],
+        }));
+        assert!(!german(CefrLevel::C1).implies(&combined));
+    }
+
+    #[test]
+    fn test_identity_attribute_claims() {
+        let not_in = |countries: &[&str]| ClaimType::NationalityNotIn {
+            countries: countries.iter().map(|c| c.to_string()).collect(),
+        };
+        assert!(not_in(&["DEU", "AUT"]).implies(&not_in(&["deu"])));
+        assert!(not_in(&["DE", "AT"]).implies(&not_in(&["AUT"])));
+        assert!(!not_in(&["DEU"]).implies(&not_in(&["DEU", "AUT"])));
+
+        let passport = ClaimType::DocumentValid {
+            document_type: Some("passport".to_string()),
+        };
+        let any_document = ClaimType::DocumentValid {
+            document_type: None,
+        };
+        assert!(passport.implies(&any_document));
+        assert!(!any_document.implies(&passport));
+
+        assert!(passport.needs_identity());
+        assert!(ClaimType::Combined {
+            criteria: vec![
+                ClaimType::PerformanceThreshold { min_percentage: 80 },
+                passport
+            ],
+        }
+        .needs_identity());
+        assert!(!ClaimType::PerformanceThreshold { min_percentage: 80 }.needs_identity());
+        assert_eq!(
+            not_in(&["DEU"]).requirements()["excluded_nationalities"],
+            serde_json::json!(["DEU"])
+        );
+    }
 }
@@ -0,0 +1,783 @@
+//! # Web5 Claims Stylus Verifier
+//!
+//! Arbitrum Stylus contract that verifies Web5 Claims ZK proofs on-chain.
+//!
+//! The contract keeps a registry of trusted Groth16 verifying keys, indexed by the `vk_hash`
+//! of a `ProofData`, checks submitted proofs against them with the BN254 precompiles and
+//! emits a `ClaimVerified` event for every accepted claim. Proof ids and claim hashes are
+//! passed as `bytes32` values: SHA-256 hashes are used as-is and the UUID proof id is hashed
+//! with SHA-256 off-chain. The public inputs are the public signals of
+//! `web5claims::evm::public_signals`: certificate hash, verification result and requirements
+//! hash. Proofs and verifying keys use the encoding of `web5claims::evm`.
+
+#![cfg_attr(not(any(test, feature = "export-abi")), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use alloy_primitives::{address, Address, FixedBytes, U256};
+use alloy_sol_types::sol;
+use stylus_sdk::{abi::Bytes, call::Call, prelude::*, stylus_core::log};
+
+/// Size of an uncompressed Groth16 BN254 proof (a, b, c as 8 big-endian words)
+pub const GROTH16_PROOF_LENGTH: usize = 256;
+
+/// Number of public inputs a proof commits to
+pub const PUBLIC_SIGNAL_COUNT: usize = 3;
+
+/// Words of a Groth16 verifying key: alpha (G1), beta, gamma and delta (G2 in EVM order),
+/// then one input commitment (G1) per public input plus one
+pub const VERIFYING_KEY_WORDS: usize = 14 + 2 * (PUBLIC_SIGNAL_COUNT + 1);
+
+/// BN254 precompiles of EIP-196 and EIP-197
+const EC_ADD: Address = address!("0x0000000000000000000000000000000000000006");
+const EC_MUL: Address = address!("0x0000000000000000000000000000000000000007");
+const EC_PAIRING: Address = address!("0x0000000000000000000000000000000000000008");
+
+/// BN254 scalar field modulus, public inputs must be below it
+const SCALAR_FIELD: U256 = U256::from_limbs([
+    0x43e1f593f0000001,
+    0x2833e84879b97091,
+    0xb85045b68181585d,
+    0x30644e72e131a029,
+]);
+
+/// BN254 base field modulus
+const BASE_FIELD: U256 = U256::from_limbs([
+    0x3c208c16d87cfd47,
+    0x97816a916871ca8d,
+    0xb85045b68181585d,
+    0x30644e72e131a029,
+]);
+
+/// Index of the verification result among the public inputs
+const VERIFICATION_RESULT_SIGNAL: usize = 1;
+
+sol! {
+    /// Emitted when the owner trusts a verification key hash
+    event VerifyingKeyTrusted(bytes32 indexed vk_hash);
+    /// Emitted when the owner revokes a verification key hash
+    event VerifyingKeyRevoked(bytes32 indexed vk_hash);
+    /// Emitted for every claim that passes verification
+    event ClaimVerified(
+        bytes32 indexed proof_id,
+        bytes32 indexed vk_hash,
+        bytes32 certificate_hash,
+        bytes32 claim_hash,
+        address verifier
+    );
+
+    error NotOwner(address caller);
+    error UntrustedVerifyingKey(bytes32 vk_hash);
+    error VerifyingKeyMismatch(bytes32 vk_hash);
+    error InvalidProof(bytes32 proof_id);
+    error ProofAlreadyVerified(bytes32 proof_id);
+    error RequirementsNotMet(bytes32 proof_id);
+}
+
+/// Errors returned by the verifier contract
+#[derive(SolidityError)]
+pub enum VerifierError {
+    NotOwner(NotOwner),
+    UntrustedVerifyingKey(UntrustedVerifyingKey),
+    VerifyingKeyMismatch(VerifyingKeyMismatch),
+    InvalidProof(InvalidProof),
+    ProofAlreadyVerified(ProofAlreadyVerified),
+    RequirementsNotMet(RequirementsNotMet),
+}
+
+sol_storage! {
+    /// On-chain verifier for Web5 Claims proofs
+    #[entrypoint]
+    pub struct Web5ClaimsVerifier {
+        /// Account allowed to manage trusted verification keys
+        address owner;
+        /// Trusted verification key hashes
+        mapping(bytes32 => bool) trusted_vk_hashes;
+        /// Groth16 verifying keys by verification key hash
+        mapping(bytes32 => uint256[]) verifying_keys;
+        /// Number of trusted verification key hashes
+        uint256 trusted_vk_count;
+        /// Proof ids that have already been verified
+        mapping(bytes32 => bool) verified_proofs;
+        /// Number of verified claims
+        uint256 verified_count;
+    }
+}
+
+#[public]
+impl Web5ClaimsVerifier {
+    /// Set the contract owner, run once by the deployment transaction
+    #[constructor]
+    pub fn constructor(&mut self, owner: Address) {
+        self.owner.set(owner);
+    }
+
+    /// Get the contract owner
+    pub fn owner(&self) -> Address {
+        self.owner.get()
+    }
+
+    /// Trust the Groth16 verifying key of a verification key hash
+    ///
+    /// A hash keeps the verifying key it was first registered with, also after revocation.
+    pub fn add_trusted_vk(
+        &mut self,
+        vk_hash: FixedBytes<32>,
+        verifying_key: [U256; VERIFYING_KEY_WORDS],
+    ) -> Result<(), VerifierError> {
+        self.only_owner()?;
+
+        match self.verifying_key(vk_hash) {
+            Some(stored) if stored != verifying_key => {
+                return Err(VerifierError::VerifyingKeyMismatch(VerifyingKeyMismatch {
+                    vk_hash,
+                }));
+            }
+            Some(_) => {}
+            None => {
+                let mut stored = self.verifying_keys.setter(vk_hash);
+                for word in verifying_key {
+                    stored.push(word);
+                }
+            }
+        }
+
+        if !self.trusted_vk_hashes.get(vk_hash) {
+            self.trusted_vk_hashes.setter(vk_hash).set(true);
+            let count = self.trusted_vk_count.get();
+            self.trusted_vk_count.set(count + U256::from(1));
+            log(self.vm(), VerifyingKeyTrusted { vk_hash });
+        }
+
+        Ok(())
+    }
+
+    /// Revoke a trusted verification key hash
+    pub fn revoke_trusted_vk(&mut self, vk_hash: FixedBytes<32>) -> Result<(), VerifierError> {
+        self.only_owner()?;
+
+        if self.trusted_vk_hashes.get(vk_hash) {
+            self.trusted_vk_hashes.setter(vk_hash).set(false);
+            let count = self.trusted_vk_count.get();
+            self.trusted_vk_count.set(count - U256::from(1));
+            log(self.vm(), VerifyingKeyRevoked { vk_hash });
+        }
+
+        Ok(())
+    }
+
+    /// Check if a verification key hash is trusted
+    pub fn is_trusted_vk(&self, vk_hash: FixedBytes<32>) -> bool {
+        self.trusted_vk_hashes.get(vk_hash)
+    }
+
+    /// Number of trusted verification key hashes
+    pub fn trusted_vk_count(&self) -> U256 {
+        self.trusted_vk_count.get()
+    }
+
+    /// Verify a Web5 Claims proof and record it
+    ///
+    /// The verification key must be trusted, the proof must pass the Groth16 pairing check
+    /// against its verifying key and public inputs, and the verification result among them
+    /// must be true. Each proof id can only be verified once.
+    pub fn verify_claim(
+        &mut self,
+        proof_id: FixedBytes<32>,
+        vk_hash: FixedBytes<32>,
+        claim_hash: FixedBytes<32>,
+        proof_bytes: Bytes,
+        public_inputs: [U256; PUBLIC_SIGNAL_COUNT],
+    ) -> Result<bool, VerifierError> {
+        let verifying_key = self
+            .verifying_key(vk_hash)
+            .filter(|_| self.trusted_vk_hashes.get(vk_hash))
+            .ok_or(VerifierError::UntrustedVerifyingKey(
+                UntrustedVerifyingKey { vk_hash },
+            ))?;
+
+        if self.verified_proofs.get(proof_id) {
+            return Err(VerifierError::ProofAlreadyVerified(ProofAlreadyVerified {
+                proof_id,
+            }));
+        }
+
+        let vm = self.vm();
+        let proof_valid = check_proof(&verifying_key, &proof_bytes, &public_inputs, |to, input| {
+            vm.static_call(&Call::new(), to, input).ok()
+        });
+        if !proof_valid {
+            return Err(VerifierError::InvalidProof(InvalidProof { proof_id }));
+        }
+
+        if public_inputs[VERIFICATION_RESULT_SIGNAL] != U256::from(1) {
+            return Err(VerifierError::RequirementsNotMet(RequirementsNotMet {
+                proof_id,
+            }));
+        }
+
+        self.verified_proofs.setter(proof_id).set(true);
+        let count = self.verified_count.get();
+        self.verified_count.set(count + U256::from(1));
+
+        let certificate_hash = FixedBytes::from(public_inputs[0].to_be_bytes::<32>());
+        let verifier = self.vm().msg_sender();
+        log(
+            self.vm(),
+            ClaimVerified {
+                proof_id,
+                vk_hash,
+                certificate_hash,
+                claim_hash,
+                verifier,
+            },
+        );
+
+        Ok(true)
+    }
+
+    /// Check if a proof id has been verified
+    pub fn is_verified(&self, proof_id: FixedBytes<32>) -> bool {
+        self.verified_proofs.get(proof_id)
+    }
+
+    /// Number of verified claims
+    pub fn verified_count(&self) -> U256 {
+        self.verified_count.get()
+    }
+}
+
+impl Web5ClaimsVerifier {
+    /// Ensure the caller is the contract owner
+    fn only_owner(&self) -> Result<(), VerifierError> {
+        let caller = self.vm().msg_sender();
+        if caller != self.owner.get() {
+            return Err(VerifierError::NotOwner(NotOwner { caller }));
+        }
+        Ok(())
+    }
+
+    /// Stored verifying key of a verification key hash
+    fn verifying_key(&self, vk_hash: FixedBytes<32>) -> Option<[U256; VERIFYING_KEY_WORDS]> {
+        let stored = self.verifying_keys.getter(vk_hash);
+        let words: Vec<U256> = (0..stored.len()).filter_map(|i| stored.get(i)).collect();
+        words.try_into().ok()
+    }
+}
+
+/// Check a Groth16 proof against a verifying key and its public inputs
+///
+/// Runs the same steps as the Solidity verifiers of `web5claims::evm`, calling the BN254
+/// precompiles through `precompile`. Malformed proofs and points the precompiles reject fail
+/// the check.
+fn check_proof(
+    verifying_key: &[U256; VERIFYING_KEY_WORDS],
+    proof_bytes: &[u8],
+    public_inputs: &[U256; PUBLIC_SIGNAL_COUNT],
+    mut precompile: impl FnMut(Address, &[u8]) -> Option<Vec<u8>>,
+) -> bool {
+    if proof_bytes.len() != GROTH16_PROOF_LENGTH
+        || public_inputs.iter().any(|input| *input >= SCALAR_FIELD)
+    {
+        return false;
+    }
+    let proof: Vec<U256> = proof_bytes.chunks(32).map(U256::from_be_slice).collect();
+    let ic = &verifying_key[14..];
+
+    // vk_x = IC0 + sum(input[i] * IC[i + 1])
+    let mut vk_x = [ic[0], ic[1]];
+    for (i, input) in public_inputs.iter().enumerate() {
+        let Some(product) = precompile(EC_MUL, &words(&[ic[2 * i + 2], ic[2 * i + 3], *input]))
+        else {
+            return false;
+        };
+        let Some(sum) = precompile(EC_ADD, &[words(&vk_x), product].concat()) else {
+            return false;
+        };
+        if sum.len() != 64 {
+            return false;
+        }
+        vk_x = [
+            U256::from_be_slice(&sum[..32]),
+            U256::from_be_slice(&sum[32..]),
+        ];
+    }
+
+    // e(-A, B) * e(alpha, beta) * e(vk_x, gamma) * e(C, delta) == 1
+    let negated_a_y = (BASE_FIELD - proof[1] % BASE_FIELD) % BASE_FIELD;
+    let mut pairing_input = words(&[proof[0], negated_a_y]);
+    pairing_input.extend(words(&proof[2..6]));
+    pairing_input.extend(words(&verifying_key[..6]));
+    pairing_input.extend(words(&vk_x));
+    pairing_input.extend(words(&verifying_key[6..10]));
+    pairing_input.extend(words(&proof[6..8]));
+    pairing_input.extend(words(&verifying_key[10..14]));
+
+    precompile(EC_PAIRING, &pairing_input)
+        .is_some_and(|result| result.len() == 32 && U256::from_be_slice(&result) == U256::from(1))
+}
+
+/// Concatenate words as big-endian bytes
+fn words(values: &[U256]) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(|value| value.to_be_bytes::<32>())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use alloy_sol_types::SolEvent;
+    use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G2Affine};
+    use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+    use ark_ff::{BigInteger, PrimeField, Zero};
+    use ark_groth16::{Groth16, ProvingKey};
+    use ark_relations::lc;
+    use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+    use ark_snark::SNARK;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use stylus_sdk::testing::*;
+
+    const OWNER: Address = address!("0x00000000000000000000000000000000000000aa");
+    const VERIFIER: Address = address!("0x00000000000000000000000000000000000000bb");
+
+    /// Circuit binding the public signals to a witness sum
+    struct SignalCircuit {
+        signals: [Fr; PUBLIC_SIGNAL_COUNT],
+    }
+
+    impl ConstraintSynthesizer<Fr> for SignalCircuit {
+        fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+            let inputs = self
+                .signals
+                .iter()
+                .map(|signal| cs.new_input_variable(|| Ok(*signal)))
+                .collect::<Result<Vec<_>, _>>()?;
+            let sum = cs.new_witness_variable(|| Ok(self.signals.iter().sum()))?;
+
+            cs.enforce_constraint(
+                lc!() + inputs[0] + inputs[1] + inputs[2],
+                lc!() + ark_relations::r1cs::Variable::One,
+                lc!() + sum,
+            )
+        }
+    }
+
+    /// Groth16 prover of the signal circuit with its verifying key in contract encoding
+    struct TestProver {
+        proving_key: ProvingKey<Bn254>,
+        verifying_key: [U256; VERIFYING_KEY_WORDS],
+    }
+
+    impl TestProver {
+        fn new() -> Self {
+            let circuit = SignalCircuit {
+                signals: [Fr::zero(); PUBLIC_SIGNAL_COUNT],
+            };
+            let (proving_key, vk) =
+                Groth16::<Bn254>::circuit_specific_setup(circuit, &mut StdRng::seed_from_u64(42))
+                    .unwrap();
+
+            let mut verifying_key = Vec::new();
+            verifying_key.extend(g1_words(vk.alpha_g1));
+            verifying_key.extend(g2_words(vk.beta_g2));
+            verifying_key.extend(g2_words(vk.gamma_g2));
+            verifying_key.extend(g2_words(vk.delta_g2));
+            for ic in &vk.gamma_abc_g1 {
+                verifying_key.extend(g1_words(*ic));
+            }
+
+            Self {
+                proving_key,
+                verifying_key: verifying_key.try_into().unwrap(),
+            }
+        }
+
+        fn prove(&self, public_inputs: &[U256; PUBLIC_SIGNAL_COUNT]) -> Vec<u8> {
+            let signals =
+                public_inputs.map(|input| Fr::from_be_bytes_mod_order(&input.to_be_bytes::<32>()));
+            let proof = Groth16::<Bn254>::prove(
+                &self.proving_key,
+                SignalCircuit { signals },
+                &mut StdRng::seed_from_u64(7),
+            )
+            .unwrap();
+
+            let mut bytes = words(&g1_words(proof.a));
+            bytes.extend(words(&g2_words(proof.b)));
+            bytes.extend(words(&g1_words(proof.c)));
+            bytes
+        }
+    }
+
+    fn fq_word(value: Fq) -> U256 {
+        U256::from_be_slice(&value.into_bigint().to_bytes_be())
+    }
+
+    fn g1_words(point: G1Affine) -> [U256; 2] {
+        [fq_word(point.x), fq_word(point.y)]
+    }
+
+    fn g2_words(point: G2Affine) -> [U256; 4] {
+        [
+            fq_word(point.x.c1),
+            fq_word(point.x.c0),
+            fq_word(point.y.c1),
+            fq_word(point.y.c0),
+        ]
+    }
+
+    fn fq(bytes: &[u8]) -> Option<Fq> {
+        (U256::from_be_slice(bytes) < BASE_FIELD).then(|| Fq::from_be_bytes_mod_order(bytes))
+    }
+
+    fn g1(bytes: &[u8]) -> Option<G1Affine> {
+        let (x, y) = (fq(&bytes[..32])?, fq(&bytes[32..64])?);
+        if x.is_zero() && y.is_zero() {
+            return Some(G1Affine::zero());
+        }
+        let point = G1Affine::new_unchecked(x, y);
+        point.is_on_curve().then_some(point)
+    }
+
+    fn g2(bytes: &[u8]) -> Option<G2Affine> {
+        let x = Fq2::new(fq(&bytes[32..64])?, fq(&bytes[..32])?);
+        let y = Fq2::new(fq(&bytes[96..128])?, fq(&bytes[64..96])?);
+        if x.is_zero() && y.is_zero() {
+            return Some(G2Affine::zero());
+        }
+        let point = G2Affine::new_unchecked(x, y);
+        (point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve()).then_some(point)
+    }
+
+    fn encode_g1(point: G1Affine) -> Vec<u8> {
+        if point.is_zero() {
+            return vec![0u8; 64];
+        }
+        words(&g1_words(point))
+    }
+
+    /// BN254 precompiles as specified by EIP-196 and EIP-197
+    fn bn254_precompile(precompile: Address, input: &[u8]) -> Option<Vec<u8>> {
+        match precompile {
+            EC_ADD if input.len() == 128 => {
+                let sum = g1(&input[..64])? + g1(&input[64..])?;
+                Some(encode_g1(sum.into_affine()))
+            }
+            EC_MUL if input.len() == 96 => {
+                let scalar = U256::from_be_slice(&input[64..]);
+                let product = g1(&input[..64])?.mul_bigint(scalar.as_limbs());
+                Some(encode_g1(product.into_affine()))
+            }
+            EC_PAIRING if input.len() % 192 == 0 => {
+                let (g1s, g2s): (Vec<_>, Vec<_>) = input
+                    .chunks(192)
+                    .map(|pair| Some((g1(&pair[..64])?, g2(&pair[64..])?)))
+                    .collect::<Option<Vec<_>>>()?
+                    .into_iter()
+                    .unzip();
+                let holds = Bn254::multi_pairing(g1s, g2s).is_zero();
+                Some(U256::from(holds as u8).to_be_bytes::<32>().to_vec())
+            }
+            _ => None,
+        }
+    }
+
+    /// Answer the precompile calls checking a proof in the TestVM with the emulated
+    /// precompiles, so the pairing check decides whether the contract accepts the proof
+    fn mock_precompiles(
+        vm: &TestVM,
+        prover: &TestProver,
+        proof_bytes: &[u8],
+        public_inputs: &[U256; PUBLIC_SIGNAL_COUNT],
+    ) {
+        check_proof(
+            &prover.verifying_key,
+            proof_bytes,
+            public_inputs,
+            |precompile, input| {
+                let output = bn254_precompile(precompile, input);
+                vm.mock_static_call(
+                    precompile,
+                    input.to_vec(),
+                    output.clone().ok_or_else(Vec::new),
+                );
+                output
+            },
+        );
+    }
+
+    fn vk_hash() -> FixedBytes<32> {
+        FixedBytes::repeat_byte(0x11)
+    }
+
+    fn proof_id(byte: u8) -> FixedBytes<32> {
+        FixedBytes::repeat_byte(byte)
+    }
+
+    fn certificate_hash() -> U256 {
+        U256::from_be_bytes([0x22; 32]) % SCALAR_FIELD
+    }
+
+    fn public_inputs(requirements_met: bool) -> [U256; PUBLIC_SIGNAL_COUNT] {
+        [
+            certificate_hash(),
+            U256::from(requirements_met as u8),
+            U256::from_be_bytes([0x44; 32]) % SCALAR_FIELD,
+        ]
+    }
+
+    fn create_test_contract(vm: &TestVM, prover: &TestProver) -> Web5ClaimsVerifier {
+        let mut contract = Web5ClaimsVerifier::from(vm);
+        vm.set_sender(OWNER);
+        contract.constructor(OWNER);
+        assert!(contract
+            .add_trusted_vk(vk_hash(), prover.verifying_key)
+            .is_ok());
+        vm.set_sender(VERIFIER);
+        contract
+    }
+
+    fn verify(
+        vm: &TestVM,
+        contract: &mut Web5ClaimsVerifier,
+        prover: &TestProver,
+        proof_id: FixedBytes<32>,
+        proof_bytes: Vec<u8>,
+        public_inputs: [U256; PUBLIC_SIGNAL_COUNT],
+    ) -> Result<bool, VerifierError> {
+        mock_precompiles(vm, prover, &proof_bytes, &public_inputs);
+        contract.verify_claim(
+            proof_id,
+            vk_hash(),
+            FixedBytes::repeat_byte(0x33),
+            Bytes::from(proof_bytes),
+            public_inputs,
+        )
+    }
+
+    #[test]
+    fn test_constructor_sets_owner() {
+        let vm = TestVM::default();
+        let prover = TestProver::new();
+        let mut contract = create_test_contract(&vm, &prover);
+
+        assert_eq!(contract.owner(), OWNER);
+        // Nobody else can take over the trusted key registry
+        assert!(matches!(
+            contract.add_trusted_vk(FixedBytes::repeat_byte(0x99), prover.verifying_key),
+            Err(VerifierError::NotOwner(_))
+        ));
+    }
+
+    #[test]
+    fn test_trusted_vk_management() {
+        let vm = TestVM::default();
+        let prover = TestProver::new();
+        let mut contract = create_test_contract(&vm, &prover);
+
+        assert!(contract.is_trusted_vk(vk_hash()));
+        assert_eq!(contract.trusted_vk_count(), U256::from(1));
+
+        // Only the owner may change the registry
+        assert!(matches!(
+            contract.revoke_trusted_vk(vk_hash()),
+            Err(VerifierError::NotOwner(_))
+        ));
+
+        vm.set_sender(OWNER);
+        assert!(contract.revoke_trusted_vk(vk_hash()).is_ok());
+        assert!(!contract.is_trusted_vk(vk_hash()));
+        assert_eq!(contract.trusted_vk_count(), U256::ZERO);
+
+        // A hash cannot be re-registered with another verifying key
+        let mut other_key = prover.verifying_key;
+        other_key[0] = U256::from(1);
+        assert!(matches!(
+            contract.add_trusted_vk(vk_hash(), other_key),
+            Err(VerifierError::VerifyingKeyMismatch(_))
+        ));
+        assert!(contract
+            .add_trusted_vk(vk_hash(), prover.verifying_key)
+            .is_ok());
+        assert!(contract.is_trusted_vk(vk_hash()));
+    }
+
+    #[test]
+    fn test_verify_claim_emits_event() {
+        let vm = TestVM::default();
+        let prover = TestProver::new();
+        let mut contract = create_test_contract(&vm, &prover);
+
+        let inputs = public_inputs(true);
+        let result = verify(
+            &vm,
+            &mut contract,
+            &prover,
+            proof_id(0x01),
+            prover.prove(&inputs),
+            inputs,
+        );
+        assert!(matches!(result, Ok(true)));
+        assert!(contract.is_verified(proof_id(0x01)));
+        assert_eq!(contract.verified_count(), U256::from(1));
+
+        let logs = vm.get_emitted_logs();
+        let (topics, data) = logs.last().unwrap();
+        assert_eq!(topics[0], ClaimVerified::SIGNATURE_HASH);
+        assert_eq!(topics[1], B256::from(proof_id(0x01)));
+        assert_eq!(topics[2], B256::from(vk_hash()));
+        // The certificate hash comes from the public inputs
+        assert_eq!(
+            &data[..32],
+            certificate_hash().to_be_bytes::<32>().as_slice()
+        );
+    }
+
+    #[test]
+    fn test_untrusted_vk_rejected() {
+        let vm = TestVM::default();
+        let prover = TestProver::new();
+        let mut contract = create_test_contract(&vm, &prover);
+
+        let inputs = public_inputs(true);
+        let result = contract.verify_claim(
+            proof_id(0x01),
+            FixedBytes::repeat_byte(0x99),
+            FixedBytes::repeat_byte(0x33),
+            Bytes::from(prover.prove(&inputs)),
+            inputs,
+        );
+
+        assert!(matches!(
+            result,
+            Err(VerifierError::UntrustedVerifyingKey(_))
+        ));
+        assert_eq!(contract.verified_count(), U256::ZERO);
+    }
+
+    #[test]
+    fn test_tampered_proof_rejected() {
+        let vm = TestVM::default();
+        let prover = TestProver::new();
+        let mut contract = create_test_contract(&vm, &prover);
+        let inputs = public_inputs(true);
+        let proof = prover.prove(&inputs);
+
+        // A valid curve point in place of C fails the pairing check
+        let mut forged = proof.clone();
+        forged[192..].copy_from_slice(&proof[..64]);
+        assert!(matches!(
+            verify(&vm, &mut contract, &prover, proof_id(0x01), forged, inputs),
+            Err(VerifierError::InvalidProof(_))
+        ));
+
+        // Flipped bytes leave A off the curve, which the precompiles reject
+        let mut flipped = proof.clone();
+        flipped[10] ^= 0x01;
+        assert!(matches!(
+            verify(&vm, &mut contract, &prover, proof_id(0x02), flipped, inputs),
+            Err(VerifierError::InvalidProof(_))
+        ));
+
+        assert!(matches!(
+            verify(
+                &vm,
+                &mut contract,
+                &prover,
+                proof_id(0x03),
+                proof[..128].to_vec(),
+                inputs
+            ),
+            Err(VerifierError::InvalidProof(_))
+        ));
+        assert_eq!(contract.verified_count(), U256::ZERO);
+    }
+
+    #[test]
+    fn test_tampered_public_inputs_rejected() {
+        let vm = TestVM::default();
+        let prover = TestProver::new();
+        let mut contract = create_test_contract(&vm, &prover);
+        let inputs = public_inputs(true);
+        let proof = prover.prove(&inputs);
+
+        let mut other_certificate = inputs;
+        other_certificate[0] = U256::from(1);
+        assert!(matches!(
+            verify(
+                &vm,
+                &mut contract,
+                &prover,
+                proof_id(0x01),
+                proof.clone(),
+                other_certificate
+            ),
+            Err(VerifierError::InvalidProof(_))
+        ));
+
+        // Inputs outside the scalar field are rejected before the pairing check
+        let mut unreduced = inputs;
+        unreduced[2] += SCALAR_FIELD;
+        assert!(matches!(
+            verify(
+                &vm,
+                &mut contract,
+                &prover,
+                proof_id(0x02),
+                proof,
+                unreduced
+            ),
+            Err(VerifierError::InvalidProof(_))
+        ));
+        assert_eq!(contract.verified_count(), U256::ZERO);
+    }
+
+    #[test]
+    fn test_requirements_not_met_rejected() {
+        let vm = TestVM::default();
+        let prover = TestProver::new();
+        let mut contract = create_test_contract(&vm, &prover);
+
+        // A valid proof of an unmet claim
+        let inputs = public_inputs(false);
+        assert!(matches!(
+            verify(
+                &vm,
+                &mut contract,
+                &prover,
+                proof_id(0x01),
+                prover.prove(&inputs),
+                inputs
+            ),
+            Err(VerifierError::RequirementsNotMet(_))
+        ));
+        assert!(!contract.is_verified(proof_id(0x01)));
+    }
+
+    #[test]
+    fn test_replay_rejected() {
+        let vm = TestVM::default();
+        let prover = TestProver::new();
+        let mut contract = create_test_contract(&vm, &prover);
+        let inputs = public_inputs(true);
+        let proof = prover.prove(&inputs);
+
+        assert!(verify(
+            &vm,
+            &mut contract,
+            &prover,
+            proof_id(0x01),
+            proof.clone(),
+            inputs
+        )
+        .is_ok());
+        assert!(matches!(
+            verify(&vm, &mut contract, &prover, proof_id(0x01), proof, inputs),
+            Err(VerifierError::ProofAlreadyVerified(_))
+        ));
+        assert_eq!(contract.verified_count(), U256::from(1));
+    }
+}
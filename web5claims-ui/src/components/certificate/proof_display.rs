@@ -52,6 +52,12 @@ pub fn proof_display(props: &ProofDisplayProps) -> Html {
                                     format!("Combined ({} claims)", criteria.len()),
                                 web5claims::ClaimType::CompletionDate { after_date } =>
                                     format!("Completed after {}", after_date.format("%Y-%m-%d")),
+                                web5claims::ClaimType::IdentityBound { name_match } =>
+                                    format!("Name matches passport ({})", name_match),
+                                web5claims::ClaimType::NationalityNotIn { countries } =>
+                                    format!("Nationality not {}", countries.join(", ")),
+                                web5claims::ClaimType::DocumentValid { .. } =>
+                                    "Identity document valid at certification".to_string(),
                             }}
                         </span>
                     </div>
@@ -253,8 +253,9 @@ pub fn certificate_display(props: &CertificateDisplayProps) -> Html {
                     })
                 };
 
+                let mut holder = state.holder.clone();
                 zk_service.generate_language_proficiency_proof(
-                    cert.clone(),
+                    &mut holder,
                     language,
                     min_level,
                     "web5claims_local".to_string(),
@@ -352,8 +353,9 @@ pub fn certificate_display(props: &CertificateDisplayProps) -> Html {
                     })
                 };
 
+                let mut holder = state.holder.clone();
                 zk_service.generate_performance_proof(
-                    cert.clone(),
+                    &mut holder,
                     threshold,
                     "web5claims_local".to_string(),
                     on_success,
@@ -499,8 +501,9 @@ pub fn certificate_display(props: &CertificateDisplayProps) -> Html {
                     })
                 };
 
+                let mut holder = state.holder.clone();
                 zk_service.generate_combined_proof(
-                    cert.clone(),
+                    &mut holder,
                     criteria,
                     "web5claims_local".to_string(),
                     on_success,
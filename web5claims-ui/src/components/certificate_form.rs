@@ -3,8 +3,16 @@ use crate::components::ui::Button;
 use crate::types::AppState;
 use chrono::Utc;
 use konnektoren_core::certificates::CertificateData;
+use web5claims::GameCatalogue;
 use yew::prelude::*;
 
+/// Number of challenges on a game path of the built-in catalogue
+fn catalogue_challenges(game_path_name: &str) -> Option<usize> {
+    GameCatalogue::builtin()
+        .game_path(game_path_name)
+        .map(|game_path| game_path.challenges.len())
+}
+
 #[derive(Properties, PartialEq)]
 pub struct CertificateFormProps {
     pub state: UseStateHandle<AppState>,
@@ -15,8 +23,8 @@ pub struct CertificateFormProps {
 #[function_component(CertificateForm)]
 pub fn certificate_form(props: &CertificateFormProps) -> Html {
     let game_path_name = use_state(|| "German_B2_Complete".to_string());
-    let total_challenges = use_state(|| 50);
-    let solved_challenges = use_state(|| 47);
+    let total_challenges = use_state(|| catalogue_challenges("German_B2_Complete").unwrap_or(1));
+    let solved_challenges = use_state(|| *total_challenges);
 
     // Initialize profile_name with verified_name if available
     let profile_name = use_state(|| {
@@ -45,9 +53,16 @@ pub fn certificate_form(props: &CertificateFormProps) -> Html {
         }
     });
 
+    // The issuer only accepts the challenge total of the game path in its catalogue
     let on_language_change = {
         let game_path_name = game_path_name.clone();
+        let total_challenges = total_challenges.clone();
+        let solved_challenges = solved_challenges.clone();
         Callback::from(move |value: String| {
+            if let Some(total) = catalogue_challenges(&value) {
+                total_challenges.set(total);
+                solved_challenges.set((*solved_challenges).min(total));
+            }
             game_path_name.set(value);
         })
     };
@@ -8,7 +8,7 @@ use crate::types::AppState;
 use crate::utils::clipboard::copy_to_clipboard_simple;
 use crate::utils::proof_link::generate_verify_link;
 use konnektoren_core::certificates::CertificateData;
-use web5claims::{CefrLevel, ClaimType};
+use web5claims::{CefrLevel, ClaimType, MintedCertificate};
 use yew::prelude::*;
 
 #[function_component(CertificateLookupPage)]
@@ -46,7 +46,14 @@ pub fn certificate_lookup_page() -> Html {
             new_state.clear_error();
             app_state.set(new_state);
 
-            // Try to decode the certificate from base64
+            // Try to decode a minted certificate, then a plain certificate from base64
+            if let Ok(minted) = MintedCertificate::from_base64(&id) {
+                let mut new_state = (*app_state).clone();
+                new_state.set_minted_certificate(minted);
+                app_state.set(new_state);
+                is_loading.set(false);
+                return;
+            }
             match CertificateData::from_base64(&id) {
                 Ok(certificate) => {
                     let mut new_state = (*app_state).clone();
@@ -113,8 +120,9 @@ pub fn certificate_lookup_page() -> Html {
                     })
                 };
 
+                let mut holder = app_state.holder.clone();
                 zk_service.generate_language_proficiency_proof(
-                    cert.clone(),
+                    &mut holder,
                     language,
                     min_level,
                     "aleo".to_string(),
@@ -130,7 +138,7 @@ pub fn certificate_lookup_page() -> Html {
         let zk_service = zk_service.clone();
 
         Callback::from(move |_| {
-            if let Some(cert) = &app_state.certificate_data {
+            if app_state.certificate_data.is_some() {
                 let mut new_state = (*app_state).clone();
                 new_state.is_generating_proof = true;
                 new_state.clear_error();
@@ -155,8 +163,9 @@ pub fn certificate_lookup_page() -> Html {
                     })
                 };
 
+                let mut holder = app_state.holder.clone();
                 zk_service.generate_performance_proof(
-                    cert.clone(),
+                    &mut holder,
                     90,
                     "aleo".to_string(),
                     on_success,
@@ -213,8 +222,9 @@ pub fn certificate_lookup_page() -> Html {
                     })
                 };
 
+                let mut holder = app_state.holder.clone();
                 zk_service.generate_combined_proof(
-                    cert.clone(),
+                    &mut holder,
                     criteria,
                     "aleo".to_string(),
                     on_success,
@@ -312,7 +322,14 @@ pub fn certificate_lookup_page() -> Html {
             new_state.clear_error();
             app_state.set(new_state);
 
-            // Try to decode the certificate from base64
+            // Try to decode a minted certificate, then a plain certificate from base64
+            if let Ok(minted) = MintedCertificate::from_base64(&id) {
+                let mut new_state = (*app_state).clone();
+                new_state.set_minted_certificate(minted);
+                app_state.set(new_state);
+                is_loading.set(false);
+                return;
+            }
             match CertificateData::from_base64(&id) {
                 Ok(certificate) => {
                     let mut new_state = (*app_state).clone();
@@ -506,8 +523,8 @@ fn simulate_certificate_lookup(id: &str) -> Option<CertificateData> {
                 use chrono::Utc;
                 CertificateData::new(
                     "German_B2_Complete".to_string(),
-                    50,
-                    47,
+                    7,
+                    7,
                     "Sample Student".to_string(),
                     Utc::now(),
                 )
@@ -521,24 +538,24 @@ fn get_sample_certificate_ids() -> Vec<(String, String)> {
 
     let cert1 = CertificateData::new(
         "German_B2_Complete".to_string(),
-        50,
-        47, // Performance 94%
+        7,
+        7, // Performance 100%
         "Alice Schmidt".to_string(),
         Utc::now(),
     );
 
     let cert2 = CertificateData::new(
         "Spanish_A2_Elementary".to_string(),
-        30,
-        28, // Performance ~93%
+        6,
+        6, // Performance 100%
         "Bob Martinez".to_string(),
         Utc::now(),
     );
 
     let cert3 = CertificateData::new(
         "French_C1_Advanced".to_string(),
-        60,
-        58, // Performance ~97%
+        6,
+        6, // Performance 100%
         "Carol Dubois".to_string(),
         Utc::now(),
     );
@@ -5,9 +5,10 @@ use crate::components::{
     ui::Card,
     wallet::{AleoWallet, WalletInfo, ZkPassportWallet},
 };
-use crate::router::Route;
 use crate::services::zkpassport_service::{PassportData, ZkPassportProof};
+use crate::services::HandoffService;
 use crate::types::AppState;
+use web5claims::handoff::HANDOFF_QUERY_PARAM;
 use web_sys::{window, UrlSearchParams};
 use yew::prelude::*;
 
@@ -18,56 +19,59 @@ pub fn issuer_page() -> Html {
     let wallet_error = use_state(|| None::<String>);
     let verified_name = use_state(|| None::<String>);
     let verification_status = use_state(|| None::<String>);
+    let handoff_error = use_state(|| None::<String>);
 
     // Add ZK Passport state
     let passport_data = use_state(|| None::<PassportData>);
     let zkpassport_proof = use_state(|| None::<ZkPassportProof>);
 
-    // Check for URL parameters from ZKPass verification
+    // Check for a signed handoff from ZKPass verification
     use_effect_with((), {
         let verified_name = verified_name.clone();
         let verification_status = verification_status.clone();
+        let handoff_error = handoff_error.clone();
 
         move |_| {
             if let Some(window) = window() {
                 if let Ok(search) = window.location().search() {
-                    if !search.is_empty() {
-                        log::info!("URL search params: {}", search);
-
-                        if let Ok(params) = UrlSearchParams::new_with_str(&search) {
-                            // Check for verified_name parameter
-                            if let Some(name) = params.get("verified_name") {
-                                log::info!("Found verified name: {}", name);
-                                verified_name.set(Some(name.clone()));
-
-                                // Check for additional verification info
-                                let mut status_parts = vec![];
-
-                                if let Some(age_verified) = params.get("verified_age") {
-                                    if age_verified == "true" {
-                                        status_parts.push("Age (18+)".to_string());
+                    if let Ok(params) = UrlSearchParams::new_with_str(&search) {
+                        if let Some(token) = params.get(HANDOFF_QUERY_PARAM) {
+                            match HandoffService::new().accept(&token) {
+                                Ok(handoff) => {
+                                    let result = &handoff.claims.result;
+                                    log::info!("Accepted ZKPass handoff: {}", result.summary());
+
+                                    let mut status_parts = vec![];
+                                    if !result.summary().is_empty() {
+                                        status_parts.push(result.summary());
                                     }
-                                }
+                                    if let Some(issued_at) = handoff.claims.issued_at() {
+                                        status_parts.push(format!(
+                                            "Verified: {}",
+                                            issued_at.format("%Y-%m-%d %H:%M:%S")
+                                        ));
+                                    }
+                                    status_parts.extend(handoff.warnings.clone());
 
-                                if params.get("verified_name").is_some() {
-                                    status_parts.push(format!("Name ({})", name));
+                                    verified_name.set(result.first_name());
+                                    verification_status.set(Some(status_parts.join(" • ")));
                                 }
-
-                                if let Some(timestamp) = params.get("verification_timestamp") {
-                                    status_parts.push(format!(
-                                        "Verified: {}",
-                                        timestamp
-                                            .chars()
-                                            .take(19)
-                                            .collect::<String>()
-                                            .replace('T', " ")
-                                    ));
+                                Err(e) => {
+                                    log::warn!("Rejected ZKPass handoff: {}", e);
+                                    handoff_error.set(Some(e));
                                 }
+                            }
 
-                                if !status_parts.is_empty() {
-                                    verification_status.set(Some(status_parts.join(" • ")));
-                                }
+                            // The token is single use, so drop it from the address bar
+                            if let Ok(history) = window.history() {
+                                let _ = history.replace_state_with_url(
+                                    &wasm_bindgen::JsValue::NULL,
+                                    "",
+                                    Some("/issuer"),
+                                );
                             }
+                        } else if params.get("verified_name").is_some() {
+                            log::warn!("Ignoring unsigned ZKPass verification parameters");
                         }
                     }
                 }
@@ -76,6 +80,13 @@ pub fn issuer_page() -> Html {
         }
     });
 
+    let launch_zkpass = Callback::from(|_: MouseEvent| {
+        let zkpass_url = HandoffService::new().launch_url();
+        if let Some(window) = window() {
+            let _ = window.open_with_url_and_target(&zkpass_url, "_blank");
+        }
+    });
+
     let on_wallet_connect = {
         let wallet_info = wallet_info.clone();
         let wallet_error = wallet_error.clone();
@@ -185,6 +196,23 @@ pub fn issuer_page() -> Html {
                 html! { <></> }
             }}
 
+            {if let Some(error) = &*handoff_error {
+                html! {
+                    <div class="alert alert-error">
+                        <svg xmlns="http://www.w3.org/2000/svg" class="stroke-current shrink-0 h-6 w-6" fill="none" viewBox="0 0 24 24">
+                            <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M10 14l2-2m0 0l2-2m-2 2l-2-2m2 2l2 2m7-2a9 9 0 11-18 0 9 9 0 0118 0z" />
+                        </svg>
+                        <div>
+                            <div class="font-semibold">{"ZKPass Verification Rejected"}</div>
+                            <div class="text-sm">{error}</div>
+                            <div class="text-xs mt-1">{"Launch ZKPass again from this page to verify your identity"}</div>
+                        </div>
+                    </div>
+                }
+            } else {
+                html! { <></> }
+            }}
+
             // Certificate Creation Section
             <Card title="🎓 Create Language Certificate">
                 <CertificateForm
@@ -231,13 +259,12 @@ pub fn issuer_page() -> Html {
                                         <p class="text-sm text-base-content/70 mb-3">
                                             {"Full-featured passport scanning with enhanced privacy"}
                                         </p>
-                                        <a
-                                            href={Route::zkpass_external_url()}
-                                            target="_blank"
+                                        <button
                                             class="btn btn-primary btn-sm w-full"
+                                            onclick={launch_zkpass}
                                         >
                                             {"🚀 Launch ZKPass"}
-                                        </a>
+                                        </button>
                                         <p class="text-xs text-base-content/60 mt-2">
                                             {"Opens in new tab with advanced verification features"}
                                         </p>
@@ -6,7 +6,9 @@ use crate::components::{
 use crate::services::ZkService;
 use crate::utils::proof_link::{decode_proof_from_url, generate_verify_link};
 use wasm_bindgen::JsCast;
-use web5claims::{VerificationResult, ZkProofClaim};
+use web5claims::{
+    DefinitionEvaluation, PresentationDefinition, VerificationResult, ZkProofClaim,
+};
 use yew::prelude::*;
 
 #[function_component(VerifierPage)]
@@ -18,6 +20,8 @@ pub fn verifier_page() -> Html {
     let is_verifying = use_state(|| false);
     let error_message = use_state(|| None::<String>);
     let verify_link = use_state(|| None::<String>);
+    let definition_json = use_state(|| String::new());
+    let definition_evaluation = use_state(|| None::<Result<DefinitionEvaluation, String>>);
 
     let on_proof_input = {
         let proof_json = proof_json.clone();
@@ -103,6 +107,29 @@ pub fn verifier_page() -> Html {
         })
     };
 
+    let on_definition_input = {
+        let definition_json = definition_json.clone();
+        let definition_evaluation = definition_evaluation.clone();
+        let proof = proof.clone();
+        let zk_service = zk_service.clone();
+
+        Callback::from(move |value: String| {
+            definition_json.set(value.clone());
+
+            if value.trim().is_empty() {
+                definition_evaluation.set(None);
+                return;
+            }
+
+            // Evaluate the definition against the pasted proof, if it verifies
+            let proofs: Vec<ZkProofClaim> = (*proof).clone().into_iter().collect();
+            let evaluation = serde_json::from_str::<PresentationDefinition>(&value)
+                .map_err(|e| format!("Invalid presentation definition: {}", e))
+                .and_then(|definition| zk_service.evaluate_definition(&definition, &proofs));
+            definition_evaluation.set(Some(evaluation));
+        })
+    };
+
     let on_dismiss_error = {
         let error_message = error_message.clone();
         Callback::from(move |_| {
@@ -228,6 +255,44 @@ pub fn verifier_page() -> Html {
                         />
                     </Card>
                 </div>
+
+                <Card title="📑 Presentation Definition" class="mt-8">
+                    <div class="space-y-4">
+                        <textarea
+                            class="textarea textarea-bordered w-full h-32 font-mono text-xs"
+                            placeholder="Paste a DIF Presentation Definition to check the proof against it..."
+                            value={(*definition_json).clone()}
+                            oninput={move |e: InputEvent| {
+                                if let Some(target) = e.target() {
+                                    if let Ok(textarea) = target.dyn_into::<web_sys::HtmlTextAreaElement>() {
+                                        on_definition_input.emit(textarea.value());
+                                    }
+                                }
+                            }}
+                        ></textarea>
+
+                        {match &*definition_evaluation {
+                            Some(Ok(evaluation)) => html! {
+                                <ul class="space-y-2 text-sm">
+                                    {for evaluation.descriptors.iter().map(|descriptor| html! {
+                                        <li class="flex justify-between">
+                                            <span class="font-medium">{&descriptor.descriptor_id}</span>
+                                            if descriptor.is_satisfied() {
+                                                <span class="badge badge-success">{"✅ Satisfied"}</span>
+                                            } else {
+                                                <span class="badge badge-error">{"❌ Not satisfied"}</span>
+                                            }
+                                        </li>
+                                    })}
+                                </ul>
+                            },
+                            Some(Err(error)) => html! {
+                                <div class="alert alert-error text-sm">{error}</div>
+                            },
+                            None => html! {},
+                        }}
+                    </div>
+                </Card>
             </div>
         </PageLayout>
     }
@@ -6,7 +6,7 @@ use crate::components::{
 use crate::router::Route;
 use crate::services::ZkService;
 use crate::utils::proof_link::decode_proof_from_query;
-use web5claims::{VerificationResult, ZkProofClaim};
+use web5claims::{ProofMode, VerificationResult, ZkProofClaim};
 use yew::prelude::*;
 use yew_router::prelude::*;
 
@@ -102,6 +102,12 @@ pub fn verify_proof_page() -> Html {
         })
     };
 
+    // The badge shows the mode the verifier established rather than the one the proof
+    // declares, and only once verification has finished
+    let verified_mode = (*verification_result)
+        .as_ref()
+        .and_then(|result| result.details.metadata.get("proof_mode").cloned());
+
     log::info!("Rendering VerifyProofPage HTML");
 
     html! {
@@ -121,6 +127,22 @@ pub fn verify_proof_page() -> Html {
                         dismissible={true}
                     />
 
+                    if let Some(mode) = verified_mode {
+                        if mode == ProofMode::Simulated.to_string() {
+                            <div class="alert alert-warning">
+                                <span class="badge badge-warning badge-lg font-bold">{"SIMULATED"}</span>
+                                <span>
+                                    {"This proof was produced by a simulated prover. It demonstrates the flow but carries no cryptographic guarantee."}
+                                </span>
+                            </div>
+                        } else if mode == ProofMode::Real.to_string() {
+                            <div class="alert alert-success">
+                                <span class="badge badge-success badge-lg font-bold">{"REAL"}</span>
+                                <span>{"This proof was verified by a real proving system."}</span>
+                            </div>
+                        }
+                    }
+
                     if *is_verifying && proof.is_some() {
                         <div class="alert alert-info">
                             <span class="loading loading-spinner loading-sm"></span>
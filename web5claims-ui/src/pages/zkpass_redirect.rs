@@ -1,22 +1,26 @@
 use crate::components::layout::PageLayout;
-use crate::router::Route;
+use crate::services::HandoffService;
 use yew::prelude::*;
 
 #[function_component(ZkPassRedirectPage)]
 pub fn zkpass_redirect_page() -> Html {
+    // The launch URL carries a fresh handoff nonce, so create it once per visit
+    let zkpass_url = use_state(|| HandoffService::new().launch_url());
+
     // Redirect to external ZKPass app
-    use_effect_with((), |_| {
-        let zkpass_url = Route::zkpass_external_url();
-
-        // Give user a moment to see the redirect message, then redirect
-        gloo::timers::callback::Timeout::new(2000, move || {
-            if let Some(window) = web_sys::window() {
-                let _ = window.location().set_href(&zkpass_url);
-            }
-        })
-        .forget();
-
-        || ()
+    use_effect_with((), {
+        let zkpass_url = (*zkpass_url).clone();
+        move |_| {
+            // Give user a moment to see the redirect message, then redirect
+            gloo::timers::callback::Timeout::new(2000, move || {
+                if let Some(window) = web_sys::window() {
+                    let _ = window.location().set_href(&zkpass_url);
+                }
+            })
+            .forget();
+
+            || ()
+        }
     });
 
     html! {
@@ -36,7 +40,7 @@ pub fn zkpass_redirect_page() -> Html {
                             <p class="text-sm text-base-content/70">
                                 {"If you're not redirected automatically, "}
                                 <a
-                                    href={Route::zkpass_external_url()}
+                                    href={(*zkpass_url).clone()}
                                     class="link link-primary"
                                     target="_blank"
                                 >
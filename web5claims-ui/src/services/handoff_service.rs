@@ -0,0 +1,82 @@
+use crate::router::Route;
+use gloo::storage::{LocalStorage, Storage};
+use web5claims::handoff::{decode_public_key, HandoffNonces, HANDOFF_NONCE_QUERY_PARAM};
+use web5claims::{HandoffVerifier, VerificationMode, VerifiedHandoff};
+
+/// Storage key of the pending and consumed handoff nonces
+const NONCE_STORAGE_KEY: &str = "web5claims.handoff_nonces";
+
+/// Base64url Ed25519 public key of the handoff service, set at build time
+const HANDOFF_PUBLIC_KEY: Option<&str> = option_env!("WEB5CLAIMS_HANDOFF_PUBLIC_KEY");
+
+pub struct HandoffService {
+    verifier: Option<HandoffVerifier>,
+}
+
+impl HandoffService {
+    pub fn new() -> Self {
+        let verifier = match HANDOFF_PUBLIC_KEY.map(decode_public_key) {
+            Some(Ok(key)) => Some(
+                HandoffVerifier::new()
+                    .with_trusted_key(key)
+                    .with_mode(VerificationMode::Strict),
+            ),
+            Some(Err(e)) => {
+                log::error!("Invalid WEB5CLAIMS_HANDOFF_PUBLIC_KEY: {}", e);
+                None
+            }
+            None => {
+                log::warn!("WEB5CLAIMS_HANDOFF_PUBLIC_KEY not set, ZKPass handoffs are disabled");
+                None
+            }
+        };
+        Self { verifier }
+    }
+
+    /// URL of the zkpass app carrying a fresh handoff nonce
+    pub fn launch_url(&self) -> String {
+        let base = Route::zkpass_external_url();
+        match &self.verifier {
+            Some(verifier) => {
+                let mut nonces = Self::load_nonces();
+                let nonce = verifier.create_nonce(&mut nonces);
+                Self::save_nonces(&nonces);
+                format!("{}?{}={}", base, HANDOFF_NONCE_QUERY_PARAM, nonce)
+            }
+            None => base,
+        }
+    }
+
+    /// Validate a handoff token returned by the zkpass app and consume its nonce
+    ///
+    /// Only handoffs whose ZKPassport proofs verified are accepted. The nonces live in
+    /// storage the user controls, so they bind the token to this browser but do not stop
+    /// the user from replaying it before it expires.
+    pub fn accept(&self, token: &str) -> Result<VerifiedHandoff, String> {
+        let verifier = self
+            .verifier
+            .as_ref()
+            .ok_or("ZKPass handoff key is not configured")?;
+
+        let mut nonces = Self::load_nonces();
+        let result = verifier.verify(token, &mut nonces);
+        Self::save_nonces(&nonces);
+        result.map_err(|e| e.to_string())
+    }
+
+    fn load_nonces() -> HandoffNonces {
+        LocalStorage::get(NONCE_STORAGE_KEY).unwrap_or_default()
+    }
+
+    fn save_nonces(nonces: &HandoffNonces) {
+        if let Err(e) = LocalStorage::set(NONCE_STORAGE_KEY, nonces) {
+            log::warn!("Handoff nonces not stored: {}", e);
+        }
+    }
+}
+
+impl Default for HandoffService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
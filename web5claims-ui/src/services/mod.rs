@@ -1,5 +1,7 @@
+pub mod handoff_service;
 pub mod zk_service;
 pub mod zkpassport_service;
 
+pub use handoff_service::HandoffService;
 pub use zk_service::ZkService;
 pub use zkpassport_service::ZkPassportService;
@@ -1,10 +1,17 @@
-use konnektoren_core::certificates::CertificateData;
+use web5claims::handoff::decode_public_key;
 use web5claims::{
-    CefrLevel, CertificateIssuer, ClaimType, ProofOptions, ProofRequest, VerificationResult,
-    ZkProofClaim, ZkProofVerifier,
+    CefrLevel, CertificateIssuer, ClaimRequest, ClaimType, DefinitionEvaluation, Holder,
+    PresentationDefinition, VerificationResult, ZkProofClaim, ZkProofVerifier,
 };
 use yew::Callback;
 
+/// Base64url Ed25519 public key of the certificate signer, set at build time
+const CERTIFICATE_SIGNER_KEY: Option<&str> = option_env!("WEB5CLAIMS_CERTIFICATE_SIGNER_KEY");
+
+/// Set to `true` at build time to generate simulated proofs from unsigned certificates (demos)
+const ACCEPT_UNSIGNED_CERTIFICATES: Option<&str> =
+    option_env!("WEB5CLAIMS_ACCEPT_UNSIGNED_CERTIFICATES");
+
 pub struct ZkService {
     issuer: CertificateIssuer,
     verifier: ZkProofVerifier,
@@ -13,17 +20,79 @@ pub struct ZkService {
 impl ZkService {
     pub fn new() -> Self {
         Self {
-            issuer: CertificateIssuer::new(
-                "web5_claims_ui_issuer".to_string(),
-                "Web5 Claims UI Issuer".to_string(),
-            ),
-            verifier: ZkProofVerifier::new("web5_claims_ui_verifier".to_string()),
+            issuer: Self::create_issuer(),
+            verifier: Self::create_verifier(),
+        }
+    }
+
+    /// Verifier accepting the name commitments of the configured certificate signer
+    fn create_verifier() -> ZkProofVerifier {
+        let verifier = ZkProofVerifier::new("web5_claims_ui_verifier".to_string());
+        match CERTIFICATE_SIGNER_KEY.map(decode_public_key) {
+            Some(Ok(key)) => verifier.with_certificate_signer(key),
+            _ => verifier,
+        }
+    }
+
+    /// Issuer accepting only certificates of the configured certificate signer
+    ///
+    /// Without a valid signer key the issuer refuses every certificate, unless the build
+    /// explicitly opted into unsigned certificates.
+    fn create_issuer() -> CertificateIssuer {
+        let issuer = CertificateIssuer::new(
+            "web5_claims_ui_issuer".to_string(),
+            "Web5 Claims UI Issuer".to_string(),
+        );
+        match CERTIFICATE_SIGNER_KEY.map(decode_public_key) {
+            Some(Ok(key)) => issuer.with_certificate_signer(key),
+            Some(Err(e)) => {
+                log::error!(
+                    "Invalid WEB5CLAIMS_CERTIFICATE_SIGNER_KEY, no certificates are accepted: {}",
+                    e
+                );
+                issuer
+            }
+            None if ACCEPT_UNSIGNED_CERTIFICATES == Some("true") => {
+                log::warn!(
+                    "WEB5CLAIMS_ACCEPT_UNSIGNED_CERTIFICATES is set, certificates are not checked against a signer"
+                );
+                issuer.accept_unsigned_certificates()
+            }
+            None => {
+                log::error!(
+                    "WEB5CLAIMS_CERTIFICATE_SIGNER_KEY not set, no certificates are accepted"
+                );
+                issuer
+            }
+        }
+    }
+
+    /// Ask the issuer for a proof bound to the holder key from the holder's certificates
+    fn answer_request(
+        &self,
+        holder: &mut Holder,
+        request: ClaimRequest,
+        on_success: Callback<ZkProofClaim>,
+        on_error: Callback<String>,
+    ) {
+        match holder.answer_request(&self.issuer, &request) {
+            Ok(proof) => {
+                log::info!(
+                    "✅ Proof generated successfully with ID: {}",
+                    proof.proof_id
+                );
+                on_success.emit(proof);
+            }
+            Err(e) => {
+                log::error!("❌ Proof generation failed: {}", e);
+                on_error.emit(format!("Proof generation failed: {}", e));
+            }
         }
     }
 
     pub fn generate_language_proficiency_proof(
         &self,
-        certificate: CertificateData,
+        holder: &mut Holder,
         language: String,
         min_level: CefrLevel,
         platform: String,
@@ -31,122 +100,71 @@ impl ZkService {
         on_error: Callback<String>,
     ) {
         log::info!("=== Language Proficiency Proof Generation ===");
-        log::info!("Certificate Details:");
-        log::info!("  - Profile: {}", certificate.profile_name);
-        log::info!("  - Course: {}", certificate.game_path_name);
-        log::info!("  - Performance: {}%", certificate.performance_percentage);
-        log::info!(
-            "  - Solved: {}/{}",
-            certificate.solved_challenges,
-            certificate.total_challenges
-        );
-        log::info!("  - Date: {}", certificate.date);
+        log::info!("Holder: {}", holder.holder_id);
         log::info!("Claim Details:");
         log::info!("  - Language: {}", language);
         log::info!("  - Min Level: {:?}", min_level);
         log::info!("  - Platform: {}", platform);
 
-        let request = ProofRequest {
-            certificate,
+        let request = ClaimRequest {
             claim_type: ClaimType::LanguageProficiency {
                 language,
                 min_level,
             },
             target_platform: platform,
-            options: ProofOptions::default(),
         };
-
-        match self.issuer.generate_proof(request) {
-            Ok(proof) => {
-                log::info!(
-                    "✅ Proof generated successfully with ID: {}",
-                    proof.proof_id
-                );
-                on_success.emit(proof);
-            }
-            Err(e) => {
-                log::error!("❌ Proof generation failed: {}", e);
-                on_error.emit(format!("Proof generation failed: {}", e));
-            }
-        }
+        self.answer_request(holder, request, on_success, on_error);
     }
 
     pub fn generate_performance_proof(
         &self,
-        certificate: CertificateData,
+        holder: &mut Holder,
         min_percentage: u8,
         platform: String,
         on_success: Callback<ZkProofClaim>,
         on_error: Callback<String>,
     ) {
         log::info!("=== Performance Proof Generation ===");
-        log::info!("Certificate Details:");
-        log::info!("  - Profile: {}", certificate.profile_name);
-        log::info!("  - Course: {}", certificate.game_path_name);
-        log::info!("  - Performance: {}%", certificate.performance_percentage);
-        log::info!(
-            "  - Solved: {}/{}",
-            certificate.solved_challenges,
-            certificate.total_challenges
-        );
+        log::info!("Holder: {}", holder.holder_id);
         log::info!("Claim Details:");
         log::info!("  - Min Percentage: {}%", min_percentage);
         log::info!("  - Platform: {}", platform);
 
-        let request = ProofRequest {
-            certificate,
+        let request = ClaimRequest {
             claim_type: ClaimType::PerformanceThreshold { min_percentage },
             target_platform: platform,
-            options: ProofOptions::default(),
         };
-
-        match self.issuer.generate_proof(request) {
-            Ok(proof) => {
-                log::info!(
-                    "✅ Performance proof generated successfully with ID: {}",
-                    proof.proof_id
-                );
-                on_success.emit(proof);
-            }
-            Err(e) => {
-                log::error!("❌ Performance proof generation failed: {}", e);
-                on_error.emit(format!("Performance proof generation failed: {}", e));
-            }
-        }
+        self.answer_request(holder, request, on_success, on_error);
     }
 
     pub fn generate_combined_proof(
         &self,
-        certificate: CertificateData,
+        holder: &mut Holder,
         criteria: Vec<ClaimType>,
         platform: String,
         on_success: Callback<ZkProofClaim>,
         on_error: Callback<String>,
     ) {
         log::info!("Starting combined proof generation");
-        log::info!("Certificate: {:?}", certificate.profile_name);
+        log::info!("Holder: {}", holder.holder_id);
         log::info!("Criteria count: {}, Platform: {}", criteria.len(), platform);
 
-        let request = ProofRequest {
-            certificate,
+        let request = ClaimRequest {
             claim_type: ClaimType::Combined { criteria },
             target_platform: platform,
-            options: ProofOptions::default(),
         };
+        self.answer_request(holder, request, on_success, on_error);
+    }
 
-        match self.issuer.generate_proof(request) {
-            Ok(proof) => {
-                log::info!(
-                    "Combined proof generated successfully with ID: {}",
-                    proof.proof_id
-                );
-                on_success.emit(proof);
-            }
-            Err(e) => {
-                log::error!("Combined proof generation failed: {}", e);
-                on_error.emit(format!("Combined proof generation failed: {}", e));
-            }
-        }
+    /// Evaluate a presentation definition against proofs this service's verifier accepts
+    pub fn evaluate_definition(
+        &self,
+        definition: &PresentationDefinition,
+        proofs: &[ZkProofClaim],
+    ) -> Result<DefinitionEvaluation, String> {
+        definition
+            .evaluate(&[], proofs, &self.verifier)
+            .map_err(|e| e.to_string())
     }
 
     pub fn verify_proof(
@@ -1,8 +1,9 @@
 use konnektoren_core::certificates::CertificateData;
-use web5claims::{VerificationResult, ZkProofClaim};
+use web5claims::{Holder, MintedCertificate, VerificationResult, ZkProofClaim};
 
 #[derive(Clone, Default, PartialEq)]
 pub struct AppState {
+    pub holder: Holder,
     pub certificate_data: Option<CertificateData>,
     pub zk_proof: Option<ZkProofClaim>,
     pub verification_result: Option<VerificationResult>,
@@ -13,6 +14,7 @@ pub struct AppState {
 
 impl AppState {
     pub fn set_certificate(&mut self, certificate: CertificateData) {
+        self.holder.add_certificate(certificate.clone());
         self.certificate_data = Some(certificate);
         // Clear previous proof data when new certificate is set
         self.zk_proof = None;
@@ -20,7 +22,15 @@ impl AppState {
         self.error_message = None;
     }
 
+    pub fn set_minted_certificate(&mut self, minted: MintedCertificate) {
+        self.holder.add_minted_certificate(minted.clone());
+        self.set_certificate(minted.certificate);
+    }
+
     pub fn set_zk_proof(&mut self, proof: ZkProofClaim) {
+        if let Err(e) = self.holder.store_proof(proof.clone()) {
+            log::warn!("Proof not stored in holder wallet: {}", e);
+        }
         self.zk_proof = Some(proof);
         // Clear previous verification when new proof is generated
         self.verification_result = None;
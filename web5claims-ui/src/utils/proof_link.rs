@@ -154,7 +154,7 @@ pub fn copy_link_to_clipboard(link: &str) -> Result<(), String> {
 mod tests {
     use super::*;
     use std::collections::HashMap;
-    use web5claims::{CefrLevel, ClaimType, ProofData, ProofMetadata, PublicInputs};
+    use web5claims::{CefrLevel, ClaimType, ProofData, ProofMetadata, ProofMode, PublicInputs};
 
     fn create_test_proof() -> ZkProofClaim {
         ZkProofClaim::new(
@@ -171,6 +171,7 @@ mod tests {
                 proof_bytes: vec![1, 2, 3, 4],
                 circuit_id: "test_circuit".to_string(),
                 vk_hash: "test_vk".to_string(),
+                mode: ProofMode::Simulated,
             },
             ProofMetadata {
                 version: "1.0.0".to_string(),
@@ -0,0 +1,132 @@
+// Server-side signer of ZKPass handoffs
+//
+// The zkpass app posts the ZKPassport proofs it collected; this service verifies them
+// itself and signs the result it verified for the issuer page. The signing key stays in
+// the server environment and never reaches the browser bundle.
+import { createServer } from "node:http";
+import { createPrivateKey, sign } from "node:crypto";
+import { ZKPassport } from "@zkpassport/sdk";
+
+const HANDOFF_TYP = "zkpass-handoff+jwt";
+const HANDOFF_ISSUER = "web5claims-zkpass";
+const HANDOFF_TTL_SECONDS = 300;
+const MAX_BODY_BYTES = 1024 * 1024;
+
+// Must match the request the zkpass app creates, which scopes it to the handoff nonce
+const ZKPASSPORT_SCOPE = "identity-verification";
+const handoffScope = (nonce) => `${ZKPASSPORT_SCOPE}:${nonce}`;
+
+const port = Number(process.env.HANDOFF_PORT ?? 8787);
+const allowedOrigin = process.env.HANDOFF_ALLOWED_ORIGIN ?? "http://localhost:8000";
+const zkPassportDomain = process.env.HANDOFF_ZKPASSPORT_DOMAIN ?? "localhost";
+// Accept proofs of ZKPassport mock passports (development only, opt-in)
+const devMode = process.env.HANDOFF_DEV_MODE === "true";
+if (devMode) {
+  console.warn("HANDOFF_DEV_MODE is set, proofs of mock passports are accepted");
+}
+
+if (!process.env.HANDOFF_SIGNING_JWK) {
+  console.error(
+    "HANDOFF_SIGNING_JWK is not set, generate it with `cargo run --example handoff_keys`",
+  );
+  process.exit(1);
+}
+const signingKey = createPrivateKey({
+  key: JSON.parse(process.env.HANDOFF_SIGNING_JWK),
+  format: "jwk",
+});
+
+const encodeSegment = (value) =>
+  Buffer.from(JSON.stringify(value)).toString("base64url");
+
+// Sign a verified ZKPassport result for the issuer page, bound to its nonce and short-lived
+const signHandoff = (nonce, result) => {
+  const issuedAt = Math.floor(Date.now() / 1000);
+  const signingInput = `${encodeSegment({ alg: "EdDSA", typ: HANDOFF_TYP })}.${encodeSegment(
+    {
+      iss: HANDOFF_ISSUER,
+      nonce,
+      iat: issuedAt,
+      exp: issuedAt + HANDOFF_TTL_SECONDS,
+      result,
+    },
+  )}`;
+  const signature = sign(null, Buffer.from(signingInput), signingKey);
+  return `${signingInput}.${signature.toString("base64url")}`;
+};
+
+// Verify the proofs against the query result and sign what they prove
+const handleHandoff = async ({ nonce, proofs, queryResult }) => {
+  if (typeof nonce !== "string" || !nonce) {
+    return [400, { error: "handoff nonce is missing" }];
+  }
+  if (!Array.isArray(proofs) || proofs.length === 0 || !queryResult) {
+    return [400, { error: "proofs and query result are required" }];
+  }
+
+  // The proofs commit to their scope, so proofs made for another handoff fail to verify
+  const zkPassport = new ZKPassport(zkPassportDomain);
+  const { verified, uniqueIdentifier, queryResultErrors } =
+    await zkPassport.verify({
+      proofs,
+      queryResult,
+      scope: handoffScope(nonce),
+      devMode,
+    });
+  if (!verified || !uniqueIdentifier) {
+    console.warn("Rejected ZKPassport proofs:", queryResultErrors);
+    return [422, { error: "ZKPassport proofs did not verify" }];
+  }
+
+  const token = signHandoff(nonce, {
+    verified,
+    uniqueIdentifier,
+    queryResult,
+  });
+  return [200, { token }];
+};
+
+const readBody = (request) =>
+  new Promise((resolve, reject) => {
+    let body = "";
+    request.on("data", (chunk) => {
+      body += chunk;
+      if (body.length > MAX_BODY_BYTES) {
+        reject(new Error("request body too large"));
+        request.destroy();
+      }
+    });
+    request.on("end", () => resolve(body));
+    request.on("error", reject);
+  });
+
+const respond = (response, status, body) => {
+  response.writeHead(status, {
+    "Access-Control-Allow-Origin": allowedOrigin,
+    "Access-Control-Allow-Methods": "POST, OPTIONS",
+    "Access-Control-Allow-Headers": "Content-Type",
+    "Content-Type": "application/json",
+  });
+  response.end(body === undefined ? undefined : JSON.stringify(body));
+};
+
+createServer(async (request, response) => {
+  if (request.method === "OPTIONS") {
+    return respond(response, 204);
+  }
+  if (request.method !== "POST" || request.url !== "/handoff") {
+    return respond(response, 404, { error: "not found" });
+  }
+
+  try {
+    const [status, body] = await handleHandoff(
+      JSON.parse(await readBody(request)),
+    );
+    respond(response, status, body);
+  } catch (error) {
+    console.error("Handoff failed:", error);
+    respond(response, 400, { error: error.message });
+  }
+}).listen(port, () => {
+  console.log(`ZKPass handoff service listening on http://localhost:${port}`);
+});
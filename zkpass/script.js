@@ -10,11 +10,20 @@ const nameInput = document.getElementById("nameInput");
 const nameInputContainer = document.getElementById("name-input-container");
 const successActions = document.getElementById("success-actions");
 
+// Handoff back to the issuer page, signed by the handoff service (handoff-server.js)
+const HANDOFF_SERVICE_URL = import.meta.env.VITE_HANDOFF_SERVICE_URL;
+const handoffNonce = new URLSearchParams(window.location.search).get(
+  "handoff_nonce",
+);
+// Request proofs of ZKPassport mock passports (development only, opt-in)
+const ZKPASSPORT_DEV_MODE = import.meta.env.VITE_ZKPASSPORT_DEV_MODE === "true";
+
 // State management
 let isVerifying = false;
 let expectedFirstName = null;
 let verificationSuccess = false;
 let verifiedData = null;
+let collectedProofs = [];
 
 // Initialize ZKPassport SDK
 const initializeZKPassport = () => {
@@ -74,6 +83,7 @@ const resetUI = () => {
   expectedFirstName = null;
   verificationSuccess = false;
   verifiedData = null;
+  collectedProofs = [];
 
   // Clear canvas
   if (canvas) {
@@ -82,30 +92,54 @@ const resetUI = () => {
   }
 };
 
-// Navigate to issuer with verified data
-const navigateToIssuer = () => {
+// Have the handoff service verify the proofs and sign the result for the issuer page
+const requestHandoff = async ({ proofs, queryResult }) => {
+  if (!HANDOFF_SERVICE_URL) {
+    throw new Error("VITE_HANDOFF_SERVICE_URL is not configured");
+  }
+
+  const response = await fetch(`${HANDOFF_SERVICE_URL}/handoff`, {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({ nonce: handoffNonce, proofs, queryResult }),
+  });
+  const body = await response.json().catch(() => ({}));
+  if (!response.ok) {
+    throw new Error(body.error ?? `handoff service answered ${response.status}`);
+  }
+  return body.token;
+};
+
+// Navigate to issuer with a signed handoff of the verified data
+const navigateToIssuer = async () => {
   if (!verifiedData) {
     showStatus("❌ No verified data available", "error");
     return;
   }
 
-  // Create URL parameters with verified data
-  const params = new URLSearchParams();
-
-  if (verifiedData.firstName) {
-    params.set("verified_name", verifiedData.firstName);
+  if (!handoffNonce) {
+    showStatus(
+      "❌ Open ZKPass from the issuer page to hand your verification over",
+      "error",
+    );
+    return;
   }
 
-  if (verifiedData.ageVerified) {
-    params.set("verified_age", "true");
+  let token;
+  try {
+    token = await requestHandoff(verifiedData.handoff);
+  } catch (error) {
+    console.error("Failed to sign handoff:", error);
+    showStatus(`❌ Failed to sign verification: ${error.message}`, "error");
+    return;
   }
 
-  params.set("verification_type", "identity");
-  params.set("verification_timestamp", new Date().toISOString());
+  const params = new URLSearchParams();
+  params.set("handoff", token);
 
   // Navigate to main app issuer page using proper path routing
   const issuerUrl = `../issuer?${params.toString()}`;
-  console.log("Navigating to issuer:", issuerUrl);
+  console.log("Navigating to issuer with signed handoff");
 
   window.location.href = issuerUrl;
 };
@@ -166,8 +200,11 @@ const performVerification = async (firstName) => {
       name: "Web5 Claims Identity Verifier",
       logo: "https://zkpassport.id/logo.png",
       purpose: "Verify age and identity for complete certificate validation",
-      scope: "identity-verification",
-      devMode: true,
+      // Binds the proofs to this handoff, the handoff service verifies them with the same scope
+      scope: handoffNonce
+        ? `identity-verification:${handoffNonce}`
+        : "identity-verification",
+      devMode: ZKPASSPORT_DEV_MODE,
     };
 
     console.log("Request config:", requestConfig);
@@ -264,8 +301,9 @@ const performVerification = async (firstName) => {
       updateButton("🔐 Generating proof...", true);
     });
 
-    onProofGenerated(({ name }) => {
-      console.log("ZK Proof generated:", name);
+    onProofGenerated((proof) => {
+      console.log("ZK Proof generated:", proof.name);
+      collectedProofs.push(proof);
       showStatus("✅ Zero-knowledge proof generated successfully", "success");
       updateButton("⏳ Verifying proof...", true);
     });
@@ -393,6 +431,8 @@ const handleVerificationResult = (verified, result, firstName) => {
   }
 
   resultData.ageVerified = ageVerified;
+  // Proofs and query result, verified again by the handoff service before it signs them
+  resultData.handoff = { proofs: collectedProofs, queryResult: result };
   success = ageVerified && nameVerified;
 
   if (success) {